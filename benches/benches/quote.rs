@@ -0,0 +1,209 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use os_display::{bench_corpus, Quoted};
+use std::fmt::Write;
+
+/// Something like the contents of a source file.
+fn text(len: usize) -> String {
+    let line = "    let path = Path::new(\"foo/bar.baz\"); // Look up the file\n";
+    line.repeat(len / line.len() + 1)[..len].replace('\n', " ")
+}
+
+fn bench_quote(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quote");
+    let mut out = String::new();
+    for &len in &[64, 4096, 4 * 1024 * 1024] {
+        let quoted = text(len);
+        let escaped = quoted.clone() + "\n";
+        // Double quotes for Unix, doubled single quotes for PowerShell
+        let single = quoted.clone() + "'";
+        // Single quotes with \' escapes for Unix
+        let mixed = quoted.clone() + "'$";
+        group.throughput(Throughput::Bytes(len as u64));
+        let cases = [
+            ("quoted", &quoted),
+            ("escaped", &escaped),
+            ("single", &single),
+            ("mixed", &mixed),
+        ];
+        for &(name, text) in &cases {
+            group.bench_function(format!("unix/{}/{}", name, len), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::unix(text)).unwrap();
+                })
+            });
+            group.bench_function(format!("windows/{}/{}", name, len), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::windows(text)).unwrap();
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Names like the ones `ls` prints, mostly short and safe.
+const FILE_NAMES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "README.md",
+    "src",
+    "lib.rs",
+    "target",
+    ".gitignore",
+    "build.rs",
+    "foo-bar_baz.tar.gz",
+    "IMG_20240101_123456.jpg",
+    "node_modules",
+    "my file.txt",
+    "notes (old).md",
+];
+
+fn bench_file_names(c: &mut Criterion) {
+    let mut group = c.benchmark_group("file_names");
+    let mut out = String::new();
+    group.throughput(Throughput::Elements(FILE_NAMES.len() as u64));
+    group.bench_function("unix/maybe_quote", |b| {
+        b.iter(|| {
+            out.clear();
+            for name in FILE_NAMES {
+                write!(out, "{}", Quoted::unix(name).force(false)).unwrap();
+            }
+        })
+    });
+    group.bench_function("unix/quote", |b| {
+        b.iter(|| {
+            out.clear();
+            for name in FILE_NAMES {
+                write!(out, "{}", Quoted::unix(name)).unwrap();
+            }
+        })
+    });
+    group.bench_function("windows/maybe_quote", |b| {
+        b.iter(|| {
+            out.clear();
+            for name in FILE_NAMES {
+                write!(out, "{}", Quoted::windows(name).force(false)).unwrap();
+            }
+        })
+    });
+    // Every write is a system call, like for stderr.
+    #[cfg(unix)]
+    {
+        use std::io::Write as _;
+
+        let mut devnull = std::fs::OpenOptions::new()
+            .write(true)
+            .open("/dev/null")
+            .unwrap();
+        group.bench_function("unix/unbuffered", |b| {
+            b.iter(|| {
+                for name in FILE_NAMES {
+                    write!(devnull, "{}", Quoted::unix(name).force(false)).unwrap();
+                }
+            })
+        });
+    }
+    group.bench_function("native_raw/maybe_quote", |b| {
+        b.iter(|| {
+            out.clear();
+            for name in FILE_NAMES {
+                write!(out, "{}", Quoted::native_raw(name.as_ref()).force(false)).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Text where no run of bytes can be skipped, so every character goes
+/// through the classification.
+fn bench_worst_case(c: &mut Criterion) {
+    let mut group = c.benchmark_group("worst_case");
+    let mut out = String::new();
+    let len = 4096;
+    let cases = [
+        // Every byte is special, and some are quotes
+        ("syntax", "'\"$`\\|&;<>()*?[]"),
+        // Non-ASCII that's ordinary
+        ("cjk", "日本語のファイル名"),
+        // Non-ASCII in the range that needs a closer look
+        ("punctuation", "“quoted” – ‘single’ … "),
+    ];
+    for &(name, piece) in &cases {
+        let text = piece.repeat(len / piece.len() + 1);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        for &(force, mode) in &[(true, "quote"), (false, "maybe_quote")] {
+            group.bench_function(format!("unix/{}/{}", mode, name), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::unix(&text).force(force)).unwrap();
+                })
+            });
+            group.bench_function(format!("windows/{}/{}", mode, name), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::windows(&text).force(force)).unwrap();
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+/// The inputs from `os_display::bench_corpus`, each of which goes through
+/// a different part of the code.
+fn bench_corpus(c: &mut Criterion) {
+    let mut group = c.benchmark_group("corpus");
+    let mut out = String::new();
+    for &len in &[64, 4096, 1024 * 1024] {
+        group.throughput(Throughput::Bytes(len as u64));
+        let long_paths = bench_corpus::long_paths(len);
+        let escape_heavy = bench_corpus::escape_heavy(len);
+        for &(name, text) in &[("long_paths", &long_paths), ("escape_heavy", &escape_heavy)] {
+            group.bench_function(format!("unix/{}/{}", name, len), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::unix(text)).unwrap();
+                })
+            });
+            group.bench_function(format!("windows/{}/{}", name, len), |b| {
+                b.iter(|| {
+                    out.clear();
+                    write!(out, "{}", Quoted::windows(text)).unwrap();
+                })
+            });
+        }
+        let bytes = bench_corpus::invalid_utf8(len);
+        group.bench_function(format!("unix_raw/invalid_utf8/{}", len), |b| {
+            b.iter(|| {
+                out.clear();
+                write!(out, "{}", Quoted::unix_raw(&bytes)).unwrap();
+            })
+        });
+        let units = bench_corpus::utf16_surrogates(len / 2);
+        group.bench_function(format!("windows_raw/utf16_surrogates/{}", len), |b| {
+            b.iter(|| {
+                out.clear();
+                write!(out, "{}", Quoted::windows_raw(&units)).unwrap();
+            })
+        });
+        let args = bench_corpus::external_args(len);
+        group.bench_function(format!("windows/external/{}", len), |b| {
+            b.iter(|| {
+                out.clear();
+                write!(out, "{}", Quoted::windows(&args).external(true)).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_quote,
+    bench_file_names,
+    bench_worst_case,
+    bench_corpus
+);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use std::env;
+use std::process::Command;
+
+// We support very old compilers, so instead of raising the MSRV we check the
+// compiler version and enable newer APIs when they're available.
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(os_display_encoded_bytes)");
+    println!("cargo:rustc-check-cfg=cfg(os_display_quoted_literal)");
+
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+
+    // OsStr::as_encoded_bytes()
+    if minor >= 74 {
+        println!("cargo:rustc-cfg=os_display_encoded_bytes");
+    }
+
+    // quoted_literal!() needs const generics, const panics and a const
+    // str::from_utf8()
+    if minor >= 63 {
+        println!("cargo:rustc-cfg=os_display_quoted_literal");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // rustc 1.74.0 (79e9716c9 2023-11-13)
+    let mut pieces = version.split_whitespace().nth(1)?.split('.');
+    if pieces.next()? != "1" {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
@@ -0,0 +1,16 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use std::fmt::Write;
+
+use os_display::Quoted;
+
+// Every style with every combination of options. Some of them refuse to
+// write some text, but none of them should panic, and measuring the output
+// should agree with writing it.
+fuzz_target!(|quoted: Quoted| {
+    let mut out = String::new();
+    if write!(out, "{}", quoted).is_ok() {
+        assert_eq!(quoted.display_len(), out.len(), "{:?}", out);
+    }
+});
@@ -1,28 +1,15 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
 
-use std::process::Command;
-
 use once_cell::sync::Lazy;
 
+use os_display::testkit::Shell;
 use os_display::Quoted;
 
-mod common;
-
-use common::Shell;
-
 // This code only runs on Linux for various reasons.
 // Grab PowerShell from https://github.com/PowerShell/PowerShell.
 
-// Set-Variable is used because `$s = ...` sees a bare string as a command.
-const PWSH_SCRIPT: &str = r#"
-foreach($line in [System.IO.File]::ReadLines("/dev/stdin")) {
-    Invoke-Expression ("Set-Variable s {0}" -f $line)
-    "{0}`0" -f $s
-}
-"#;
-static POWERSHELL: Lazy<Shell> =
-    Lazy::new(|| Shell::raw(Command::new("pwsh").arg("-c").arg(PWSH_SCRIPT)));
+static POWERSHELL: Lazy<Shell> = Lazy::new(Shell::powershell);
 
 fuzz_target!(|data: &[u8]| {
     // Can't pass null bytes
@@ -45,7 +32,12 @@ fuzz_target!(|data: &[u8]| {
             .external(false)
             .force(false)
             .to_string();
-        assert_eq!(POWERSHELL.send(&quote), data, "{:?}", quote);
-        assert_eq!(POWERSHELL.send(&maybe_quote), data, "{:?}", maybe_quote);
+        assert_eq!(POWERSHELL.roundtrip(&quote), data, "{:?}", quote);
+        assert_eq!(
+            POWERSHELL.roundtrip(&maybe_quote),
+            data,
+            "{:?}",
+            maybe_quote
+        );
     }
 });
@@ -5,12 +5,9 @@ use std::process::Command;
 
 use once_cell::sync::Lazy;
 
+use os_display::testkit::Shell;
 use os_display::Quoted;
 
-mod common;
-
-use common::Shell;
-
 // For some reason /usr/bin/printf is ~8 times as fast as printf.
 // This is still 4 times slower than the internal method, but judging
 // by the rates I get from other shells it's probably the maximum
@@ -22,7 +19,7 @@ foreach($line in [System.IO.File]::ReadLines("/dev/stdin")) {
 }
 "#;
 static POWERSHELL: Lazy<Shell> =
-    Lazy::new(|| Shell::raw(Command::new("pwsh").arg("-c").arg(PWSH_SCRIPT)));
+    Lazy::new(|| Shell::raw(Command::new("pwsh").arg("-c").arg(PWSH_SCRIPT)).unwrap());
 
 fuzz_target!(|data: &[u8]| {
     // Can't pass null bytes
@@ -35,7 +32,12 @@ fuzz_target!(|data: &[u8]| {
             .external(true)
             .force(false)
             .to_string();
-        assert_eq!(POWERSHELL.send(&quote), data, "{:?}", quote);
-        assert_eq!(POWERSHELL.send(&maybe_quote), data, "{:?}", maybe_quote);
+        assert_eq!(POWERSHELL.roundtrip(&quote), data, "{:?}", quote);
+        assert_eq!(
+            POWERSHELL.roundtrip(&maybe_quote),
+            data,
+            "{:?}",
+            maybe_quote
+        );
     }
 });
@@ -7,29 +7,26 @@ use std::process::Command;
 
 use once_cell::sync::Lazy;
 
+use os_display::testkit::Shell;
 use os_display::Quotable;
 
-mod common;
-
-use common::Shell;
-
 // All these are packaged on Debian:
 // apt install bash zsh ksh mksh busybox dash posh yash fish tcsh
 
 // ksh-compatible shells
-static BASH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("bash")));
-static ZSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("zsh")));
-static KSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("ksh")));
-static MKSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("mksh")));
-static BUSYBOX: Lazy<Shell> = Lazy::new(|| Shell::new(Command::new("busybox").arg("sh")));
+static BASH: Lazy<Shell> = Lazy::new(Shell::bash);
+static ZSH: Lazy<Shell> = Lazy::new(Shell::zsh);
+static KSH: Lazy<Shell> = Lazy::new(Shell::ksh);
+static MKSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("mksh")).unwrap());
+static BUSYBOX: Lazy<Shell> = Lazy::new(|| Shell::new(Command::new("busybox").arg("sh")).unwrap());
 
 // Shells without $'' but with everything else
-static DASH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("dash")));
-static POSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("posh")));
+static DASH: Lazy<Shell> = Lazy::new(Shell::dash);
+static POSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("posh")).unwrap());
 // I didn't know about yash until running `apt search shell`, but it claims
 // POSIX compliance, and it's good to throw obscure implementations in here.
 // The rust port may be interesting? https://github.com/magicant/yash-rs
-static YASH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("yash")));
+static YASH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("yash")).unwrap());
 
 static FISH: Lazy<Shell> = Lazy::new(|| {
     Shell::new(
@@ -39,13 +36,14 @@ static FISH: Lazy<Shell> = Lazy::new(|| {
             .arg("-c")
             .arg("while read line; eval $line; end"),
     )
+    .unwrap()
 });
 // tcsh seems to leak memory at ~100MB/h, so maybe don't include it in long runs.
-static TCSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("tcsh")));
+static TCSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("tcsh")).unwrap());
 // Debian has a port of OpenBSD's csh.
 // It's omitted for now because `printf '%s\0\n' ܠ` consumes gigabytes of memory:
 // https://bugs.debian.org/cgi-bin/bugreport.cgi?bug=995013
-// static CSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("csh")));
+// static CSH: Lazy<Shell> = Lazy::new(|| Shell::new(&mut Command::new("csh")).unwrap());
 
 fuzz_target!(|data: &[u8]| {
     // Can't pass null bytes
@@ -55,24 +53,24 @@ fuzz_target!(|data: &[u8]| {
     let maybe_quote = text.maybe_quote().to_string();
 
     // Loop unrolled to easily see which line panics
-    assert_eq!(BASH.send(&quote), data, "{:?}", text);
-    assert_eq!(BASH.send(&maybe_quote), data, "{:?}", text);
-    assert_eq!(ZSH.send(&quote), data, "{:?}", text);
-    assert_eq!(ZSH.send(&maybe_quote), data, "{:?}", text);
-    assert_eq!(KSH.send(&quote), data, "{:?}", text);
-    assert_eq!(KSH.send(&maybe_quote), data, "{:?}", text);
-    assert_eq!(MKSH.send(&quote), data, "{:?}", text);
-    assert_eq!(MKSH.send(&maybe_quote), data, "{:?}", text);
-    assert_eq!(BUSYBOX.send(&quote), data, "{:?}", text);
-    assert_eq!(BUSYBOX.send(&maybe_quote), data, "{:?}", text);
+    assert_eq!(BASH.roundtrip(&quote), data, "{:?}", text);
+    assert_eq!(BASH.roundtrip(&maybe_quote), data, "{:?}", text);
+    assert_eq!(ZSH.roundtrip(&quote), data, "{:?}", text);
+    assert_eq!(ZSH.roundtrip(&maybe_quote), data, "{:?}", text);
+    assert_eq!(KSH.roundtrip(&quote), data, "{:?}", text);
+    assert_eq!(KSH.roundtrip(&maybe_quote), data, "{:?}", text);
+    assert_eq!(MKSH.roundtrip(&quote), data, "{:?}", text);
+    assert_eq!(MKSH.roundtrip(&maybe_quote), data, "{:?}", text);
+    assert_eq!(BUSYBOX.roundtrip(&quote), data, "{:?}", text);
+    assert_eq!(BUSYBOX.roundtrip(&maybe_quote), data, "{:?}", text);
 
     if !quote.starts_with('$') {
-        assert_eq!(DASH.send(&quote), data, "{:?}", text);
-        assert_eq!(DASH.send(&maybe_quote), data, "{:?}", text);
-        assert_eq!(POSH.send(&quote), data, "{:?}", text);
-        assert_eq!(POSH.send(&maybe_quote), data, "{:?}", text);
-        assert_eq!(YASH.send(&quote), data, "{:?}", text);
-        assert_eq!(YASH.send(&maybe_quote), data, "{:?}", text);
+        assert_eq!(DASH.roundtrip(&quote), data, "{:?}", text);
+        assert_eq!(DASH.roundtrip(&maybe_quote), data, "{:?}", text);
+        assert_eq!(POSH.roundtrip(&quote), data, "{:?}", text);
+        assert_eq!(POSH.roundtrip(&maybe_quote), data, "{:?}", text);
+        assert_eq!(YASH.roundtrip(&quote), data, "{:?}", text);
+        assert_eq!(YASH.roundtrip(&maybe_quote), data, "{:?}", text);
     }
 
     // Limited testing of fish and csh, to at least pin down what
@@ -87,8 +85,8 @@ fuzz_target!(|data: &[u8]| {
             .chars()
             .any(|ch| ('\u{F600}'..='\u{F6FF}').contains(&ch))
         {
-            assert_eq!(FISH.send(&quote), data, "{:?}", text);
-            assert_eq!(FISH.send(&maybe_quote), data, "{:?}", text);
+            assert_eq!(FISH.roundtrip(&quote), data, "{:?}", text);
+            assert_eq!(FISH.roundtrip(&maybe_quote), data, "{:?}", text);
         }
 
         // csh doesn't like a # in the middle of an argument in
@@ -96,10 +94,10 @@ fuzz_target!(|data: &[u8]| {
         // so that doesn't really matter.)
         // It also doesn't like a ! in the middle.
         if !quote.contains(&['#', '!'][..]) {
-            assert_eq!(TCSH.send(&quote), data, "{:?}", text);
-            assert_eq!(TCSH.send(&maybe_quote), data, "{:?}", text);
-            // assert_eq!(CSH.send(&quote), data, "{:?}", text);
-            // assert_eq!(CSH.send(&maybe_quote), data, "{:?}", text);
+            assert_eq!(TCSH.roundtrip(&quote), data, "{:?}", text);
+            assert_eq!(TCSH.roundtrip(&maybe_quote), data, "{:?}", text);
+            // assert_eq!(CSH.roundtrip(&quote), data, "{:?}", text);
+            // assert_eq!(CSH.roundtrip(&maybe_quote), data, "{:?}", text);
         }
     }
 });
@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+"""Generate src/confusables.rs from the Unicode confusables data.
+
+Usage:
+    curl -O https://www.unicode.org/Public/security/16.0.0/confusables.txt
+    scripts/gen_confusables.py confusables.txt > src/confusables.rs
+
+Only single codepoints that are confusable with a single printable ASCII
+character are kept. That's all we need to spot spoofed ASCII text.
+"""
+
+import sys
+
+HEADER = """\
+// This file is generated by scripts/gen_confusables.py. Do not edit it by hand.
+// Source: confusables.txt from Unicode {version}
+
+/// Non-ASCII characters that are confusable with a printable ASCII character,
+/// sorted by codepoint.
+pub(crate) const CONFUSABLES: &[(char, char)] = &[
+"""
+
+
+def rust_char(ch):
+    if ch in "'\\":
+        return "'\\%s'" % ch
+    return "'%s'" % ch
+
+
+def main(path, version="16.0.0"):
+    table = []
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if not line:
+                continue
+            source, target = [field.strip() for field in line.split(";")[:2]]
+            source = int(source, 16)
+            target = [int(cp, 16) for cp in target.split()]
+            if source < 0x80 or len(target) != 1:
+                continue
+            if not 0x21 <= target[0] <= 0x7E:
+                continue
+            table.append((source, target[0]))
+    table.sort()
+    sys.stdout.write(HEADER.format(version=version))
+    for source, target in table:
+        sys.stdout.write("    ('\\u{%X}', %s),\n" % (source, rust_char(chr(target))))
+    sys.stdout.write("];\n")
+
+
+if __name__ == "__main__":
+    main(*sys.argv[1:])
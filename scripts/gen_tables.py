@@ -0,0 +1,182 @@
+#!/usr/bin/env python3
+"""Generate src/tables.rs from the Unicode Character Database.
+
+Usage:
+    for v in 14.0.0 15.1.0 16.0.0; do
+        mkdir -p ucd/$v
+        curl -o ucd/$v/UnicodeData.txt https://www.unicode.org/Public/$v/ucd/UnicodeData.txt
+        curl -o ucd/$v/PropList.txt https://www.unicode.org/Public/$v/ucd/PropList.txt
+    done
+    scripts/gen_tables.py ucd/14.0.0 ucd/15.1.0 ucd/16.0.0 > src/tables.rs
+
+The directories must be named after their version. Each version gets a
+`unicodeNN` feature, named after its major version. The last one is the
+default.
+"""
+
+import os
+import sys
+
+HEADER = """\
+// This file is generated by scripts/gen_tables.py. Do not edit it by hand.
+
+//! Unicode data, for the version that's selected by the `unicodeNN` features.
+//!
+//! Each table is a sorted list of inclusive ranges.
+
+// Which tables are used depends on the quoting styles that are enabled.
+#![allow(dead_code)]
+"""
+
+# Table name, and the general categories or binary property it contains.
+CATEGORIES = [
+    ("SPACE_SEPARATOR", ["Zs"]),
+    ("LINE_PARAGRAPH_SEPARATOR", ["Zl", "Zp"]),
+    ("FORMAT", ["Cf"]),
+    ("PRIVATE_USE", ["Co"]),
+]
+PROPERTIES = [
+    ("WHITE_SPACE", "White_Space"),
+]
+
+
+def parse_codepoints(field):
+    if ".." in field:
+        first, last = field.split("..")
+        return int(first, 16), int(last, 16)
+    return int(field, 16), int(field, 16)
+
+
+def read_categories(path):
+    """Map each general category to a list of (first, last) ranges."""
+    categories = {}
+    first = None
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            fields = line.split(";")
+            codepoint, name, category = int(fields[0], 16), fields[1], fields[2]
+            if name.endswith(", First>"):
+                first = codepoint
+                continue
+            if name.endswith(", Last>"):
+                ranges = (first, codepoint)
+            else:
+                ranges = (codepoint, codepoint)
+            categories.setdefault(category, []).append(ranges)
+    return categories
+
+
+def read_properties(path):
+    """Map each binary property to a list of (first, last) ranges."""
+    properties = {}
+    with open(path, encoding="utf-8") as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if not line:
+                continue
+            codepoints, prop = [field.strip() for field in line.split(";")]
+            properties.setdefault(prop, []).append(parse_codepoints(codepoints))
+    return properties
+
+
+def merge(ranges):
+    merged = []
+    for first, last in sorted(ranges):
+        if merged and first <= merged[-1][1] + 1:
+            merged[-1] = (merged[-1][0], max(merged[-1][1], last))
+        else:
+            merged.append((first, last))
+    return merged
+
+
+def complement(ranges):
+    """The codepoints that aren't in any of the ranges. The ranges have to
+    include the surrogates, which can't be chars."""
+    missing = []
+    next_codepoint = 0
+    for first, last in merge(ranges):
+        if first > next_codepoint:
+            missing.append((next_codepoint, first - 1))
+        next_codepoint = last + 1
+    if next_codepoint <= 0x10FFFF:
+        missing.append((next_codepoint, 0x10FFFF))
+    return missing
+
+
+def write_table(out, name, ranges):
+    out.write("    pub(crate) const %s: &[(char, char)] = &[\n" % name)
+    for first, last in merge(ranges):
+        out.write("        ('\\u{%X}', '\\u{%X}'),\n" % (first, last))
+    out.write("    ];\n")
+
+
+def feature(version):
+    return "unicode" + version.split(".")[0]
+
+
+def main(dirs):
+    versions = [os.path.basename(os.path.normpath(d)) for d in dirs]
+    features = [feature(version) for version in versions]
+    out = sys.stdout
+    out.write(HEADER)
+
+    for index, (path, version) in enumerate(zip(dirs, versions)):
+        categories = read_categories(os.path.join(path, "UnicodeData.txt"))
+        properties = read_properties(os.path.join(path, "PropList.txt"))
+
+        # The oldest version that's asked for wins. The last one is the
+        # default, so it doesn't need its feature.
+        older = ['feature = "%s"' % f for f in features[:index]]
+        older = older[0] if len(older) == 1 else "any(%s)" % ", ".join(older)
+        if index == len(versions) - 1:
+            cfg = "not(%s)" % older
+        elif index > 0:
+            cfg = 'all(feature = "%s", not(%s))' % (features[index], older)
+        else:
+            cfg = 'feature = "%s"' % features[index]
+        out.write("\n#[cfg(%s)]\n" % cfg)
+        out.write("#[rustfmt::skip]\n")
+        out.write("mod data {\n")
+        major, minor, update = version.split(".")
+        out.write(
+            "    pub(crate) const UNICODE_VERSION: (u8, u8, u8) = (%s, %s, %s);\n\n"
+            % (major, minor, update)
+        )
+        for table, wanted in CATEGORIES:
+            ranges = [r for category in wanted for r in categories.get(category, [])]
+            write_table(out, table, ranges)
+            out.write("\n")
+        # Category Cn isn't listed, it's everything else.
+        assigned = [r for ranges in categories.values() for r in ranges]
+        write_table(out, "UNASSIGNED", complement(assigned))
+        out.write("\n")
+        for table, prop in PROPERTIES:
+            write_table(out, table, properties[prop])
+        out.write("}\n")
+
+    out.write(
+        """
+pub(crate) use self::data::*;
+
+/// Check whether a character is in one of the tables.
+pub(crate) fn contains(table: &[(char, char)], ch: char) -> bool {
+    table
+        .binary_search_by(|&(first, last)| {
+            if ch < first {
+                core::cmp::Ordering::Greater
+            } else if ch > last {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+"""
+    )
+
+
+if __name__ == "__main__":
+    if len(sys.argv) < 2:
+        sys.exit(__doc__)
+    main(sys.argv[1:])
@@ -0,0 +1,177 @@
+//! [`Arbitrary`] implementations for fuzzing, for the `arbitrary` feature.
+//!
+//! A [`Quoted`] is made with an arbitrary style and arbitrary values for all
+//! of its options, so a fuzzer can explore every combination without
+//! calling each builder method itself. The text is always a `&str`, to work
+//! with every style. Highlighting and hyperlinks are left out.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+#[cfg(feature = "alloc")]
+use crate::pattern::Pattern;
+#[cfg(feature = "windows")]
+use crate::CmdContext;
+use crate::{
+    AsciiSet, BidiPolicy, ControlStyle, DockerfileForm, Fallback, MagicTokens, Multiline,
+    OnUnrepresentable, Options, QuoteChar, QuoteContext, Quoted, Quoting, SqlDialect, Style, Width,
+};
+
+/// Implement `Arbitrary` for an enum by picking one of the given variants.
+macro_rules! arbitrary_enum {
+    ($name:ident: $($variant:ident),+) => {
+        impl<'a> Arbitrary<'a> for $name {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(*u.choose(&[$($name::$variant),+])?)
+            }
+        }
+    };
+}
+
+arbitrary_enum!(QuoteChar: Single, Double);
+arbitrary_enum!(Quoting: Minimal, Standard, Paranoid);
+arbitrary_enum!(QuoteContext: Standalone, InsideDoubleQuotes);
+arbitrary_enum!(Multiline: Escape, HereDoc);
+arbitrary_enum!(Fallback: AnsiC, PrintfCommand);
+arbitrary_enum!(SqlDialect: Standard, MySql);
+arbitrary_enum!(DockerfileForm: Shell, Exec);
+#[cfg(feature = "windows")]
+arbitrary_enum!(CmdContext: Interactive, BatchFile, DelayedExpansion);
+arbitrary_enum!(MagicTokens: Quote, Strict);
+arbitrary_enum!(BidiPolicy: Escape, Allow, Isolate);
+arbitrary_enum!(Width: Narrow, Wide);
+arbitrary_enum!(ControlStyle: Escape, Caret, Picture);
+
+impl<'a> Arbitrary<'a> for OnUnrepresentable {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        #[cfg(feature = "alloc")]
+        let policies = [
+            OnUnrepresentable::Escape,
+            OnUnrepresentable::ReplacementChar,
+            OnUnrepresentable::Error,
+        ];
+        #[cfg(not(feature = "alloc"))]
+        let policies = [OnUnrepresentable::Escape, OnUnrepresentable::Error];
+        Ok(*u.choose(&policies)?)
+    }
+}
+
+/// Only the styles that are enabled are picked.
+impl<'a> Arbitrary<'a> for Style {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut styles = [Style::__NonExhaustive; 16];
+        let mut len = 0;
+        let mut add = |style| {
+            styles[len] = style;
+            len += 1;
+        };
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        add(Style::Unix);
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        add(Style::Powershell);
+        #[cfg(feature = "windows")]
+        add(Style::WindowsArgv);
+        #[cfg(feature = "sql")]
+        add(Style::Sql);
+        #[cfg(feature = "html")]
+        add(Style::Html);
+        #[cfg(feature = "systemd")]
+        add(Style::SystemdUnit);
+        #[cfg(feature = "dotenv")]
+        add(Style::Dotenv);
+        #[cfg(feature = "dockerfile")]
+        add(Style::Dockerfile);
+        #[cfg(feature = "cmake")]
+        add(Style::Cmake);
+        #[cfg(feature = "reg")]
+        add(Style::Reg);
+        #[cfg(feature = "sudoers")]
+        add(Style::Sudoers);
+        #[cfg(feature = "git")]
+        {
+            add(Style::GitIgnore);
+            add(Style::GitAttributes);
+            add(Style::GitConfig);
+        }
+        #[cfg(feature = "sanitize")]
+        add(Style::Sanitize);
+        Ok(*u.choose(&styles[..len])?)
+    }
+}
+
+/// Every option, including the ones that only matter to other styles.
+fn options(u: &mut Unstructured<'_>) -> Result<Options> {
+    Ok(Options {
+        force_quote: u.arbitrary()?,
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        external: u.arbitrary()?,
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        expression: u.arbitrary()?,
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        magic_tokens: u.arbitrary()?,
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        iex_safe: u.arbitrary()?,
+        #[cfg(feature = "windows")]
+        cmd_context: u.arbitrary()?,
+        prefer: u.arbitrary()?,
+        quoting: u.arbitrary()?,
+        // Only the bits for ASCII characters exist.
+        extra_safe: AsciiSet(u.arbitrary()?),
+        extra_unsafe: AsciiSet(u.arbitrary()?),
+        on_unrepresentable: u.arbitrary()?,
+        bidi: u.arbitrary()?,
+        escape_invisible: u.arbitrary()?,
+        escape_hidden_text: u.arbitrary()?,
+        max_combining: u.arbitrary()?,
+        max_width: u.arbitrary()?,
+        ambiguous_width: u.arbitrary()?,
+        control_style: u.arbitrary()?,
+        #[cfg(feature = "confusables")]
+        confusables: u.arbitrary()?,
+        highlight: None,
+        context: u.arbitrary()?,
+        #[cfg(feature = "alloc")]
+        remote: u.int_in_range(0..=2)?,
+        #[cfg(feature = "alloc")]
+        pattern: *u.choose(&[None, Some(Pattern::Glob), Some(Pattern::Regex)])?,
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        multiline: u.arbitrary()?,
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        fallback: u.arbitrary()?,
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        crontab: u.arbitrary()?,
+        #[cfg(feature = "sql")]
+        sql_dialect: u.arbitrary()?,
+        #[cfg(feature = "dockerfile")]
+        dockerfile_form: u.arbitrary()?,
+        #[cfg(feature = "cmake")]
+        cmake_list: u.arbitrary()?,
+        #[cfg(feature = "sanitize")]
+        strip_ansi: u.arbitrary()?,
+        #[cfg(feature = "sanitize")]
+        hex_c1: u.arbitrary()?,
+    })
+}
+
+/// The options are taken first, so that the text can be the rest of the
+/// input in [`arbitrary_take_rest`](Arbitrary::arbitrary_take_rest).
+impl<'a> Arbitrary<'a> for Quoted<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let opts = options(u)?;
+        let style = u.arbitrary()?;
+        let text = u.arbitrary()?;
+        Ok(Quoted {
+            opts,
+            ..Quoted::new(style, text)
+        })
+    }
+
+    fn arbitrary_take_rest(mut u: Unstructured<'a>) -> Result<Self> {
+        let opts = options(&mut u)?;
+        let style = u.arbitrary()?;
+        let text = <&str>::arbitrary_take_rest(u)?;
+        Ok(Quoted {
+            opts,
+            ..Quoted::new(style, text)
+        })
+    }
+}
@@ -0,0 +1,121 @@
+//! Inputs for measuring performance.
+//!
+//! These are the inputs that the crate's own benchmarks use. They're
+//! generated from a fixed pattern, so the same length always gives the same
+//! input, and each one aims at a different part of the quoting code. Other
+//! crates can use them to check that a dependency update didn't make their
+//! output slower.
+//!
+//! ```
+//! use os_display::{bench_corpus, Quoted};
+//!
+//! # #[cfg(feature = "unix")] {
+//! let text = bench_corpus::escape_heavy(4096);
+//! assert_eq!(text.len(), 4096);
+//! let quoted = Quoted::unix(&text).to_string();
+//! assert!(quoted.len() > text.len());
+//! # }
+//! ```
+//!
+//! Lengths are in bytes, or in code units for [`utf16_surrogates`]. The text
+//! is cut at a character boundary, so it can be a little shorter.
+//!
+//! # Optional
+//! This requires the `bench-corpus` feature.
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+
+/// A long path with many short components, mostly safe.
+///
+/// This spends most of its time in the scan for characters that need quotes.
+pub fn long_paths(len: usize) -> String {
+    let components = [
+        "usr",
+        "share",
+        "doc",
+        "os_display",
+        "examples",
+        "src",
+        "target",
+        "release",
+        "build",
+        "out.d",
+    ];
+    let mut text = String::with_capacity(len);
+    let mut i = 0;
+    while text.len() < len {
+        text.push('/');
+        text.push_str(components[i % components.len()]);
+        i += 1;
+    }
+    truncate(&mut text, len);
+    text
+}
+
+/// Text where most characters need an escape, like terminal control codes,
+/// newlines, bidirectional overrides and quotes.
+///
+/// This spends most of its time writing escapes rather than scanning.
+pub fn escape_heavy(len: usize) -> String {
+    let piece = "\x1b[31mred\x1b[0m\t'quoted'\n\"$x\"\r\u{202E}txt.exe\u{7F}\u{200B}";
+    repeat(piece, len)
+}
+
+/// Bytes that aren't valid UTF-8, mixed with ordinary text.
+///
+/// This is for [`Quoted::unix_raw`](crate::Quoted::unix_raw), which has to
+/// decode the text a character at a time.
+pub fn invalid_utf8(len: usize) -> Vec<u8> {
+    let piece: &[u8] = b"caf\xE9 \xFF\xFEname\xC3\x28.txt\xF0\x9F\x98 ok/";
+    let mut bytes = Vec::with_capacity(len);
+    while bytes.len() < len {
+        bytes.extend_from_slice(piece);
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// UTF-16 with unpaired surrogates, mixed with valid surrogate pairs and
+/// ordinary text.
+///
+/// This is for [`Quoted::windows_raw`](crate::Quoted::windows_raw).
+pub fn utf16_surrogates(len: usize) -> Vec<u16> {
+    // "file\u{D800} name\u{DC00}\u{1F600}.txt", where the first two are unpaired
+    let piece: &[u16] = &[
+        0x66, 0x69, 0x6C, 0x65, 0xD800, 0x20, 0x6E, 0x61, 0x6D, 0x65, 0xDC00, 0xD83D, 0xDE00, 0x2E,
+        0x74, 0x78, 0x74,
+    ];
+    let mut units = Vec::with_capacity(len);
+    while units.len() < len {
+        units.extend_from_slice(piece);
+    }
+    units.truncate(len);
+    units
+}
+
+/// Arguments for external commands on Windows, with double quotes and runs
+/// of backslashes that have to be escaped.
+///
+/// This is for [`Quoted::external`](crate::Quoted::external).
+pub fn external_args(len: usize) -> String {
+    let piece = r#"C:\Program Files\App\ --name="my \"file\"" \\server\share\\ "#;
+    repeat(piece, len)
+}
+
+fn repeat(piece: &str, len: usize) -> String {
+    let mut text = String::with_capacity(len + piece.len());
+    while text.len() < len {
+        text.push_str(piece);
+    }
+    truncate(&mut text, len);
+    text
+}
+
+fn truncate(text: &mut String, len: usize) {
+    let mut end = len.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+}
@@ -0,0 +1,204 @@
+//! A C interface, for the `capi` feature.
+//!
+//! The functions quote a string into a buffer supplied by the caller and
+//! return the length of the output, like `snprintf()`:
+//!
+//! ```c
+//! char buf[64];
+//! size_t len = os_display_quote_unix((const uint8_t *)"foo bar", 7, buf, sizeof buf);
+//! if (len < sizeof buf) {
+//!     puts(buf); // 'foo bar'
+//! }
+//! ```
+//!
+//! The output is NUL-terminated. If the buffer is too small for it, an empty
+//! string is written instead, and the return value says how much room the
+//! output needs (not counting the NUL). Pass a `NULL` buffer with a length of
+//! 0 to only measure the output.
+//!
+//! Nothing is allocated, so there's nothing to free.
+//!
+//! To use this from C, build the crate as a library with
+//! `cargo rustc --release --features capi,unix,windows --crate-type staticlib`
+//! (or `cdylib`). That needs the default `std` feature, since a `no_std`
+//! library has no panic handler. A header can be generated with
+//! [cbindgen](https://github.com/mozilla/cbindgen) using the `cbindgen.toml`
+//! in the repository.
+//!
+//! This is the only part of the crate that uses `unsafe`.
+//!
+//! # Optional
+//! This requires the `capi` feature, and the `unix` or `windows` feature
+//! for each style. The UTF-16 functions also need `alloc`.
+
+use core::ffi::c_char;
+use core::slice;
+
+use crate::Quoted;
+
+/// Returned instead of a length if the input can't be quoted, e.g. because
+/// it's not valid UTF-8.
+pub const OS_DISPLAY_ERROR: usize = usize::MAX;
+
+/// Borrow the input. A length of 0 allows a null pointer.
+unsafe fn input<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Borrow the output buffer. A length of 0 allows a null pointer.
+unsafe fn output<'a>(ptr: *mut c_char, len: usize) -> &'a mut [u8] {
+    if ptr.is_null() || len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(ptr as *mut u8, len)
+    }
+}
+
+/// Write the output with a NUL after it, or an empty string if there's no
+/// room, and return its length.
+fn render(quoted: Quoted<'_>, out: &mut [u8]) -> usize {
+    let room = match out.len().checked_sub(1) {
+        Some(room) => room,
+        None => return quoted.display_len(),
+    };
+    match quoted.render_into(&mut out[..room]) {
+        Ok(len) => {
+            out[len] = 0;
+            len
+        }
+        Err(err) => {
+            out[0] = 0;
+            err.required()
+        }
+    }
+}
+
+/// Quote bytes in bash/ksh style, like [`Quoted::unix_raw`]. The input
+/// doesn't have to be valid UTF-8.
+///
+/// # Safety
+/// `input` must point to `input_len` readable bytes and `output` to
+/// `output_len` writable bytes. Either may be null if its length is 0.
+#[cfg(feature = "unix")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_quote_unix(
+    input: *const u8,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    let quoted = Quoted::unix_raw(self::input(input, input_len));
+    render(quoted, self::output(output, output_len))
+}
+
+/// Like [`os_display_quote_unix`], but only add quotes if they're needed.
+///
+/// # Safety
+/// See [`os_display_quote_unix`].
+#[cfg(feature = "unix")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_maybe_quote_unix(
+    input: *const u8,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    let quoted = Quoted::unix_raw(self::input(input, input_len)).force(false);
+    render(quoted, self::output(output, output_len))
+}
+
+/// Quote UTF-8 text in PowerShell style, like [`Quoted::windows`].
+///
+/// Returns [`OS_DISPLAY_ERROR`] if the input isn't valid UTF-8. Use
+/// [`os_display_quote_windows_utf16`] for file names that might not be.
+///
+/// # Safety
+/// See [`os_display_quote_unix`].
+#[cfg(feature = "windows")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_quote_windows(
+    input: *const u8,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    quote_windows_utf8(
+        self::input(input, input_len),
+        self::output(output, output_len),
+        true,
+    )
+}
+
+/// Like [`os_display_quote_windows`], but only add quotes if they're needed.
+///
+/// # Safety
+/// See [`os_display_quote_unix`].
+#[cfg(feature = "windows")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_maybe_quote_windows(
+    input: *const u8,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    quote_windows_utf8(
+        self::input(input, input_len),
+        self::output(output, output_len),
+        false,
+    )
+}
+
+#[cfg(feature = "windows")]
+fn quote_windows_utf8(input: &[u8], out: &mut [u8], force: bool) -> usize {
+    match core::str::from_utf8(input) {
+        Ok(text) => render(Quoted::windows(text).force(force), out),
+        Err(_) => {
+            if let Some(first) = out.first_mut() {
+                *first = 0;
+            }
+            OS_DISPLAY_ERROR
+        }
+    }
+}
+
+/// Quote UTF-16 in PowerShell style, like [`Quoted::windows_raw`]. The
+/// input is a count of 16-bit units, not bytes, and doesn't have to be
+/// valid UTF-16. The output is UTF-8.
+///
+/// # Safety
+/// `input` must point to `input_len` readable 16-bit units and `output` to
+/// `output_len` writable bytes. Either may be null if its length is 0.
+#[cfg(feature = "windows")]
+#[cfg(feature = "alloc")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_quote_windows_utf16(
+    input: *const u16,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    let quoted = Quoted::windows_raw(self::input(input, input_len));
+    render(quoted, self::output(output, output_len))
+}
+
+/// Like [`os_display_quote_windows_utf16`], but only add quotes if they're
+/// needed.
+///
+/// # Safety
+/// See [`os_display_quote_windows_utf16`].
+#[cfg(feature = "windows")]
+#[cfg(feature = "alloc")]
+#[no_mangle]
+pub unsafe extern "C" fn os_display_maybe_quote_windows_utf16(
+    input: *const u16,
+    input_len: usize,
+    output: *mut c_char,
+    output_len: usize,
+) -> usize {
+    let quoted = Quoted::windows_raw(self::input(input, input_len)).force(false);
+    render(quoted, self::output(output, output_len))
+}
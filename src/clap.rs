@@ -0,0 +1,112 @@
+//! Quoting invalid values in [`clap`](https://docs.rs/clap) errors.
+//!
+//! clap puts plain single quotes around a value that it couldn't parse,
+//! so a value with a newline or an escape sequence is echoed back as it is.
+//! [`QuotedValueParser`] wraps another parser and quotes the value with
+//! [`Quoted::native_raw`] instead:
+//!
+//! ```
+//! use clap::{value_parser, Arg, Command};
+//! use os_display::clap::QuotedValueParser;
+//!
+//! let cmd = Command::new("prog").arg(
+//!     Arg::new("count")
+//!         .long("count")
+//!         .value_parser(QuotedValueParser::new(value_parser!(u8))),
+//! );
+//! let err = cmd
+//!     .try_get_matches_from(["prog", "--count", "1\x1B[2J"])
+//!     .unwrap_err();
+//! # #[cfg(unix)]
+//! assert!(err
+//!     .to_string()
+//!     .starts_with("error: invalid value $'1\\x1B[2J' for '--count <count>': "));
+//! ```
+//!
+//! # Optional
+//! This requires the `clap`, `native` and `std` features.
+
+use std::boxed::Box;
+use std::error::Error as _;
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::format;
+
+use ::clap::builder::{PossibleValue, TypedValueParser};
+use ::clap::error::{Error, ErrorKind};
+use ::clap::{Arg, Command};
+
+use crate::Quoted;
+
+/// A value parser that quotes the value in its errors, see the
+/// [module documentation](self).
+///
+/// Only errors that say why the value is invalid are rewritten, like the
+/// error from parsing a number, along with invalid UTF-8. Other errors, like
+/// a value that isn't one of the possible values, are passed on unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct QuotedValueParser<P> {
+    inner: P,
+}
+
+impl<P: TypedValueParser> QuotedValueParser<P> {
+    /// Wrap a parser, like one from [`clap::value_parser!`](::clap::value_parser).
+    pub fn new(inner: P) -> Self {
+        QuotedValueParser { inner }
+    }
+}
+
+impl<P: TypedValueParser> TypedValueParser for QuotedValueParser<P> {
+    type Value = P::Value;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        self.inner.parse_ref(cmd, arg, value).map_err(|err| {
+            if let Some(source) = err.source() {
+                invalid_value(cmd, arg, value, source)
+            } else if err.kind() == ErrorKind::InvalidUtf8 {
+                invalid_value(cmd, arg, value, "invalid UTF-8")
+            } else {
+                err
+            }
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        self.inner.possible_values()
+    }
+}
+
+/// Make an error for an invalid value, with the value quoted, for use in
+/// a custom [`TypedValueParser`].
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use os_display::clap::invalid_value;
+///
+/// let cmd = Command::new("prog");
+/// let arg = Arg::new("name").long("name").num_args(1);
+/// let err = invalid_value(&cmd, Some(&arg), "foo bar".as_ref(), "no spaces allowed");
+/// # #[cfg(unix)]
+/// assert!(err
+///     .to_string()
+///     .starts_with("error: invalid value 'foo bar' for '--name <name>': no spaces allowed\n"));
+/// ```
+pub fn invalid_value(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &OsStr,
+    reason: impl Display,
+) -> Error {
+    let value = Quoted::native_raw(value);
+    let message = match arg {
+        Some(arg) => format!("invalid value {} for '{}': {}", value, arg, reason),
+        // An external subcommand.
+        None => format!("invalid value {}: {}", value, reason),
+    };
+    cmd.clone().error(ErrorKind::ValueValidation, message)
+}
@@ -0,0 +1,344 @@
+//! Why text was quoted or escaped, for [`Quoted::classify`](crate::Quoted::classify).
+
+use core::fmt;
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+
+use crate::security;
+use crate::sink::Sink;
+use crate::tables;
+use crate::width;
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+use crate::Quoted;
+use crate::{Original, Width};
+
+/// A reason for quoting or escaping text, from [`Quoted::classify`](crate::Quoted::classify).
+///
+/// More reasons may be added in the future, so a `match` needs a wildcard
+/// arm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Reason {
+    /// Nothing else called for quotes, they were asked for with
+    /// [`Quoted::force`](crate::Quoted::force) or a similar option, or the
+    /// style always uses them.
+    Forced,
+    /// The text is empty, and the quotes are the only thing that's left.
+    Empty,
+    /// The text contains whitespace, which would split it.
+    Whitespace,
+    /// The text contains a character that means something to a shell, like
+    /// `$` or `*`, or that can't appear as-is inside the quotes.
+    ShellSpecial,
+    /// The text starts with a `-`, which PowerShell would take as a
+    /// parameter.
+    LeadingDash,
+    /// The text contains a character that might not show up at all, like a
+    /// zero-width space.
+    ZeroWidth,
+    /// The text contains a control character or another character that isn't
+    /// safe to print to a terminal, like a line separator. With the
+    /// `strict-unassigned` feature this includes unassigned and private-use
+    /// codepoints.
+    Control,
+    /// The text contains a bidi control character that could make it look
+    /// different from what it is, see [`BidiPolicy`](crate::BidiPolicy).
+    Bidi,
+    /// The text isn't valid UTF-8 or UTF-16.
+    InvalidUnicode,
+    /// The text contains a character that looks like ASCII, see
+    /// [`Quoted::escape_confusables`](crate::Quoted::escape_confusables).
+    Confusable,
+    /// The text has too many combining marks stacked together, see
+    /// [`Quoted::max_combining`](crate::Quoted::max_combining).
+    Combining,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+const REASONS: [Reason; 11] = [
+    Reason::Forced,
+    Reason::Empty,
+    Reason::Whitespace,
+    Reason::ShellSpecial,
+    Reason::LeadingDash,
+    Reason::ZeroWidth,
+    Reason::Control,
+    Reason::Bidi,
+    Reason::InvalidUnicode,
+    Reason::Confusable,
+    Reason::Combining,
+];
+
+impl Reason {
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// What was done to text and why, from
+/// [`Quoted::classify`](crate::Quoted::classify).
+///
+/// Text that's written as it is has no reasons at all.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Classification {
+    quoted: bool,
+    escaped: bool,
+    reasons: u16,
+}
+
+impl Classification {
+    /// Whether the output has quotes or other syntax around the text.
+    pub fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+
+    /// Whether the output has escape sequences.
+    pub fn is_escaped(&self) -> bool {
+        self.escaped
+    }
+
+    /// Whether the text was written as it is.
+    pub fn is_plain(&self) -> bool {
+        !self.quoted && !self.escaped
+    }
+
+    /// Check for a single reason.
+    pub fn contains(&self, reason: Reason) -> bool {
+        self.reasons & reason.bit() != 0
+    }
+
+    /// All the reasons, in the order they're listed in [`Reason`].
+    pub fn reasons(&self) -> impl Iterator<Item = Reason> {
+        let reasons = self.reasons;
+        REASONS
+            .iter()
+            .cloned()
+            .filter(move |reason| reasons & reason.bit() != 0)
+    }
+
+    fn add(&mut self, reason: Reason) {
+        self.reasons |= reason.bit();
+    }
+}
+
+/// Characters that shells treat specially, in any of the supported styles.
+/// Some of them only matter at the start.
+const SPECIAL: &[u8] = b"|&;<>()$`\\\"'*?[]=^{},~#!@%";
+
+/// The reason that a character is a problem wherever it appears, if any.
+pub(crate) fn char_reason(ch: char) -> Option<Reason> {
+    if security::is_bidi(ch) {
+        Some(Reason::Bidi)
+    } else if crate::requires_escape(ch) {
+        Some(Reason::Control)
+    } else if security::is_invisible(ch) {
+        Some(Reason::ZeroWidth)
+    } else if crate::is_confusable(ch) {
+        Some(Reason::Confusable)
+    } else {
+        None
+    }
+}
+
+/// The reason that a character was escaped. If it's not a problem by itself
+/// then it must have meant something to the shell.
+fn escape_reason(original: Original) -> Reason {
+    match original {
+        Original::Char(ch) => match char_reason(ch) {
+            Some(reason) => reason,
+            None if security::is_combining(ch) => Reason::Combining,
+            None => Reason::ShellSpecial,
+        },
+        Original::Byte(_) | Original::Unit(_) => Reason::InvalidUnicode,
+    }
+}
+
+/// Looks at the output as it's written. Escape sequences explain themselves,
+/// and the reason for quotes has to be somewhere in the text inside them.
+pub(crate) struct Classifier {
+    classification: Classification,
+    powershell: bool,
+    depth: usize,
+    /// Whether anything has been written inside the outermost quotes.
+    content: bool,
+    ambiguous: Width,
+}
+
+impl Classifier {
+    pub(crate) fn new(powershell: bool, ambiguous: Width) -> Self {
+        Classifier {
+            classification: Classification::default(),
+            powershell,
+            depth: 0,
+            content: false,
+            ambiguous,
+        }
+    }
+
+    /// The first character of the text gets extra scrutiny.
+    fn first(&mut self, ch: char) {
+        if self.content {
+            return;
+        }
+        self.content = true;
+        if self.depth == 0 {
+            return;
+        }
+        if width::may_be_zero_width(ch, self.ambiguous) {
+            self.classification.add(Reason::ZeroWidth);
+        }
+        if self.powershell && ch == '-' {
+            self.classification.add(Reason::LeadingDash);
+        }
+    }
+
+    /// Work out the reasons once the whole output has been seen.
+    pub(crate) fn finish(mut self) -> Classification {
+        let classification = &mut self.classification;
+        if classification.quoted && classification.reasons == 0 {
+            classification.add(if self.content {
+                Reason::Forced
+            } else {
+                Reason::Empty
+            });
+        }
+        self.classification
+    }
+}
+
+impl Sink for Classifier {
+    fn open(&mut self, _text: &str) -> fmt::Result {
+        self.classification.quoted = true;
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn close(&mut self, _text: &str) -> fmt::Result {
+        self.depth = self.depth.saturating_sub(1);
+        Ok(())
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        for ch in text.chars() {
+            self.first(ch);
+            if self.depth == 0 {
+                continue;
+            }
+            if tables::contains(tables::WHITE_SPACE, ch) || ch == '\u{2800}' {
+                self.classification.add(Reason::Whitespace);
+            }
+            if ch.is_ascii() && SPECIAL.contains(&(ch as u8)) {
+                self.classification.add(Reason::ShellSpecial);
+            }
+            if crate::requires_escape(ch) {
+                self.classification.add(Reason::Control);
+            }
+        }
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, _rendered: &str) -> fmt::Result {
+        if let Original::Char(ch) = original {
+            self.first(ch);
+        }
+        self.content = true;
+        self.classification.escaped = true;
+        self.classification.add(escape_reason(original));
+        Ok(())
+    }
+}
+
+/// Check whether text can be passed to the default shell for the platform as
+/// it is, without any quoting or escaping.
+///
+/// That's the case if [`Quoted::native_raw`] would write it unchanged with
+/// [`Quoted::force`] turned off. This stops at the first character that
+/// needs something, without formatting the rest.
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use os_display::is_shell_safe;
+///
+/// assert!(is_shell_safe(OsStr::new("report-2024.txt")));
+/// assert!(!is_shell_safe(OsStr::new("my report.txt")));
+/// assert!(!is_shell_safe(OsStr::new("")));
+/// ```
+///
+/// # Optional
+/// This requires the `native` and `std` features.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub fn is_shell_safe(text: &OsStr) -> bool {
+    Quoted::native_raw(text)
+        .force(false)
+        .write(&mut Check { quotes: false })
+        .is_ok()
+}
+
+/// Check whether text can be printed to a terminal as it is.
+///
+/// That's the case if [`Quoted::native_raw`] wouldn't escape any characters
+/// other than shell syntax, with [`Quoted::escape_invisible`] turned on: no
+/// control characters, no invisible characters (including bidi control
+/// characters) and no invalid unicode. This stops at the first one.
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use os_display::is_terminal_safe;
+///
+/// assert!(is_terminal_safe(OsStr::new("my report.txt")));
+/// assert!(!is_terminal_safe(OsStr::new("\x1B]0;pwned\x07")));
+/// assert!(!is_terminal_safe(OsStr::new("invoice\u{202E}fdp.exe")));
+/// assert!(!is_terminal_safe(OsStr::new("\u{200B}")));
+/// ```
+///
+/// See [`security::dangerous_chars`](crate::security::dangerous_chars) to
+/// find out which characters are the problem.
+///
+/// # Optional
+/// This requires the `native` and `std` features.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub fn is_terminal_safe(text: &OsStr) -> bool {
+    Quoted::native_raw(text)
+        .escape_invisible(true)
+        .write(&mut Check { quotes: true })
+        .is_ok()
+}
+
+/// Fails as soon as something is quoted or escaped that isn't allowed.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+struct Check {
+    quotes: bool,
+}
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+impl Sink for Check {
+    fn open(&mut self, _text: &str) -> fmt::Result {
+        if self.quotes {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+
+    fn close(&mut self, _text: &str) -> fmt::Result {
+        Ok(())
+    }
+
+    fn literal(&mut self, _text: &str) -> fmt::Result {
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, _rendered: &str) -> fmt::Result {
+        if self.quotes && escape_reason(original) == Reason::ShellSpecial {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Arguments in CMake code, for [`Quoted::cmake`](crate::Quoted::cmake).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::{Options, Original};
+
+/// Bytes that can appear in an unquoted argument. A `;` would split it into
+/// a list, and most other punctuation means something somewhere.
+fn is_bare(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => true,
+        b'_' | b'-' | b'.' | b'/' | b':' | b'@' | b'%' | b'+' | b',' | b'=' => true,
+        _ => false,
+    }
+}
+
+/// This writes a quoted argument. Everything in it is literal except for
+/// backslash escapes and variable references, so `$` is escaped too.
+pub(crate) fn write(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if !opts.force_quote && !text.is_empty() && text.bytes().all(is_bare) {
+        return out.literal(text);
+    }
+
+    out.open("\"")?;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '\\' => "\\\\",
+            '"' => "\\\"",
+            '$' => "\\$",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            // This one keeps its backslash, which stops the list commands
+            // from splitting on it.
+            ';' if opts.cmake_list => "\\;",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        out.escape(Original::Char(ch), escape)?;
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("\"")
+}
@@ -0,0 +1,158 @@
+//! Arguments in Dockerfile instructions, for
+//! [`Quoted::dockerfile`](crate::Quoted::dockerfile).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::{DockerfileForm, Options, Original, TextEscapes};
+
+pub(crate) fn write(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    match opts.dockerfile_form {
+        DockerfileForm::Shell => write_shell(out, text, opts),
+        DockerfileForm::Exec => write_exec(out, text, opts),
+    }
+}
+
+/// Check whether a character has to be escaped. A newline would end the
+/// instruction, so it's never written as it is.
+fn escapes(ch: char, opts: &Options, text_escapes: TextEscapes) -> bool {
+    opts.escapes(ch) || text_escapes.contains(ch)
+}
+
+/// Bytes that can appear in an unquoted shell word.
+fn is_bare(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => true,
+        b'_' | b'-' | b'.' | b'/' | b':' | b'@' | b'%' | b'+' | b',' | b'=' => true,
+        _ => false,
+    }
+}
+
+/// The command is run with `/bin/sh -c`, which is often dash or busybox, so
+/// we stick to POSIX: single quotes if possible, and otherwise the output of
+/// a `printf` command.
+fn write_shell(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    let text_escapes = opts.text_escapes(text.chars().map(Some));
+    if text.chars().any(|ch| escapes(ch, opts, text_escapes)) {
+        return write_printf(out, text, opts, text_escapes);
+    }
+    if !opts.force_quote && !text.is_empty() && text.bytes().all(is_bare) {
+        return out.literal(text);
+    }
+    let mut first = true;
+    for chunk in text.split('\'') {
+        if !first {
+            out.escape(Original::Char('\''), "\\'")?;
+        }
+        if !chunk.is_empty() || (first && text.is_empty()) {
+            out.open("'")?;
+            out.literal(chunk)?;
+            out.close("'")?;
+        }
+        first = false;
+    }
+    Ok(())
+}
+
+/// Command substitution drops trailing newlines, so those get lost, see
+/// [`drops_newlines`].
+fn write_printf(
+    out: &mut dyn Sink,
+    text: &str,
+    opts: &Options,
+    text_escapes: TextEscapes,
+) -> fmt::Result {
+    out.open("\"$(printf '")?;
+    let mut text = text;
+    if text.starts_with('-') {
+        // Otherwise it would look like an option.
+        out.escape(Original::Char('-'), "\\055")?;
+        text = &text[1..];
+    }
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '\\' => "\\\\",
+            '%' => "%%",
+            '\'' => "\\047",
+            _ if escapes(ch, opts, text_escapes) => "",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        if escape.is_empty() {
+            let bytes = &text.as_bytes()[index..start];
+            out.escape_fmt(Original::Char(ch), format_args!("{}", Octal(bytes)))?;
+        } else {
+            out.escape(Original::Char(ch), escape)?;
+        }
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("')\"")
+}
+
+/// Check whether the output loses newlines at the end of the text. Newlines
+/// are always escaped, which in shell form means printf, inside a command
+/// substitution that strips them again.
+pub(crate) fn drops_newlines(text: &str, opts: &Options) -> bool {
+    opts.dockerfile_form == DockerfileForm::Shell && text.ends_with('\n')
+}
+
+/// Exec form is a JSON array, so each argument is a JSON string.
+fn write_exec(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    let text_escapes = opts.text_escapes(text.chars().map(Some));
+    out.open("\"")?;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\t' => "\\t",
+            '\r' => "\\r",
+            _ if escapes(ch, opts, text_escapes) => "",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        if escape.is_empty() {
+            out.escape_fmt(Original::Char(ch), format_args!("{}", Utf16Escapes(ch)))?;
+        } else {
+            out.escape(Original::Char(ch), escape)?;
+        }
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("\"")
+}
+
+/// Formats bytes as a series of `\ooo` escapes for `printf`.
+struct Octal<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Octal<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "\\{:03o}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a character as one or two `\uXXXX` escapes for JSON.
+struct Utf16Escapes(char);
+
+impl fmt::Display for Utf16Escapes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for unit in self.0.encode_utf16(&mut [0; 2]) {
+            write!(f, "\\u{:04X}", unit)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Values in `.env` files, for [`Quoted::dotenv`](crate::Quoted::dotenv).
+//!
+//! There's no standard for the format. This targets the parser in Docker
+//! Compose (compose-go) and python-dotenv, and stays away from anything they
+//! disagree on where possible.
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::{Options, Original};
+
+/// Bytes that can appear in a bare value. Anything else might be trimmed,
+/// start a comment, or be interpolated.
+fn is_bare(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => true,
+        b'_' | b'-' | b'.' | b'/' | b':' | b'@' | b'%' | b'+' | b',' => true,
+        _ => false,
+    }
+}
+
+/// Single quotes are taken literally, except that python-dotenv unescapes
+/// `\\` and `\'` inside them.
+fn is_single_safe(byte: u8) -> bool {
+    byte != b'\'' && byte != b'\\' && !byte.is_ascii_control()
+}
+
+pub(crate) fn write(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if !opts.force_quote && !text.is_empty() && text.bytes().all(is_bare) {
+        return out.literal(text);
+    }
+    if text.bytes().all(is_single_safe) {
+        out.open("'")?;
+        out.literal(text)?;
+        return out.close("'");
+    }
+
+    out.open("\"")?;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '\\' => "\\\\",
+            '"' => "\\\"",
+            // Without this compose would interpolate a variable.
+            '$' => "\\$",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\u{7}' => "\\a",
+            '\u{8}' => "\\b",
+            '\u{B}' => "\\v",
+            '\u{C}' => "\\f",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        out.escape(Original::Char(ch), escape)?;
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("\"")
+}
@@ -0,0 +1,35 @@
+//! Error messages about paths.
+//!
+//! # Optional
+//! This requires the `native` and `std` features.
+
+use std::io;
+use std::path::Path;
+
+use crate::{PathError, QuotedPathBuf};
+
+/// Attach a path to an I/O error, for a message like
+/// `'my file.txt': No such file or directory`.
+///
+/// The path is quoted with
+/// [`Quotable::maybe_quote`](crate::Quotable::maybe_quote) semantics, and
+/// only when the error is displayed. Until then it's stored as it is, so
+/// errors that are handled without being printed don't pay for it.
+///
+/// This is a shortcut for [`QuotedPathBuf::with_error`], see [`PathError`]
+/// for the details.
+///
+/// ```
+/// use os_display::error;
+/// use std::path::Path;
+///
+/// let path = Path::new("/nonexistent/my file.txt");
+/// let err = std::fs::read(path).map_err(|err| error::context(err, path));
+/// let err = err.unwrap_err();
+/// assert_eq!(err.error().kind(), std::io::ErrorKind::NotFound);
+/// # #[cfg(unix)]
+/// assert!(err.to_string().starts_with("'/nonexistent/my file.txt': "));
+/// ```
+pub fn context(err: io::Error, path: &Path) -> PathError<io::Error> {
+    QuotedPathBuf::new(path).with_error(err)
+}
@@ -0,0 +1,165 @@
+//! The escape sequences for single characters.
+//!
+//! These are the same sequences that [`Quoted`](crate::Quoted) uses with its
+//! default options, for tools that build their own strings piece by piece.
+//! The caller is responsible for the quotes around them.
+//!
+//! ```
+//! use os_display::escape;
+//!
+//! # #[cfg(feature = "unix")]
+//! assert_eq!(escape::unix_char('\n').to_string(), "\\n");
+//! # #[cfg(feature = "windows")]
+//! assert_eq!(escape::powershell_char('\n').to_string(), "`n");
+//! ```
+
+#[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+use core::fmt::{self, Display, Formatter, Write};
+
+/// Whether a character is escaped with the default options.
+#[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+fn escapes(ch: char) -> bool {
+    crate::requires_escape(ch)
+        || crate::security::is_bidi(ch)
+        || crate::security::is_hidden_text(ch)
+}
+
+/// Escape a character for use inside bash/ksh's `$'...'`.
+///
+/// Characters that don't need an escape are written as they are. After a
+/// `\x` escape a hex digit would be read as part of it, so in that case
+/// the quoting has to be interrupted (`'$'`) between the two.
+///
+/// ```
+/// # #[cfg(feature = "unix")] {
+/// use os_display::escape::unix_char;
+///
+/// assert_eq!(unix_char('\'').to_string(), r"\'");
+/// assert_eq!(unix_char('\u{1B}').to_string(), r"\x1B");
+/// assert_eq!(unix_char('\u{202E}').to_string(), r"\xE2\x80\xAE");
+/// assert_eq!(unix_char('a').to_string(), "a");
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `unix` feature, or the `native` feature on platforms
+/// other than Windows.
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+pub fn unix_char(ch: char) -> impl Display + Copy {
+    #[derive(Copy, Clone)]
+    struct UnixChar(char);
+
+    impl Display for UnixChar {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            use crate::unix::{escape_sequence, Escape, HexEscapes};
+
+            let ch = self.0;
+            match escape_sequence(ch, escapes(ch)) {
+                Some(Escape::Fixed(rendered)) => f.write_str(rendered),
+                Some(Escape::Hex) => {
+                    let mut buf = [0; 4];
+                    HexEscapes(ch.encode_utf8(&mut buf).as_bytes()).fmt(f)
+                }
+                None => f.write_char(ch),
+            }
+        }
+    }
+
+    UnixChar(ch)
+}
+
+/// Escape a byte that's not part of valid UTF-8 for use inside bash/ksh's
+/// `$'...'`.
+///
+/// ```
+/// # #[cfg(feature = "unix")] {
+/// use os_display::escape::unix_byte;
+///
+/// assert_eq!(unix_byte(0xFF).to_string(), r"\xFF");
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `unix` feature, or the `native` feature on platforms
+/// other than Windows.
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+pub fn unix_byte(byte: u8) -> impl Display + Copy {
+    #[derive(Copy, Clone)]
+    struct UnixByte(u8);
+
+    impl Display for UnixByte {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "\\x{:02X}", self.0)
+        }
+    }
+
+    UnixByte(byte)
+}
+
+/// Escape a character for use inside a PowerShell `"..."` string.
+///
+/// Characters that don't need an escape are written as they are. That
+/// includes backslashes, which only need escaping before a double quote if
+/// the string is passed to an external program.
+///
+/// ```
+/// # #[cfg(feature = "windows")] {
+/// use os_display::escape::powershell_char;
+///
+/// assert_eq!(powershell_char('$').to_string(), "`$");
+/// assert_eq!(powershell_char('\u{1B}').to_string(), "`u{1B}");
+/// assert_eq!(powershell_char('\u{201C}').to_string(), "`\u{201C}");
+/// assert_eq!(powershell_char('a').to_string(), "a");
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `windows` feature, or the `native` feature on Windows.
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+pub fn powershell_char(ch: char) -> impl Display + Copy {
+    #[derive(Copy, Clone)]
+    struct PowerShellChar(char);
+
+    impl Display for PowerShellChar {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            use crate::windows::{escape_sequence, Escape};
+
+            let ch = self.0;
+            match escape_sequence(ch, escapes(ch), false) {
+                Some(Escape::Fixed(rendered)) => f.write_str(rendered),
+                Some(Escape::Unicode) => write!(f, "`u{{{:02X}}}", ch as u32),
+                Some(Escape::Backtick) => write!(f, "`{}", ch),
+                None => f.write_char(ch),
+            }
+        }
+    }
+
+    PowerShellChar(ch)
+}
+
+/// Escape a UTF-16 code unit that's not part of valid UTF-16 (an unpaired
+/// surrogate) for use inside a PowerShell `"..."` string.
+///
+/// ```
+/// # #[cfg(feature = "windows")] {
+/// use os_display::escape::powershell_unit;
+///
+/// assert_eq!(powershell_unit(0xD800).to_string(), "`u{D800}");
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `windows` feature, or the `native` feature on Windows.
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+pub fn powershell_unit(unit: u16) -> impl Display + Copy {
+    #[derive(Copy, Clone)]
+    struct PowerShellUnit(u16);
+
+    impl Display for PowerShellUnit {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "`u{{{:04X}}}", self.0)
+        }
+    }
+
+    PowerShellUnit(unit)
+}
@@ -0,0 +1,165 @@
+//! Patterns in `.gitignore` and `.gitattributes` files and values in git
+//! config files, for [`Quoted::gitignore`](crate::Quoted::gitignore),
+//! [`Quoted::gitattributes`](crate::Quoted::gitattributes) and
+//! [`Quoted::git_config`](crate::Quoted::git_config).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::{Options, Original};
+
+/// The wildcards, and the backslash that escapes them. `]` only means
+/// something after a `[`.
+fn is_glob(ch: char) -> bool {
+    match ch {
+        '*' | '?' | '[' | '\\' => true,
+        _ => false,
+    }
+}
+
+/// A `#` at the start makes a comment and a `!` negates the pattern.
+fn is_special_start(ch: char) -> bool {
+    ch == '#' || ch == '!'
+}
+
+/// A `.gitignore` line can't contain control characters other than tabs.
+pub(crate) fn is_forbidden_in_gitignore(ch: char) -> bool {
+    ch.is_control() && ch != '\t'
+}
+
+/// Every special character gets a backslash. Only trailing spaces would be
+/// trimmed, but a backslash before each one is easier to read.
+///
+/// Forbidden characters are replaced by U+FFFD, so that the pattern can't
+/// match them. Empty text is written as nothing at all, which is a blank
+/// line that doesn't match anything either.
+pub(crate) fn write_gitignore(out: &mut dyn Sink, text: &str) -> fmt::Result {
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let backslash =
+            is_glob(ch) || ch == ' ' || ch == '\t' || (index == 0 && is_special_start(ch));
+        if !backslash && !is_forbidden_in_gitignore(ch) {
+            continue;
+        }
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        if backslash {
+            out.escape_fmt(Original::Char(ch), format_args!("\\{}", ch))?;
+        } else {
+            out.escape(Original::Char(ch), "\u{FFFD}")?;
+        }
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    Ok(())
+}
+
+/// The pattern ends at the first whitespace, so text with whitespace or
+/// control characters goes in C-style double quotes. Those are decoded
+/// before the pattern is parsed, so the backslashes that escape wildcards
+/// are doubled inside them.
+pub(crate) fn write_gitattributes(out: &mut dyn Sink, text: &str) -> fmt::Result {
+    let quote = text.is_empty()
+        || text.starts_with('"')
+        || text.chars().any(|ch| ch == ' ' || ch.is_control());
+    if quote {
+        out.open("\"")?;
+    }
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let backslash = is_glob(ch) || (index == 0 && is_special_start(ch));
+        let c_escape = quote && (ch == '"' || ch.is_control());
+        if !backslash && !c_escape {
+            continue;
+        }
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        match (quote, backslash) {
+            (false, _) => out.escape_fmt(Original::Char(ch), format_args!("\\{}", ch))?,
+            (true, true) if ch == '\\' => out.escape(Original::Char(ch), "\\\\\\\\")?,
+            (true, true) => out.escape_fmt(Original::Char(ch), format_args!("\\\\{}", ch))?,
+            (true, false) => write_c_escape(out, ch)?,
+        }
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    if quote {
+        out.close("\"")?;
+    }
+    Ok(())
+}
+
+/// The escapes that git's `unquote_c_style()` understands. A NUL would cut
+/// the pattern short, so it's replaced like in `.gitignore`.
+fn write_c_escape(out: &mut dyn Sink, ch: char) -> fmt::Result {
+    let rendered = match ch {
+        '\0' => "\u{FFFD}",
+        '"' => "\\\"",
+        '\x07' => "\\a",
+        '\x08' => "\\b",
+        '\x0C' => "\\f",
+        '\n' => "\\n",
+        '\r' => "\\r",
+        '\t' => "\\t",
+        '\x0B' => "\\v",
+        _ => {
+            let mut buf = [0; 4];
+            for &byte in ch.encode_utf8(&mut buf).as_bytes() {
+                out.escape_fmt(Original::Char(ch), format_args!("\\{:03o}", byte))?;
+            }
+            return Ok(());
+        }
+    };
+    out.escape(Original::Char(ch), rendered)
+}
+
+/// A git config value can't contain control characters other than these,
+/// which have escapes.
+pub(crate) fn is_forbidden_in_config(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t' && ch != '\x08'
+}
+
+/// Bytes that can appear in an unquoted value. Outside quotes `#` and `;`
+/// start a comment and whitespace at the ends is trimmed.
+fn is_bare(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => true,
+        b'_' | b'-' | b'.' | b'/' | b':' | b'@' | b'%' | b'+' | b',' | b'=' | b'~' => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn write_config(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if !opts.force_quote && !text.is_empty() && text.bytes().all(is_bare) {
+        return out.literal(text);
+    }
+
+    out.open("\"")?;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\t' => "\\t",
+            '\x08' => "\\b",
+            _ if is_forbidden_in_config(ch) => "\u{FFFD}",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        out.escape(Original::Char(ch), escape)?;
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("\"")
+}
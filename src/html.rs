@@ -0,0 +1,79 @@
+//! HTML text, for [`Quoted::html`](crate::Quoted::html).
+
+use core::fmt;
+use core::str::from_utf8;
+
+use crate::security::{self, Bidi};
+use crate::sink::Sink;
+use crate::utf8::chars;
+use crate::util::utf8_chunks;
+use crate::{Options, Original};
+
+pub(crate) fn write(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    let escapes = opts.text_escapes(chars(text));
+
+    // The bidi characters can only be left alone if we can see all of them.
+    // Text with invalid unicode gets them escaped, like in a shell.
+    let mut escape_bidi = true;
+    let mut isolate = false;
+    if let Ok(text) = from_utf8(text) {
+        if text.chars().any(security::is_bidi) {
+            match security::apply_bidi_policy(text, opts.bidi) {
+                Bidi::Ok => escape_bidi = false,
+                Bidi::Unterminated => {
+                    escape_bidi = false;
+                    isolate = true;
+                }
+                Bidi::Suspicious => (),
+            }
+        }
+    }
+
+    if isolate {
+        out.open("<bdi>")?;
+    }
+    for chunk in utf8_chunks(text) {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(byte) => {
+                out.escape(Original::Byte(byte), "&#xFFFD;")?;
+                continue;
+            }
+        };
+        let mut start = 0;
+        for (index, ch) in chunk.char_indices() {
+            let reference = match ch {
+                '<' => Some("&lt;"),
+                '>' => Some("&gt;"),
+                '&' => Some("&amp;"),
+                '"' => Some("&quot;"),
+                '\'' => Some("&#39;"),
+                _ => None,
+            };
+            let numeric = reference.is_none()
+                && (crate::requires_escape(ch)
+                    || (escape_bidi && security::is_bidi(ch))
+                    || (opts.escape_hidden_text && security::is_hidden_text(ch))
+                    || (opts.escape_invisible && security::is_invisible(ch))
+                    || escapes.contains(ch));
+            if reference.is_none() && !numeric {
+                continue;
+            }
+            if start < index {
+                out.literal(&chunk[start..index])?;
+            }
+            start = index + ch.len_utf8();
+            match reference {
+                Some(reference) => out.escape(Original::Char(ch), reference)?,
+                None => out.escape_fmt(Original::Char(ch), format_args!("&#x{:X};", ch as u32))?,
+            }
+        }
+        if start < chunk.len() {
+            out.literal(&chunk[start..])?;
+        }
+    }
+    if isolate {
+        out.close("</bdi>")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! OSC 8 hyperlinks: <https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda>
+
+use core::fmt::{self, Write};
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+use std::{borrow::Cow, path::Path};
+
+use crate::sink::{Plain, Sink};
+use crate::util::utf8_chunks;
+use crate::Original;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Link<'a> {
+    Uri(&'a str),
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    Path(&'a Path),
+}
+
+/// Wrap the output of `inner` in a hyperlink.
+///
+/// If no URL can be made for the link target the output is left as it is.
+pub(crate) fn write(
+    f: &mut dyn Write,
+    link: Link<'_>,
+    inner: impl FnOnce(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    match link {
+        Link::Uri(uri) => {
+            f.write_str("\x1B]8;;")?;
+            // Anything is fine as long as it doesn't end the sequence, so we
+            // only encode whitespace, control codes and non-ASCII.
+            write_percent_encoded(&mut Plain(&mut *f), uri.as_bytes(), |byte| {
+                byte.is_ascii_graphic()
+            })?;
+        }
+        #[cfg(feature = "native")]
+        #[cfg(feature = "std")]
+        Link::Path(path) => {
+            let path = match file_url_path(path) {
+                Some(path) => path,
+                None => return inner(f),
+            };
+            f.write_str("\x1B]8;;")?;
+            write_file_url(&mut Plain(&mut *f), &path)?;
+        }
+    }
+    f.write_str("\x1B\\")?;
+    inner(f)?;
+    f.write_str("\x1B]8;;\x1B\\")
+}
+
+/// Make a path absolute, if it can be expressed as a `file://` URL.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub(crate) fn file_url_path(path: &Path) -> Option<Cow<'_, Path>> {
+    let path = if path.is_absolute() {
+        Cow::Borrowed(path)
+    } else {
+        Cow::Owned(std::env::current_dir().ok()?.join(path))
+    };
+    if is_linkable(&path) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Only ASCII bytes can be kept. Each other character becomes a single
+/// escape, with one `%XX` for every byte.
+fn write_percent_encoded(
+    out: &mut dyn Sink,
+    bytes: &[u8],
+    keep: impl Fn(u8) -> bool,
+) -> fmt::Result {
+    for chunk in utf8_chunks(bytes) {
+        match chunk {
+            Ok(chunk) => {
+                let mut pos = 0;
+                for (index, ch) in chunk.char_indices() {
+                    if ch.is_ascii() && keep(ch as u8) {
+                        continue;
+                    }
+                    if pos < index {
+                        out.literal(&chunk[pos..index])?;
+                    }
+                    pos = index + ch.len_utf8();
+                    let encoded = PercentEncoded(&chunk.as_bytes()[index..pos]);
+                    out.escape_fmt(Original::Char(ch), format_args!("{}", encoded))?;
+                }
+                if pos < chunk.len() {
+                    out.literal(&chunk[pos..])?;
+                }
+            }
+            Err(byte) => out.escape_fmt(Original::Byte(byte), format_args!("%{:02X}", byte))?,
+        }
+    }
+    Ok(())
+}
+
+/// Formats bytes as a series of `%XX` escapes.
+struct PercentEncoded<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for PercentEncoded<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "%{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Characters that may appear unencoded in the path of a `file://` URL.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+fn is_path_safe(byte: u8) -> bool {
+    match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => true,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+#[cfg(not(windows))]
+fn is_linkable(_path: &Path) -> bool {
+    true
+}
+
+/// We leave the host empty, which means the local machine.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+#[cfg(not(windows))]
+pub(crate) fn write_file_url(out: &mut dyn Sink, path: &Path) -> fmt::Result {
+    out.literal("file://")?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        write_percent_encoded(out, path.as_os_str().as_bytes(), is_path_safe)
+    }
+    #[cfg(not(unix))]
+    {
+        write_percent_encoded(out, path.to_string_lossy().as_bytes(), is_path_safe)
+    }
+}
+
+/// Only drive letters and UNC shares can be expressed as a `file://` URL.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+#[cfg(windows)]
+fn is_linkable(path: &Path) -> bool {
+    use std::path::{Component, Prefix};
+
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) | Prefix::VerbatimDisk(_) => true,
+            Prefix::UNC(..) | Prefix::VerbatimUNC(..) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// `C:\foo` becomes `file:///C:/foo` and `\\server\share\foo` becomes
+/// `file://server/share/foo`.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+#[cfg(windows)]
+pub(crate) fn write_file_url(out: &mut dyn Sink, path: &Path) -> fmt::Result {
+    use std::path::{Component, Prefix};
+
+    out.literal("file://")?;
+    let mut at_root = false;
+    for component in path.components() {
+        at_root = component == Component::RootDir;
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                    write!(out, "/{}:", letter as char)?
+                }
+                Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => {
+                    write_percent_encoded(out, server.to_string_lossy().as_bytes(), is_path_safe)?;
+                    out.literal("/")?;
+                    write_percent_encoded(out, share.to_string_lossy().as_bytes(), is_path_safe)?;
+                }
+                _ => (),
+            },
+            Component::RootDir => (),
+            Component::CurDir => out.literal("/.")?,
+            Component::ParentDir => out.literal("/..")?,
+            Component::Normal(part) => {
+                out.literal("/")?;
+                write_percent_encoded(out, part.to_string_lossy().as_bytes(), is_path_safe)?;
+            }
+        }
+    }
+    if at_root {
+        out.literal("/")?;
+    }
+    Ok(())
+}
@@ -34,10 +34,13 @@
 //! ```
 
 #![no_std]
-#![forbid(unsafe_code)]
+// The C interface is the only place where unsafe code is allowed.
+#![cfg_attr(not(feature = "capi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "capi", deny(unsafe_code))]
 #![warn(missing_docs)]
 
 use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -48,7 +51,6 @@ extern crate std;
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
-#[cfg(feature = "windows")]
 #[cfg(feature = "std")]
 use std as alloc;
 
@@ -61,559 +63,8564 @@ mod unix;
 #[cfg(any(feature = "windows", all(feature = "native", windows)))]
 mod windows;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bench-corpus")]
+pub mod bench_corpus;
+#[cfg(feature = "capi")]
+#[cfg(any(feature = "unix", feature = "windows"))]
+// Old compilers check this even if the module is disabled.
+#[cfg_attr(feature = "capi", allow(unsafe_code))]
+#[clippy::msrv = "1.64"]
+pub mod capi;
+#[cfg(feature = "clap")]
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub mod clap;
+mod classify;
+#[cfg(feature = "cmake")]
+mod cmake;
+#[cfg(feature = "confusables")]
+mod confusables;
+#[cfg(feature = "dockerfile")]
+mod dockerfile;
+#[cfg(feature = "dotenv")]
+mod dotenv;
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub mod error;
+pub mod escape;
+#[cfg(feature = "git")]
+mod git;
+#[cfg(feature = "html")]
+mod html;
+mod hyperlink;
+#[cfg(feature = "native")]
+#[cfg(os_display_quoted_literal)]
+#[clippy::msrv = "1.63"]
+#[doc(hidden)]
+pub mod literal;
+#[cfg(any(feature = "tracing", feature = "valuable", feature = "defmt"))]
+mod logging;
+mod nul;
+#[cfg(feature = "alloc")]
+#[cfg(feature = "systemd")]
+pub mod parse;
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "alloc")]
+mod pattern;
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[cfg(feature = "unix")]
+mod posix;
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[cfg(feature = "windows")]
+mod powershell;
+#[cfg(feature = "alloc")]
+mod prepared;
+#[cfg(feature = "pyo3")]
+#[cfg(feature = "std")]
+#[clippy::msrv = "1.74"]
+pub mod python;
+#[cfg(feature = "reg")]
+mod reg;
+#[cfg(feature = "sanitize")]
+mod sanitize;
+#[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+mod scan;
+pub mod security;
+#[cfg(feature = "serde")]
+pub mod serde_quoted;
+pub mod shell_chars;
+mod sink;
+#[cfg(feature = "sql")]
+mod sql;
+#[cfg(feature = "std")]
+#[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+mod stream;
+#[cfg(feature = "sudoers")]
+mod sudoers;
+#[cfg(feature = "systemd")]
+mod systemd;
+mod tables;
+#[cfg(any(feature = "proptest", feature = "testkit"))]
+pub mod testing;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+mod utf8;
+pub mod util;
+mod width;
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub use crate::classify::{is_shell_safe, is_terminal_safe};
+pub use crate::classify::{Classification, Reason};
+use crate::hyperlink::Link;
+#[cfg(feature = "std")]
+pub use crate::nul::write_nul_separated;
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+pub use crate::nul::{quote_nul_separated, NulSeparated, UnsupportedStyleError};
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub use crate::path::{PathError, QuotedPathBuf};
+#[cfg(feature = "alloc")]
+pub use crate::prepared::Prepared;
+#[cfg(feature = "std")]
+#[cfg(feature = "sanitize")]
+pub use crate::sanitize::SanitizingIoWriter;
+#[cfg(feature = "sanitize")]
+pub use crate::sanitize::{AnsiFilter, SanitizingWriter};
+use crate::sink::{Columns, Controls, Events, Highlighter, Measure, Plain, Sink, Spans, Truncate};
+#[cfg(feature = "std")]
+#[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+pub use crate::stream::quote_stream;
+
 /// A wrapper around string types for displaying with quoting and escaping applied.
 #[derive(Debug, Copy, Clone)]
 pub struct Quoted<'a> {
     source: Kind<'a>,
-    force_quote: bool,
-    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
-    external: bool,
+    opts: Options,
+    link: Option<Link<'a>>,
 }
 
+/// The settings that are passed on to the writers.
 #[derive(Debug, Copy, Clone)]
-enum Kind<'a> {
-    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
-    Unix(&'a str),
-    #[cfg(feature = "unix")]
-    UnixRaw(&'a [u8]),
+pub(crate) struct Options {
+    pub(crate) force_quote: bool,
     #[cfg(any(feature = "windows", all(feature = "native", windows)))]
-    Windows(&'a str),
+    pub(crate) external: bool,
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    pub(crate) expression: bool,
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    pub(crate) magic_tokens: MagicTokens,
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    pub(crate) iex_safe: bool,
     #[cfg(feature = "windows")]
+    pub(crate) cmd_context: Option<CmdContext>,
+    pub(crate) prefer: QuoteChar,
+    pub(crate) quoting: Quoting,
+    pub(crate) extra_safe: AsciiSet,
+    pub(crate) extra_unsafe: AsciiSet,
+    pub(crate) on_unrepresentable: OnUnrepresentable,
+    pub(crate) bidi: BidiPolicy,
+    pub(crate) escape_invisible: bool,
+    pub(crate) escape_hidden_text: bool,
+    pub(crate) max_combining: Option<usize>,
+    pub(crate) max_width: Option<usize>,
+    pub(crate) ambiguous_width: Width,
+    pub(crate) control_style: ControlStyle,
+    #[cfg(feature = "confusables")]
+    pub(crate) confusables: bool,
+    pub(crate) highlight: Option<Highlight>,
+    pub(crate) context: QuoteContext,
     #[cfg(feature = "alloc")]
-    WindowsRaw(&'a [u16]),
-    #[cfg(feature = "native")]
-    #[cfg(feature = "std")]
-    NativeRaw(&'a std::ffi::OsStr),
+    pub(crate) remote: usize,
+    #[cfg(feature = "alloc")]
+    pub(crate) pattern: Option<pattern::Pattern>,
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    pub(crate) multiline: Multiline,
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    pub(crate) fallback: Fallback,
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    pub(crate) crontab: bool,
+    #[cfg(feature = "sql")]
+    pub(crate) sql_dialect: SqlDialect,
+    #[cfg(feature = "dockerfile")]
+    pub(crate) dockerfile_form: DockerfileForm,
+    #[cfg(feature = "cmake")]
+    pub(crate) cmake_list: bool,
+    #[cfg(feature = "sanitize")]
+    pub(crate) strip_ansi: bool,
+    #[cfg(feature = "sanitize")]
+    pub(crate) hex_c1: bool,
 }
 
-impl<'a> Quoted<'a> {
-    fn new(source: Kind<'a>) -> Self {
-        Quoted {
-            source,
-            force_quote: true,
-            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
-            external: false,
+impl Options {
+    /// Check whether a character has to be escaped, once we've committed to
+    /// an escaped string.
+    pub(crate) fn escapes(&self, ch: char) -> bool {
+        requires_escape(ch)
+            || security::is_bidi(ch)
+            || (self.escape_hidden_text && security::is_hidden_text(ch))
+            || (self.escape_invisible && security::is_invisible(ch))
+    }
+
+    /// Check whether the caller's settings call for quotes, before the
+    /// writer looks for special characters itself.
+    pub(crate) fn quotes_anyway(&self, text: &str) -> bool {
+        let extra_safe = self.extra_safe;
+        (self.quoting == Quoting::Paranoid
+            && !text
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || extra_safe.contains(byte)))
+            || text.bytes().any(|byte| self.extra_unsafe.contains(byte))
+    }
+
+    /// Turn a kind of pattern on or off. Only one can be on at a time.
+    #[cfg(feature = "alloc")]
+    fn set_pattern(&mut self, pattern: pattern::Pattern, enable: bool) {
+        if enable {
+            self.pattern = Some(pattern);
+        } else if self.pattern == Some(pattern) {
+            self.pattern = None;
         }
     }
 
-    /// Quote a string with the default style for the platform.
+    /// Check whether confusable characters should be escaped in this text.
     ///
-    /// On Windows this is PowerShell syntax, on all other platforms this is
-    /// bash/ksh syntax.
-    #[cfg(feature = "native")]
-    pub fn native(text: &'a str) -> Self {
-        #[cfg(windows)]
-        return Quoted::new(Kind::Windows(text));
-        #[cfg(not(windows))]
-        return Quoted::new(Kind::Unix(text));
+    /// `None` stands for invalid unicode.
+    #[allow(unused_variables)]
+    pub(crate) fn escapes_confusables(&self, chars: impl Iterator<Item = Option<char>>) -> bool {
+        #[cfg(feature = "confusables")]
+        return self.confusables && security::spoofs_ascii(chars);
+        #[cfg(not(feature = "confusables"))]
+        return false;
     }
 
-    /// Quote an `OsStr` with the default style for the platform.
-    ///
-    /// On platforms other than Windows, Unix and WASI, if the encoding is
-    /// invalid, the `Debug` representation will be used.
-    #[cfg(feature = "native")]
-    #[cfg(feature = "std")]
-    pub fn native_raw(text: &'a OsStr) -> Self {
-        Quoted::new(Kind::NativeRaw(text))
+    /// Check whether combining marks should be escaped in this text, because
+    /// too many of them are stacked together.
+    pub(crate) fn escapes_combining(&self, chars: impl Iterator<Item = Option<char>>) -> bool {
+        self.max_combining.is_some() && self.exceeds_combining(security::combining_run(chars))
     }
 
-    /// Quote a string using bash/ksh syntax.
-    ///
-    /// # Optional
-    /// This requires the optional `unix` feature.
-    #[cfg(feature = "unix")]
-    pub fn unix(text: &'a str) -> Self {
-        Quoted::new(Kind::Unix(text))
+    /// Check whether a run of combining marks of this length is too long.
+    pub(crate) fn exceeds_combining(&self, run: usize) -> bool {
+        match self.max_combining {
+            Some(limit) => run > limit,
+            None => false,
+        }
     }
 
-    /// Quote possibly invalid UTF-8 using bash/ksh syntax.
-    ///
-    /// # Optional
-    /// This requires the optional `unix` feature.
-    #[cfg(feature = "unix")]
-    pub fn unix_raw(bytes: &'a [u8]) -> Self {
-        Quoted::new(Kind::UnixRaw(bytes))
+    /// Check which characters have to be escaped because of the rest of the
+    /// text, for the writers that escape characters one at a time.
+    pub(crate) fn text_escapes(
+        &self,
+        chars: impl Iterator<Item = Option<char>> + Clone,
+    ) -> TextEscapes {
+        TextEscapes {
+            confusables: self.escapes_confusables(chars.clone()),
+            combining: self.escapes_combining(chars),
+        }
     }
+}
 
-    /// Quote a string using PowerShell syntax.
-    ///
-    /// # Optional
-    /// This requires the optional `windows` feature.
-    #[cfg(feature = "windows")]
-    pub fn windows(text: &'a str) -> Self {
-        Quoted::new(Kind::Windows(text))
+/// Characters that are only escaped because of what else is in the text,
+/// from [`Options::text_escapes`].
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct TextEscapes {
+    confusables: bool,
+    combining: bool,
+}
+
+impl TextEscapes {
+    pub(crate) fn contains(self, ch: char) -> bool {
+        (self.confusables && is_confusable(ch)) || (self.combining && security::is_combining(ch))
     }
+}
 
-    /// Quote possibly invalid UTF-16 using PowerShell syntax.
-    ///
-    /// # Optional
-    /// This requires the optional `windows` feature and the (default) `alloc` feature.
+/// A quoting style, for [`Quoted::new`].
+///
+/// Each one corresponds to a constructor, and only exists if that constructor
+/// is enabled. More styles may be added in the future, so a `match` needs a
+/// wildcard arm.
+///
+/// ```
+/// use os_display::{Quoted, Style};
+///
+/// # #[cfg(feature = "unix")] {
+/// let style = Style::Unix;
+/// assert_eq!(Quoted::new(style, "it's").to_string(), r#""it's""#);
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// bash/ksh syntax, like [`Quoted::unix`].
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    Unix,
+    /// PowerShell syntax, like [`Quoted::windows`].
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    Powershell,
+    /// A raw Windows command line, like [`Quoted::windows_argv`].
     #[cfg(feature = "windows")]
-    #[cfg(feature = "alloc")]
-    pub fn windows_raw(units: &'a [u16]) -> Self {
-        Quoted::new(Kind::WindowsRaw(units))
-    }
+    WindowsArgv,
+    /// An SQL string literal, like [`Quoted::sql`].
+    #[cfg(feature = "sql")]
+    Sql,
+    /// HTML text, like [`Quoted::html`].
+    #[cfg(feature = "html")]
+    Html,
+    /// A systemd unit name, like [`Quoted::systemd_unit`].
+    #[cfg(feature = "systemd")]
+    SystemdUnit,
+    /// A value in a `.env` file, like [`Quoted::dotenv`].
+    #[cfg(feature = "dotenv")]
+    Dotenv,
+    /// An argument in a Dockerfile, like [`Quoted::dockerfile`].
+    #[cfg(feature = "dockerfile")]
+    Dockerfile,
+    /// An argument in CMake code, like [`Quoted::cmake`].
+    #[cfg(feature = "cmake")]
+    Cmake,
+    /// A string value in a `.reg` file, like [`Quoted::reg`].
+    #[cfg(feature = "reg")]
+    Reg,
+    /// A command in a sudoers file, like [`Quoted::sudoers`].
+    #[cfg(feature = "sudoers")]
+    Sudoers,
+    /// A pattern in a `.gitignore` file, like [`Quoted::gitignore`].
+    #[cfg(feature = "git")]
+    GitIgnore,
+    /// A pattern in a `.gitattributes` file, like [`Quoted::gitattributes`].
+    #[cfg(feature = "git")]
+    GitAttributes,
+    /// A value in a git config file, like [`Quoted::git_config`].
+    #[cfg(feature = "git")]
+    GitConfig,
+    /// Text for display only, like [`Quoted::sanitize`].
+    #[cfg(feature = "sanitize")]
+    Sanitize,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
 
-    /// Toggle forced quoting. If `true`, quotes are added even if no special
-    /// characters are present.
-    ///
-    /// Defaults to `true`.
-    pub fn force(mut self, force: bool) -> Self {
-        self.force_quote = force;
-        self
+impl Style {
+    /// The default style for the platform, like [`Quoted::native`].
+    #[cfg(feature = "native")]
+    pub fn native() -> Self {
+        #[cfg(windows)]
+        return Style::Powershell;
+        #[cfg(not(windows))]
+        return Style::Unix;
     }
 
-    /// When quoting for PowerShell, toggle whether to quote for external programs.
+    /// Pick a style for a shell, given the path or name of its executable,
+    /// like the value of `$SHELL`.
     ///
-    /// If enabled, double quotes (and sometimes backslashes) will be escaped so
-    /// that they can be passed to external programs.
+    /// ```
+    /// use os_display::Style;
     ///
-    /// If disabled, quoting will suit internal commandlets and .NET functions.
-    /// Strings that look like options or numbers will be quoted.
+    /// # #[cfg(all(feature = "unix", feature = "windows"))] {
+    /// assert_eq!(Style::from_shell_path("/usr/bin/zsh"), Some(Style::Unix));
+    /// assert_eq!(Style::from_shell_path(r"C:\Program Files\PowerShell\7\pwsh.exe"), Some(Style::Powershell));
+    /// assert_eq!(Style::from_shell_path("/usr/bin/fish"), None);
+    /// # }
+    /// ```
     ///
-    /// It is sadly impossible to quote a string such that it's suitable for both
-    /// external and internal commands.
+    /// The directory, a `.exe` extension and a leading `-` (as in the name of
+    /// a login shell) are ignored, and so is the case.
     ///
-    /// The experimental `PSNativeCommandArgumentPassing` feature in PowerShell 7.2
-    /// disables the stripping of double quotes and backslashes. If it's enabled
-    /// then this setting should be disabled.
+    /// This returns `None` for shells with incompatible quoting rules, like
+    /// fish, csh, nushell and `cmd.exe`, as well as for unknown shells and
+    /// styles that aren't enabled. POSIX shells like dash only understand
+    /// the `$'...'` syntax in newer versions.
+    pub fn from_shell_path(path: &str) -> Option<Style> {
+        let name = path
+            .rsplit(|ch| ch == '/' || ch == '\\')
+            .next()
+            .unwrap_or(path);
+        let name = name.trim_start_matches('-').as_bytes();
+        let name = match name.len().checked_sub(4) {
+            Some(end) if name[end..].eq_ignore_ascii_case(b".exe") => &name[..end],
+            _ => name,
+        };
+
+        let mut buf = [0; 16];
+        if name.len() > buf.len() {
+            return None;
+        }
+        let buf = &mut buf[..name.len()];
+        buf.copy_from_slice(name);
+        buf.make_ascii_lowercase();
+
+        match &*buf {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            b"sh" | b"ash" | b"dash" | b"bash" | b"rbash" | b"ksh" | b"ksh93" | b"mksh"
+            | b"pdksh" | b"oksh" | b"loksh" | b"zsh" | b"yash" | b"busybox" => Some(Style::Unix),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            b"pwsh" | b"pwsh-preview" | b"powershell" | b"powershell_ise" => {
+                Some(Style::Powershell)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A kind of quote, for [`Quoted::prefer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuoteChar {
+    /// `'`
+    Single,
+    /// `"`
+    Double,
+}
+
+/// How eagerly to add quotes if they're optional, for [`Quoted::quoting`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Quoting {
+    /// Only add quotes if the text would mean something else without them.
     ///
-    /// Defaults to `false`. This could change in a future (breaking) release.
+    /// For bash/ksh this leaves out some characters that are only special
+    /// in certain positions, so `a=b`, `x^y` and `{}` stay bare. Quotes are
+    /// still added for unicode whitespace and leading zero-width characters,
+    /// to keep the text readable. For PowerShell this is the same as
+    /// [`Quoting::Standard`].
+    Minimal,
+    /// Also quote characters that are special in other contexts or in older
+    /// or less common shells. This is what GNU coreutils does.
+    Standard,
+    /// Quote anything that isn't made up of ASCII letters and digits.
+    Paranoid,
+}
+
+/// What to do with text that the output can't express, for
+/// [`Quoted::on_unrepresentable`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnUnrepresentable {
+    /// Write it the best way there is anyway, like `$'\x00'` for a NUL.
+    Escape,
+    /// Write U+FFFD instead.
     ///
     /// # Optional
-    /// This requires either the `windows` or the `native` feature. It has no effect
-    /// on Unix-style quoting.
-    #[cfg(any(feature = "windows", feature = "native"))]
-    #[allow(unused_mut, unused_variables)]
-    pub fn external(mut self, external: bool) -> Self {
-        #[cfg(any(feature = "windows", windows))]
-        {
-            self.external = external;
-        }
-        self
-    }
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    ReplacementChar,
+    /// Fail with [`fmt::Error`].
+    Error,
 }
 
-impl<'a> Display for Quoted<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.source {
-            #[cfg(feature = "native")]
-            #[cfg(feature = "std")]
-            Kind::NativeRaw(text) => {
-                #[cfg(unix)]
-                use std::os::unix::ffi::OsStrExt;
-                #[cfg(target_os = "wasi")]
-                use std::os::wasi::ffi::OsStrExt;
-                #[cfg(windows)]
-                use std::os::windows::ffi::OsStrExt;
+/// Where the output is going to end up, for [`Quoted::context`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuoteContext {
+    /// A complete argument, with its own quotes if it needs them.
+    Standalone,
+    /// The inside of a double-quoted string that the caller opens and closes.
+    ///
+    /// For bash/ksh only `"`, `$`, `` ` `` and `\` are escaped, with a
+    /// backslash. Control characters and other text that has to be escaped
+    /// can't be written inside double quotes at all, so the string is briefly
+    /// interrupted with ANSI-C quoting, as in `"$'\n'"`.
+    ///
+    /// For PowerShell the usual escape sequences are used, since they work
+    /// inside double quotes.
+    ///
+    /// In an interactive bash session `!` may still trigger history
+    /// expansion. That doesn't happen in scripts.
+    InsideDoubleQuotes,
+}
 
-                #[cfg(windows)]
-                match text.to_str() {
-                    Some(text) => windows::write(f, text, self.force_quote, self.external),
-                    None => {
-                        windows::write_escaped(f, decode_utf16(text.encode_wide()), self.external)
-                    }
-                }
-                #[cfg(any(unix, target_os = "wasi"))]
-                match text.to_str() {
-                    Some(text) => unix::write(f, text, self.force_quote),
-                    None => unix::write_escaped(f, text.as_bytes()),
-                }
-                #[cfg(not(any(windows, unix, target_os = "wasi")))]
-                match text.to_str() {
-                    Some(text) => unix::write(f, text, self.force_quote),
-                    // Debug is our best shot for not losing information.
-                    // But you probably can't paste it into a shell.
-                    None => write!(f, "{:?}", text),
-                }
-            }
+/// How to write text with newlines, for [`Quoted::multiline`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Multiline {
+    /// Escape the newlines like other control characters, as in `$'a\nb'`.
+    Escape,
+    /// Write a here-document, like `<<'EOF'` followed by the lines and `EOF`.
+    ///
+    /// This is only used if the text ends with a newline and has nothing
+    /// else that needs escaping (tabs are fine). Otherwise it's escaped as
+    /// usual.
+    HereDoc,
+}
 
-            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
-            Kind::Unix(text) => unix::write(f, text, self.force_quote),
+/// What to write if text can't be put in plain quotes, for [`Quoted::fallback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fallback {
+    /// Use ANSI-C quoting, as in `$'a\nb'`. Most shells support this, but
+    /// dash and fish don't.
+    AnsiC,
+    /// Write a `printf` command that prints the text, as in `printf 'a\nb'`.
+    ///
+    /// This is not an argument but a whole command. It works in any shell
+    /// that has a POSIX `printf`.
+    PrintfCommand,
+}
 
-            #[cfg(feature = "unix")]
-            Kind::UnixRaw(bytes) => match core::str::from_utf8(bytes) {
-                Ok(text) => unix::write(f, text, self.force_quote),
-                Err(_) => unix::write_escaped(f, bytes),
-            },
+/// The flavor of SQL to write, for [`Quoted::sql_dialect`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// Standard SQL, where a quote is escaped by doubling it and backslashes
+    /// have no special meaning. Other escaped text is written as hex
+    /// literals joined with `||`, as in `'a' || x'0A'`.
+    ///
+    /// This is right for SQLite and most other databases. PostgreSQL reads
+    /// plain strings the same way, but takes `x'...'` to be a bit string.
+    Standard,
+    /// MySQL and MariaDB, where backslashes start escape sequences (unless
+    /// the `NO_BACKSLASH_ESCAPES` mode is on). Hex literals are joined with
+    /// `CONCAT()`, since `||` means OR.
+    MySql,
+}
 
-            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
-            Kind::Windows(text) => windows::write(f, text, self.force_quote, self.external),
+/// The kind of Dockerfile instruction, for [`Quoted::dockerfile_form`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DockerfileForm {
+    /// Shell form, as in `RUN ls -l 'my dir'`. The command is run with
+    /// `/bin/sh -c`, so the argument is quoted for a POSIX shell.
+    ///
+    /// Text that has to be escaped is written as the output of a `printf`
+    /// command, as in `"$(printf 'a\011b')"`, since `$'...'` isn't portable.
+    /// Trailing newlines are lost that way.
+    Shell,
+    /// Exec form, as in `RUN ["ls", "-l", "my dir"]`. Each argument is a
+    /// JSON string, and the caller writes the brackets and commas.
+    Exec,
+}
 
-            #[cfg(feature = "windows")]
-            #[cfg(feature = "alloc")]
-            // Avoiding this allocation is possible in theory, but it'd require either
-            // complicating or slowing down the common case.
-            // Perhaps we could offer a non-allocating API for known-invalid UTF-16 strings
-            // that we pass straight to write_escaped(), but it seems a bit awkward.
-            // Please open an issue if you have a need for this.
-            Kind::WindowsRaw(units) => match alloc::string::String::from_utf16(units) {
-                Ok(text) => windows::write(f, &text, self.force_quote, self.external),
-                Err(_) => {
-                    windows::write_escaped(f, decode_utf16(units.iter().cloned()), self.external)
-                }
-            },
-        }
-    }
+/// Where a `cmd.exe` command line is run, for [`Quoted::cmd_context`].
+///
+/// Each of these escapes what the one before it does, and more.
+#[cfg(feature = "windows")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CmdContext {
+    /// Typed at the prompt or passed to `cmd /c`. A `%` gets a `^`, which
+    /// keeps it from forming a variable reference.
+    Interactive,
+    /// A line in a batch file, where a `%` is written as `%%`.
+    BatchFile,
+    /// A line in a batch file after `setlocal EnableDelayedExpansion`, where
+    /// a `!` is escaped too. Arguments that contain one get their carets
+    /// doubled, since they're removed a second time.
+    DelayedExpansion,
 }
 
-#[cfg(any(feature = "windows", all(feature = "native", feature = "std", windows)))]
-#[cfg(feature = "alloc")]
-fn decode_utf16(units: impl IntoIterator<Item = u16>) -> impl Iterator<Item = Result<char, u16>> {
-    core::char::decode_utf16(units).map(|res| res.map_err(|err| err.unpaired_surrogate()))
+/// How to treat PowerShell's special tokens, for [`Quoted::magic_tokens`].
+///
+/// These are things like the stop-parsing token `--%`, automatic variables
+/// like `$?` and `$_`, and array subexpressions like `@(...)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MagicTokens {
+    /// Quote them like any other text with special characters, so
+    /// `$?` becomes `'$?'`.
+    Quote,
+    /// Don't trust single quotes with text that starts with `$` or contains
+    /// `@(`. It's written in double quotes with the `$` escaped instead, so
+    /// `$?` becomes ``"`$?"``.
+    ///
+    /// The output means the same thing, but it doesn't depend on the single
+    /// quotes surviving. That's useful if it's embedded in a single-quoted
+    /// string that's passed to `Invoke-Expression`.
+    Strict,
 }
 
-/// Characters that may not be safe to print in a terminal.
+/// What to do with suspicious bidirectional text, for [`Quoted::bidi_policy`].
 ///
-/// This includes all the ASCII control characters.
-fn requires_escape(ch: char) -> bool {
-    ch.is_control() || is_separator(ch)
+/// See [`security::is_suspicious_bidi`] for what counts as suspicious.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BidiPolicy {
+    /// Escape the bidi control characters. The result is safe and can be
+    /// copied, but right-to-left text becomes hard to read.
+    Escape,
+    /// Don't check for suspicious bidi at all.
+    ///
+    /// Only use this if you take care of it some other way, e.g. by isolating
+    /// the whole message.
+    Allow,
+    /// Wrap the output in `U+2068 FIRST STRONG ISOLATE` and
+    /// `U+2069 POP DIRECTIONAL ISOLATE` so it can't affect the surrounding text.
+    ///
+    /// Those two characters are not part of the quoted string, so they must be
+    /// left out when copying it. Most terminals don't render them at all,
+    /// which makes that hard to do. This is more suitable for display-only
+    /// output.
+    ///
+    /// Text that can't be contained this way (because it has unpaired
+    /// terminators or is nested too deeply) is escaped instead.
+    Isolate,
 }
 
-/// U+2028 LINE SEPARATOR and U+2029 PARAGRAPH SEPARATOR are currently the only
-/// in their categories. The terminals I tried don't treat them very specially,
-/// but gedit does.
-fn is_separator(ch: char) -> bool {
-    ch == '\u{2028}' || ch == '\u{2029}'
+/// How to render control characters, for [`Quoted::control_style`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ControlStyle {
+    /// Use the style's own escape sequences, like `\r` or `` `r ``. The
+    /// output can be copied and pasted.
+    Escape,
+    /// Use caret notation, like `^M` for a carriage return and `^?` for
+    /// DEL, the way `less` and `cat -v` show them.
+    Caret,
+    /// Use the characters from the Control Pictures block, like `␍` for
+    /// a carriage return.
+    Picture,
 }
 
-/// These two ranges in PropList.txt:
-/// LEFT-TO-RIGHT EMBEDDING..RIGHT-TO-LEFT OVERRIDE
-/// LEFT-TO-RIGHT ISOLATE..POP DIRECTIONAL ISOLATE
-fn is_bidi(ch: char) -> bool {
-    match ch {
-        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => true,
-        _ => false,
-    }
+/// How wide characters with an ambiguous East Asian width are, for
+/// [`Quoted::ambiguous_width`].
+///
+/// Characters like `±`, `①` and box drawing characters take up one column in
+/// most terminals, but two in terminals that are set up for Chinese, Japanese
+/// or Korean.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Width {
+    /// One column, like in most terminals.
+    Narrow,
+    /// Two columns, like in many CJK terminals.
+    Wide,
 }
 
-/// Check whether text uses bidi in a potentially problematic way.
+/// Colors for the different parts of the output, for [`Quoted::highlight`].
 ///
-/// See https://trojansource.codes/ and
-/// https://www.unicode.org/reports/tr9/tr9-42.html.
+/// The colors are given as parameters for ANSI SGR ("Select Graphic
+/// Rendition") sequences, like `"2"` for dim text or `"1;31"` for bold red.
 ///
-/// If text fails this check then it's handled by write_escaped(), which
-/// escapes these bidi control characters no matter what.
+/// ```
+/// use os_display::Highlight;
 ///
-/// We can safely assume that there are no newlines (or unicode separators)
-/// in the text because those would get it sent to write_escaped() earlier.
-/// In unicode terms, this is all a single paragraph.
-#[inline(never)]
-fn is_suspicious_bidi(text: &str) -> bool {
-    #[derive(Clone, Copy, PartialEq)]
-    enum Kind {
-        Formatting,
-        Isolate,
-    }
-    const STACK_SIZE: usize = 16;
-    // Can't use a Vec because of no_std
-    let mut stack: [Option<Kind>; STACK_SIZE] = [None; STACK_SIZE];
-    let mut pos = 0;
-    for ch in text.chars() {
-        match ch {
-            '\u{202A}' | '\u{202B}' | '\u{202D}' | '\u{202E}' => {
-                if pos >= STACK_SIZE {
-                    // Suspicious amount of nesting.
-                    return true;
-                }
-                stack[pos] = Some(Kind::Formatting);
-                pos += 1;
-            }
-            '\u{202C}' => {
-                if pos == 0 {
-                    // Unpaired terminator.
-                    // Not necessarily dangerous, but suspicious and
-                    // could disrupt preceding text.
-                    return true;
-                }
-                pos -= 1;
-                if stack[pos] != Some(Kind::Formatting) {
-                    // Terminator doesn't match.
-                    // UAX #9 says to pop the stack until we find a match.
-                    // But we'll keep things simple and cautious.
-                    return true;
-                }
-            }
-            '\u{2066}' | '\u{2067}' | '\u{2068}' => {
-                if pos >= STACK_SIZE {
-                    return true;
-                }
-                stack[pos] = Some(Kind::Isolate);
-                pos += 1;
-            }
-            '\u{2069}' => {
-                if pos == 0 {
-                    return true;
-                }
-                pos -= 1;
-                if stack[pos] != Some(Kind::Isolate) {
-                    return true;
-                }
-            }
-            _ => (),
-        }
-    }
-    pos != 0
+/// let highlight = Highlight::new().quotes("2").escapes("31");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Highlight {
+    quotes: Option<&'static str>,
+    escapes: Option<&'static str>,
 }
 
-#[cfg(feature = "native")]
-mod native {
-    use super::*;
+impl Highlight {
+    /// Don't highlight anything.
+    pub fn new() -> Self {
+        Highlight {
+            quotes: None,
+            escapes: None,
+        }
+    }
 
-    /// An extension trait to apply quoting to strings.
+    /// Set the style for quotes and other syntax that isn't part of the
+    /// original text.
     ///
-    /// This is implemented on [`str`], [`OsStr`] and [`Path`].
+    /// # Panics
+    /// If `params` contains anything other than ASCII digits and semicolons.
+    pub fn quotes(mut self, params: &'static str) -> Self {
+        assert!(is_sgr_params(params), "invalid SGR parameters");
+        self.quotes = Some(params);
+        self
+    }
+
+    /// Set the style for escape sequences, like `\n` or `` `u{FEFF} ``.
     ///
-    /// For finer control, see the constructors on [`Quoted`].
-    pub trait Quotable {
-        /// Returns an object that implements [`Display`] for printing strings with
-        /// proper quoting and escaping for the platform.
-        ///
-        /// On Unix this corresponds to bash/ksh syntax, on Windows PowerShell syntax
-        /// is used.
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// use std::path::Path;
-        /// use os_display::Quotable;
-        ///
-        /// let path = Path::new("foo/bar.baz");
-        ///
-        /// println!("Found file {}", path.quote()); // Prints "Found file 'foo/bar.baz'"
-        /// ```
-        fn quote(&self) -> Quoted<'_>;
+    /// # Panics
+    /// If `params` contains anything other than ASCII digits and semicolons.
+    pub fn escapes(mut self, params: &'static str) -> Self {
+        assert!(is_sgr_params(params), "invalid SGR parameters");
+        self.escapes = Some(params);
+        self
+    }
+}
 
-        /// Like `quote()`, but don't actually add quotes unless necessary because of
-        /// whitespace or special characters.
-        ///
-        /// # Examples
-        ///
-        /// ```
-        /// use std::path::Path;
-        /// use os_display::Quotable;
-        ///
-        /// let foo = Path::new("foo/bar.baz");
-        /// let bar = "foo bar";
-        ///
-        /// println!("{}: Not found", foo.maybe_quote()); // Prints "foo/bar.baz: Not found"
-        /// println!("{}: Not found", bar.maybe_quote()); // Prints "'foo bar': Not found"
-        /// ```
-        fn maybe_quote(&self) -> Quoted<'_> {
-            let mut quoted = self.quote();
-            quoted.force_quote = false;
-            quoted
+/// The error returned by [`Quoted::render_into`] if the buffer is too small.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CapacityError {
+    required: usize,
+}
+
+impl CapacityError {
+    /// The size the buffer needs to have.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small, {} bytes required", self.required)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// The error returned by [`Quoted::try_render`] if the output wouldn't stand
+/// for the original text.
+///
+/// # Optional
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnrepresentableError {
+    reason: &'static str,
+}
+
+#[cfg(feature = "alloc")]
+impl Display for UnrepresentableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnrepresentableError {}
+
+/// Quoted output together with its width, from [`Quoted::to_string_and_width`].
+///
+/// Formatting it with a width, like `{:<30}`, pads it based on the stored
+/// width, so laying out columns doesn't measure the output again. The
+/// precision is ignored because the output is already complete, use it on
+/// the [`Quoted`] instead.
+///
+/// # Optional
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuotedAndWidth {
+    text: alloc::string::String,
+    width: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl QuotedAndWidth {
+    /// The quoted output.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The width of the output in terminal columns, including the quotes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Take the quoted output.
+    pub fn into_string(self) -> alloc::string::String {
+        self.text
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for QuotedAndWidth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_padded(f, || self.width, |f| f.write_str(&self.text))
+    }
+}
+
+/// A piece of quoted output, for [`Quoted::events`].
+///
+/// Concatenating the text of all the events gives the same output as
+/// formatting the `Quoted` normally (without highlighting or hyperlinks).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuoteEvent<'a> {
+    /// The start of a quoted section, like `'` or `$'`.
+    OpenQuote(&'a str),
+    /// Text that's written as-is.
+    Literal(&'a str),
+    /// An escape sequence that stands for a single character or code unit.
+    Escape {
+        /// What's being escaped.
+        original: Original,
+        /// The escape sequence, like `\n` or `` `u{FEFF} ``.
+        rendered: &'a str,
+    },
+    /// The end of a quoted section.
+    CloseQuote(&'a str),
+}
+
+/// The position of an escape sequence, for [`Quoted::escaped_spans`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapedSpan {
+    /// What's being escaped.
+    pub original: Original,
+    /// Where it is in the original text.
+    pub input: Range<usize>,
+    /// Where the escape sequence is in the output, in bytes.
+    pub output: Range<usize>,
+}
+
+/// The part of the original text that an escape sequence stands for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Original {
+    /// A valid character.
+    Char(char),
+    /// A byte that's not part of valid UTF-8.
+    Byte(u8),
+    /// A UTF-16 code unit that's not part of valid UTF-16 (an unpaired surrogate).
+    Unit(u16),
+}
+
+/// The version of Unicode that's used to decide which characters are
+/// whitespace or separators, as `(major, minor, update)`.
+///
+/// This is the newest version the crate knows about, unless an older one is
+/// picked with one of the `unicode14` and `unicode15` features. If several are
+/// enabled the oldest one wins. The `unicode16` feature asks for the newest
+/// version explicitly, so it won't change silently in a future release.
+///
+/// The widths of characters come from the `unicode-width` crate and may use a
+/// different version.
+pub const UNICODE_VERSION: (u8, u8, u8) = tables::UNICODE_VERSION;
+
+/// A set of ASCII characters, for [`Quoted::extra_safe_chars`] and
+/// [`Quoted::extra_unsafe_chars`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct AsciiSet(u128);
+
+impl AsciiSet {
+    const EMPTY: AsciiSet = AsciiSet(0);
+
+    fn new(chars: &str) -> Self {
+        assert!(chars.is_ascii(), "only ASCII characters are supported");
+        AsciiSet(chars.bytes().fold(0, |set, byte| set | 1 << byte))
+    }
+
+    pub(crate) fn contains(self, byte: u8) -> bool {
+        byte < 128 && self.0 & 1 << byte != 0
+    }
+
+    pub(crate) fn contains_char(self, ch: char) -> bool {
+        ch.is_ascii() && self.contains(ch as u8)
+    }
+}
+
+/// Only allow parameters that can't end the sequence early and inject other
+/// control codes.
+fn is_sgr_params(params: &str) -> bool {
+    params
+        .bytes()
+        .all(|byte| byte.is_ascii_digit() || byte == b';')
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Kind<'a> {
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    Unix(&'a str),
+    #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+    UnixRaw(&'a [u8]),
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    Windows(&'a str),
+    #[cfg(feature = "windows")]
+    #[cfg(feature = "alloc")]
+    WindowsRaw(&'a [u16]),
+    #[cfg(feature = "windows")]
+    WindowsWtf8(&'a [u8]),
+    #[cfg(feature = "windows")]
+    WindowsArgv(&'a str),
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    NativeRaw(&'a std::ffi::OsStr),
+    #[cfg(feature = "sql")]
+    Sql(&'a [u8]),
+    #[cfg(feature = "html")]
+    Html(&'a [u8]),
+    #[cfg(feature = "systemd")]
+    SystemdUnit(&'a [u8]),
+    #[cfg(feature = "dotenv")]
+    Dotenv(&'a str),
+    #[cfg(feature = "dockerfile")]
+    Dockerfile(&'a str),
+    #[cfg(feature = "cmake")]
+    Cmake(&'a str),
+    #[cfg(feature = "reg")]
+    Reg(&'a str),
+    #[cfg(feature = "reg")]
+    RegRaw(&'a [u16]),
+    #[cfg(feature = "sudoers")]
+    Sudoers(&'a str),
+    #[cfg(feature = "git")]
+    GitIgnore(&'a str),
+    #[cfg(feature = "git")]
+    GitAttributes(&'a str),
+    #[cfg(feature = "git")]
+    GitConfig(&'a str),
+    #[cfg(feature = "sanitize")]
+    Sanitize(&'a [u8]),
+    // The style is only needed to quote the operator for an outer level.
+    #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    Operator(Style, &'a str),
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    FileUrl(&'a Path),
+    #[cfg(feature = "alloc")]
+    Prepared(&'a prepared::Recording),
+}
+
+impl<'a> Kind<'a> {
+    /// Whether this is PowerShell syntax.
+    fn is_powershell(&self) -> bool {
+        #[allow(unreachable_patterns)]
+        match *self {
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(_) => true,
+            #[cfg(feature = "windows")]
+            #[cfg(feature = "alloc")]
+            Kind::WindowsRaw(_) => true,
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(_) => true,
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(_) => cfg!(windows),
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::FileUrl(_) => cfg!(windows),
+            #[cfg(feature = "alloc")]
+            Kind::Prepared(recording) => recording.is_powershell(),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Operator(Style::Powershell, _) => true,
+            _ => false,
         }
     }
 
-    impl Quotable for str {
-        fn quote(&self) -> Quoted<'_> {
-            Quoted::native(self)
+    /// Whether this is bash/ksh syntax.
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    fn is_unix(&self) -> bool {
+        #[allow(unreachable_patterns)]
+        match *self {
+            Kind::Unix(_) => true,
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(_) => true,
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(_) => cfg!(not(windows)),
+            _ => false,
         }
     }
 
-    #[cfg(feature = "std")]
-    impl Quotable for OsStr {
-        fn quote(&self) -> Quoted<'_> {
-            Quoted::native_raw(self)
+    /// The length of the original text, in bytes or UTF-16 code units.
+    fn len(&self) -> usize {
+        match *self {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) => text.len(),
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) => bytes.len(),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) => text.len(),
+            #[cfg(feature = "windows")]
+            #[cfg(feature = "alloc")]
+            Kind::WindowsRaw(units) => units.len(),
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) => bytes.len(),
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text) => text.len(),
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(text) => text.len(),
+            #[cfg(feature = "sql")]
+            Kind::Sql(bytes) => bytes.len(),
+            #[cfg(feature = "html")]
+            Kind::Html(bytes) => bytes.len(),
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(bytes) => bytes.len(),
+            #[cfg(feature = "dotenv")]
+            Kind::Dotenv(text) => text.len(),
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(text) => text.len(),
+            #[cfg(feature = "cmake")]
+            Kind::Cmake(text) => text.len(),
+            #[cfg(feature = "reg")]
+            Kind::Reg(text) => text.len(),
+            #[cfg(feature = "reg")]
+            Kind::RegRaw(units) => units.len(),
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers(text) => text.len(),
+            #[cfg(feature = "git")]
+            Kind::GitIgnore(text) | Kind::GitAttributes(text) | Kind::GitConfig(text) => text.len(),
+            #[cfg(feature = "sanitize")]
+            Kind::Sanitize(bytes) => bytes.len(),
+            #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+            Kind::Operator(_, text) => text.len(),
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::FileUrl(path) => path.as_os_str().len(),
+            #[cfg(feature = "alloc")]
+            Kind::Prepared(recording) => recording.len(),
         }
     }
 
-    #[cfg(feature = "std")]
-    impl Quotable for Path {
-        fn quote(&self) -> Quoted<'_> {
-            Quoted::native_raw(self.as_ref())
+    /// Whether the original text is measured in UTF-16 code units.
+    fn is_wide(&self) -> bool {
+        #[allow(unreachable_patterns)]
+        match *self {
+            #[cfg(feature = "windows")]
+            #[cfg(feature = "alloc")]
+            Kind::WindowsRaw(_) => true,
+            #[cfg(feature = "reg")]
+            Kind::RegRaw(_) => true,
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(_) => cfg!(windows),
+            _ => false,
         }
     }
 
-    impl<'a, T: Quotable + ?Sized> From<&'a T> for Quoted<'a> {
-        fn from(val: &'a T) -> Self {
-            val.quote()
+    /// The same style of quoting, for text that was already quoted once.
+    #[cfg(feature = "alloc")]
+    fn requote<'b>(&self, text: &'b str) -> Kind<'b> {
+        match *self {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(_) => Kind::Unix(text),
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(_) => Kind::Unix(text),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(_) => Kind::Windows(text),
+            #[cfg(feature = "windows")]
+            Kind::WindowsRaw(_) | Kind::WindowsWtf8(_) => Kind::Windows(text),
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(_) => Kind::WindowsArgv(text),
+            #[cfg(feature = "sql")]
+            Kind::Sql(_) => Kind::Sql(text.as_bytes()),
+            #[cfg(feature = "html")]
+            Kind::Html(_) => Kind::Html(text.as_bytes()),
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(_) => Kind::SystemdUnit(text.as_bytes()),
+            #[cfg(feature = "dotenv")]
+            Kind::Dotenv(_) => Kind::Dotenv(text),
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(_) => Kind::Dockerfile(text),
+            #[cfg(feature = "cmake")]
+            Kind::Cmake(_) => Kind::Cmake(text),
+            #[cfg(feature = "reg")]
+            Kind::Reg(_) | Kind::RegRaw(_) => Kind::Reg(text),
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers(_) => Kind::Sudoers(text),
+            #[cfg(feature = "git")]
+            Kind::GitIgnore(_) => Kind::GitIgnore(text),
+            #[cfg(feature = "git")]
+            Kind::GitAttributes(_) => Kind::GitAttributes(text),
+            #[cfg(feature = "git")]
+            Kind::GitConfig(_) => Kind::GitConfig(text),
+            #[cfg(feature = "sanitize")]
+            Kind::Sanitize(_) => Kind::Sanitize(text.as_bytes()),
+            // The operator is meant for the innermost shell, so the outer
+            // ones get it quoted.
+            #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+            Kind::Operator(style, _) => Quoted::new(style, text).source,
+            // A URL doesn't need quoting, so this is only reached if it
+            // fell back to the path.
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::FileUrl(_) => Kind::NativeRaw(OsStr::new(text)),
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(_) => Quoted::native(text).source,
+            // write_levels() replays a recording without requoting it.
+            Kind::Prepared(_) => unreachable!(),
         }
     }
 }
 
-#[cfg(feature = "native")]
-pub use crate::native::Quotable;
+impl<'a> Quoted<'a> {
+    fn from_kind(source: Kind<'a>) -> Self {
+        Quoted {
+            source,
+            link: None,
+            opts: Options {
+                force_quote: true,
+                #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+                external: false,
+                #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+                expression: false,
+                #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+                magic_tokens: MagicTokens::Quote,
+                #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+                iex_safe: false,
+                #[cfg(feature = "windows")]
+                cmd_context: None,
+                prefer: QuoteChar::Single,
+                quoting: Quoting::Standard,
+                extra_safe: AsciiSet::EMPTY,
+                extra_unsafe: AsciiSet::EMPTY,
+                on_unrepresentable: OnUnrepresentable::Escape,
+                bidi: BidiPolicy::Escape,
+                escape_invisible: false,
+                escape_hidden_text: true,
+                max_combining: Some(4),
+                max_width: None,
+                ambiguous_width: Width::Narrow,
+                control_style: ControlStyle::Escape,
+                #[cfg(feature = "confusables")]
+                confusables: false,
+                highlight: None,
+                context: QuoteContext::Standalone,
+                #[cfg(feature = "alloc")]
+                remote: 0,
+                #[cfg(feature = "alloc")]
+                pattern: None,
+                #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+                multiline: Multiline::Escape,
+                #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+                fallback: Fallback::AnsiC,
+                #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+                crontab: false,
+                #[cfg(feature = "sql")]
+                sql_dialect: SqlDialect::Standard,
+                #[cfg(feature = "dockerfile")]
+                dockerfile_form: DockerfileForm::Shell,
+                #[cfg(feature = "cmake")]
+                cmake_list: false,
+                #[cfg(feature = "sanitize")]
+                strip_ansi: false,
+                #[cfg(feature = "sanitize")]
+                hex_c1: false,
+            },
+        }
+    }
 
-#[cfg(feature = "std")]
-#[cfg(test)]
-mod tests {
-    #![allow(unused)]
+    /// Quote a string in the given style.
+    ///
+    /// This is the same as calling the style's own constructor, like
+    /// [`Quoted::unix`] for [`Style::Unix`]. There are no styles for raw
+    /// input, so use the constructors for those.
+    ///
+    /// # Panics
+    /// If `style` is the hidden placeholder variant.
+    pub fn new(style: Style, text: &'a str) -> Self {
+        Quoted::from_kind(match style {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Style::Unix => Kind::Unix(text),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Style::Powershell => Kind::Windows(text),
+            #[cfg(feature = "windows")]
+            Style::WindowsArgv => Kind::WindowsArgv(text),
+            #[cfg(feature = "sql")]
+            Style::Sql => Kind::Sql(text.as_bytes()),
+            #[cfg(feature = "html")]
+            Style::Html => Kind::Html(text.as_bytes()),
+            #[cfg(feature = "systemd")]
+            Style::SystemdUnit => Kind::SystemdUnit(text.as_bytes()),
+            #[cfg(feature = "dotenv")]
+            Style::Dotenv => Kind::Dotenv(text),
+            #[cfg(feature = "dockerfile")]
+            Style::Dockerfile => Kind::Dockerfile(text),
+            #[cfg(feature = "cmake")]
+            Style::Cmake => Kind::Cmake(text),
+            #[cfg(feature = "reg")]
+            Style::Reg => Kind::Reg(text),
+            #[cfg(feature = "sudoers")]
+            Style::Sudoers => Kind::Sudoers(text),
+            #[cfg(feature = "git")]
+            Style::GitIgnore => Kind::GitIgnore(text),
+            #[cfg(feature = "git")]
+            Style::GitAttributes => Kind::GitAttributes(text),
+            #[cfg(feature = "git")]
+            Style::GitConfig => Kind::GitConfig(text),
+            #[cfg(feature = "sanitize")]
+            Style::Sanitize => Kind::Sanitize(text.as_bytes()),
+            Style::__NonExhaustive => panic!("not a real style"),
+        })
+    }
 
-    use super::*;
+    /// Write a shell operator or redirection without quotes, so that it's
+    /// interpreted by the shell.
+    ///
+    /// This is for building a command line out of quoted arguments and
+    /// operators. Only known operators are accepted, so the result can't
+    /// run anything by itself.
+    ///
+    /// ```
+    /// use os_display::{Quoted, Style};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let command = format!(
+    ///     "{} {} {} {}",
+    ///     Quoted::unix("make"),
+    ///     Quoted::operator(Style::Unix, "2>&1"),
+    ///     Quoted::operator(Style::Unix, "|"),
+    ///     Quoted::unix("tee build log.txt"),
+    /// );
+    /// assert_eq!(command, "'make' 2>&1 | 'tee build log.txt'");
+    /// # }
+    /// ```
+    ///
+    /// For bash/ksh these are the control operators, like `&&`, `|` and `;`,
+    /// the brackets and braces for grouping, `!`, and redirections, which may
+    /// start with a file descriptor (`2>`) and end with one (`>&2`, `<&-`).
+    /// For PowerShell these are the pipeline chain operators, `|`, `;`, the
+    /// call operator `&`, brackets and braces, and redirections like `>`,
+    /// `2>>` and `*>&1`.
+    ///
+    /// With [`Quoted::for_remote`] the operator is left bare for the remote
+    /// shell and quoted for the local one.
+    ///
+    /// # Panics
+    /// If `style` isn't a shell style or `op` isn't one of its operators.
+    #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+    pub fn operator(style: Style, op: &'a str) -> Self {
+        #[allow(unreachable_patterns)]
+        let valid = match style {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Style::Unix => unix::is_operator(op),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Style::Powershell => windows::is_operator(op),
+            _ => panic!("not a shell style"),
+        };
+        assert!(valid, "not an operator: {:?}", op);
+        Quoted::from_kind(Kind::Operator(style, op))
+    }
 
-    use std::string::{String, ToString};
+    /// Quote a string with the default style for the platform.
+    ///
+    /// On Windows this is PowerShell syntax, on all other platforms this is
+    /// bash/ksh syntax.
+    #[cfg(feature = "native")]
+    pub fn native(text: &'a str) -> Self {
+        Quoted::new(Style::native(), text)
+    }
 
-    const BOTH_ALWAYS: &[(&str, &str)] = &[
-        ("foo", "'foo'"),
-        ("foo/bar.baz", "'foo/bar.baz'"),
-        ("can't", r#""can't""#),
-    ];
-    const BOTH_MAYBE: &[(&str, &str)] = &[
-        ("foo", "foo"),
-        ("foo bar", "'foo bar'"),
-        ("$foo", "'$foo'"),
-        ("-", "-"),
-        ("a#b", "a#b"),
-        ("#ab", "'#ab'"),
-        ("a~b", "a~b"),
-        ("!", "'!'"),
-        ("}", ("'}'")),
-        ("\u{200B}", "'\u{200B}'"),
-        ("\u{200B}a", "'\u{200B}a'"),
-        ("a\u{200B}", "a\u{200B}"),
-        ("\u{2000}", "'\u{2000}'"),
-        ("\u{2800}", "'\u{2800}'"),
-        // Odd but safe bidi
-        (
-            "\u{2067}\u{2066}abc\u{2069}\u{2066}def\u{2069}\u{2069}",
-            "'\u{2067}\u{2066}abc\u{2069}\u{2066}def\u{2069}\u{2069}'",
-        ),
-    ];
+    /// Quote an `OsStr` with the default style for the platform.
+    ///
+    /// On platforms other than Windows, Unix and WASI, if the encoding is
+    /// invalid, the raw encoded bytes are escaped as if they were UTF-8. On
+    /// compilers older than Rust 1.74 the `Debug` representation is used
+    /// instead. If the `lossy` feature is enabled invalid parts are replaced
+    /// by `U+FFFD` instead of either.
+    ///
+    /// On WASI the raw bytes are escaped too. Every compiler that has the
+    /// `wasm32-wasip2` target is new enough for that, but if the build
+    /// script can't check its version the invalid parts are replaced on
+    /// preview 2, unless the `modern-os-str` feature is enabled.
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    pub fn native_raw(text: &'a OsStr) -> Self {
+        Quoted::from_kind(Kind::NativeRaw(text))
+    }
 
-    const UNIX_ALWAYS: &[(&str, &str)] = &[
-        ("", "''"),
-        (r#"can'"t"#, r#"'can'\''"t'"#),
-        (r#"can'$t"#, r#"'can'\''$t'"#),
-        ("foo\nb\ta\r\\\0`r", r#"$'foo\nb\ta\r\\\x00`r'"#),
-        ("foo\x02", r#"$'foo\x02'"#),
-        (r#"'$''"#, r#"\''$'\'\'"#),
-    ];
-    const UNIX_MAYBE: &[(&str, &str)] = &[
-        ("", "''"),
-        ("-x", "-x"),
-        ("a,b", "a,b"),
-        ("a\\b", "'a\\b'"),
-        ("\x02AB", "$'\\x02'$'AB'"),
-        ("\x02GH", "$'\\x02GH'"),
-        ("\t", r#"$'\t'"#),
-        ("\r", r#"$'\r'"#),
-        ("\u{85}", r#"$'\xC2\x85'"#),
-        ("\u{85}a", r#"$'\xC2\x85'$'a'"#),
-        ("\u{2028}", r#"$'\xE2\x80\xA8'"#),
-        // Dangerous bidi
-        (
-            "user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}",
-            r#"$'user\xE2\x80\xAE \xE2\x81\xA6// Check if admin\xE2\x81\xA9 \xE2\x81\xA6'"#,
-        ),
-    ];
-    const UNIX_RAW: &[(&[u8], &str)] = &[
-        (b"foo\xFF", r#"$'foo\xFF'"#),
-        (b"foo\xFFbar", r#"$'foo\xFF'$'bar'"#),
-    ];
+    /// Like [`Quoted::native_raw`], but also turn the output into a terminal
+    /// hyperlink to the path. See [`Quoted::hyperlink`].
+    ///
+    /// The link is a `file://` URL without a host. Relative paths are resolved
+    /// against the current directory when the `Quoted` is formatted. If that
+    /// fails, or if the path can't be expressed as a URL (like a Windows
+    /// device path), no hyperlink is added.
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    pub fn native_raw_linked(path: &'a Path) -> Self {
+        let mut quoted = Quoted::native_raw(path.as_os_str());
+        quoted.link = Some(Link::Path(path));
+        quoted
+    }
+
+    /// Write a path as a `file://` URL, with every byte that isn't safe in
+    /// a URL percent-encoded.
+    ///
+    /// This is an alternative to quoting for logs and other output that
+    /// should be clickable and machine-readable. On Unix any path can be
+    /// written this way without losing information.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "native", feature = "std", unix))] {
+    /// use std::path::Path;
+    /// use os_display::Quoted;
+    ///
+    /// let path = Path::new("/tmp/my report.txt");
+    /// assert_eq!(Quoted::file_url(path).to_string(), "file:///tmp/my%20report.txt");
+    /// # }
+    /// ```
+    ///
+    /// Relative paths are resolved against the current directory when the
+    /// `Quoted` is formatted. If that fails, or if the path can't be
+    /// expressed as a URL (like a Windows device path), it's quoted like
+    /// [`Quoted::native_raw`] instead.
+    ///
+    /// On Windows, parts of the path that aren't valid unicode are replaced
+    /// by `U+FFFD`.
+    #[cfg(feature = "native")]
+    #[cfg(feature = "std")]
+    pub fn file_url(path: &'a Path) -> Self {
+        Quoted::from_kind(Kind::FileUrl(path))
+    }
+
+    /// Quote a string using bash/ksh syntax.
+    ///
+    /// # Optional
+    /// This requires the optional `unix` feature.
+    #[cfg(feature = "unix")]
+    pub fn unix(text: &'a str) -> Self {
+        Quoted::new(Style::Unix, text)
+    }
+
+    /// Quote possibly invalid UTF-8 using bash/ksh syntax.
+    ///
+    /// # Optional
+    /// This requires the optional `unix` feature.
+    #[cfg(feature = "unix")]
+    pub fn unix_raw(bytes: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::UnixRaw(bytes))
+    }
+
+    /// Quote a string using PowerShell syntax.
+    ///
+    /// # Optional
+    /// This requires the optional `windows` feature.
+    #[cfg(feature = "windows")]
+    pub fn windows(text: &'a str) -> Self {
+        Quoted::new(Style::Powershell, text)
+    }
+
+    /// Quote possibly invalid UTF-16 using PowerShell syntax.
+    ///
+    /// # Optional
+    /// This requires the optional `windows` feature and the (default) `alloc` feature.
+    #[cfg(feature = "windows")]
+    #[cfg(feature = "alloc")]
+    pub fn windows_raw(units: &'a [u16]) -> Self {
+        Quoted::from_kind(Kind::WindowsRaw(units))
+    }
+
+    /// Quote possibly ill-formed UTF-16 that's been encoded as WTF-8, using
+    /// PowerShell syntax.
+    ///
+    /// WTF-8 is UTF-8 that also allows unpaired surrogates. It's what `OsStr`
+    /// uses on Windows, so this can quote the output of
+    /// `OsStr::as_encoded_bytes()` directly, without converting to UTF-16.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// assert_eq!(Quoted::windows_wtf8(b"a\xED\xA0\x80b").to_string(), r#""a`u{D800}b""#);
+    /// assert_eq!(Quoted::windows_wtf8(b"foo bar").to_string(), "'foo bar'");
+    /// # }
+    /// ```
+    ///
+    /// Bytes that aren't valid WTF-8 are replaced by U+FFFD.
+    ///
+    /// # Optional
+    /// This requires the optional `windows` feature.
+    #[cfg(feature = "windows")]
+    pub fn windows_wtf8(bytes: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::WindowsWtf8(bytes))
+    }
+
+    /// Quote a string as a single argument for a raw Windows command line,
+    /// following the rules of `CommandLineToArgvW` and the Microsoft C runtime.
+    ///
+    /// This is not PowerShell syntax. It's for building the command line that
+    /// gets passed to `CreateProcess`. Double quotes are escaped with a
+    /// backslash, and backslashes are doubled only if they come before a
+    /// double quote.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// assert_eq!(Quoted::windows_argv(r"C:\Program Files\").to_string(), r#""C:\Program Files\\""#);
+    /// assert_eq!(Quoted::windows_argv(r#"say "hi""#).force(false).to_string(), r#""say \"hi\"""#);
+    /// assert_eq!(Quoted::windows_argv(r"C:\foo").force(false).to_string(), r"C:\foo");
+    /// # }
+    /// ```
+    ///
+    /// There's no way to escape other characters, so control codes and bidi
+    /// characters are written as they are and the output is not safe to print
+    /// to a terminal. The first argument of a command line (the program name)
+    /// is parsed differently and can't contain double quotes at all.
+    ///
+    /// `cmd.exe` has more special characters, like `%` and `^`. Use
+    /// [`Quoted::cmd_context`] to take care of those.
+    ///
+    /// If the output is truncated with a precision (`{:.N}`) and the cut
+    /// falls right after a backslash, the closing quote gets escaped.
+    ///
+    /// # Optional
+    /// This requires the optional `windows` feature.
+    #[cfg(feature = "windows")]
+    pub fn windows_argv(text: &'a str) -> Self {
+        Quoted::new(Style::WindowsArgv, text)
+    }
+
+    /// Escape the output of [`Quoted::windows_argv`] for a command line that's
+    /// run by `cmd.exe`, like a line in a batch file.
+    ///
+    /// `cmd.exe` parses the line before the program does, and it has its own
+    /// special characters. Each of those gets a `^`, double quotes too, so
+    /// that `cmd.exe` passes the line on the way [`Quoted::windows_argv`]
+    /// wrote it. How a `%` or `!` is escaped depends on the [`CmdContext`]:
+    ///
+    /// ```
+    /// use os_display::{CmdContext, Quoted};
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// let arg = |context| Quoted::windows_argv("100% & more!").cmd_context(context).to_string();
+    /// assert_eq!(arg(CmdContext::Interactive), r#"^"100^% ^& more!^""#);
+    /// assert_eq!(arg(CmdContext::BatchFile), r#"^"100%% ^& more!^""#);
+    /// assert_eq!(arg(CmdContext::DelayedExpansion), r#"^"100%% ^& more^^!^""#);
+    /// # }
+    /// ```
+    ///
+    /// There's no way to pass on a line break, so those are replaced by
+    /// U+FFFD and count as unrepresentable. This only applies with
+    /// [`QuoteContext::Standalone`]. Commands inside `for /f ('...')` are
+    /// parsed by `cmd.exe` a second time, so they need to be escaped twice.
+    ///
+    /// Defaults to not escaping anything for `cmd.exe`.
+    ///
+    /// # Optional
+    /// This requires the optional `windows` feature.
+    #[cfg(feature = "windows")]
+    pub fn cmd_context(mut self, context: CmdContext) -> Self {
+        self.opts.cmd_context = Some(context);
+        self
+    }
+
+    /// Write a string as an SQL string literal.
+    ///
+    /// It's always quoted. Control characters, bidi characters and other
+    /// text that would be escaped in a shell is written as a hex literal
+    /// instead, so the output stays safe to print to a terminal:
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sql")] {
+    /// assert_eq!(Quoted::sql("it's").to_string(), "'it''s'");
+    /// assert_eq!(Quoted::sql("a\nb").to_string(), "'a' || x'0A' || 'b'");
+    /// # }
+    /// ```
+    ///
+    /// The default dialect is standard SQL, see [`Quoted::sql_dialect`].
+    ///
+    /// # Optional
+    /// This requires the optional `sql` feature.
+    #[cfg(feature = "sql")]
+    pub fn sql(text: &'a str) -> Self {
+        Quoted::new(Style::Sql, text)
+    }
+
+    /// Write bytes as an SQL string literal. Bytes that aren't part of valid
+    /// UTF-8 are written as hex literals.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sql")]
+    /// assert_eq!(Quoted::sql_raw(b"caf\xE9").to_string(), "'caf' || x'E9'");
+    /// ```
+    ///
+    /// A database may reject a string with invalid UTF-8, or store it as
+    /// a blob.
+    ///
+    /// # Optional
+    /// This requires the optional `sql` feature.
+    #[cfg(feature = "sql")]
+    pub fn sql_raw(bytes: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::Sql(bytes))
+    }
+
+    /// Toggle forced quoting. If `true`, quotes are added even if no special
+    /// characters are present.
+    ///
+    /// Defaults to `true`.
+    pub fn force(mut self, force: bool) -> Self {
+        self.opts.force_quote = force;
+        self
+    }
+
+    /// Choose which kind of quotes to use if either would work.
+    ///
+    /// This is only a preference. If the text contains a single quote then
+    /// double quotes may be used anyway, and vice versa. For PowerShell the
+    /// typographic quotes count too.
+    ///
+    /// ```
+    /// use os_display::{Quoted, QuoteChar};
+    ///
+    /// assert_eq!(Quoted::native("foo").prefer(QuoteChar::Double).to_string(), "\"foo\"");
+    /// assert_eq!(Quoted::native("$foo").prefer(QuoteChar::Double).to_string(), "'$foo'");
+    /// ```
+    ///
+    /// In the bash/ksh style text with a `!` never goes in double quotes,
+    /// since interactive shells expand the history there.
+    ///
+    /// Defaults to [`QuoteChar::Single`].
+    pub fn prefer(mut self, quote: QuoteChar) -> Self {
+        self.opts.prefer = quote;
+        self
+    }
+
+    /// Choose how eagerly to add quotes, if [`Quoted::force`] is off.
+    ///
+    /// ```
+    /// use os_display::{Quoted, Quoting};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let quote = |text, quoting| Quoted::unix(text).force(false).quoting(quoting).to_string();
+    /// assert_eq!(quote("key=value", Quoting::Minimal), "key=value");
+    /// assert_eq!(quote("key=value", Quoting::Standard), "'key=value'");
+    /// assert_eq!(quote("file.txt", Quoting::Standard), "file.txt");
+    /// assert_eq!(quote("file.txt", Quoting::Paranoid), "'file.txt'");
+    /// # }
+    /// ```
+    ///
+    /// This only applies to bash/ksh and PowerShell. Defaults to
+    /// [`Quoting::Standard`].
+    pub fn quoting(mut self, quoting: Quoting) -> Self {
+        self.opts.quoting = quoting;
+        self
+    }
+
+    /// Add characters that don't need quotes, if [`Quoted::force`] is off.
+    ///
+    /// This loosens the rules, e.g. for `=` in `key=value` arguments or `,`
+    /// in PowerShell. It also applies to [`Quoting::Paranoid`].
+    ///
+    /// ```
+    /// use os_display::{Quoted, Quoting};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert_eq!(Quoted::unix("uid=0").force(false).to_string(), "'uid=0'");
+    /// assert_eq!(Quoted::unix("uid=0").force(false).extra_safe_chars("=").to_string(), "uid=0");
+    ///
+    /// let paranoid = Quoted::unix("/tmp/x.txt").force(false).quoting(Quoting::Paranoid);
+    /// assert_eq!(paranoid.extra_safe_chars("./").to_string(), "/tmp/x.txt");
+    /// # }
+    /// ```
+    ///
+    /// Nothing stops you from marking a character as safe when it isn't,
+    /// like `'` or a space, so only add characters that you know to be
+    /// harmless where the output goes. Control characters are escaped
+    /// either way. This replaces the characters from an earlier call.
+    ///
+    /// This only applies to bash/ksh and PowerShell.
+    ///
+    /// # Panics
+    /// If `chars` isn't ASCII.
+    pub fn extra_safe_chars(mut self, chars: &str) -> Self {
+        self.opts.extra_safe = AsciiSet::new(chars);
+        self
+    }
+
+    /// Add characters that need quotes, if [`Quoted::force`] is off.
+    ///
+    /// This tightens the rules for places where more characters are special,
+    /// e.g. `%` in a crontab, or `#` anywhere in an fstab line. It takes
+    /// precedence over [`Quoted::extra_safe_chars`].
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert_eq!(Quoted::unix("50%").force(false).to_string(), "50%");
+    /// assert_eq!(Quoted::unix("50%").force(false).extra_unsafe_chars("%").to_string(), "'50%'");
+    /// # }
+    /// ```
+    ///
+    /// Quotes don't always take away the special meaning: `%` still has to
+    /// be escaped with a backslash in a crontab, and this won't do that.
+    /// This replaces the characters from an earlier call.
+    ///
+    /// This only applies to bash/ksh and PowerShell.
+    ///
+    /// # Panics
+    /// If `chars` isn't ASCII.
+    pub fn extra_unsafe_chars(mut self, chars: &str) -> Self {
+        self.opts.extra_unsafe = AsciiSet::new(chars);
+        self
+    }
+
+    /// Choose what to do with text that can't be written in a way that gets
+    /// it across, like a NUL character in a command-line argument. See
+    /// [`Quoted::try_render`] for the full list.
+    ///
+    /// ```
+    /// use os_display::{OnUnrepresentable, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let text = "a\0b";
+    /// let quoted = Quoted::unix(text).on_unrepresentable(OnUnrepresentable::ReplacementChar);
+    /// assert_eq!(quoted.to_string(), "'a\u{FFFD}b'");
+    ///
+    /// let quoted = Quoted::unix(text).on_unrepresentable(OnUnrepresentable::Error);
+    /// let mut out = String::new();
+    /// assert!(std::fmt::Write::write_fmt(&mut out, format_args!("{}", quoted)).is_err());
+    /// # }
+    /// ```
+    ///
+    /// With [`OnUnrepresentable::Error`] formatting fails, so `to_string()`
+    /// panics. Use [`Quoted::try_render`] to get an error value instead.
+    ///
+    /// Invalid unicode is replaced by U+FFFD where there's no other way to
+    /// write it, unless this is set to [`OnUnrepresentable::Error`].
+    ///
+    /// Defaults to [`OnUnrepresentable::Escape`].
+    pub fn on_unrepresentable(mut self, policy: OnUnrepresentable) -> Self {
+        self.opts.on_unrepresentable = policy;
+        self
+    }
+
+    /// Choose how to handle suspicious bidirectional text.
+    ///
+    /// ```
+    /// use os_display::{BidiPolicy, Quoted};
+    ///
+    /// let text = "foo\u{202E}bar";
+    /// assert_eq!(Quoted::native(text).bidi_policy(BidiPolicy::Allow).to_string(), "'foo\u{202E}bar'");
+    /// assert_eq!(
+    ///     Quoted::native(text).bidi_policy(BidiPolicy::Isolate).to_string(),
+    ///     "\u{2068}'foo\u{202E}bar'\u{2069}",
+    /// );
+    /// ```
+    ///
+    /// Defaults to [`BidiPolicy::Escape`].
+    pub fn bidi_policy(mut self, policy: BidiPolicy) -> Self {
+        self.opts.bidi = policy;
+        self
+    }
+
+    /// Toggle escaping of invisible characters.
+    ///
+    /// By default zero-width characters only cause quoting if they appear at
+    /// the start, so that the text can be selected. If this is enabled then
+    /// all of the characters matched by [`security::is_invisible`] (like
+    /// `U+200B ZERO WIDTH SPACE` and variation selectors) are escaped wherever
+    /// they appear, so they can't hide in plain sight.
+    ///
+    /// This also escapes properly terminated bidi control characters.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// assert_eq!(Quoted::native("a\u{200B}b").force(false).to_string(), "a\u{200B}b");
+    /// # #[cfg(not(windows))]
+    /// assert_eq!(
+    ///     Quoted::native("a\u{200B}b").force(false).escape_invisible(true).to_string(),
+    ///     r"$'a\xE2\x80\x8B'$'b'",
+    /// );
+    /// ```
+    ///
+    /// Defaults to `false`.
+    pub fn escape_invisible(mut self, escape: bool) -> Self {
+        self.opts.escape_invisible = escape;
+        self
+    }
+
+    /// Set how many combining marks can be stacked on a single character
+    /// before they're escaped, or `None` for no limit.
+    ///
+    /// "Zalgo" text piles up dozens of combining marks, so that a short name
+    /// spills over the lines above and below it. If any character in the text
+    /// has more than this many then all of the combining marks in the text
+    /// are escaped, which keeps the output in place. See
+    /// [`security::is_combining`] for the characters that count.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(not(windows))] {
+    /// let zalgo = "Z\u{351}\u{36B}\u{343}\u{36A}\u{302}";
+    /// assert_eq!(
+    ///     Quoted::native(zalgo).to_string(),
+    ///     r"$'Z\xCD\x91\xCD\xAB\xCD\x83\xCD\xAA\xCC\x82'",
+    /// );
+    /// assert_eq!(Quoted::native(zalgo).max_combining(None).to_string(), format!("'{}'", zalgo));
+    /// assert_eq!(Quoted::native("Vie\u{302}\u{323}t").to_string(), "'Vie\u{302}\u{323}t'");
+    /// # }
+    /// ```
+    ///
+    /// Defaults to `Some(4)`. Real text doesn't come close.
+    pub fn max_combining(mut self, limit: Option<usize>) -> Self {
+        self.opts.max_combining = limit;
+        self
+    }
+
+    /// Toggle escaping of characters that carry text that isn't shown, like
+    /// tag characters and interlinear annotation controls.
+    ///
+    /// Tag characters mirror ASCII but are invisible, so a whole message can
+    /// hide inside a single emoji or file name. See
+    /// [`security::is_hidden_text`] for the full list.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(not(windows))] {
+    /// assert_eq!(
+    ///     Quoted::native("a\u{E0041}").force(false).to_string(),
+    ///     r"$'a\xF3\xA0\x81\x81'",
+    /// );
+    /// assert_eq!(
+    ///     Quoted::native("a\u{E0041}").force(false).escape_hidden_text(false).to_string(),
+    ///     "a\u{E0041}",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// This also escapes the tags in the emoji flags for England, Scotland
+    /// and Wales. Disable it to keep those readable, and the characters are
+    /// only escaped with [`Quoted::escape_invisible`].
+    ///
+    /// Defaults to `true`.
+    pub fn escape_hidden_text(mut self, escape: bool) -> Self {
+        self.opts.escape_hidden_text = escape;
+        self
+    }
+
+    /// Choose how to render ASCII control characters.
+    ///
+    /// Caret notation and control pictures are easier to read than escape
+    /// sequences, but the output stops being valid syntax. Only use them for
+    /// output that's meant to be read, not copied. The quotes are kept so
+    /// that the extent of the text is still clear.
+    ///
+    /// ```
+    /// use os_display::{ControlStyle, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let quoted = Quoted::unix("line\r\n");
+    /// assert_eq!(quoted.to_string(), r"$'line\r\n'");
+    /// assert_eq!(quoted.control_style(ControlStyle::Caret).to_string(), "$'line^M^J'");
+    /// assert_eq!(
+    ///     quoted.control_style(ControlStyle::Picture).to_string(),
+    ///     "$'line\u{240D}\u{240A}'",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// Other characters that are escaped, like invalid unicode and the C1
+    /// control characters, keep their escape sequences.
+    ///
+    /// Defaults to [`ControlStyle::Escape`].
+    pub fn control_style(mut self, style: ControlStyle) -> Self {
+        self.opts.control_style = style;
+        self
+    }
+
+    /// Toggle escaping of characters that look like ASCII.
+    ///
+    /// If the text is otherwise pure ASCII then characters like the Cyrillic
+    /// `а` or the Greek `ο` are likely to be an attempt at spoofing. If this
+    /// is enabled they're escaped so they stand out. Text that contains other
+    /// non-ASCII characters is left alone, since it's more likely legitimate.
+    ///
+    /// See [`security::ascii_confusable`] for the characters involved.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(not(windows))]
+    /// assert_eq!(
+    ///     Quoted::native("p\u{0430}ypal").escape_confusables(true).to_string(),
+    ///     r"$'p\xD0\xB0ypal'",
+    /// );
+    /// ```
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the optional `confusables` feature, which includes a
+    /// table of about 1500 characters.
+    #[cfg(feature = "confusables")]
+    pub fn escape_confusables(mut self, escape: bool) -> Self {
+        self.opts.confusables = escape;
+        self
+    }
+
+    /// Choose where the output is going to be used.
+    ///
+    /// With [`QuoteContext::InsideDoubleQuotes`] the output can be spliced
+    /// into a double-quoted string that's already being built, like a
+    /// command for `ssh` or `sh -c`. No surrounding quotes are added, and
+    /// [`Quoted::force`], [`Quoted::prefer`] and [`BidiPolicy::Isolate`] have
+    /// no effect.
+    ///
+    /// ```
+    /// use os_display::{Quoted, QuoteContext};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let inner = Quoted::unix("$HOME/a \"b\"\n").context(QuoteContext::InsideDoubleQuotes);
+    /// assert_eq!(
+    ///     std::format!("ssh host \"ls {}\"", inner),
+    ///     r#"ssh host "ls \$HOME/a \"b\""$'\n'"""#,
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// Defaults to [`QuoteContext::Standalone`].
+    pub fn context(mut self, context: QuoteContext) -> Self {
+        self.opts.context = context;
+        self
+    }
+
+    /// Quote the text again for each extra shell it has to pass through.
+    ///
+    /// `ssh` joins its arguments with spaces and hands them to a shell on
+    /// the other end, so an argument in `ssh host cmd arg` is parsed twice:
+    /// once locally and once remotely. That takes `for_remote(1)`. Every
+    /// nested `ssh` adds another level.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert_eq!(Quoted::unix("foo bar").for_remote(1).to_string(), r#""'foo bar'""#);
+    /// assert_eq!(Quoted::unix("it's").for_remote(1).to_string(), r#"'"it'\''s"'"#);
+    /// # }
+    /// ```
+    ///
+    /// The inner levels are always escaped rather than isolated, since the
+    /// isolating characters would become part of the text. Highlighting,
+    /// hyperlinks and the [`QuoteContext`] only apply to the outermost level.
+    ///
+    /// Defaults to `0`.
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn for_remote(mut self, levels: usize) -> Self {
+        self.opts.remote = levels;
+        self
+    }
+
+    /// Escape a string for use as text in an HTML document or attribute
+    /// value.
+    ///
+    /// The characters `<>&"'` are replaced by character references. So are
+    /// control characters and anything else that would be escaped in a shell,
+    /// like suspicious bidi characters and (with [`Quoted::escape_invisible`])
+    /// invisible characters. Nothing is quoted.
+    ///
+    /// ```
+    /// use os_display::{BidiPolicy, Quoted};
+    ///
+    /// # #[cfg(feature = "html")] {
+    /// assert_eq!(Quoted::html("<b>Tom & Jerry's</b>").to_string(), "&lt;b&gt;Tom &amp; Jerry&#39;s&lt;/b&gt;");
+    /// assert_eq!(Quoted::html("a\u{202E}b").to_string(), "a&#x202E;b");
+    /// assert_eq!(
+    ///     Quoted::html("a\u{202E}b").bidi_policy(BidiPolicy::Isolate).to_string(),
+    ///     "<bdi>a\u{202E}b</bdi>",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// With [`BidiPolicy::Isolate`] unterminated bidi text is wrapped in a
+    /// `<bdi>` element instead of the isolate characters a terminal would get.
+    ///
+    /// Browsers decode the references, so control and invisible characters
+    /// stay invisible on the rendered page. The point is that the markup
+    /// itself stays well-formed and safe to print.
+    ///
+    /// # Optional
+    /// This requires the optional `html` feature.
+    #[cfg(feature = "html")]
+    pub fn html(text: &'a str) -> Self {
+        Quoted::new(Style::Html, text)
+    }
+
+    /// Escape bytes for use in HTML. Bytes that aren't part of valid UTF-8
+    /// become `&#xFFFD;`, the replacement character.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "html")]
+    /// assert_eq!(Quoted::html_raw(b"caf\xE9").to_string(), "caf&#xFFFD;");
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the optional `html` feature.
+    #[cfg(feature = "html")]
+    pub fn html_raw(bytes: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::Html(bytes))
+    }
+
+    /// Quote a value for a `.env` file, as read by Docker Compose and
+    /// python-dotenv. Use [`assign`] to write a whole `KEY=value` line.
+    ///
+    /// ```
+    /// use os_display::{assign, Quoted};
+    ///
+    /// # #[cfg(feature = "dotenv")] {
+    /// assert_eq!(Quoted::dotenv("/usr/local/bin").force(false).to_string(), "/usr/local/bin");
+    /// assert_eq!(assign("PASSWORD", Quoted::dotenv("pa$$ word")).unwrap().to_string(), "PASSWORD='pa$$ word'");
+    /// assert_eq!(assign("GREETING", Quoted::dotenv("it's\n$HOME")).unwrap().to_string(), r#"GREETING="it's\n\$HOME""#);
+    /// # }
+    /// ```
+    ///
+    /// Single quotes are used if possible, since no parser interpolates
+    /// variables inside them. Otherwise the value goes in double quotes,
+    /// with backslash escapes for newlines and other control characters,
+    /// `"`, `\\` and `$`. python-dotenv doesn't understand `\$` and leaves
+    /// the backslash in place.
+    ///
+    /// There's no way to escape most other control characters or bidi
+    /// characters, so they're written as they are and the output is not
+    /// safe to print to a terminal.
+    ///
+    /// # Optional
+    /// This requires the optional `dotenv` feature.
+    #[cfg(feature = "dotenv")]
+    pub fn dotenv(text: &'a str) -> Self {
+        Quoted::new(Style::Dotenv, text)
+    }
+
+    /// Quote an argument for a `RUN`, `CMD` or `ENTRYPOINT` instruction in a
+    /// Dockerfile.
+    ///
+    /// ```
+    /// use os_display::{DockerfileForm, Quoted};
+    ///
+    /// # #[cfg(feature = "dockerfile")] {
+    /// let path = "/srv/my app";
+    /// let shell = format!("RUN ls -l {}", Quoted::dockerfile(path));
+    /// assert_eq!(shell, "RUN ls -l '/srv/my app'");
+    ///
+    /// let exec = format!("RUN [\"ls\", \"-l\", {}]", Quoted::dockerfile(path).dockerfile_form(DockerfileForm::Exec));
+    /// assert_eq!(exec, r#"RUN ["ls", "-l", "/srv/my app"]"#);
+    /// # }
+    /// ```
+    ///
+    /// The output never contains a newline, which would end the instruction,
+    /// or other characters that are escaped in a shell. The default is
+    /// shell form, see [`Quoted::dockerfile_form`].
+    ///
+    /// In shell form such text is written as `"$(printf '...')"`, and command
+    /// substitution drops newlines at the end. So `"a\n"` comes out as `a`.
+    /// [`Quoted::try_render`] reports this, and so does formatting with
+    /// [`OnUnrepresentable::Error`]. Exec form doesn't have this problem.
+    ///
+    /// ```
+    /// use os_display::{DockerfileForm, Quoted};
+    ///
+    /// # #[cfg(feature = "dockerfile")] {
+    /// let quoted = Quoted::dockerfile("a\n");
+    /// assert_eq!(quoted.to_string(), r#""$(printf 'a\012')""#);
+    /// assert!(quoted.try_render().is_err());
+    ///
+    /// let exec = quoted.dockerfile_form(DockerfileForm::Exec);
+    /// assert_eq!(exec.try_render().unwrap(), r#""a\n""#);
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the optional `dockerfile` feature.
+    #[cfg(feature = "dockerfile")]
+    pub fn dockerfile(text: &'a str) -> Self {
+        Quoted::new(Style::Dockerfile, text)
+    }
+
+    /// Quote an argument for a command in CMake code, like a
+    /// `CMakeLists.txt` file or an initial cache script.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "cmake")] {
+    /// let path = r"C:\Program Files\${app}";
+    /// let line = format!("set(APP_DIR {})", Quoted::cmake(path));
+    /// assert_eq!(line, r#"set(APP_DIR "C:\\Program Files\\\${app}")"#);
+    /// assert_eq!(Quoted::cmake("src/main.c").force(false).to_string(), "src/main.c");
+    /// # }
+    /// ```
+    ///
+    /// A `;` is kept as it is, so it will separate list elements if the
+    /// value is used as a list later. Use [`Quoted::cmake_list`] to prevent
+    /// that.
+    ///
+    /// There's no way to escape control characters other than newlines,
+    /// carriage returns and tabs, or bidi characters, so they're written as
+    /// they are and the output is not safe to print to a terminal.
+    ///
+    /// For a `-D` option on the command line the value isn't parsed as CMake
+    /// code, so quote it for the shell instead.
+    ///
+    /// # Optional
+    /// This requires the optional `cmake` feature.
+    #[cfg(feature = "cmake")]
+    pub fn cmake(text: &'a str) -> Self {
+        Quoted::new(Style::Cmake, text)
+    }
+
+    /// Quote a string value for a `.reg` file, as imported by `regedit` and
+    /// `reg import`.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "reg")] {
+    /// let line = format!("\"Path\"={}", Quoted::reg(r#"C:\Program Files\"App""#));
+    /// assert_eq!(line, r#""Path"="C:\\Program Files\\\"App\"""#);
+    /// assert_eq!(Quoted::reg("a\nb").to_string(), "hex(1):61,00,0a,00,62,00,00,00");
+    /// # }
+    /// ```
+    ///
+    /// A string can't contain newlines and only has escapes for `\\` and
+    /// `"`, so text that has to be escaped is written as a hex `REG_SZ`
+    /// value instead.
+    ///
+    /// Use the `Windows Registry Editor Version 5.00` header and save the
+    /// file as UTF-16 if the text isn't ASCII. The result isn't suitable for
+    /// value names, which can't be written in hex.
+    ///
+    /// # Optional
+    /// This requires the optional `reg` feature.
+    #[cfg(feature = "reg")]
+    pub fn reg(text: &'a str) -> Self {
+        Quoted::new(Style::Reg, text)
+    }
+
+    /// Quote possibly invalid UTF-16 as a string value for a `.reg` file.
+    ///
+    /// Unpaired surrogates are kept intact in the hex form.
+    ///
+    /// # Optional
+    /// This requires the optional `reg` feature.
+    #[cfg(feature = "reg")]
+    pub fn reg_raw(units: &'a [u16]) -> Self {
+        Quoted::from_kind(Kind::RegRaw(units))
+    }
+
+    /// Escape a command path or argument for a sudoers file, as in a user
+    /// specification or a `Cmnd_Alias`.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sudoers")] {
+    /// let line = format!(
+    ///     "deploy ALL = {} {}",
+    ///     Quoted::sudoers("/opt/My App/bin/restart"),
+    ///     Quoted::sudoers("web,api=*"),
+    /// );
+    /// assert_eq!(line, r"deploy ALL = /opt/My\ App/bin/restart web\,api\=\*");
+    /// # }
+    /// ```
+    ///
+    /// Nothing is quoted. `,:=\`, whitespace, `#` and the wildcards `*?[]`
+    /// get a backslash, so the entry matches only this exact text.
+    ///
+    /// A command can't contain control characters other than tabs, so those
+    /// are replaced by U+FFFD and the entry won't match them. Empty text
+    /// becomes `""`, which only allows the command without any arguments.
+    /// Both count as unrepresentable, see [`Quoted::on_unrepresentable`].
+    /// Bidi characters are written as they are, so the output is not safe to
+    /// print to a terminal.
+    ///
+    /// # Optional
+    /// This requires the optional `sudoers` feature.
+    #[cfg(feature = "sudoers")]
+    pub fn sudoers(text: &'a str) -> Self {
+        Quoted::new(Style::Sudoers, text)
+    }
+
+    /// Escape a path as a pattern for a `.gitignore` file, or another file
+    /// with the same syntax like `.git/info/exclude`.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "git")] {
+    /// assert_eq!(Quoted::gitignore("#notes [draft].txt ").to_string(), r"\#notes\ \[draft].txt\ ");
+    /// assert_eq!(Quoted::gitignore("!important").to_string(), r"\!important");
+    /// # }
+    /// ```
+    ///
+    /// A leading `#` or `!`, spaces, tabs, the wildcards `*?[` and `\` get a
+    /// backslash, so the pattern matches this name and nothing else. Nothing
+    /// is quoted.
+    ///
+    /// The pattern matches the name in any directory below the `.gitignore`
+    /// file, unless it contains a `/`. Put a `/` in front to only match it
+    /// next to the file, and add one at the end to only match directories.
+    ///
+    /// A pattern can't contain other control characters, so those are
+    /// replaced by U+FFFD and the pattern won't match them. Empty text is
+    /// written as nothing, which doesn't match anything. Both count as
+    /// unrepresentable, see [`Quoted::on_unrepresentable`].
+    ///
+    /// # Optional
+    /// This requires the optional `git` feature.
+    #[cfg(feature = "git")]
+    pub fn gitignore(text: &'a str) -> Self {
+        Quoted::new(Style::GitIgnore, text)
+    }
+
+    /// Escape a path as a pattern for a `.gitattributes` file.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "git")] {
+    /// let line = format!("{} binary", Quoted::gitattributes("assets/*.psd"));
+    /// assert_eq!(line, r"assets/\*.psd binary");
+    /// let line = format!("{} -diff", Quoted::gitattributes("My Docs/[old].txt"));
+    /// assert_eq!(line, r#""My Docs/\\[old].txt" -diff"#);
+    /// # }
+    /// ```
+    ///
+    /// The wildcards are escaped like for [`Quoted::gitignore`]. Text with
+    /// whitespace or control characters goes in C-style double quotes,
+    /// which git decodes first. Empty text becomes `""`, which
+    /// doesn't match anything and counts as unrepresentable.
+    ///
+    /// # Optional
+    /// This requires the optional `git` feature.
+    #[cfg(feature = "git")]
+    pub fn gitattributes(text: &'a str) -> Self {
+        Quoted::new(Style::GitAttributes, text)
+    }
+
+    /// Quote a value for a git config file, like `.git/config` or
+    /// `.gitmodules`.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "git")] {
+    /// let line = format!("path = {}", Quoted::git_config(r"C:\My Repos\#1"));
+    /// assert_eq!(line, r#"path = "C:\\My Repos\\#1""#);
+    /// assert_eq!(Quoted::git_config("main").force(false).to_string(), "main");
+    /// # }
+    /// ```
+    ///
+    /// Inside the quotes `"` and `\` are escaped, and so are newlines, tabs
+    /// and backspaces. There's no way to write other control characters, so
+    /// they're replaced by U+FFFD and count as unrepresentable. Bidi
+    /// characters are written as they are, so the output is not safe to
+    /// print to a terminal.
+    ///
+    /// # Optional
+    /// This requires the optional `git` feature.
+    #[cfg(feature = "git")]
+    pub fn git_config(text: &'a str) -> Self {
+        Quoted::new(Style::GitConfig, text)
+    }
+
+    /// Make text safe to print to a terminal or a log, without quoting it.
+    ///
+    /// This is for output that's only meant to be read, where the text
+    /// doesn't have to survive a round trip through a shell. Only the
+    /// characters that could mess with the terminal or the reader are
+    /// escaped: control characters (including the ESC that starts CSI and
+    /// OSC sequences, and the C1 versions of those), line separators,
+    /// suspicious bidi characters, and invalid unicode.
+    /// [`Quoted::escape_invisible`] and [`Quoted::escape_confusables`] add
+    /// to that. Nothing is quoted, and backslashes aren't escaped, so the
+    /// escapes can't always be told apart from the text itself.
+    ///
+    /// ```
+    /// use os_display::{ControlStyle, Quoted};
+    ///
+    /// # #[cfg(feature = "sanitize")] {
+    /// assert_eq!(Quoted::sanitize("my file.txt").to_string(), "my file.txt");
+    /// assert_eq!(
+    ///     Quoted::sanitize("\x1B]8;;http://evil/\x07click\x1B]8;;\x07").to_string(),
+    ///     r"\x1B]8;;http://evil/\x07click\x1B]8;;\x07",
+    /// );
+    /// assert_eq!(Quoted::sanitize("line\r\nfake entry").to_string(), r"line\r\nfake entry");
+    /// assert_eq!(Quoted::sanitize("a\u{202E}b").to_string(), r"a\u{202E}b");
+    /// assert_eq!(
+    ///     Quoted::sanitize("line\r\n").control_style(ControlStyle::Caret).to_string(),
+    ///     "line^M^J",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// With [`BidiPolicy::Isolate`] unterminated bidi text is wrapped in
+    /// isolate characters, like with the shell styles.
+    ///
+    /// # Optional
+    /// This requires the optional `sanitize` feature.
+    #[cfg(feature = "sanitize")]
+    pub fn sanitize(text: &'a str) -> Self {
+        Quoted::new(Style::Sanitize, text)
+    }
+
+    /// Make bytes safe to display, like [`Quoted::sanitize`]. Bytes that
+    /// aren't part of valid UTF-8 are written as `\xFF`. That includes
+    /// lone bytes from 0x80 to 0x9F, which some terminals read as C1
+    /// controls, see [`Quoted::hex_c1`].
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sanitize")]
+    /// assert_eq!(Quoted::sanitize_raw(b"caf\xE9 \x9B2J").to_string(), r"caf\xE9 \x9B2J");
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the optional `sanitize` feature.
+    #[cfg(feature = "sanitize")]
+    pub fn sanitize_raw(bytes: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::Sanitize(bytes))
+    }
+
+    /// Turn a path into the name of a systemd unit, like
+    /// `systemd-escape --path`. This is how the names of mount units and
+    /// path-based template instances are made.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "systemd")] {
+    /// assert_eq!(Quoted::systemd_unit("/home/my-files").to_string(), r"home-my\x2dfiles");
+    /// assert_eq!(Quoted::systemd_unit("/").to_string(), "-");
+    /// # }
+    /// ```
+    ///
+    /// The path is simplified first, so repeated slashes, trailing slashes
+    /// and `.` components are left out. Relative paths are treated as if
+    /// they were absolute, which systemd warns about. `..` components are
+    /// kept as they are, but systemd refuses those, so avoid them.
+    ///
+    /// The output contains no control codes or other special characters, so
+    /// it's safe to print. The suffix, like `.mount`, has to be added
+    /// separately. [`parse::systemd_unit`] does the reverse.
+    ///
+    /// # Optional
+    /// This requires the optional `systemd` feature.
+    #[cfg(feature = "systemd")]
+    pub fn systemd_unit(path: &'a str) -> Self {
+        Quoted::new(Style::SystemdUnit, path)
+    }
+
+    /// Like [`Quoted::systemd_unit`], for a path that may not be valid UTF-8.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "systemd")]
+    /// assert_eq!(Quoted::systemd_unit_raw(b"/mnt/\xFF").to_string(), r"mnt-\xff");
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the optional `systemd` feature.
+    #[cfg(feature = "systemd")]
+    pub fn systemd_unit_raw(path: &'a [u8]) -> Self {
+        Quoted::from_kind(Kind::SystemdUnit(path))
+    }
+
+    /// Pick the flavor of SQL for [`Quoted::sql`] and [`Quoted::sql_raw`].
+    ///
+    /// ```
+    /// use os_display::{Quoted, SqlDialect};
+    ///
+    /// # #[cfg(feature = "sql")] {
+    /// let mysql = |text| Quoted::sql(text).sql_dialect(SqlDialect::MySql).to_string();
+    /// assert_eq!(mysql(r"it's C:\"), r"'it\'s C:\\'");
+    /// assert_eq!(mysql("a\u{1B}"), "CONCAT('a', x'1B')");
+    /// # }
+    /// ```
+    ///
+    /// Defaults to [`SqlDialect::Standard`].
+    ///
+    /// # Optional
+    /// This requires the optional `sql` feature.
+    #[cfg(feature = "sql")]
+    pub fn sql_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.opts.sql_dialect = dialect;
+        self
+    }
+
+    /// Pick the kind of instruction for [`Quoted::dockerfile`].
+    ///
+    /// Defaults to [`DockerfileForm::Shell`].
+    ///
+    /// # Optional
+    /// This requires the optional `dockerfile` feature.
+    #[cfg(feature = "dockerfile")]
+    pub fn dockerfile_form(mut self, form: DockerfileForm) -> Self {
+        self.opts.dockerfile_form = form;
+        self
+    }
+
+    /// Toggle escaping of semicolons for [`Quoted::cmake`], so that the text
+    /// stays a single element if it's used as a list.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "cmake")] {
+    /// assert_eq!(Quoted::cmake("a;b").to_string(), r#""a;b""#);
+    /// assert_eq!(Quoted::cmake("a;b").cmake_list(true).to_string(), r#""a\;b""#);
+    /// # }
+    /// ```
+    ///
+    /// The backslash is part of the value, and the list commands remove it
+    /// when they take out the element. Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the optional `cmake` feature.
+    #[cfg(feature = "cmake")]
+    pub fn cmake_list(mut self, escape: bool) -> Self {
+        self.opts.cmake_list = escape;
+        self
+    }
+
+    /// Toggle removal of ANSI escape sequences for [`Quoted::sanitize`].
+    ///
+    /// By default only the control characters that start them are escaped,
+    /// which leaves the rest of each sequence behind as text. With this
+    /// enabled complete sequences are recognized and removed, like colors
+    /// and cursor movement (CSI), window titles and hyperlinks (OSC) and
+    /// DCS strings. That includes their 8-bit forms, which start with a C1
+    /// control character, or with a lone byte like `0x9B` in
+    /// [`Quoted::sanitize_raw`].
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sanitize")] {
+    /// let text = "\x1B[1;31merror\x1B[0m: \x1B]8;;http://evil/\x1B\\click\x1B]8;;\x1B\\";
+    /// assert_eq!(
+    ///     Quoted::sanitize(text).to_string(),
+    ///     r"\x1B[1;31merror\x1B[0m: \x1B]8;;http://evil/\x1B\click\x1B]8;;\x1B\",
+    /// );
+    /// assert_eq!(Quoted::sanitize(text).strip_ansi(true).to_string(), "error: click");
+    /// // Not part of a sequence
+    /// assert_eq!(Quoted::sanitize("a\x07b").strip_ansi(true).to_string(), r"a\x07b");
+    /// # }
+    /// ```
+    ///
+    /// An unterminated string sequence lasts until the end of the line.
+    /// Lone control characters are still escaped. The removed characters
+    /// show up in [`Quoted::events`] as escapes with empty text.
+    ///
+    /// See [`AnsiFilter`] to remove sequences from a stream of text instead.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the optional `sanitize` feature.
+    #[cfg(feature = "sanitize")]
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.opts.strip_ansi = strip;
+        self
+    }
+
+    /// Toggle writing the C1 control characters (U+0080 to U+009F) as `\x85`
+    /// instead of `\u{85}`, for [`Quoted::sanitize`].
+    ///
+    /// Several terminals act on C1 controls, so they're always escaped. By
+    /// default a valid C1 character is written like any other non-ASCII
+    /// character, while a lone byte in the same range from
+    /// [`Quoted::sanitize_raw`] is written as a byte. With this enabled
+    /// both are written as a byte, so they look the same and can be found
+    /// with a single search. The output doesn't show which one it was.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "sanitize")] {
+    /// assert_eq!(Quoted::sanitize("\u{9B}2J").to_string(), r"\u{9B}2J");
+    /// assert_eq!(Quoted::sanitize("\u{9B}2J").hex_c1(true).to_string(), r"\x9B2J");
+    /// assert_eq!(Quoted::sanitize_raw(b"\x9B2J").to_string(), r"\x9B2J");
+    /// # }
+    /// ```
+    ///
+    /// DEL (U+007F) is always written as `\x7F`, or as `^?` with
+    /// [`ControlStyle::Caret`].
+    ///
+    /// See [`SanitizingWriter::hex_c1`] for the same setting on a stream.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the optional `sanitize` feature.
+    #[cfg(feature = "sanitize")]
+    pub fn hex_c1(mut self, hex: bool) -> Self {
+        self.opts.hex_c1 = hex;
+        self
+    }
+
+    /// Toggle escaping of wildcards, for places where the text is used as a
+    /// glob pattern after quote removal.
+    ///
+    /// Some commands expand wildcards in their arguments themselves, like
+    /// `scp` and `rsync` for remote paths, `find -name`, or PowerShell's
+    /// `-Path` parameters. With this enabled the text is turned into a
+    /// pattern that only matches itself before it's quoted: for bash/ksh
+    /// `*?[]{}` and `\` get a backslash, for PowerShell `*?[]` and `` ` `` get
+    /// a backtick.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(Quoted::unix("notes [draft].txt").glob_safe(true).to_string(), r"'notes \[draft\].txt'");
+    /// # #[cfg(feature = "windows")]
+    /// assert_eq!(Quoted::windows("notes [draft].txt").glob_safe(true).to_string(), "'notes `[draft`].txt'");
+    /// ```
+    ///
+    /// For `find -name` and other users of `fnmatch(3)` the pattern is
+    /// matched against a single file name, so pass only the last component
+    /// of a path:
+    ///
+    /// ```
+    /// # #[cfg(feature = "unix")] {
+    /// use os_display::Quoted;
+    ///
+    /// let name = "report*{2}.txt";
+    /// let command = format!("find . -name {}", Quoted::unix(name).glob_safe(true));
+    /// assert_eq!(command, r"find . -name 'report\*\{2\}.txt'");
+    /// # }
+    /// ```
+    ///
+    /// This has no effect on [`Quoted::windows_argv`].
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn glob_safe(mut self, glob_safe: bool) -> Self {
+        self.opts.set_pattern(pattern::Pattern::Glob, glob_safe);
+        self
+    }
+
+    /// Toggle escaping of regular expression syntax, for suggesting commands
+    /// like `grep -E` that take the text as a pattern.
+    ///
+    /// The characters `.[]()*+?{}|^$\` get a backslash before they're quoted.
+    /// That works for POSIX extended regular expressions, PCRE, .NET (for
+    /// PowerShell's `-match` and `Select-String`) and most other flavors.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(Quoted::unix("v1.2 (beta)").as_regex(true).to_string(), r"'v1\.2 \(beta\)'");
+    /// ```
+    ///
+    /// A pattern that starts with `-` may still be read as an option, so put
+    /// `--` or `-e` before it.
+    ///
+    /// This replaces [`Quoted::glob_safe`], whichever is enabled last wins.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn as_regex(mut self, as_regex: bool) -> Self {
+        self.opts.set_pattern(pattern::Pattern::Regex, as_regex);
+        self
+    }
+
+    /// Highlight the quotes and escape sequences with ANSI colors.
+    ///
+    /// This is only suitable for output to a terminal.
+    ///
+    /// Terminals don't copy the color codes along with the text, so the output
+    /// can still be pasted into a shell. The codes only ever appear between
+    /// whole quotes, escape sequences and runs of ordinary text, and removing
+    /// them gives back exactly the output without highlighting. The last code
+    /// resets all attributes.
+    ///
+    /// ```
+    /// use os_display::{Highlight, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(
+    ///     Quoted::unix("foo\nbar")
+    ///         .highlight(Highlight::new().quotes("2").escapes("31"))
+    ///         .to_string(),
+    ///     "\x1B[2m$'\x1B[0mfoo\x1B[31m\\n\x1B[0mbar\x1B[2m'\x1B[0m",
+    /// );
+    /// ```
+    pub fn highlight(mut self, highlight: Highlight) -> Self {
+        self.opts.highlight = Some(highlight);
+        self
+    }
+
+    /// Turn the output into a terminal hyperlink, using the
+    /// [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda)
+    /// escape sequence.
+    ///
+    /// Like [`Quoted::highlight`] this is only suitable for output to a
+    /// terminal. Terminals that don't support hyperlinks ignore the sequence.
+    /// The visible text is quoted and escaped as usual.
+    ///
+    /// Characters in `uri` that would break the sequence (whitespace, control
+    /// codes and non-ASCII) are percent-encoded.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(
+    ///     Quoted::unix("docs").hyperlink("https://docs.rs").to_string(),
+    ///     "\x1B]8;;https://docs.rs\x1B\\'docs'\x1B]8;;\x1B\\",
+    /// );
+    /// ```
+    pub fn hyperlink(mut self, uri: &'a str) -> Self {
+        self.link = Some(Link::Uri(uri));
+        self
+    }
+
+    /// Cut the output off after this many terminal columns, like a precision
+    /// in a format string (`{:.N}`) but without having to pass it every time.
+    ///
+    /// The output stays valid: escape sequences are left out entirely if they
+    /// don't fit, and quotes are always closed. If there's a precision as
+    /// well the smaller of the two is used.
+    ///
+    /// With the `unicode-segmentation` feature the text is cut between
+    /// grapheme clusters, so emoji sequences and Hangul syllables are kept
+    /// whole. Otherwise it's cut between characters.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert_eq!(Quoted::unix("foo bar").max_width(6).to_string(), "'foo '");
+    /// assert_eq!(format!("{:.4}", Quoted::unix("foo bar").max_width(6)), "'fo'");
+    /// # }
+    /// ```
+    pub fn max_width(mut self, columns: usize) -> Self {
+        self.opts.max_width = Some(columns);
+        self
+    }
+
+    /// Set how many columns characters with an ambiguous East Asian width
+    /// take up, for terminals that render them wide.
+    ///
+    /// This changes the widths used for padding (`{:>20}`), for truncation
+    /// (`{:.20}` and [`Quoted::max_width`]) and for
+    /// [`Quoted::display_width`], so that columns of names line up in the
+    /// terminal.
+    ///
+    /// ```
+    /// use os_display::{Quoted, Width};
+    ///
+    /// # #[cfg(all(feature = "unix", feature = "unicode-width"))] {
+    /// let quoted = Quoted::unix("\u{2460}\u{2461}");
+    /// assert_eq!(quoted.display_width(), 4);
+    /// assert_eq!(quoted.ambiguous_width(Width::Wide).display_width(), 6);
+    /// assert_eq!(format!("{:.4}", quoted.ambiguous_width(Width::Wide)), "'\u{2460}'");
+    /// # }
+    /// ```
+    ///
+    /// Defaults to [`Width::Narrow`]. Without the `unicode-width` feature
+    /// every character counts as one column either way.
+    pub fn ambiguous_width(mut self, width: Width) -> Self {
+        self.opts.ambiguous_width = width;
+        self
+    }
+
+    /// Produce the output as a series of events instead of as a string.
+    ///
+    /// This makes it possible to render quotes and escape sequences
+    /// differently, e.g. in a GUI or a syntax highlighter. For terminals,
+    /// [`Quoted::highlight`] is easier to use.
+    ///
+    /// ```
+    /// use os_display::{Original, Quoted, QuoteEvent};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let mut events = Vec::new();
+    /// Quoted::unix("a\nb").events(|event| events.push(std::format!("{:?}", event)));
+    /// assert_eq!(
+    ///     events,
+    ///     [
+    ///         r#"OpenQuote("$'")"#,
+    ///         r#"Literal("a")"#,
+    ///         r#"Escape { original: Char('\n'), rendered: "\\n" }"#,
+    ///         r#"Literal("b")"#,
+    ///         r#"CloseQuote("'")"#,
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    pub fn events(&self, callback: impl FnMut(QuoteEvent<'_>)) {
+        // This can't fail, our sink doesn't produce errors.
+        let _ = self.write_within(&mut Events(callback), None);
+    }
+
+    /// Report where each escape sequence is, both in the original text and
+    /// in the output. This makes it possible to point out exactly which
+    /// characters were a problem, e.g. by underlining them in an editor.
+    ///
+    /// ```
+    /// use os_display::{EscapedSpan, Original, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let mut spans = Vec::new();
+    /// Quoted::unix("a\tb\n").escaped_spans(|span| spans.push(span));
+    /// assert_eq!(
+    ///     spans,
+    ///     [
+    ///         EscapedSpan { original: Original::Char('\t'), input: 1..2, output: 3..5 },
+    ///         EscapedSpan { original: Original::Char('\n'), input: 3..4, output: 6..8 },
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// Positions in the output are in bytes. Positions in the original text
+    /// are in bytes too, except for UTF-16 input ([`Quoted::windows_raw`],
+    /// [`Quoted::reg_raw`] and `OsStr` on Windows), where they're in code
+    /// units.
+    ///
+    /// The positions in the original text don't line up if the text is
+    /// changed before quoting, like with [`Quoted::for_remote`] and
+    /// [`Quoted::glob_safe`], or for formats that rewrite it, like
+    /// [`Quoted::file_url`] and [`Quoted::systemd_unit`].
+    pub fn escaped_spans(&self, callback: impl FnMut(EscapedSpan)) {
+        // This can't fail either.
+        let _ = self.write_within(&mut Spans::new(callback, self.source.is_wide()), None);
+    }
+
+    /// Find out whether the text was quoted or escaped, and why.
+    ///
+    /// This is meant for explaining the output to a user, or for deciding
+    /// whether to warn about a file name.
+    ///
+    /// ```
+    /// use os_display::{Quoted, Reason};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert!(Quoted::unix("foo").force(false).classify().is_plain());
+    ///
+    /// let class = Quoted::unix("foo bar\n").classify();
+    /// assert!(class.is_quoted() && class.is_escaped());
+    /// assert_eq!(class.reasons().collect::<Vec<_>>(), [Reason::Whitespace, Reason::Control]);
+    ///
+    /// assert!(Quoted::unix("").classify().contains(Reason::Empty));
+    /// assert!(Quoted::unix("foo").classify().contains(Reason::Forced));
+    /// # }
+    /// ```
+    ///
+    /// The reasons are worked out from the output, so they're about this
+    /// style in particular. A `$` is [`Reason::ShellSpecial`] for
+    /// [`Quoted::unix`] but doesn't need anything in a `.gitignore` file.
+    pub fn classify(&self) -> Classification {
+        let mut classifier =
+            classify::Classifier::new(self.source.is_powershell(), self.opts.ambiguous_width);
+        // This can't fail either.
+        let _ = self.write(&mut classifier);
+        classifier.finish()
+    }
+
+    /// The length of the output in bytes.
+    ///
+    /// This is the same as `.to_string().len()`, but without allocating.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(Quoted::unix("foo bar").display_len(), 9);
+    /// ```
+    pub fn display_len(&self) -> usize {
+        struct Counter(usize);
+
+        impl fmt::Write for Counter {
+            fn write_str(&mut self, text: &str) -> fmt::Result {
+                self.0 += text.len();
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        // This can't fail, our Write impl doesn't produce errors.
+        let _ = self.write_to(&mut counter, None);
+        counter.0
+    }
+
+    /// The width of the output in terminal columns.
+    ///
+    /// This uses the same width data as the decisions about zero-width
+    /// characters, from the `unicode-width` crate. Color codes and hyperlinks
+    /// don't take up any space.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")]
+    /// assert_eq!(Quoted::unix("\u{65E5}\u{672C}").display_width(), 6);
+    /// ```
+    pub fn display_width(&self) -> usize {
+        self.width_within(None)
+    }
+
+    /// Format the output, or report that it wouldn't get the original text
+    /// across.
+    ///
+    /// Formatting never fails, so text that can't be written exactly still
+    /// comes out as something. This catches those cases:
+    ///
+    /// - A NUL character in text that becomes a command-line argument, where
+    ///   it would cut the argument short. That's bash/ksh quoting,
+    ///   [`Quoted::windows_argv`], [`Quoted::dockerfile`], and PowerShell
+    ///   quoting with [`Quoted::external`]. PowerShell strings can contain
+    ///   NUL otherwise.
+    /// - A NUL character in [`Quoted::systemd_unit`] and [`Quoted::reg`],
+    ///   which can't contain one either.
+    /// - Newlines at the end of the text in [`Quoted::dockerfile`]'s shell
+    ///   form, which get lost.
+    /// - Invalid unicode that gets replaced, in [`Quoted::html_raw`] and
+    ///   [`Quoted::windows_wtf8`], and in an `OsStr` on platforms where its
+    ///   bytes aren't available.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// assert_eq!(Quoted::unix("a\nb").try_render().unwrap(), "$'a\\nb'");
+    /// assert!(Quoted::unix("a\0b").try_render().is_err());
+    /// # }
+    /// ```
+    ///
+    /// `$'...'` is taken to work, since most shells support it. Use
+    /// [`Fallback::PrintfCommand`] for the ones that don't.
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn try_render(&self) -> Result<alloc::string::String, UnrepresentableError> {
+        match self.unrepresentable() {
+            Some(reason) => Err(UnrepresentableError { reason }),
+            None => Ok(alloc::string::ToString::to_string(self)),
+        }
+    }
+
+    /// Why the output wouldn't stand for the text, if it wouldn't.
+    fn unrepresentable(&self) -> Option<&'static str> {
+        #[cfg(any(
+            feature = "unix",
+            feature = "windows",
+            feature = "native",
+            feature = "dockerfile"
+        ))]
+        const ARGUMENT: &str = "a command-line argument can't contain a NUL character";
+        #[cfg(any(
+            feature = "html",
+            feature = "windows",
+            all(feature = "native", feature = "std", not(windows))
+        ))]
+        const INVALID: &str = "invalid unicode would be replaced";
+        #[cfg(feature = "reg")]
+        const REGISTRY: &str = "a registry string can't contain a NUL character";
+
+        #[allow(unreachable_patterns)]
+        match self.source {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) if text.contains('\0') => Some(ARGUMENT),
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) if bytes.contains(&0) => Some(ARGUMENT),
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) if self.opts.external && text.contains('\0') => Some(ARGUMENT),
+            #[cfg(feature = "windows")]
+            #[cfg(feature = "alloc")]
+            Kind::WindowsRaw(units) if self.opts.external && units.contains(&0) => Some(ARGUMENT),
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) if !windows::is_wtf8(bytes) => Some(INVALID),
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) if self.opts.external && bytes.contains(&0) => Some(ARGUMENT),
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text) if text.contains('\0') => Some(ARGUMENT),
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text)
+                if self.opts.cmd_context.is_some()
+                    && self.opts.context == QuoteContext::Standalone
+                    && text.contains(|ch| ch == '\n' || ch == '\r') =>
+            {
+                Some("cmd.exe can't pass on a line break")
+            }
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            #[cfg(not(windows))]
+            Kind::NativeRaw(text) if text.to_str().is_none() && os_str_bytes(text).is_none() => {
+                Some(INVALID)
+            }
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            #[cfg(not(windows))]
+            Kind::NativeRaw(text) if text.to_string_lossy().contains('\0') => Some(ARGUMENT),
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            #[cfg(windows)]
+            Kind::NativeRaw(text)
+                if self.opts.external && text.to_string_lossy().contains('\0') =>
+            {
+                Some(ARGUMENT)
+            }
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(text) if text.contains('\0') => Some(ARGUMENT),
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(text) if dockerfile::drops_newlines(text, &self.opts) => {
+                Some("command substitution would drop the trailing newlines")
+            }
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(path) if path.contains(&0) => {
+                Some("a unit name can't contain a NUL character")
+            }
+            #[cfg(feature = "reg")]
+            Kind::Reg(text) if text.contains('\0') => Some(REGISTRY),
+            #[cfg(feature = "reg")]
+            Kind::RegRaw(units) if units.contains(&0) => Some(REGISTRY),
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers(text) if text.contains(sudoers::is_forbidden) => {
+                Some("a sudoers command can't contain control characters")
+            }
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers("") => Some("a sudoers command can't have an empty argument"),
+            #[cfg(feature = "git")]
+            Kind::GitIgnore(text) if text.contains(git::is_forbidden_in_gitignore) => {
+                Some("a gitignore pattern can't contain control characters")
+            }
+            #[cfg(feature = "git")]
+            Kind::GitAttributes(text) if text.contains('\0') => {
+                Some("a pattern can't contain a NUL character")
+            }
+            #[cfg(feature = "git")]
+            Kind::GitIgnore("") | Kind::GitAttributes("") => {
+                Some("an empty pattern doesn't match anything")
+            }
+            #[cfg(feature = "git")]
+            Kind::GitConfig(text) if text.contains(git::is_forbidden_in_config) => {
+                Some("a git config value can't contain most control characters")
+            }
+            #[cfg(feature = "html")]
+            Kind::Html(bytes) if core::str::from_utf8(bytes).is_err() => Some(INVALID),
+            _ => None,
+        }
+    }
+
+    /// Format the output and measure its width in one go.
+    ///
+    /// The width is the same as [`Quoted::display_width`]'s. It includes
+    /// the quotes and escape sequences, which is what `ls`-like tools need
+    /// to line up columns of names that may or may not be quoted.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let names = ["foo", "\u{65E5}\u{672C} bar", "baz\n"];
+    /// let quoted: Vec<_> = names
+    ///     .iter()
+    ///     .map(|name| Quoted::unix(name).force(false).to_string_and_width())
+    ///     .collect();
+    /// let widths: Vec<_> = quoted.iter().map(|name| name.width()).collect();
+    /// assert_eq!(widths, [3, 10, 8]);
+    ///
+    /// let column = quoted.iter().map(|name| name.width()).max().unwrap();
+    /// let line: Vec<_> = quoted.iter().map(|name| format!("{:<1$}|", name, column)).collect();
+    /// assert_eq!(line, ["foo       |", "'\u{65E5}\u{672C} bar'|", "$'baz\\n'  |"]);
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_and_width(&self) -> QuotedAndWidth {
+        let mut text = alloc::string::String::new();
+        let mut width = 0;
+        // This can't fail, a String doesn't produce errors.
+        let _ = self.write_measured(&mut text, None, Some(&mut width));
+        QuotedAndWidth { text, width }
+    }
+
+    /// The width of the output when it's truncated to `limit` columns.
+    fn width_within(&self, limit: Option<usize>) -> usize {
+        let mut columns = Columns::new(self.opts.ambiguous_width);
+        // This can't fail, our Sink impl doesn't produce errors.
+        let _ = self.write_within(&mut columns, limit);
+        columns.width
+    }
+
+    /// Write the output into a byte buffer and return its length.
+    ///
+    /// This doesn't need `alloc`, for environments where formatting into a
+    /// `String` isn't possible.
+    ///
+    /// If the buffer is too small an error is returned that says how large
+    /// it needs to be. The contents of the buffer are unspecified in that case.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let mut buf = [0; 16];
+    /// let len = Quoted::unix("foo bar").render_into(&mut buf).unwrap();
+    /// assert_eq!(&buf[..len], b"'foo bar'");
+    ///
+    /// let err = Quoted::unix("foo bar").render_into(&mut buf[..4]).unwrap_err();
+    /// assert_eq!(err.required(), 9);
+    /// # }
+    /// ```
+    pub fn render_into(&self, buf: &mut [u8]) -> Result<usize, CapacityError> {
+        struct SliceWriter<'a> {
+            buf: &'a mut [u8],
+            len: usize,
+        }
+
+        impl<'a> fmt::Write for SliceWriter<'a> {
+            fn write_str(&mut self, text: &str) -> fmt::Result {
+                let end = self.len + text.len();
+                if end <= self.buf.len() {
+                    self.buf[self.len..end].copy_from_slice(text.as_bytes());
+                }
+                // Keep counting so we can report the required length.
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        let capacity = buf.len();
+        let mut writer = SliceWriter { buf, len: 0 };
+        // This can't fail, our Write impl doesn't produce errors.
+        let _ = self.write_to(&mut writer, None);
+        if writer.len > capacity {
+            Err(CapacityError {
+                required: writer.len,
+            })
+        } else {
+            Ok(writer.len)
+        }
+    }
+
+    /// Work out the output once, so that formatting it later is quicker.
+    ///
+    /// See [`Prepared`].
+    ///
+    /// # Optional
+    /// This requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn prepared(&self) -> Prepared<'a> {
+        Prepared::new(self)
+    }
+
+    /// When quoting for bash/ksh, choose how to write text with newlines.
+    ///
+    /// Long text with many lines is hard to read in `$'...'`. A here-document
+    /// keeps the lines as they are, which suits file contents. It's a
+    /// redirection rather than an argument, so the output goes at the end of
+    /// a command that reads from its input:
+    ///
+    /// ```
+    /// use os_display::{Multiline, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let contents = Quoted::unix("[user]\n\tname = me\nEOF\n").multiline(Multiline::HereDoc);
+    /// assert_eq!(
+    ///     std::format!("cat > config {}", contents),
+    ///     "cat > config <<'EOF1'\n[user]\n\tname = me\nEOF\nEOF1",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// The delimiter is `EOF`, with a number added if the text contains a
+    /// line that would end the here-document early.
+    ///
+    /// Defaults to [`Multiline::Escape`].
+    ///
+    /// # Optional
+    /// This requires either the `unix` or the `native` feature. It has no effect
+    /// on PowerShell quoting.
+    #[cfg(any(feature = "unix", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn multiline(mut self, multiline: Multiline) -> Self {
+        #[cfg(any(feature = "unix", not(windows)))]
+        {
+            self.opts.multiline = multiline;
+        }
+        self
+    }
+
+    /// When quoting for bash/ksh, choose what to write if the text needs
+    /// escape sequences.
+    ///
+    /// With [`Fallback::PrintfCommand`] such text becomes a command that
+    /// reproduces the exact bytes, for shells without `$'...'`. Special
+    /// characters are written as octal escapes, since not every `printf`
+    /// understands `\x`.
+    ///
+    /// ```
+    /// use os_display::{Fallback, Quoted};
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let quoted = Quoted::unix_raw(b"it's 100%\xFF\n").fallback(Fallback::PrintfCommand);
+    /// assert_eq!(quoted.to_string(), r"printf 'it\047s 100%%\377\n'");
+    /// // Text that doesn't need escapes is quoted as usual.
+    /// assert_eq!(Quoted::unix("foo bar").fallback(Fallback::PrintfCommand).to_string(), "'foo bar'");
+    /// # }
+    /// ```
+    ///
+    /// Defaults to [`Fallback::AnsiC`].
+    ///
+    /// # Optional
+    /// This requires either the `unix` or the `native` feature. It has no effect
+    /// on PowerShell quoting.
+    #[cfg(any(feature = "unix", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        #[cfg(any(feature = "unix", not(windows)))]
+        {
+            self.opts.fallback = fallback;
+        }
+        self
+    }
+
+    /// When quoting for bash/ksh, toggle extra escaping for the command
+    /// field of a crontab entry.
+    ///
+    /// cron turns every `%` in a command into a newline and passes the text
+    /// after the first one on stdin, so a path like `/backup/100%` gets cut
+    /// short. If this is enabled then each `%` gets a backslash, which cron
+    /// removes again, even inside quotes.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// let quoted = Quoted::unix("/backup/100% done").crontab(true);
+    /// assert_eq!(quoted.to_string(), r"'/backup/100\% done'");
+    /// assert_eq!(
+    ///     std::format!("0 3 * * * tar czf {} ~", quoted),
+    ///     r"0 3 * * * tar czf '/backup/100\% done' ~",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// Newlines are always escaped, since they'd end the entry. cron runs
+    /// commands with `/bin/sh` unless the crontab sets `SHELL`, so consider
+    /// [`Fallback::PrintfCommand`] as well.
+    ///
+    /// The output is not suitable for any other use.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires either the `unix` or the `native` feature. It has no effect
+    /// on PowerShell quoting.
+    #[cfg(any(feature = "unix", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn crontab(mut self, crontab: bool) -> Self {
+        #[cfg(any(feature = "unix", not(windows)))]
+        {
+            self.opts.crontab = crontab;
+        }
+        self
+    }
+
+    /// When quoting for PowerShell, toggle whether to quote for external programs.
+    ///
+    /// If enabled, double quotes (and sometimes backslashes) will be escaped so
+    /// that they can be passed to external programs.
+    ///
+    /// If disabled, quoting will suit internal commandlets and .NET functions.
+    /// Strings that look like options or numbers will be quoted.
+    ///
+    /// It is sadly impossible to quote a string such that it's suitable for both
+    /// external and internal commands.
+    ///
+    /// The experimental `PSNativeCommandArgumentPassing` feature in PowerShell 7.2
+    /// disables the stripping of double quotes and backslashes. If it's enabled
+    /// then this setting should be disabled.
+    ///
+    /// Defaults to `false`. This could change in a future (breaking) release.
+    ///
+    /// # Optional
+    /// This requires either the `windows` or the `native` feature. It has no effect
+    /// on Unix-style quoting.
+    #[cfg(any(feature = "windows", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn external(mut self, external: bool) -> Self {
+        #[cfg(any(feature = "windows", windows))]
+        {
+            self.opts.external = external;
+        }
+        self
+    }
+
+    /// When quoting for PowerShell, choose how to treat special tokens like
+    /// `$?` and `@(...)`.
+    ///
+    /// ```
+    /// use os_display::{MagicTokens, Quoted};
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// assert_eq!(Quoted::windows("$?").to_string(), "'$?'");
+    /// assert_eq!(Quoted::windows("$?").magic_tokens(MagicTokens::Strict).to_string(), "\"`$?\"");
+    /// # }
+    /// ```
+    ///
+    /// Defaults to [`MagicTokens::Quote`].
+    ///
+    /// # Optional
+    /// This requires either the `windows` or the `native` feature. It has no effect
+    /// on Unix-style quoting.
+    #[cfg(any(feature = "windows", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn magic_tokens(mut self, tokens: MagicTokens) -> Self {
+        #[cfg(any(feature = "windows", windows))]
+        {
+            self.opts.magic_tokens = tokens;
+        }
+        self
+    }
+
+    /// When quoting for PowerShell, toggle extra escaping for scripts that
+    /// build a command line in a double-quoted string and pass it to
+    /// `Invoke-Expression`.
+    ///
+    /// Such a command line is evaluated twice: once as a string and once as a
+    /// command. The quoting that's needed for the command isn't enough to get
+    /// through the string, since `$` and `` ` `` work inside single quotes
+    /// there. If this is enabled then those and double quotes get an extra
+    /// backtick, for the string to remove.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// let quoted = Quoted::windows("$HOME").iex_safe(true);
+    /// assert_eq!(quoted.to_string(), "'`$HOME'");
+    /// assert_eq!(
+    ///     std::format!("Invoke-Expression \"Write-Output {}\"", quoted),
+    ///     "Invoke-Expression \"Write-Output '`$HOME'\"",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// The output is not suitable for any other use.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires either the `windows` or the `native` feature. It has no effect
+    /// on Unix-style quoting.
+    #[cfg(any(feature = "windows", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn iex_safe(mut self, iex_safe: bool) -> Self {
+        #[cfg(any(feature = "windows", windows))]
+        {
+            self.opts.iex_safe = iex_safe;
+        }
+        self
+    }
+
+    /// When quoting for PowerShell, toggle whether to write unprintable
+    /// characters as an expression that also works on Windows PowerShell 5.1.
+    ///
+    /// The `` `u{XXXX} `` escape sequence only exists since PowerShell 6. It's
+    /// the only way to write an unpaired surrogate inside a string, so invalid
+    /// UTF-16 can't be copied into older versions. If this is enabled then
+    /// `[char]` casts are concatenated to the string instead, which produces
+    /// the same text.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "windows")] {
+    /// let quoted = Quoted::windows_raw(&[0x78, 0xD800]).expression(true);
+    /// assert_eq!(quoted.to_string(), r#"("x" + [char]0xD800)"#);
+    /// # }
+    /// ```
+    ///
+    /// With [`QuoteContext::InsideDoubleQuotes`] the casts are written as
+    /// subexpressions instead, like `$([char]0xD800)`.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Optional
+    /// This requires either the `windows` or the `native` feature. It has no effect
+    /// on Unix-style quoting.
+    #[cfg(any(feature = "windows", feature = "native"))]
+    #[allow(unused_mut, unused_variables)]
+    pub fn expression(mut self, expression: bool) -> Self {
+        #[cfg(any(feature = "windows", windows))]
+        {
+            self.opts.expression = expression;
+        }
+        self
+    }
+}
+
+/// The width, alignment and precision of the format string are supported,
+/// all measured in terminal columns.
+///
+/// The quotes and escape sequences count toward the width, so padded names
+/// line up in columns whether they're quoted or not.
+///
+/// The precision truncates the output without breaking it: escape sequences
+/// are left out entirely if they don't fit, and quotes are always closed.
+///
+/// ```
+/// use os_display::Quoted;
+///
+/// # #[cfg(feature = "unix")] {
+/// assert_eq!(format!("[{:>12}]", Quoted::unix("foo bar")), "[   'foo bar']");
+/// assert_eq!(format!("[{:.6}]", Quoted::unix("foo bar")), "['foo ']");
+/// assert_eq!(format!("[{:.6}]", Quoted::unix("foo\nbar")), "[$'foo']");
+/// # }
+/// ```
+impl<'a> Display for Quoted<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let limit = f.precision();
+        write_padded(f, || self.width_within(limit), |f| self.write_to(f, limit))
+    }
+}
+
+/// Pad output to the width the formatter asks for, if any. It's measured in
+/// terminal columns, like [`Quoted::display_width`], so the quotes and escapes
+/// count too.
+fn write_padded(
+    f: &mut Formatter<'_>,
+    width: impl FnOnce() -> usize,
+    write: impl FnOnce(&mut Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let target = match f.width() {
+        Some(target) => target,
+        None => return write(f),
+    };
+    let padding = target.saturating_sub(width());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Left) | None => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Two `Quoted`s are equal if they produce the same output, even if they
+/// were made from different strings or with different options.
+///
+/// ```
+/// use os_display::Quoted;
+///
+/// # #[cfg(feature = "unix")]
+/// assert_eq!(Quoted::unix("foo bar"), Quoted::unix("foo bar").force(false));
+/// ```
+#[cfg(feature = "alloc")]
+impl<'a, 'b> PartialEq<Quoted<'b>> for Quoted<'a> {
+    fn eq(&self, other: &Quoted<'b>) -> bool {
+        /// Checks the output piece by piece against an expected string.
+        struct Compare<'s>(&'s str);
+
+        impl<'s> fmt::Write for Compare<'s> {
+            fn write_str(&mut self, text: &str) -> fmt::Result {
+                if self.0.starts_with(text) {
+                    self.0 = &self.0[text.len()..];
+                    Ok(())
+                } else {
+                    Err(fmt::Error)
+                }
+            }
+        }
+
+        // We only need to allocate for one side.
+        let other = alloc::string::ToString::to_string(other);
+        let mut compare = Compare(&other);
+        self.write_to(&mut compare, None).is_ok() && compare.0.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Eq for Quoted<'a> {}
+
+/// This hashes the output, to match the [`PartialEq`] impl.
+#[cfg(feature = "alloc")]
+impl<'a> core::hash::Hash for Quoted<'a> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        alloc::string::ToString::to_string(self).hash(state)
+    }
+}
+
+/// A statement that assigns a value to a shell variable, see [`assign`] and
+/// [`export`].
+#[derive(Debug, Copy, Clone)]
+pub struct Assignment<'a> {
+    name: &'a str,
+    value: Quoted<'a>,
+    exported: bool,
+}
+
+/// The error from [`assign`] and [`export`] for a name that can't be a
+/// variable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidNameError {
+    _private: (),
+}
+
+impl Display for InvalidNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid variable name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidNameError {}
+
+/// Assign a quoted value to a variable, for scripts that set up an
+/// environment.
+///
+/// The syntax follows the style of `value`: `NAME='value'` for bash/ksh and
+/// `.env` files and `$NAME = 'value'` for PowerShell. The value is always
+/// quoted, since a bare word means something else in an assignment
+/// (PowerShell runs it as a command, and bash expands `~` after a `:`).
+///
+/// ```
+/// use os_display::{assign, Quoted};
+///
+/// # #[cfg(feature = "unix")] {
+/// let line = assign("GREETING", Quoted::unix("hi there")).unwrap();
+/// assert_eq!(line.to_string(), "GREETING='hi there'");
+/// assert!(assign("GREETING-TEXT", Quoted::unix("hi there")).is_err());
+/// # }
+/// # #[cfg(feature = "windows")] {
+/// let line = assign("GREETING", Quoted::windows("hi there")).unwrap();
+/// assert_eq!(line.to_string(), "$GREETING = 'hi there'");
+/// let line = assign("env:PATH", Quoted::windows("C:\\bin")).unwrap();
+/// assert_eq!(line.to_string(), "$env:PATH = 'C:\\bin'");
+/// # }
+/// ```
+///
+/// PowerShell allows any variable name, names with unusual characters are
+/// written as `${...}`.
+///
+/// # Errors
+/// If the style isn't PowerShell and `name` isn't a valid variable name:
+/// ASCII letters, digits and underscores, not starting with a digit.
+///
+/// # Panics
+/// If `value` was made by [`Quoted::windows_argv`], which has no variables, or by
+/// [`Quoted::sql`], [`Quoted::html`], [`Quoted::systemd_unit`],
+/// [`Quoted::dockerfile`], [`Quoted::cmake`], [`Quoted::reg`],
+/// [`Quoted::sudoers`], [`Quoted::sanitize`] or one of the git styles.
+pub fn assign<'a>(name: &'a str, value: Quoted<'a>) -> Result<Assignment<'a>, InvalidNameError> {
+    #[allow(unreachable_patterns)]
+    match value.source {
+        #[cfg(feature = "windows")]
+        Kind::WindowsArgv(_) => panic!("Windows command lines don't have variables"),
+        #[cfg(feature = "sql")]
+        Kind::Sql(_) => panic!("SQL literals can't be assigned to shell variables"),
+        #[cfg(feature = "html")]
+        Kind::Html(_) => panic!("HTML can't be assigned to shell variables"),
+        #[cfg(feature = "systemd")]
+        Kind::SystemdUnit(_) => panic!("unit names can't be assigned to shell variables"),
+        #[cfg(feature = "dockerfile")]
+        Kind::Dockerfile(_) => panic!("use an ENV instruction in a Dockerfile"),
+        #[cfg(feature = "cmake")]
+        Kind::Cmake(_) => panic!("use set() in CMake"),
+        #[cfg(feature = "reg")]
+        Kind::Reg(_) | Kind::RegRaw(_) => {
+            panic!("registry values can't be assigned to shell variables")
+        }
+        #[cfg(feature = "sudoers")]
+        Kind::Sudoers(_) => panic!("sudoers commands can't be assigned to shell variables"),
+        #[cfg(feature = "git")]
+        Kind::GitIgnore(_) | Kind::GitAttributes(_) | Kind::GitConfig(_) => {
+            panic!("git patterns and values can't be assigned to shell variables")
+        }
+        #[cfg(feature = "sanitize")]
+        Kind::Sanitize(_) => panic!("sanitized text can't be assigned to shell variables"),
+        #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+        Kind::Operator(..) => panic!("operators can't be assigned to variables"),
+        _ => (),
+    }
+    if !value.source.is_powershell() && !is_posix_name(name) {
+        return Err(InvalidNameError { _private: () });
+    }
+    let mut value = value.force(true);
+    value.opts.context = QuoteContext::Standalone;
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    {
+        value.opts.multiline = Multiline::Escape;
+        value.opts.fallback = Fallback::AnsiC;
+    }
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    {
+        // This goes to PowerShell itself, not to an external program.
+        value.opts.external = false;
+    }
+    Ok(Assignment {
+        name,
+        value,
+        exported: false,
+    })
+}
+
+/// Set an environment variable to a quoted value, for tools that print
+/// instructions for setting up an environment.
+///
+/// This is like [`assign`], but the variable is passed on to commands that
+/// are run afterwards: `export NAME='value'` for bash/ksh and
+/// `$env:NAME = 'value'` for PowerShell.
+///
+/// ```
+/// use os_display::{export, Quoted};
+///
+/// # #[cfg(feature = "unix")] {
+/// let line = export("JAVA_HOME", Quoted::unix("/opt/my jdk")).unwrap();
+/// assert_eq!(line.to_string(), "export JAVA_HOME='/opt/my jdk'");
+/// assert!(export("JAVA-HOME", Quoted::unix("/opt/jdk")).is_err());
+/// # }
+/// # #[cfg(feature = "windows")] {
+/// let line = export("JAVA_HOME", Quoted::windows(r"C:\Program Files\jdk")).unwrap();
+/// assert_eq!(line.to_string(), r"$env:JAVA_HOME = 'C:\Program Files\jdk'");
+/// let line = export("ProgramFiles(x86)", Quoted::windows(r"C:\x86")).unwrap();
+/// assert_eq!(line.to_string(), r"${env:ProgramFiles(x86)} = 'C:\x86'");
+/// # }
+/// ```
+///
+/// # Errors
+/// If `name` isn't a valid variable name. For bash/ksh that means ASCII
+/// letters, digits and underscores, not starting with a digit. For
+/// PowerShell it means anything that Windows accepts: not empty, and no `=`
+/// or NUL characters.
+///
+/// # Panics
+/// If `value` wasn't made in bash/ksh or PowerShell style. A `.env` file
+/// has no `export`, use [`assign`] instead.
+pub fn export<'a>(name: &'a str, value: Quoted<'a>) -> Result<Assignment<'a>, InvalidNameError> {
+    #[cfg(feature = "dotenv")]
+    {
+        if let Kind::Dotenv(_) = value.source {
+            panic!(".env files don't have export, use assign()");
+        }
+    }
+    let is_valid = if value.source.is_powershell() {
+        !name.is_empty() && !name.contains(|ch| ch == '=' || ch == '\0')
+    } else {
+        is_posix_name(name)
+    };
+    if !is_valid {
+        return Err(InvalidNameError { _private: () });
+    }
+    let mut assignment = assign(name, value)?;
+    assignment.exported = true;
+    Ok(assignment)
+}
+
+impl<'a> Display for Assignment<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+
+        if !self.value.source.is_powershell() {
+            if self.exported {
+                f.write_str("export ")?;
+            }
+            return write!(f, "{}={}", self.name, self.value);
+        }
+        // Without the scope a name can have a scope of its own, like env:PATH.
+        let scope = if self.exported { "env:" } else { "" };
+        let is_simple = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || ch == '_' || (ch == ':' && !self.exported));
+        if is_simple {
+            write!(f, "${}{}", scope, self.name)?;
+        } else {
+            f.write_str("${")?;
+            f.write_str(scope)?;
+            for ch in self.name.chars() {
+                if ch == '}' || ch == '`' {
+                    f.write_char('`')?;
+                }
+                f.write_char(ch)?;
+            }
+            f.write_char('}')?;
+        }
+        write!(f, " = {}", self.value)
+    }
+}
+
+/// A command that defines a shortcut for another command, see [`alias`].
+///
+/// # Optional
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone)]
+pub struct Alias<'a> {
+    name: &'a str,
+    command: &'a [Quoted<'a>],
+    powershell: bool,
+}
+
+/// Define a shortcut for a command, made up of quoted arguments and
+/// operators.
+///
+/// For bash/ksh this is an `alias`. The command goes inside a quoted string,
+/// so each argument is quoted twice: once for when the alias is used and
+/// once for the definition.
+///
+/// PowerShell aliases can't include arguments, so this writes a function
+/// that passes on its own arguments with `@args`, like an alias would.
+///
+/// ```
+/// use os_display::{alias, Quoted, Style};
+///
+/// # #[cfg(feature = "unix")] {
+/// let arg = |text| Quoted::unix(text).force(false);
+/// let command = [arg("ls"), arg("-l"), arg("my dir")];
+/// assert_eq!(alias("ll", &command).to_string(), r#"alias ll="ls -l 'my dir'""#);
+///
+/// let command = [arg("grep"), arg("it's"), Quoted::operator(Style::Unix, "|"), arg("less")];
+/// assert_eq!(alias("g", &command).to_string(), r#"alias g='grep "it'\''s" | less'"#);
+/// # }
+/// # #[cfg(feature = "windows")] {
+/// let arg = |text| Quoted::windows(text).force(false);
+/// let command = [arg("notepad"), arg("C:\\my notes.txt")];
+/// assert_eq!(alias("notes", &command).to_string(), r"function notes { notepad 'C:\my notes.txt' @args }");
+/// # }
+/// ```
+///
+/// The arguments are written the way they're set up, so use
+/// [`Quoted::force(false)`](Quoted::force) to leave out unnecessary quotes.
+///
+/// # Panics
+/// If `command` is empty, if its parts aren't all in the same style, or if
+/// that style isn't bash/ksh or PowerShell. Also if `name` isn't a valid
+/// name: ASCII letters, digits, `_`, `-` and `.`, not starting with `-`.
+///
+/// # Optional
+/// This requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub fn alias<'a>(name: &'a str, command: &'a [Quoted<'a>]) -> Alias<'a> {
+    assert!(!command.is_empty(), "an alias needs a command");
+    for part in command {
+        #[allow(unreachable_patterns)]
+        match part.source {
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(_) => panic!("not a shell style"),
+            #[cfg(feature = "sql")]
+            Kind::Sql(_) => panic!("not a shell style"),
+            #[cfg(feature = "html")]
+            Kind::Html(_) => panic!("not a shell style"),
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(_) => panic!("not a shell style"),
+            #[cfg(feature = "dotenv")]
+            Kind::Dotenv(_) => panic!("not a shell style"),
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(_) => panic!("not a shell style"),
+            #[cfg(feature = "cmake")]
+            Kind::Cmake(_) => panic!("not a shell style"),
+            #[cfg(feature = "reg")]
+            Kind::Reg(_) | Kind::RegRaw(_) => panic!("not a shell style"),
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers(_) => panic!("not a shell style"),
+            #[cfg(feature = "git")]
+            Kind::GitIgnore(_) | Kind::GitAttributes(_) | Kind::GitConfig(_) => {
+                panic!("not a shell style")
+            }
+            #[cfg(feature = "sanitize")]
+            Kind::Sanitize(_) => panic!("not a shell style"),
+            _ => (),
+        }
+    }
+    let powershell = command[0].source.is_powershell();
+    assert!(
+        command
+            .iter()
+            .all(|part| part.source.is_powershell() == powershell),
+        "the parts of an alias are in different styles"
+    );
+    let is_valid = !name.starts_with('-')
+        && !name.is_empty()
+        && name.bytes().all(|byte| {
+            byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' || byte == b'.'
+        });
+    assert!(is_valid, "invalid alias name");
+    Alias {
+        name,
+        command,
+        powershell,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Display for Alias<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+
+        // The command has to stay on one line and stand on its own.
+        let mut parts = self.command.iter().map(|&part| {
+            let mut part = part;
+            part.opts.context = QuoteContext::Standalone;
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            {
+                part.opts.multiline = Multiline::Escape;
+            }
+            part
+        });
+        if self.powershell {
+            write!(f, "function {} {{", self.name)?;
+            for part in parts {
+                write!(f, " {}", part)?;
+            }
+            return f.write_str(" @args }");
+        }
+        let mut command = alloc::string::String::new();
+        if let Some(first) = parts.next() {
+            write!(command, "{}", first)?;
+        }
+        for part in parts {
+            write!(command, " {}", part)?;
+        }
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        {
+            let command = Quoted::from_kind(Kind::Unix(&command));
+            write!(f, "alias {}={}", self.name, command)
+        }
+        #[cfg(not(any(feature = "unix", all(feature = "native", not(windows)))))]
+        {
+            unreachable!()
+        }
+    }
+}
+
+/// Check whether a name can be assigned to in a POSIX shell.
+fn is_posix_name(name: &str) -> bool {
+    match name.bytes().next() {
+        Some(first) if !first.is_ascii_digit() => name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_'),
+        _ => false,
+    }
+}
+
+/// A path with each of its components quoted separately.
+///
+/// Unlike [`Quoted::native_raw`], which quotes the path as a whole, this only
+/// quotes the components that need it and joins them with the platform's
+/// separator. That reads better when only part of a path is interesting, like
+/// in a tree view:
+///
+/// ```
+/// # #[cfg(unix)] {
+/// use std::path::Path;
+/// use os_display::QuotedComponents;
+///
+/// let path = Path::new("/home/user/my files/notes.txt");
+/// assert_eq!(
+///     QuotedComponents::new(path).to_string(),
+///     "/home/user/'my files'/notes.txt",
+/// );
+/// # }
+/// ```
+///
+/// bash and ksh treat the output as a single word. PowerShell does not join
+/// adjacent strings like that, so on Windows the output is only suitable for
+/// display.
+///
+/// # Optional
+/// This requires the `native` and `std` features.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct QuotedComponents<'a> {
+    path: &'a Path,
+    force_quote: bool,
+}
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+impl<'a> QuotedComponents<'a> {
+    /// Quote the components of a path with the default style for the platform.
+    pub fn new(path: &'a Path) -> Self {
+        QuotedComponents {
+            path,
+            force_quote: false,
+        }
+    }
+
+    /// Quote every component, even if it doesn't need it. Defaults to `false`.
+    ///
+    /// The prefix of a Windows path (like `C:`) is never quoted unless it has
+    /// to be.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force_quote = force;
+        self
+    }
+}
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+impl<'a> Display for QuotedComponents<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+        use std::path::{Component, MAIN_SEPARATOR};
+
+        // Whether the next component needs a separator before it.
+        let mut separate = false;
+        for component in self.path.components() {
+            match component {
+                Component::Prefix(prefix) => {
+                    Quoted::native_raw(prefix.as_os_str()).force(false).fmt(f)?;
+                    separate = false;
+                }
+                Component::RootDir => {
+                    f.write_char(MAIN_SEPARATOR)?;
+                    separate = false;
+                }
+                _ => {
+                    if separate {
+                        f.write_char(MAIN_SEPARATOR)?;
+                    }
+                    Quoted::native_raw(component.as_os_str())
+                        .force(self.force_quote)
+                        .fmt(f)?;
+                    separate = true;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Quoted<'a> {
+    fn write_to(&self, f: &mut dyn fmt::Write, limit: Option<usize>) -> fmt::Result {
+        #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+        {
+            if limit.is_none() {
+                if let Some(text) = self.safe_text() {
+                    return f.write_str(text);
+                }
+            }
+        }
+        if self.source.len() < sink::SHORT_INPUT {
+            return self.write_buffered(f, limit);
+        }
+        self.write_measured(f, limit, None)
+    }
+
+    /// Render the output on the stack first and pass it on in one piece.
+    ///
+    /// Short text is quoted in a handful of tiny writes, and some writers pay
+    /// for each of them: stdout looks for newlines every time, and every write
+    /// to stderr is a system call. Writing to a `String` gets a little slower,
+    /// but unbuffered output gets much faster. If the output doesn't fit after
+    /// all, it's rendered again straight into `f`.
+    fn write_buffered(&self, f: &mut dyn fmt::Write, limit: Option<usize>) -> fmt::Result {
+        let mut buf = sink::ShortBuf::new();
+        match self.write_measured(&mut buf, limit, None) {
+            Ok(()) => f.write_str(buf.as_str()),
+            Err(_) if buf.overflowed() => self.write_measured(f, limit, None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The fast path for short, boring file names: if the text only has
+    /// bytes from [`scan::is_safe`] and none of the options could add quotes
+    /// or wrap it in anything, return it to be written as-is.
+    ///
+    /// This gives the same output as the full scan, just without going
+    /// through the sinks and the per-character checks.
+    #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+    fn safe_text(&self) -> Option<&'a str> {
+        let opts = &self.opts;
+        if opts.force_quote
+            || opts.max_width.is_some()
+            || self.link.is_some()
+            || opts.highlight.is_some()
+            || opts.quoting == Quoting::Paranoid
+            || opts.extra_unsafe != AsciiSet::EMPTY
+            || opts.context != QuoteContext::Standalone
+        {
+            return None;
+        }
+        #[cfg(feature = "alloc")]
+        {
+            if opts.remote > 0 || opts.pattern.is_some() {
+                return None;
+            }
+        }
+        #[allow(unused_variables)]
+        let powershell = self.source.is_powershell();
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        {
+            if powershell && opts.iex_safe {
+                return None;
+            }
+        }
+        #[allow(unreachable_patterns)]
+        let text: &str = match self.source {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) => text,
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) => core::str::from_utf8(bytes).ok()?,
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) => text,
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) => core::str::from_utf8(bytes).ok()?,
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(text) => text.to_str()?,
+            _ => return None,
+        };
+        if !scan::is_safe(text.as_bytes()) {
+            return None;
+        }
+        // PowerShell may read a leading digit as a number and a leading dash
+        // as a parameter, so leave those to the full scan.
+        if powershell {
+            let mut bytes = text.bytes();
+            match (bytes.next(), bytes.next()) {
+                (Some(b'.'), Some(second)) if !second.is_ascii_digit() => (),
+                (Some(b'.'), None) | (Some(b'_'), _) | (Some(b'/'), _) => (),
+                (Some(first), _) if first.is_ascii_alphabetic() => (),
+                _ => return None,
+            }
+        }
+        Some(text)
+    }
+
+    /// Write the output, and store its width if asked to. The width doesn't
+    /// include color codes and hyperlinks.
+    fn write_measured(
+        &self,
+        f: &mut dyn fmt::Write,
+        limit: Option<usize>,
+        width: Option<&mut usize>,
+    ) -> fmt::Result {
+        match self.link {
+            Some(link) => hyperlink::write(f, link, |f| self.write_text(f, limit, width)),
+            None => self.write_text(f, limit, width),
+        }
+    }
+
+    fn write_text(
+        &self,
+        f: &mut dyn fmt::Write,
+        limit: Option<usize>,
+        width: Option<&mut usize>,
+    ) -> fmt::Result {
+        match self.opts.highlight {
+            Some(highlight) => {
+                let mut out = Highlighter::new(f, highlight);
+                self.write_sink(&mut out, limit, width)?;
+                out.finish()
+            }
+            None => self.write_sink(&mut Plain(f), limit, width),
+        }
+    }
+
+    fn write_sink(
+        &self,
+        out: &mut dyn Sink,
+        limit: Option<usize>,
+        width: Option<&mut usize>,
+    ) -> fmt::Result {
+        match width {
+            Some(width) => {
+                let mut out = Measure::new(out, self.opts.ambiguous_width);
+                self.write_within(&mut out, limit)?;
+                *width = out.width;
+                Ok(())
+            }
+            None => self.write_within(out, limit),
+        }
+    }
+
+    /// Write the output, cut off after `limit` columns if there's a limit.
+    fn write_within(&self, out: &mut dyn Sink, limit: Option<usize>) -> fmt::Result {
+        let limit = match (limit, self.opts.max_width) {
+            (Some(limit), Some(max_width)) => Some(limit.min(max_width)),
+            (limit, max_width) => limit.or(max_width),
+        };
+        match limit {
+            Some(limit) => self.write(&mut Truncate::new(out, limit, self.opts.ambiguous_width)),
+            None => self.write(out),
+        }
+    }
+
+    fn write(&self, out: &mut dyn Sink) -> fmt::Result {
+        match self.opts.on_unrepresentable {
+            OnUnrepresentable::Escape => (),
+            #[cfg(feature = "alloc")]
+            OnUnrepresentable::ReplacementChar => {
+                if self.unrepresentable().is_some() {
+                    return self.write_replaced(out);
+                }
+            }
+            OnUnrepresentable::Error => {
+                if self.unrepresentable().is_some() {
+                    return Err(fmt::Error);
+                }
+            }
+        }
+        #[cfg(feature = "alloc")]
+        {
+            if let Some(pattern) = self.opts.pattern {
+                return self.write_pattern(out, pattern);
+            }
+        }
+        if self.opts.control_style != ControlStyle::Escape {
+            let mut quoted = *self;
+            quoted.opts.control_style = ControlStyle::Escape;
+            return quoted.write(&mut Controls {
+                out,
+                style: self.opts.control_style,
+            });
+        }
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        {
+            if self.opts.iex_safe && self.source.is_powershell() {
+                let mut quoted = *self;
+                quoted.opts.iex_safe = false;
+                return quoted.write(&mut windows::IexSafe(out));
+            }
+        }
+        #[cfg(feature = "windows")]
+        {
+            if let (Some(context), Kind::WindowsArgv(text)) = (self.opts.cmd_context, self.source) {
+                if self.opts.context == QuoteContext::Standalone {
+                    let mut quoted = *self;
+                    quoted.opts.cmd_context = None;
+                    return quoted.write(&mut windows::CmdEscaped::new(out, context, text));
+                }
+            }
+        }
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        {
+            if self.opts.crontab && self.source.is_unix() {
+                // The flag stays on, unix::write() needs it too.
+                let mut quoted = *self;
+                quoted.opts.multiline = Multiline::Escape;
+                return quoted.write_levels(&mut unix::Crontab(out));
+            }
+        }
+        self.write_levels(out)
+    }
+
+    /// Write the text with as many levels of quoting as it needs.
+    fn write_levels(&self, out: &mut dyn Sink) -> fmt::Result {
+        #[cfg(feature = "alloc")]
+        {
+            match self.source {
+                // The recording already has every level of quoting, even if
+                // the options were changed afterwards.
+                Kind::Prepared(recording) => return recording.replay(out),
+                _ if self.opts.remote > 0 => return self.write_remote(out),
+                _ => (),
+            }
+        }
+        self.write_source(out)
+    }
+
+    /// Replace NUL characters with U+FFFD before quoting the text, for
+    /// [`OnUnrepresentable::ReplacementChar`]. The writers already replace
+    /// invalid unicode where they have to.
+    #[cfg(feature = "alloc")]
+    fn write_replaced(&self, out: &mut dyn Sink) -> fmt::Result {
+        #[cfg(any(
+            feature = "unix",
+            feature = "windows",
+            feature = "native",
+            feature = "dockerfile",
+            feature = "reg"
+        ))]
+        fn replace_str(text: &str) -> alloc::string::String {
+            text.replace('\0', "\u{FFFD}")
+        }
+        #[cfg(any(
+            feature = "unix",
+            feature = "windows",
+            feature = "systemd",
+            all(feature = "native", unix)
+        ))]
+        fn replace_bytes(bytes: &[u8]) -> alloc::vec::Vec<u8> {
+            let mut replaced = alloc::vec::Vec::with_capacity(bytes.len());
+            for &byte in bytes {
+                match byte {
+                    0 => replaced.extend_from_slice("\u{FFFD}".as_bytes()),
+                    _ => replaced.push(byte),
+                }
+            }
+            replaced
+        }
+        #[cfg(any(
+            feature = "windows",
+            feature = "reg",
+            all(feature = "native", feature = "std", windows)
+        ))]
+        fn replace_units(units: &[u16]) -> alloc::vec::Vec<u16> {
+            units
+                .iter()
+                .map(|&unit| if unit == 0 { 0xFFFD } else { unit })
+                .collect()
+        }
+
+        let mut quoted = *self;
+        quoted.opts.on_unrepresentable = OnUnrepresentable::Escape;
+        match self.source {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) => {
+                let text = replace_str(text);
+                quoted.source = Kind::Unix(&text);
+                quoted.write(out)
+            }
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) => {
+                let bytes = replace_bytes(bytes);
+                quoted.source = Kind::UnixRaw(&bytes);
+                quoted.write(out)
+            }
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) => {
+                let text = replace_str(text);
+                quoted.source = Kind::Windows(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "windows")]
+            Kind::WindowsRaw(units) => {
+                let units = replace_units(units);
+                quoted.source = Kind::WindowsRaw(&units);
+                quoted.write(out)
+            }
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) => {
+                let bytes = replace_bytes(bytes);
+                quoted.source = Kind::WindowsWtf8(&bytes);
+                quoted.write(out)
+            }
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text) => {
+                let text = replace_str(text);
+                quoted.source = Kind::WindowsArgv(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(text) => {
+                #[cfg(unix)]
+                let text: std::ffi::OsString = {
+                    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+                    std::ffi::OsString::from_vec(replace_bytes(text.as_bytes()))
+                };
+                #[cfg(windows)]
+                let text: std::ffi::OsString = {
+                    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+                    let units: alloc::vec::Vec<u16> = text.encode_wide().collect();
+                    std::ffi::OsString::from_wide(&replace_units(&units))
+                };
+                #[cfg(not(any(unix, windows)))]
+                let text: std::ffi::OsString = match text.to_str() {
+                    Some(text) => replace_str(text).into(),
+                    None => text.into(),
+                };
+                quoted.source = Kind::NativeRaw(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(text) => {
+                let text = replace_str(text);
+                quoted.source = Kind::Dockerfile(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(path) => {
+                let path = replace_bytes(path);
+                quoted.source = Kind::SystemdUnit(&path);
+                quoted.write(out)
+            }
+            #[cfg(feature = "reg")]
+            Kind::Reg(text) => {
+                let text = replace_str(text);
+                quoted.source = Kind::Reg(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "reg")]
+            Kind::RegRaw(units) => {
+                let units = replace_units(units);
+                quoted.source = Kind::RegRaw(&units);
+                quoted.write(out)
+            }
+            _ => quoted.write(out),
+        }
+    }
+
+    /// Escape the pattern syntax in the text before quoting it, see
+    /// [`Quoted::glob_safe`] and [`Quoted::as_regex`].
+    #[cfg(feature = "alloc")]
+    fn write_pattern(&self, out: &mut dyn Sink, pattern: pattern::Pattern) -> fmt::Result {
+        let mut quoted = *self;
+        quoted.opts.pattern = None;
+        match self.source {
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) => {
+                let text = pattern::unix_str(text, pattern);
+                quoted.source = Kind::Unix(&text);
+                quoted.write(out)
+            }
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) => {
+                let bytes = pattern::unix_bytes(bytes, pattern);
+                quoted.source = Kind::UnixRaw(&bytes);
+                quoted.write(out)
+            }
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) => {
+                let text = pattern::powershell_str(text, pattern);
+                quoted.source = Kind::Windows(&text);
+                quoted.write(out)
+            }
+            #[cfg(feature = "windows")]
+            Kind::WindowsRaw(units) => {
+                let units = pattern::powershell_units(units, pattern);
+                quoted.source = Kind::WindowsRaw(&units);
+                quoted.write(out)
+            }
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) => {
+                let mut units = alloc::vec::Vec::with_capacity(bytes.len());
+                for ch in windows::Wtf8Chars::new(bytes) {
+                    match ch {
+                        Ok(ch) => units.extend_from_slice(ch.encode_utf16(&mut [0; 2])),
+                        Err(unit) => units.push(unit),
+                    }
+                }
+                let units = pattern::powershell_units(&units, pattern);
+                quoted.source = Kind::WindowsRaw(&units);
+                quoted.write(out)
+            }
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(text) => {
+                #[cfg(unix)]
+                let text: std::ffi::OsString = {
+                    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+                    std::ffi::OsString::from_vec(pattern::unix_bytes(text.as_bytes(), pattern))
+                };
+                #[cfg(windows)]
+                let text: std::ffi::OsString = {
+                    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+                    let units: alloc::vec::Vec<u16> = text.encode_wide().collect();
+                    std::ffi::OsString::from_wide(&pattern::powershell_units(&units, pattern))
+                };
+                // Elsewhere we only get at valid unicode.
+                #[cfg(not(any(unix, windows)))]
+                let text: std::ffi::OsString = match text.to_str() {
+                    Some(text) => pattern::unix_str(text, pattern).into(),
+                    None => text.into(),
+                };
+                quoted.source = Kind::NativeRaw(&text);
+                quoted.write(out)
+            }
+            // The program gets the text as it is, so only a regex needs escaping.
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text) if pattern == pattern::Pattern::Regex => {
+                let text = pattern::powershell_str(text, pattern);
+                quoted.source = Kind::WindowsArgv(&text);
+                quoted.write(out)
+            }
+            _ => quoted.write(out),
+        }
+    }
+
+    /// Quote the text once for every level, see [`Quoted::for_remote`].
+    #[cfg(feature = "alloc")]
+    fn write_remote(&self, out: &mut dyn Sink) -> fmt::Result {
+        fn once(source: Kind<'_>, opts: Options) -> Quoted<'_> {
+            Quoted {
+                source,
+                opts,
+                link: None,
+            }
+        }
+
+        let inner = Options {
+            remote: 0,
+            context: QuoteContext::Standalone,
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            multiline: Multiline::Escape,
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            fallback: Fallback::AnsiC,
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            crontab: false,
+            #[cfg(feature = "windows")]
+            cmd_context: None,
+            #[cfg(feature = "sql")]
+            sql_dialect: self.opts.sql_dialect,
+            #[cfg(feature = "dockerfile")]
+            dockerfile_form: self.opts.dockerfile_form,
+            #[cfg(feature = "cmake")]
+            cmake_list: self.opts.cmake_list,
+            bidi: match self.opts.bidi {
+                BidiPolicy::Isolate => BidiPolicy::Escape,
+                policy => policy,
+            },
+            // Only the outermost level is shown.
+            max_width: None,
+            ..self.opts
+        };
+        let mut text = alloc::string::String::new();
+        fmt::Write::write_fmt(&mut text, format_args!("{}", once(self.source, inner)))?;
+        for _ in 1..self.opts.remote {
+            let mut requoted = alloc::string::String::new();
+            fmt::Write::write_fmt(
+                &mut requoted,
+                format_args!("{}", once(self.source.requote(&text), inner)),
+            )?;
+            text = requoted;
+        }
+        let outer = Options {
+            remote: 0,
+            ..self.opts
+        };
+        once(self.source.requote(&text), outer).write_source(out)
+    }
+
+    #[allow(unused_variables)]
+    fn write_source(&self, out: &mut dyn Sink) -> fmt::Result {
+        match self.source {
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::NativeRaw(text) => {
+                #[cfg(windows)]
+                use std::os::windows::ffi::OsStrExt;
+
+                #[cfg(windows)]
+                match text.to_str() {
+                    Some(text) => windows::write(out, text, &self.opts),
+                    None => {
+                        windows::write_escaped(out, decode_utf16(text.encode_wide()), &self.opts)
+                    }
+                }
+                #[cfg(not(windows))]
+                match text.to_str() {
+                    Some(text) => unix::write(out, text, &self.opts),
+                    None => match os_str_bytes(text) {
+                        Some(bytes) => unix::write_escaped(out, bytes, &self.opts),
+                        #[cfg(any(target_os = "wasi", feature = "lossy"))]
+                        None => unix::write(out, &text.to_string_lossy(), &self.opts),
+                        // Debug is our best shot for not losing information.
+                        // But you probably can't paste it into a shell.
+                        #[cfg(not(any(target_os = "wasi", feature = "lossy")))]
+                        None => fmt::Write::write_fmt(out, format_args!("{:?}", text)),
+                    },
+                }
+            }
+
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Kind::Unix(text) => unix::write(out, text, &self.opts),
+
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Kind::UnixRaw(bytes) => match core::str::from_utf8(bytes) {
+                Ok(text) => unix::write(out, text, &self.opts),
+                Err(_) => unix::write_escaped(out, bytes, &self.opts),
+            },
+
+            #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+            Kind::Windows(text) => windows::write(out, text, &self.opts),
+
+            #[cfg(feature = "windows")]
+            #[cfg(feature = "alloc")]
+            // Avoiding this allocation is possible in theory, but it'd require either
+            // complicating or slowing down the common case.
+            // Perhaps we could offer a non-allocating API for known-invalid UTF-16 strings
+            // that we pass straight to write_escaped(), but it seems a bit awkward.
+            // Please open an issue if you have a need for this.
+            Kind::WindowsRaw(units) => match alloc::string::String::from_utf16(units) {
+                Ok(text) => windows::write(out, &text, &self.opts),
+                Err(_) => windows::write_escaped(out, util::utf16_chunks(units), &self.opts),
+            },
+
+            #[cfg(feature = "windows")]
+            Kind::WindowsWtf8(bytes) => match core::str::from_utf8(bytes) {
+                Ok(text) => windows::write(out, text, &self.opts),
+                Err(_) => windows::write_escaped(out, windows::Wtf8Chars::new(bytes), &self.opts),
+            },
+
+            #[cfg(feature = "windows")]
+            Kind::WindowsArgv(text) => windows::write_argv(out, text, &self.opts),
+
+            #[cfg(feature = "sql")]
+            Kind::Sql(bytes) => sql::write(out, bytes, &self.opts),
+
+            #[cfg(feature = "html")]
+            Kind::Html(bytes) => html::write(out, bytes, &self.opts),
+
+            #[cfg(feature = "systemd")]
+            Kind::SystemdUnit(path) => systemd::write(out, path),
+
+            #[cfg(feature = "dotenv")]
+            Kind::Dotenv(text) => dotenv::write(out, text, &self.opts),
+
+            #[cfg(feature = "dockerfile")]
+            Kind::Dockerfile(text) => dockerfile::write(out, text, &self.opts),
+
+            #[cfg(feature = "cmake")]
+            Kind::Cmake(text) => cmake::write(out, text, &self.opts),
+
+            #[cfg(feature = "reg")]
+            Kind::Reg(text) => reg::write_str(out, text, &self.opts),
+            #[cfg(feature = "sudoers")]
+            Kind::Sudoers(text) => sudoers::write(out, text),
+            #[cfg(feature = "git")]
+            Kind::GitIgnore(text) => git::write_gitignore(out, text),
+            #[cfg(feature = "git")]
+            Kind::GitAttributes(text) => git::write_gitattributes(out, text),
+            #[cfg(feature = "git")]
+            Kind::GitConfig(text) => git::write_config(out, text, &self.opts),
+            #[cfg(feature = "sanitize")]
+            Kind::Sanitize(bytes) => sanitize::write(out, bytes, &self.opts),
+            #[cfg(feature = "reg")]
+            Kind::RegRaw(units) => reg::write_units(out, units, &self.opts),
+
+            #[cfg(any(feature = "unix", feature = "windows", feature = "native"))]
+            Kind::Operator(_, op) => out.literal(op),
+
+            #[cfg(feature = "native")]
+            #[cfg(feature = "std")]
+            Kind::FileUrl(path) => match hyperlink::file_url_path(path) {
+                Some(path) => hyperlink::write_file_url(out, &path),
+                None => Quoted {
+                    source: Kind::NativeRaw(path.as_os_str()),
+                    ..*self
+                }
+                .write_source(out),
+            },
+
+            #[cfg(feature = "alloc")]
+            Kind::Prepared(recording) => recording.replay(out),
+        }
+    }
+}
+
+/// Get the bytes of an `OsStr` on platforms where they're (usually) UTF-8.
+///
+/// On Unix this is always possible. Elsewhere we need `OsStr::as_encoded_bytes()`
+/// (Rust 1.74) or, on WASI preview 1, the stable parts of `std::os::wasi`.
+/// build.rs checks the compiler version, but that's not possible in every build
+/// system, so the `modern-os-str` feature can be used to skip the check.
+///
+/// The encoding on other platforms is officially unspecified, but in
+/// practice it's a superset of UTF-8 (WTF-8, or arbitrary bytes). Passing
+/// that through the Unix writer is more useful than the `Debug` output, so we
+/// only avoid it if the `lossy` feature asks us to.
+#[cfg(all(feature = "native", feature = "std", not(windows)))]
+// as_encoded_bytes() is only used if build.rs detects a new enough compiler
+// or if the modern-os-str feature promises one.
+#[allow(unreachable_code, clippy::incompatible_msrv)]
+fn os_str_bytes(text: &OsStr) -> Option<&[u8]> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        return Some(text.as_bytes());
+    }
+    #[cfg(all(
+        not(unix),
+        any(os_display_encoded_bytes, feature = "modern-os-str"),
+        any(target_os = "wasi", not(feature = "lossy"))
+    ))]
+    {
+        return Some(text.as_encoded_bytes());
+    }
+    #[cfg(all(
+        target_os = "wasi",
+        not(target_env = "p2"),
+        not(any(os_display_encoded_bytes, feature = "modern-os-str"))
+    ))]
+    {
+        use std::os::wasi::ffi::OsStrExt;
+        return Some(text.as_bytes());
+    }
+    let _ = text;
+    None
+}
+
+#[cfg(all(feature = "native", feature = "std", windows))]
+fn decode_utf16<I>(units: I) -> impl Iterator<Item = Result<char, u16>> + Clone
+where
+    I: IntoIterator<Item = u16>,
+    I::IntoIter: Clone,
+{
+    core::char::decode_utf16(units).map(|res| res.map_err(|err| err.unpaired_surrogate()))
+}
+
+#[cfg(feature = "confusables")]
+fn is_confusable(ch: char) -> bool {
+    security::ascii_confusable(ch).is_some()
+}
+
+#[cfg(not(feature = "confusables"))]
+fn is_confusable(_: char) -> bool {
+    false
+}
+
+/// Characters that may not be safe to print in a terminal.
+///
+/// This includes all the ASCII control characters, and with the
+/// `strict-unassigned` feature unassigned and private-use codepoints.
+fn requires_escape(ch: char) -> bool {
+    ch.is_control() || is_separator(ch) || is_unknown(ch)
+}
+
+#[cfg(feature = "strict-unassigned")]
+fn is_unknown(ch: char) -> bool {
+    security::is_unassigned(ch) || security::is_private_use(ch)
+}
+
+#[cfg(not(feature = "strict-unassigned"))]
+fn is_unknown(_: char) -> bool {
+    false
+}
+
+/// U+2028 LINE SEPARATOR and U+2029 PARAGRAPH SEPARATOR are currently the only
+/// in their categories. The terminals I tried don't treat them very specially,
+/// but gedit does.
+fn is_separator(ch: char) -> bool {
+    tables::contains(tables::LINE_PARAGRAPH_SEPARATOR, ch)
+}
+
+#[cfg(feature = "native")]
+mod native {
+    use super::*;
+
+    /// An extension trait to apply quoting to strings.
+    ///
+    /// This is implemented on [`str`], [`OsStr`] and [`Path`] and their owned
+    /// versions, and on references and smart pointers to any of them. On Unix
+    /// it's also implemented on byte strings (`[u8]`, `Vec<u8>` and
+    /// `Cow<[u8]>`), which are quoted like an `OsStr` with the same bytes.
+    ///
+    /// For finer control, see the constructors on [`Quoted`].
+    pub trait Quotable {
+        /// Returns an object that implements [`Display`] for printing strings with
+        /// proper quoting and escaping for the platform.
+        ///
+        /// On Unix this corresponds to bash/ksh syntax, on Windows PowerShell syntax
+        /// is used.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::path::Path;
+        /// use os_display::Quotable;
+        ///
+        /// let path = Path::new("foo/bar.baz");
+        ///
+        /// println!("Found file {}", path.quote()); // Prints "Found file 'foo/bar.baz'"
+        /// ```
+        fn quote(&self) -> Quoted<'_>;
+
+        /// Like `quote()`, but don't actually add quotes unless necessary because of
+        /// whitespace or special characters.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::path::Path;
+        /// use os_display::Quotable;
+        ///
+        /// let foo = Path::new("foo/bar.baz");
+        /// let bar = "foo bar";
+        ///
+        /// println!("{}: Not found", foo.maybe_quote()); // Prints "foo/bar.baz: Not found"
+        /// println!("{}: Not found", bar.maybe_quote()); // Prints "'foo bar': Not found"
+        /// ```
+        fn maybe_quote(&self) -> Quoted<'_> {
+            let mut quoted = self.quote();
+            quoted.opts.force_quote = false;
+            quoted
+        }
+
+        /// Shorthand for `quote().to_string()`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use os_display::Quotable;
+        ///
+        /// assert_eq!("foo".to_quoted_string(), "'foo'");
+        /// ```
+        ///
+        /// # Optional
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        fn to_quoted_string(&self) -> alloc::string::String {
+            alloc::string::ToString::to_string(&self.quote())
+        }
+
+        /// Shorthand for `maybe_quote().to_string()`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use os_display::Quotable;
+        ///
+        /// assert_eq!("foo".to_maybe_quoted_string(), "foo");
+        /// assert_eq!("foo bar".to_maybe_quoted_string(), "'foo bar'");
+        /// ```
+        ///
+        /// # Optional
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        fn to_maybe_quoted_string(&self) -> alloc::string::String {
+            alloc::string::ToString::to_string(&self.maybe_quote())
+        }
+
+        /// Append the output of `quote()` to a `String`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use os_display::Quotable;
+        ///
+        /// let mut message = String::from("Can't open ");
+        /// "foo bar".quote_into(&mut message);
+        /// assert_eq!(message, "Can't open 'foo bar'");
+        /// ```
+        ///
+        /// # Optional
+        /// This requires the `alloc` feature.
+        #[cfg(feature = "alloc")]
+        fn quote_into(&self, buf: &mut alloc::string::String) {
+            // Writing to a String can't fail.
+            let _ = fmt::Write::write_fmt(buf, format_args!("{}", self.quote()));
+        }
+    }
+
+    impl Quotable for str {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native(self)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Quotable for OsStr {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Quotable for Path {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self.as_ref())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Quotable for alloc::string::String {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native(self)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Quotable for std::ffi::OsString {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Quotable for std::path::PathBuf {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self.as_os_str())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<'a> Quotable for std::path::Component<'a> {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self.as_os_str())
+        }
+    }
+
+    /// This quotes the remaining path as a whole. To quote each component
+    /// separately, see [`QuotedComponents`].
+    #[cfg(feature = "std")]
+    impl<'a> Quotable for std::path::Components<'a> {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::native_raw(self.as_path().as_os_str())
+        }
+    }
+
+    // These make it possible to write generic code that takes `Q: Quotable`.
+
+    impl<T: Quotable + ?Sized> Quotable for &T {
+        fn quote(&self) -> Quoted<'_> {
+            (**self).quote()
+        }
+
+        fn maybe_quote(&self) -> Quoted<'_> {
+            (**self).maybe_quote()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: Quotable + ?Sized> Quotable for alloc::boxed::Box<T> {
+        fn quote(&self) -> Quoted<'_> {
+            (**self).quote()
+        }
+
+        fn maybe_quote(&self) -> Quoted<'_> {
+            (**self).maybe_quote()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: Quotable + ?Sized> Quotable for alloc::rc::Rc<T> {
+        fn quote(&self) -> Quoted<'_> {
+            (**self).quote()
+        }
+
+        fn maybe_quote(&self) -> Quoted<'_> {
+            (**self).maybe_quote()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<T: Quotable + ?Sized> Quotable for alloc::sync::Arc<T> {
+        fn quote(&self) -> Quoted<'_> {
+            (**self).quote()
+        }
+
+        fn maybe_quote(&self) -> Quoted<'_> {
+            (**self).maybe_quote()
+        }
+    }
+
+    #[cfg(unix)]
+    impl Quotable for [u8] {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::from_kind(Kind::UnixRaw(self))
+        }
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "alloc")]
+    impl Quotable for alloc::vec::Vec<u8> {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::from_kind(Kind::UnixRaw(self))
+        }
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "alloc")]
+    impl<'a> Quotable for alloc::borrow::Cow<'a, [u8]> {
+        fn quote(&self) -> Quoted<'_> {
+            Quoted::from_kind(Kind::UnixRaw(self))
+        }
+    }
+
+    impl<'a, T: Quotable + ?Sized> From<&'a T> for Quoted<'a> {
+        fn from(val: &'a T) -> Self {
+            val.quote()
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+pub use crate::native::Quotable;
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    #![allow(unused)]
+
+    use super::*;
+
+    use std::string::{String, ToString};
+
+    const BOTH_ALWAYS: &[(&str, &str)] = &[
+        ("foo", "'foo'"),
+        ("foo/bar.baz", "'foo/bar.baz'"),
+        ("can't", r#""can't""#),
+    ];
+    const BOTH_MAYBE: &[(&str, &str)] = &[
+        ("foo", "foo"),
+        ("foo bar", "'foo bar'"),
+        ("$foo", "'$foo'"),
+        ("-", "-"),
+        ("a#b", "a#b"),
+        ("#ab", "'#ab'"),
+        ("a~b", "a~b"),
+        ("!", "'!'"),
+        ("}", ("'}'")),
+        ("\u{200B}", "'\u{200B}'"),
+        ("\u{200B}a", "'\u{200B}a'"),
+        ("a\u{200B}", "a\u{200B}"),
+        ("\u{2000}", "'\u{2000}'"),
+        ("\u{2800}", "'\u{2800}'"),
+        // Odd but safe bidi
+        (
+            "\u{2067}\u{2066}abc\u{2069}\u{2066}def\u{2069}\u{2069}",
+            "'\u{2067}\u{2066}abc\u{2069}\u{2066}def\u{2069}\u{2069}'",
+        ),
+    ];
+
+    const UNIX_ALWAYS: &[(&str, &str)] = &[
+        ("", "''"),
+        (r#"can'"t"#, r#"'can'\''"t'"#),
+        (r#"can'$t"#, r#"'can'\''$t'"#),
+        ("foo\nb\ta\r\\\0`r", r#"$'foo\nb\ta\r\\\x00`r'"#),
+        ("foo\x02", r#"$'foo\x02'"#),
+        (r#"'$''"#, r#"\''$'\'\'"#),
+    ];
+    const UNIX_MAYBE: &[(&str, &str)] = &[
+        ("", "''"),
+        ("-x", "-x"),
+        ("a,b", "a,b"),
+        ("a\\b", "'a\\b'"),
+        ("\x02AB", "$'\\x02'$'AB'"),
+        ("\x02GH", "$'\\x02GH'"),
+        ("\t", r#"$'\t'"#),
+        ("\r", r#"$'\r'"#),
+        ("\u{85}", r#"$'\xC2\x85'"#),
+        ("\u{85}a", r#"$'\xC2\x85'$'a'"#),
+        ("\u{2028}", r#"$'\xE2\x80\xA8'"#),
+        // Dangerous bidi
+        (
+            "user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}",
+            r#"$'user\xE2\x80\xAE \xE2\x81\xA6// Check if admin\xE2\x81\xA9 \xE2\x81\xA6'"#,
+        ),
+    ];
+    const UNIX_PREFER_DOUBLE: &[(&str, &str)] = &[
+        ("foo", r#""foo""#),
+        ("can't", r#""can't""#),
+        ("$foo", "'$foo'"),
+        (r#"a"b"#, r#"'a"b'"#),
+        ("a\\b", r#"'a\b'"#),
+        ("a\nb", r#"$'a\nb'"#),
+        ("a!b", "'a!b'"),
+        ("can't!", r"'can'\''t!'"),
+    ];
+    const UNIX_RAW: &[(&[u8], &str)] = &[
+        (b"foo\xFF", r#"$'foo\xFF'"#),
+        (b"foo\xFFbar", r#"$'foo\xFF'$'bar'"#),
+    ];
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn unix() {
+        for &(orig, expected) in UNIX_ALWAYS.iter().chain(BOTH_ALWAYS) {
+            assert_eq!(Quoted::unix(orig).to_string(), expected);
+        }
+        for &(orig, expected) in UNIX_MAYBE.iter().chain(BOTH_MAYBE) {
+            assert_eq!(Quoted::unix(orig).force(false).to_string(), expected);
+        }
+        for &(orig, expected) in UNIX_RAW {
+            assert_eq!(Quoted::unix_raw(orig).to_string(), expected);
+        }
+        for &(orig, expected) in UNIX_PREFER_DOUBLE {
+            assert_eq!(
+                Quoted::unix(orig).prefer(QuoteChar::Double).to_string(),
+                expected
+            );
+        }
+        assert_eq!(
+            Quoted::unix("foo")
+                .prefer(QuoteChar::Double)
+                .force(false)
+                .to_string(),
+            "foo"
+        );
+        let bidi_ok = nest_bidi(16);
+        assert_eq!(
+            Quoted::unix(&bidi_ok).to_string(),
+            "'".to_string() + &bidi_ok + "'"
+        );
+        let bidi_too_deep = nest_bidi(17);
+        assert!(Quoted::unix(&bidi_too_deep).to_string().starts_with('$'));
+    }
+
+    const WINDOWS_ALWAYS: &[(&str, &str)] = &[
+        (r#"foo\bar"#, r#"'foo\bar'"#),
+        (r#"can'"t"#, r#"'can''"t'"#),
+        (r#"can'$t"#, r#"'can''$t'"#),
+        ("foo\nb\ta\r\\\0`r", r#""foo`nb`ta`r\`0``r""#),
+        ("foo\x02", r#""foo`u{02}""#),
+        (r#"'$''"#, r#"'''$'''''"#),
+    ];
+    const WINDOWS_MAYBE: &[(&str, &str)] = &[
+        ("--%", "'--%'"),
+        ("--ok", "--ok"),
+        ("—x", "'—x'"),
+        ("a,b", "'a,b'"),
+        ("a\\b", "a\\b"),
+        ("‘", r#""‘""#),
+        (r#"‘""#, r#"''‘"'"#),
+        ("„\0", r#""`„`0""#),
+        ("\t", r#""`t""#),
+        ("\r", r#""`r""#),
+        ("\u{85}", r#""`u{85}""#),
+        ("\u{2028}", r#""`u{2028}""#),
+        (
+            "user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}",
+            r#""user`u{202E} `u{2066}// Check if admin`u{2069} `u{2066}""#,
+        ),
+    ];
+    const WINDOWS_PREFER_DOUBLE: &[(&str, &str)] = &[
+        ("foo", r#""foo""#),
+        ("can't", r#""can't""#),
+        ("‘", r#""‘""#),
+        ("$foo", "'$foo'"),
+        (r#"a"b"#, r#"'a"b'"#),
+        (r#"a\b"#, r#""a\b""#),
+    ];
+    const WINDOWS_RAW: &[(&[u16], &str)] = &[(&[b'x' as u16, 0xD800], r#""x`u{D800}""#)];
+    const WINDOWS_EXTERNAL: &[(&str, &str)] = &[
+        ("", r#"'""'"#),
+        (r#"\""#, r#"'\\\"'"#),
+        (r#"\\""#, r#"'\\\\\"'"#),
+        (r#"\x\""#, r#"'\x\\\"'"#),
+        (r#"\x\"'""#, r#"'\x\\\"''\"'"#),
+        ("\n\\\"", r#""`n\\\`"""#),
+        ("\n\\\\\"", r#""`n\\\\\`"""#),
+        ("\n\\x\\\"", r#""`n\x\\\`"""#),
+        ("\n\\x\\\"'\"", r#""`n\x\\\`"'\`"""#),
+        ("-x:", "'-x:'"),
+        ("-x.x", "'-x.x'"),
+        ("--%", r#"'"--%"'"#),
+        ("--ok", "--ok"),
+    ];
+    const WINDOWS_INTERNAL: &[(&str, &str)] = &[
+        ("", "''"),
+        (r#"can'"t"#, r#"'can''"t'"#),
+        ("-x", "'-x'"),
+        ("—x", "'—x'"),
+        ("‘\"", r#"''‘"'"#),
+        ("--%", "'--%'"),
+        ("--ok", "--ok"),
+    ];
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn windows() {
+        for &(orig, expected) in WINDOWS_ALWAYS.iter().chain(BOTH_ALWAYS) {
+            assert_eq!(Quoted::windows(orig).to_string(), expected);
+        }
+        for &(orig, expected) in WINDOWS_MAYBE.iter().chain(BOTH_MAYBE) {
+            assert_eq!(Quoted::windows(orig).force(false).to_string(), expected);
+        }
+        for &(orig, expected) in WINDOWS_RAW {
+            assert_eq!(Quoted::windows_raw(orig).to_string(), expected);
+        }
+        for &(orig, expected) in WINDOWS_PREFER_DOUBLE {
+            assert_eq!(
+                Quoted::windows(orig).prefer(QuoteChar::Double).to_string(),
+                expected
+            );
+        }
+        for &(orig, expected) in WINDOWS_EXTERNAL {
+            assert_eq!(
+                Quoted::windows(orig)
+                    .force(false)
+                    .external(true)
+                    .to_string(),
+                expected
+            );
+        }
+        for &(orig, expected) in WINDOWS_INTERNAL {
+            assert_eq!(
+                Quoted::windows(orig)
+                    .force(false)
+                    .external(false)
+                    .to_string(),
+                expected
+            );
+        }
+        let bidi_ok = nest_bidi(16);
+        assert_eq!(
+            Quoted::windows(&bidi_ok).to_string(),
+            "'".to_string() + &bidi_ok + "'"
+        );
+        let bidi_too_deep = nest_bidi(17);
+        assert!(Quoted::windows(&bidi_too_deep).to_string().contains('`'));
+    }
+
+    #[cfg(all(feature = "windows", feature = "std"))]
+    #[test]
+    fn windows_wtf8() {
+        /// Encode UTF-16 as WTF-8, the way `OsStr` does on Windows.
+        fn wtf8(units: &[u16]) -> std::vec::Vec<u8> {
+            let mut bytes = std::vec::Vec::new();
+            for ch in crate::util::utf16_chunks(units) {
+                let code = match ch {
+                    Ok(ch) => ch as u32,
+                    Err(unit) => u32::from(unit),
+                };
+                match code {
+                    0..=0x7F => bytes.push(code as u8),
+                    0x80..=0x7FF => bytes
+                        .extend_from_slice(&[0xC0 | (code >> 6) as u8, 0x80 | (code & 0x3F) as u8]),
+                    0x800..=0xFFFF => bytes.extend_from_slice(&[
+                        0xE0 | (code >> 12) as u8,
+                        0x80 | (code >> 6 & 0x3F) as u8,
+                        0x80 | (code & 0x3F) as u8,
+                    ]),
+                    _ => bytes.extend_from_slice(&[
+                        0xF0 | (code >> 18) as u8,
+                        0x80 | (code >> 12 & 0x3F) as u8,
+                        0x80 | (code >> 6 & 0x3F) as u8,
+                        0x80 | (code & 0x3F) as u8,
+                    ]),
+                }
+            }
+            bytes
+        }
+
+        for &(orig, expected) in WINDOWS_ALWAYS.iter().chain(BOTH_ALWAYS) {
+            assert_eq!(Quoted::windows_wtf8(orig.as_bytes()).to_string(), expected);
+        }
+        let units: &[&[u16]] = &[
+            &[0xD800],
+            &[0x78, 0xDFFF, 0x31],
+            &[0xDBFF, 0xD83D, 0xDE00, 0xDC00],
+            &[0xD800, 0x27, 0x0A, 0xE9],
+        ];
+        for orig in WINDOWS_RAW
+            .iter()
+            .map(|&(orig, _)| orig)
+            .chain(units.iter().cloned())
+        {
+            assert_eq!(
+                Quoted::windows_wtf8(&wtf8(orig)).to_string(),
+                Quoted::windows_raw(orig).to_string()
+            );
+        }
+
+        // Not WTF-8 at all.
+        assert_eq!(
+            Quoted::windows_wtf8(b"a\xFF\xED\x9F").to_string(),
+            "\"a\u{FFFD}\u{FFFD}\u{FFFD}\""
+        );
+
+        let mut spans = std::vec::Vec::new();
+        Quoted::windows_wtf8(b"\xED\xA0\x80\n").escaped_spans(|span| spans.push(span));
+        assert_eq!(spans[0].input, 0..3);
+        assert_eq!(spans[1].input, 3..4);
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn bidi_policy() {
+        const CASES: &[(&str, BidiPolicy, &str, &str)] = &[
+            (
+                "a\u{202E}b",
+                BidiPolicy::Escape,
+                r#"$'a\xE2\x80\xAE'$'b'"#,
+                r#""a`u{202E}b""#,
+            ),
+            (
+                "a\u{202E}b",
+                BidiPolicy::Allow,
+                "'a\u{202E}b'",
+                "'a\u{202E}b'",
+            ),
+            (
+                "a\u{202E}b",
+                BidiPolicy::Isolate,
+                "\u{2068}'a\u{202E}b'\u{2069}",
+                "\u{2068}'a\u{202E}b'\u{2069}",
+            ),
+            (
+                "\u{2066}\u{202E}a",
+                BidiPolicy::Isolate,
+                "\u{2068}'\u{2066}\u{202E}a'\u{2069}",
+                "\u{2068}'\u{2066}\u{202E}a'\u{2069}",
+            ),
+            // Unpaired terminators can't be isolated
+            (
+                "a\u{2069}",
+                BidiPolicy::Isolate,
+                r#"$'a\xE2\x81\xA9'"#,
+                r#""a`u{2069}""#,
+            ),
+            ("a\u{2069}", BidiPolicy::Allow, "'a\u{2069}'", "'a\u{2069}'"),
+            // Properly terminated bidi is left alone
+            (
+                "\u{2067}a\u{2069}",
+                BidiPolicy::Isolate,
+                "'\u{2067}a\u{2069}'",
+                "'\u{2067}a\u{2069}'",
+            ),
+        ];
+        for &(orig, policy, unix, windows) in CASES {
+            assert_eq!(Quoted::unix(orig).bidi_policy(policy).to_string(), unix);
+            assert_eq!(
+                Quoted::windows(orig).bidi_policy(policy).to_string(),
+                windows
+            );
+        }
+        assert_eq!(
+            Quoted::unix("a\u{202E}")
+                .bidi_policy(BidiPolicy::Isolate)
+                .force(false)
+                .to_string(),
+            "\u{2068}a\u{202E}\u{2069}"
+        );
+        let bidi_too_deep = nest_bidi(17);
+        assert!(Quoted::unix(&bidi_too_deep)
+            .bidi_policy(BidiPolicy::Isolate)
+            .to_string()
+            .starts_with('$'));
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn escape_invisible() {
+        const CASES: &[(&str, &str, &str)] = &[
+            ("a\u{200B}b", r#"$'a\xE2\x80\x8B'$'b'"#, r#""a`u{200B}b""#),
+            ("\u{00AD}", r#"$'\xC2\xAD'"#, r#""`u{AD}""#),
+            ("a\u{FE0F}", r#"$'a\xEF\xB8\x8F'"#, r#""a`u{FE0F}""#),
+            (
+                "\u{2067}x\u{2069}",
+                r#"$'\xE2\x81\xA7x\xE2\x81\xA9'"#,
+                r#""`u{2067}x`u{2069}""#,
+            ),
+            ("e\u{301}", "'e\u{301}'", "'e\u{301}'"),
+        ];
+        for &(orig, unix, windows) in CASES {
+            assert_eq!(Quoted::unix(orig).escape_invisible(true).to_string(), unix);
+            assert_eq!(
+                Quoted::windows(orig).escape_invisible(true).to_string(),
+                windows
+            );
+        }
+        assert_eq!(
+            Quoted::unix_raw(b"\xFF\xE2\x80\x8D")
+                .escape_invisible(true)
+                .to_string(),
+            r#"$'\xFF\xE2\x80\x8D'"#
+        );
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows", feature = "confusables"))]
+    #[test]
+    fn escape_confusables() {
+        const CASES: &[(&str, &str, &str)] = &[
+            ("p\u{0430}ypal", r#"$'p\xD0\xB0ypal'"#, r#""p`u{430}ypal""#),
+            ("\u{03BF}k", r#"$'\xCE\xBFk'"#, r#""`u{3BF}k""#),
+            ("a b\u{0430}", r#"$'a b\xD0\xB0'"#, r#""a b`u{430}""#),
+            // Other non-ASCII, so probably legitimate
+            ("\u{0430}\u{0436}", "\u{0430}\u{0436}", "\u{0430}\u{0436}"),
+            ("\u{0430}\u{00E9}", "\u{0430}\u{00E9}", "\u{0430}\u{00E9}"),
+            ("paypal", "paypal", "paypal"),
+        ];
+        for &(orig, unix, windows) in CASES {
+            assert_eq!(
+                Quoted::unix(orig)
+                    .force(false)
+                    .escape_confusables(true)
+                    .to_string(),
+                unix
+            );
+            assert_eq!(
+                Quoted::windows(orig)
+                    .force(false)
+                    .escape_confusables(true)
+                    .to_string(),
+                windows
+            );
+        }
+        assert_eq!(
+            Quoted::unix("p\u{0430}ypal").force(false).to_string(),
+            "p\u{0430}ypal"
+        );
+        // Invalid unicode is escaped anyway but doesn't count as ASCII
+        assert_eq!(
+            Quoted::unix_raw(b"\xFF\xD0\xB0")
+                .escape_confusables(true)
+                .to_string(),
+            "$'\\xFF\u{0430}'"
+        );
+        assert_eq!(
+            Quoted::windows_raw(&[0xD800, 0x0430])
+                .escape_confusables(true)
+                .to_string(),
+            "\"`u{D800}\u{0430}\""
+        );
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn highlight() {
+        fn strip_sgr(text: &str) -> String {
+            let mut out = String::new();
+            let mut in_sgr = false;
+            for ch in text.chars() {
+                match ch {
+                    '\x1B' => in_sgr = true,
+                    'm' if in_sgr => in_sgr = false,
+                    _ if in_sgr => (),
+                    ch => out.push(ch),
+                }
+            }
+            out
+        }
+
+        let highlight = Highlight::new().quotes("2").escapes("1;31");
+        let unix = UNIX_ALWAYS
+            .iter()
+            .chain(UNIX_MAYBE)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in unix {
+            let plain = Quoted::unix(orig).to_string();
+            let highlighted = Quoted::unix(orig).highlight(highlight).to_string();
+            assert_eq!(strip_sgr(&highlighted), plain);
+        }
+        let windows = WINDOWS_ALWAYS
+            .iter()
+            .chain(WINDOWS_MAYBE)
+            .chain(WINDOWS_EXTERNAL)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in windows {
+            for &external in &[false, true] {
+                let plain = Quoted::windows(orig).external(external).to_string();
+                let highlighted = Quoted::windows(orig)
+                    .external(external)
+                    .highlight(highlight)
+                    .to_string();
+                assert_eq!(strip_sgr(&highlighted), plain);
+            }
+        }
+
+        assert_eq!(
+            Quoted::unix("it's").highlight(highlight).to_string(),
+            "\x1B[2m\"\x1B[0mit's\x1B[2m\"\x1B[0m"
+        );
+        assert_eq!(
+            Quoted::unix_raw(b"\xFF\xFE")
+                .highlight(highlight)
+                .to_string(),
+            "\x1B[2m$'\x1B[0m\x1B[1;31m\\xFF\\xFE\x1B[0m\x1B[2m'\x1B[0m"
+        );
+        assert_eq!(
+            Quoted::windows("a\0")
+                .highlight(Highlight::new().escapes("31"))
+                .to_string(),
+            "\"a\x1B[31m`0\x1B[0m\""
+        );
+        assert_eq!(
+            Quoted::unix("foo").highlight(Highlight::new()).to_string(),
+            "'foo'"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn highlight_injection() {
+        Highlight::new().quotes("0m\x1B]8;;");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn events() {
+        fn collect(quoted: Quoted) -> String {
+            let mut out = String::new();
+            quoted.events(|event| match event {
+                QuoteEvent::OpenQuote(text)
+                | QuoteEvent::Literal(text)
+                | QuoteEvent::CloseQuote(text) => out.push_str(text),
+                QuoteEvent::Escape { rendered, .. } => out.push_str(rendered),
+            });
+            out
+        }
+
+        let unix = UNIX_ALWAYS
+            .iter()
+            .chain(UNIX_MAYBE)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in unix {
+            let quoted = Quoted::unix(orig);
+            assert_eq!(collect(quoted), quoted.to_string());
+        }
+        for &(orig, _) in UNIX_RAW {
+            let quoted = Quoted::unix_raw(orig);
+            assert_eq!(collect(quoted), quoted.to_string());
+        }
+        let windows = WINDOWS_ALWAYS
+            .iter()
+            .chain(WINDOWS_MAYBE)
+            .chain(WINDOWS_EXTERNAL)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in windows {
+            for &external in &[false, true] {
+                let quoted = Quoted::windows(orig).external(external);
+                assert_eq!(collect(quoted), quoted.to_string());
+            }
+        }
+        for &(orig, _) in WINDOWS_RAW {
+            let quoted = Quoted::windows_raw(orig);
+            assert_eq!(collect(quoted), quoted.to_string());
+        }
+
+        let mut events = std::vec::Vec::new();
+        Quoted::windows("a\\\"\0")
+            .external(true)
+            .events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(
+            events,
+            [
+                r#"OpenQuote("\"")"#,
+                r#"Literal("a")"#,
+                r#"Escape { original: Char('\\'), rendered: "\\\\" }"#,
+                r#"Escape { original: Char('"'), rendered: "\\`\"" }"#,
+                r#"Escape { original: Char('\0'), rendered: "`0" }"#,
+                r#"CloseQuote("\"")"#,
+            ]
+        );
+        events.clear();
+        Quoted::unix_raw(b"\xFFa").events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(
+            events,
+            [
+                r#"OpenQuote("$'")"#,
+                r#"Escape { original: Byte(255), rendered: "\\xFF" }"#,
+                r#"CloseQuote("'")"#,
+                r#"OpenQuote("$'")"#,
+                r#"Literal("a")"#,
+                r#"CloseQuote("'")"#,
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn display_len() {
+        let unix = UNIX_ALWAYS
+            .iter()
+            .chain(UNIX_MAYBE)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in unix {
+            let quoted = Quoted::unix(orig);
+            assert_eq!(quoted.display_len(), quoted.to_string().len());
+            let quoted = quoted.highlight(Highlight::new().quotes("2").escapes("31"));
+            assert_eq!(quoted.display_len(), quoted.to_string().len());
+        }
+        let windows = WINDOWS_ALWAYS
+            .iter()
+            .chain(WINDOWS_MAYBE)
+            .chain(BOTH_ALWAYS)
+            .chain(BOTH_MAYBE);
+        for &(orig, _) in windows {
+            let quoted = Quoted::windows(orig);
+            assert_eq!(quoted.display_len(), quoted.to_string().len());
+        }
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn eq_hash() {
+        use std::collections::HashSet;
+
+        assert_eq!(Quoted::unix("foo"), Quoted::unix("foo"));
+        assert_eq!(
+            Quoted::unix("foo bar"),
+            Quoted::unix("foo bar").force(false)
+        );
+        assert_eq!(Quoted::unix("foo"), Quoted::unix_raw(b"foo"));
+        assert_ne!(Quoted::unix("foo"), Quoted::unix("foo").force(false));
+        assert_ne!(Quoted::unix("foo"), Quoted::unix("fo"));
+        assert_ne!(Quoted::unix("fo"), Quoted::unix("foo"));
+        assert_ne!(
+            Quoted::unix("foo"),
+            Quoted::unix("foo").highlight(Highlight::new().quotes("2"))
+        );
+
+        let set: HashSet<_> = [
+            Quoted::unix("foo"),
+            Quoted::unix_raw(b"foo"),
+            Quoted::unix("bar"),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Quoted::unix_raw(b"bar")));
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn format_spec() {
+        use std::format;
+
+        let quoted = Quoted::unix("foo bar");
+        assert_eq!(format!("{:12}|", quoted), "'foo bar'   |");
+        assert_eq!(format!("{:<12}|", quoted), "'foo bar'   |");
+        assert_eq!(format!("{:>12}|", quoted), "   'foo bar'|");
+        assert_eq!(format!("{:^12}|", quoted), " 'foo bar'  |");
+        assert_eq!(format!("{:*^13}|", quoted), "**'foo bar'**|");
+        assert_eq!(format!("{:4}|", quoted), "'foo bar'|");
+        assert_eq!(format!("{:>6.3}|", quoted), "   'f'|");
+        assert_eq!(format!("{:>5}|", Quoted::unix("\u{65E5}")), " '\u{65E5}'|");
+
+        let cases: &[(Quoted, usize, &str)] = &[
+            (quoted, 0, ""),
+            (quoted, 1, ""),
+            (quoted, 2, "''"),
+            (quoted, 3, "'f'"),
+            (quoted, 9, "'foo bar'"),
+            (quoted, 20, "'foo bar'"),
+            (Quoted::unix("foo").force(false), 2, "fo"),
+            (Quoted::unix("\u{65E5}\u{672C}"), 3, "''"),
+            (Quoted::unix("\u{65E5}\u{672C}"), 5, "'\u{65E5}'"),
+            (Quoted::unix("a\nb"), 4, "$'a'"),
+            (Quoted::unix("a\nb"), 5, "$'a'"),
+            (Quoted::unix("a\nb"), 6, "$'a\\n'"),
+            (Quoted::unix_raw(b"\xFFa b"), 7, "$'\\xFF'"),
+            (Quoted::unix_raw(b"\xFFa b"), 11, "$'\\xFF'$'a'"),
+            (
+                Quoted::unix("a\u{202E}b").bidi_policy(BidiPolicy::Isolate),
+                3,
+                "\u{2068}'a\u{202E}'\u{2069}",
+            ),
+            (
+                Quoted::unix("a\u{202E}b").bidi_policy(BidiPolicy::Isolate),
+                1,
+                "\u{2068}\u{2069}",
+            ),
+            (Quoted::windows("a\"b").external(true), 4, "'a'"),
+            (Quoted::windows("a\"b").external(true), 5, "'a\\\"'"),
+        ];
+        for &(quoted, limit, expected) in cases {
+            assert_eq!(format!("{:.*}", limit, quoted), expected);
+        }
+
+        let highlighted = quoted.highlight(Highlight::new().quotes("2"));
+        assert_eq!(
+            format!("{:>6.3}|", highlighted),
+            "   \x1B[2m'\x1B[0mf\x1B[2m'\x1B[0m|"
+        );
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn prepared() {
+        use std::format;
+        use std::vec::Vec;
+
+        fn check(quoted: Quoted) {
+            let prepared = quoted.prepared();
+            assert_eq!(prepared.to_string(), quoted.to_string());
+            assert_eq!(format!("{:>30.12}", prepared), format!("{:>30.12}", quoted));
+            assert_eq!(prepared.as_quoted(), quoted);
+            let mut events = Vec::new();
+            prepared
+                .as_quoted()
+                .events(|event| events.push(format!("{:?}", event)));
+            let mut expected = Vec::new();
+            quoted.events(|event| expected.push(format!("{:?}", event)));
+            // Consecutive literals are merged, but everything else is kept.
+            assert_eq!(
+                events.concat().replace("\")Literal(\"", ""),
+                expected.concat().replace("\")Literal(\"", "")
+            );
+        }
+
+        let highlight = Highlight::new().quotes("2").escapes("31");
+        for &(orig, _) in UNIX_ALWAYS.iter().chain(UNIX_MAYBE).chain(BOTH_ALWAYS) {
+            check(Quoted::unix(orig));
+            check(Quoted::unix(orig).force(false).highlight(highlight));
+        }
+        for &(orig, _) in WINDOWS_ALWAYS.iter().chain(WINDOWS_MAYBE).chain(BOTH_MAYBE) {
+            check(Quoted::windows(orig));
+            check(
+                Quoted::windows(orig)
+                    .external(true)
+                    .hyperlink("https://example.com"),
+            );
+        }
+        check(Quoted::unix_raw(b"foo\xFFbar baz"));
+    }
+
+    #[cfg(feature = "unicode-format")]
+    #[test]
+    fn unicode_format() {
+        // INTERLINEAR ANNOTATION ANCHOR isn't in the hardcoded list.
+        assert!(security::is_invisible('\u{FFF9}'));
+        assert_eq!(
+            Quoted::unix("a\u{FFF9}b")
+                .escape_invisible(true)
+                .to_string(),
+            "$'a\\xEF\\xBF\\xB9'$'b'"
+        );
+        // EGYPTIAN HIEROGLYPH INSERT AT TOP START, new in Unicode 15.0.
+        assert_eq!(
+            security::is_invisible('\u{13439}'),
+            UNICODE_VERSION >= (15, 0, 0)
+        );
+        assert!(!security::is_invisible('a'));
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn magic_tokens() {
+        let strict = |text| {
+            Quoted::windows(text)
+                .magic_tokens(MagicTokens::Strict)
+                .force(false)
+                .to_string()
+        };
+        assert_eq!(strict("$?"), "\"`$?\"");
+        assert_eq!(strict("$_'s"), "\"`$_'s\"");
+        assert_eq!(strict("a@(b)"), "\"a@(b)\"");
+        assert_eq!(strict("a$b"), "'a$b'");
+        assert_eq!(strict("--%"), "'--%'");
+        assert_eq!(strict("foo"), "foo");
+        assert_eq!(
+            Quoted::windows("$\"")
+                .magic_tokens(MagicTokens::Strict)
+                .external(true)
+                .to_string(),
+            "\"`$\\`\"\""
+        );
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn printf_command() {
+        let printf = |bytes: &[u8]| {
+            Quoted::unix_raw(bytes)
+                .fallback(Fallback::PrintfCommand)
+                .to_string()
+        };
+        assert_eq!(printf(b"foo"), "'foo'");
+        assert_eq!(printf(b"a\nb"), r"printf 'a\nb'");
+        assert_eq!(printf(b"\x1B1\t\r"), r"printf '\0331\t\r'");
+        assert_eq!(printf(b"-n\n"), r"printf '\055n\n'");
+        assert_eq!(printf(b"%s\\'\n"), r"printf '%%s\\\047\n'");
+        assert_eq!(printf("\u{202E}\n".as_bytes()), r"printf '\342\200\256\n'");
+        assert_eq!(printf(b"\xFF\xFE"), r"printf '\377\376'");
+        assert_eq!(printf("\u{E9}\n".as_bytes()), "printf '\u{E9}\\n'");
+        assert_eq!(
+            std::format!(
+                "{:.14}",
+                Quoted::unix("abc\ndef").fallback(Fallback::PrintfCommand)
+            ),
+            r"printf 'abc\n'"
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "windows"))]
+    #[test]
+    fn glob_safe() {
+        let unix = |text| Quoted::unix(text).glob_safe(true).to_string();
+        assert_eq!(unix("foo"), "'foo'");
+        assert_eq!(unix("*.txt"), r"'\*.txt'");
+        assert_eq!(unix(r"a?[b]{c,d}\\"), r"'a\?\[b\]\{c,d\}\\\\'");
+        assert_eq!(unix("it's *"), r"'it'\''s \*'");
+        assert_eq!(unix("*\x1B"), r"$'\\*\x1B'");
+        assert_eq!(
+            Quoted::unix_raw(b"[\xFF]").glob_safe(true).to_string(),
+            r"$'\\[\xFF\\]'"
+        );
+        assert_eq!(
+            Quoted::unix("*")
+                .glob_safe(true)
+                .context(QuoteContext::InsideDoubleQuotes)
+                .to_string(),
+            r"\\*"
+        );
+
+        // Checked against find -name.
+        assert_eq!(unix("a*b"), r"'a\*b'");
+        assert_eq!(unix("a[1]"), r"'a\[1\]'");
+        assert_eq!(unix("a{x}"), r"'a\{x\}'");
+        assert_eq!(unix(r"a\c"), r"'a\\c'");
+
+        let windows = |text| Quoted::windows(text).glob_safe(true).to_string();
+        assert_eq!(windows("foo"), "'foo'");
+        assert_eq!(windows("*.txt"), "'`*.txt'");
+        assert_eq!(windows("a?[b]{c}`"), "'a`?`[b`]{c}``'");
+        assert_eq!(windows("*\x1B"), "\"``*`u{1B}\"");
+        assert_eq!(
+            Quoted::windows_raw(&[u16::from(b'*'), 0xD800])
+                .glob_safe(true)
+                .to_string(),
+            "\"``*`u{D800}\""
+        );
+        // The program does its own wildcard expansion, if any.
+        assert_eq!(
+            Quoted::windows_argv("*.txt").glob_safe(true).to_string(),
+            "\"*.txt\""
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "windows"))]
+    #[test]
+    fn as_regex() {
+        let unix = |text| Quoted::unix(text).as_regex(true).to_string();
+        assert_eq!(unix("a.b"), r"'a\.b'");
+        assert_eq!(unix(r"[a]{2}+?*\"), r"'\[a\]\{2\}\+\?\*\\'");
+        assert_eq!(unix("x|y^$"), r"'x\|y\^\$'");
+        assert_eq!(unix("(it's)"), r"'\(it'\''s\)'");
+        assert_eq!(unix("a.\n"), r"$'a\\.\n'");
+        assert_eq!(
+            Quoted::unix_raw(b"a.\xFF").as_regex(true).to_string(),
+            r"$'a\\.\xFF'"
+        );
+        // Only one kind of pattern at a time.
+        assert_eq!(
+            Quoted::unix("a*.")
+                .glob_safe(true)
+                .as_regex(true)
+                .to_string(),
+            r"'a\*\.'"
+        );
+        assert_eq!(
+            Quoted::unix("a*.")
+                .as_regex(true)
+                .glob_safe(true)
+                .to_string(),
+            r"'a\*.'"
+        );
+        assert_eq!(
+            Quoted::unix("a*.")
+                .as_regex(true)
+                .glob_safe(false)
+                .to_string(),
+            r"'a\*\.'"
+        );
+
+        let windows = |text| Quoted::windows(text).as_regex(true).to_string();
+        assert_eq!(windows(r"C:\x (1).txt"), r"'C:\\x \(1\)\.txt'");
+        assert_eq!(windows("a`$."), r"'a`\$\.'");
+        assert_eq!(
+            Quoted::windows_argv("a \"b\".c").as_regex(true).to_string(),
+            r#""a \"b\"\.c""#
+        );
+    }
+
+    #[cfg(all(feature = "native", feature = "std", unix))]
+    #[test]
+    fn file_url() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let url = |bytes| Quoted::file_url(Path::new(OsStr::from_bytes(bytes))).to_string();
+        assert_eq!(url(b"/"), "file:///");
+        assert_eq!(url(b"/tmp/a-b_c.~d"), "file:///tmp/a-b_c.~d");
+        assert_eq!(url(b"/tmp/50% 'off'"), "file:///tmp/50%25%20%27off%27");
+        assert_eq!(url(b"/caf\xC3\xA9/\xFF\n"), "file:///caf%C3%A9/%FF%0A");
+        assert!(url(b"relative/path").starts_with("file:///"));
+        assert!(url(b"relative/path").ends_with("/relative/path"));
+
+        let mut events = std::vec::Vec::new();
+        Quoted::file_url(Path::new("/\u{E9}"))
+            .events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(
+            events,
+            [
+                "Literal(\"file://\")",
+                "Literal(\"/\")",
+                "Escape { original: Char('\u{E9}'), rendered: \"%C3%A9\" }",
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn quoting() {
+        let unix = |text, quoting| Quoted::unix(text).force(false).quoting(quoting).to_string();
+        let cases: &[(&str, &str, &str)] = &[
+            ("a=b", "a=b", "'a=b'"),
+            ("x^y", "x^y", "'x^y'"),
+            ("a]b", "a]b", "'a]b'"),
+            ("foo{", "foo{", "'foo{'"),
+            ("{}", "'{}'", "'{}'"),
+            ("{a,b}", "'{a,b}'", "'{a,b}'"),
+            ("a=~", "'a=~'", "'a=~'"),
+            ("=foo", "'=foo'", "'=foo'"),
+            ("~x", "'~x'", "'~x'"),
+            ("a b", "'a b'", "'a b'"),
+            ("a~b", "a~b", "a~b"),
+        ];
+        for &(text, minimal, standard) in cases {
+            assert_eq!(unix(text, Quoting::Minimal), minimal);
+            assert_eq!(unix(text, Quoting::Standard), standard);
+        }
+        assert_eq!(unix("abc123", Quoting::Paranoid), "abc123");
+        assert_eq!(unix("a-b", Quoting::Paranoid), "'a-b'");
+        assert_eq!(unix("\u{E9}", Quoting::Paranoid), "'\u{E9}'");
+        assert_eq!(unix("", Quoting::Minimal), "''");
+
+        let windows = |text, quoting| {
+            Quoted::windows(text)
+                .force(false)
+                .quoting(quoting)
+                .to_string()
+        };
+        assert_eq!(windows("C:\\foo", Quoting::Standard), "C:\\foo");
+        assert_eq!(windows("C:\\foo", Quoting::Paranoid), "'C:\\foo'");
+        assert_eq!(windows("a=b", Quoting::Minimal), "'a=b'");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn escaped_spans() {
+        fn collect(quoted: Quoted<'_>) -> (std::string::String, std::vec::Vec<EscapedSpan>) {
+            let mut spans = std::vec::Vec::new();
+            quoted.escaped_spans(|span| spans.push(span));
+            (quoted.to_string(), spans)
+        }
+
+        let texts = [
+            "",
+            "foo",
+            "a\tb\nc",
+            "\x1B[31m\u{202E}",
+            "it's \"\n\"",
+            "caf\u{E9}\u{1F600}\u{FEFF}\r",
+            r#"C:\"x\"\\"#,
+            "\u{1F600}\u{80}",
+        ];
+        for &text in &texts {
+            let quoted = [
+                Quoted::unix(text),
+                Quoted::unix(text).context(QuoteContext::InsideDoubleQuotes),
+                Quoted::unix(text).fallback(Fallback::PrintfCommand),
+                Quoted::windows(text),
+                Quoted::windows(text).external(true),
+                Quoted::windows(text).expression(true),
+                Quoted::windows_argv(text),
+            ];
+            for &quoted in &quoted {
+                let (output, spans) = collect(quoted);
+                let mut events = std::vec::Vec::new();
+                quoted.events(|event| {
+                    if let QuoteEvent::Escape { original, rendered } = event {
+                        events.push((original, std::string::ToString::to_string(rendered)));
+                    }
+                });
+                assert_eq!(spans.len(), events.len());
+                for (span, (original, rendered)) in spans.iter().zip(&events) {
+                    assert_eq!(span.original, *original);
+                    assert_eq!(&output[span.output.clone()], rendered.as_str());
+                    match span.original {
+                        Original::Char(ch) => {
+                            assert_eq!(text[span.input.clone()].chars().next(), Some(ch))
+                        }
+                        _ => panic!("{:?}", span),
+                    }
+                }
+            }
+        }
+
+        let (_, spans) = collect(Quoted::unix_raw(b"a\xFFb\n"));
+        assert_eq!(spans[0].input, 1..2);
+        assert_eq!(spans[1].input, 3..4);
+
+        let units = [0xD83D, 0xDE00, 0xD800, 0x0A];
+        let (output, spans) = collect(Quoted::windows_raw(&units));
+        assert_eq!(output, "\"\u{1F600}`u{D800}`n\"");
+        assert_eq!(spans[0].input, 2..3);
+        assert_eq!(spans[1].input, 3..4);
+    }
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn classify() {
+        fn reasons(quoted: Quoted<'_>) -> std::vec::Vec<Reason> {
+            quoted.classify().reasons().collect()
+        }
+
+        let plain = Quoted::unix("foo").force(false).classify();
+        assert!(plain.is_plain());
+        assert_eq!(plain.reasons().count(), 0);
+        assert!(Quoted::windows("foo").force(false).classify().is_plain());
+
+        assert_eq!(reasons(Quoted::unix("")), [Reason::Empty]);
+        assert_eq!(reasons(Quoted::unix("foo")), [Reason::Forced]);
+        assert_eq!(reasons(Quoted::windows("foo")), [Reason::Forced]);
+        assert_eq!(reasons(Quoted::unix("a b")), [Reason::Whitespace]);
+        assert_eq!(reasons(Quoted::unix("$x")), [Reason::ShellSpecial]);
+        assert_eq!(reasons(Quoted::unix("it's")), [Reason::ShellSpecial]);
+        assert_eq!(
+            reasons(Quoted::windows("-foo").force(false)),
+            [Reason::LeadingDash]
+        );
+        assert_eq!(reasons(Quoted::unix("-foo").force(false)), []);
+        assert_eq!(
+            reasons(Quoted::unix("\u{301}a").force(false)),
+            [Reason::ZeroWidth]
+        );
+        assert_eq!(
+            reasons(Quoted::unix("a\u{200B}").escape_invisible(true)),
+            [Reason::ZeroWidth]
+        );
+        assert_eq!(reasons(Quoted::unix("a\tb")), [Reason::Control]);
+        assert_eq!(reasons(Quoted::unix("a\u{2028}")), [Reason::Control]);
+        assert_eq!(reasons(Quoted::unix("a\u{202E}b")), [Reason::Bidi]);
+        assert_eq!(
+            reasons(Quoted::unix_raw(b"a\xFF")),
+            [Reason::InvalidUnicode]
+        );
+        assert_eq!(
+            reasons(Quoted::windows_raw(&[0xD800])),
+            [Reason::InvalidUnicode]
+        );
+        assert_eq!(
+            reasons(Quoted::unix("a b\n$")),
+            [Reason::Whitespace, Reason::ShellSpecial, Reason::Control]
+        );
+
+        let escaped = Quoted::unix("\n").classify();
+        assert!(escaped.is_quoted() && escaped.is_escaped());
+        assert!(escaped.contains(Reason::Control));
+        assert!(!escaped.contains(Reason::Empty));
+    }
+    #[cfg(feature = "unix")]
+    #[test]
+    fn dangerous_chars() {
+        use crate::security::dangerous_chars;
+
+        let texts: &[&[u8]] = &[
+            b"",
+            b"foo bar $x",
+            b"-",
+            b"a-b",
+            b"\x1B[31m\xFF\xC3",
+            "\u{202E}\u{2066}\u{200B}\u{FEFF}\u{2028}".as_bytes(),
+            "caf\u{E9}\t\u{1F600}\n".as_bytes(),
+        ];
+        for &text in texts {
+            let found: std::vec::Vec<_> = dangerous_chars(text).collect();
+            // Everything except a leading dash gets escaped.
+            let mut spans = std::vec::Vec::new();
+            Quoted::unix_raw(text)
+                .escape_invisible(true)
+                .escaped_spans(|span| spans.push(span));
+            let escaped: std::vec::Vec<_> = found
+                .iter()
+                .filter(|&&(_, _, reason)| reason != Reason::LeadingDash)
+                .collect();
+            assert_eq!(escaped.len(), spans.len(), "{:?}", text);
+            for (&&(offset, ch, reason), span) in escaped.iter().zip(&spans) {
+                assert_eq!(offset, span.input.start);
+                match span.original {
+                    Original::Char(orig) => assert_eq!(orig, ch),
+                    Original::Byte(_) => assert_eq!(reason, Reason::InvalidUnicode),
+                    Original::Unit(_) => unreachable!(),
+                }
+            }
+        }
+
+        let found: std::vec::Vec<_> = dangerous_chars("-\u{2066}x\u{200B}").collect();
+        assert_eq!(
+            found,
+            [
+                (0, '-', Reason::LeadingDash),
+                (1, '\u{2066}', Reason::Bidi),
+                (5, '\u{200B}', Reason::ZeroWidth),
+            ]
+        );
+        let found: std::vec::Vec<_> = dangerous_chars(b"\xFF\xC3a\xC3").collect();
+        assert_eq!(
+            found,
+            [
+                (0, '\u{FFFD}', Reason::InvalidUnicode),
+                (1, '\u{FFFD}', Reason::InvalidUnicode),
+                (3, '\u{FFFD}', Reason::InvalidUnicode),
+            ]
+        );
+    }
+    #[cfg(all(feature = "native", feature = "std", unix))]
+    #[test]
+    fn filename_safety() {
+        use std::os::unix::ffi::OsStrExt;
+
+        // The text, and whether it's safe for a terminal
+        let cases: &[(&[u8], bool)] = &[
+            (b"", true),
+            (b"foo", true),
+            (b"-rf", true),
+            (b"foo bar", true),
+            (b"$HOME", true),
+            (b"it's", true),
+            ("caf\u{E9}".as_bytes(), true),
+            ("\u{301}".as_bytes(), true),
+            (b"a\tb", false),
+            (b"\x1B[2J", false),
+            (b"\xFF", false),
+            ("\u{202E}txt.exe".as_bytes(), false),
+            ("\u{2067}abc\u{2069}".as_bytes(), false),
+            ("a\u{200B}b".as_bytes(), false),
+            ("a\u{2028}b".as_bytes(), false),
+        ];
+        for &(text, terminal_safe) in cases {
+            let os_str = OsStr::from_bytes(text);
+            let output = Quoted::native_raw(os_str).force(false).to_string();
+            assert_eq!(
+                is_shell_safe(os_str),
+                output.as_bytes() == text,
+                "{:?}",
+                text
+            );
+            assert_eq!(is_terminal_safe(os_str), terminal_safe, "{:?}", text);
+        }
+    }
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn control_style() {
+        let caret = |quoted: Quoted<'_>| quoted.control_style(ControlStyle::Caret).to_string();
+        let picture = |quoted: Quoted<'_>| quoted.control_style(ControlStyle::Picture).to_string();
+
+        assert_eq!(caret(Quoted::unix("\0\x1B\x1F\x7F")), "$'^@^[^_^?'");
+        assert_eq!(
+            picture(Quoted::unix("\0\x1B\x1F\x7F")),
+            "$'\u{2400}\u{241B}\u{241F}\u{2421}'"
+        );
+        assert_eq!(caret(Quoted::windows("a\tb")), "\"a^Ib\"");
+        assert_eq!(picture(Quoted::windows("a\tb")), "\"a\u{2409}b\"");
+
+        // Everything else is left alone
+        assert_eq!(caret(Quoted::unix("foo bar")), "'foo bar'");
+        assert_eq!(
+            caret(Quoted::unix("\u{85}\u{2028}")),
+            Quoted::unix("\u{85}\u{2028}").to_string()
+        );
+        assert_eq!(caret(Quoted::unix_raw(b"\xFF\r")), "$'\\xFF^M'");
+        assert_eq!(
+            caret(Quoted::unix("a\u{202E}")),
+            Quoted::unix("a\u{202E}").to_string()
+        );
+
+        for ch in (0..0x20).chain(Some(0x7F)) {
+            let text = [ch];
+            let output = caret(Quoted::unix_raw(&text).force(false));
+            assert_eq!(output.len(), 5, "{:?}", output);
+            let output = picture(Quoted::unix_raw(&text).force(false));
+            assert_eq!(output.chars().count(), 4, "{:?}", output);
+        }
+
+        let mut events = std::vec::Vec::new();
+        Quoted::unix("\r")
+            .control_style(ControlStyle::Caret)
+            .events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(
+            events,
+            [
+                r#"OpenQuote("$'")"#,
+                r#"Escape { original: Char('\r'), rendered: "^M" }"#,
+                r#"CloseQuote("'")"#,
+            ]
+        );
+    }
+    #[cfg(feature = "sanitize")]
+    #[test]
+    fn sanitize() {
+        let sanitize = |text| Quoted::sanitize(text).to_string();
+        assert_eq!(sanitize(""), "");
+        assert_eq!(sanitize("it's a \"test\" $x"), "it's a \"test\" $x");
+        assert_eq!(
+            sanitize("\u{9B}31m\u{9D}0;x\u{9C}"),
+            r"\u{9B}31m\u{9D}0;x\u{9C}"
+        );
+        assert_eq!(sanitize("a\u{2028}b"), r"a\u{2028}b");
+        assert_eq!(sanitize("a\u{200B}b"), "a\u{200B}b");
+        assert_eq!(
+            Quoted::sanitize("a\u{200B}b")
+                .escape_invisible(true)
+                .to_string(),
+            r"a\u{200B}b"
+        );
+        assert_eq!(
+            Quoted::sanitize("a\u{202E}b")
+                .bidi_policy(BidiPolicy::Isolate)
+                .to_string(),
+            "\u{2068}a\u{202E}b\u{2069}"
+        );
+        assert_eq!(
+            Quoted::sanitize_raw(b"\xFF\x1B\xE2\x80\xAE").to_string(),
+            r"\xFF\x1B\u{202E}"
+        );
+
+        // Whatever the input, nothing dangerous is left.
+        for byte in 0..=0xFF {
+            for &text in &[[byte, b'x'], [0xC2, byte]] {
+                let output = Quoted::sanitize_raw(&text).to_string();
+                assert!(!output.chars().any(requires_escape), "{:?}", output);
+                assert!(!output.chars().any(security::is_bidi), "{:?}", output);
+            }
+        }
+    }
+    #[cfg(feature = "sanitize")]
+    #[test]
+    fn strip_ansi() {
+        use core::fmt::Write;
+
+        let cases: &[(&[u8], &str)] = &[
+            (b"plain", "plain"),
+            (b"\x1B[1;31mred\x1B[m", "red"),
+            (b"\x1B[?25l", ""),
+            (b"\x1B(Bx", "x"),
+            (b"\x1B7\x1B8", ""),
+            (b"\x1B]0;title\x07x", "x"),
+            (b"\x1B]8;;http://a/\x1B\\link\x1B]8;;\x1B\\", "link"),
+            (b"\x1BPq#0;2;0;0;0\x1B\\x", "x"),
+            (b"\x1B_apc\x1B\\\x1B^pm\x1B\\\x1BXsos\x1B\\x", "x"),
+            ("\u{9B}31mx\u{9D}0;t\u{9C}y".as_bytes(), "xy"),
+            (b"\x9B31mx\x9D0;t\x9Cy", "xy"),
+            (b"\x9B\xFF", r"\xFF"),
+            // An interrupted sequence leaves the rest alone
+            ("\x1B[31\u{E9}".as_bytes(), "\u{E9}"),
+            (b"\x1B[31\n", r"\n"),
+            (b"\x1B]0;title\nnext", r"\nnext"),
+            (b"\x1B]0;a\x1B[31mx", "x"),
+            (b"\x1B\x1B[mx", "x"),
+            (b"x\x1B", "x"),
+            (b"a\x07b\x9Cc", r"a\x07b\x9Cc"),
+        ];
+        for &(text, expected) in cases {
+            let quoted = Quoted::sanitize_raw(text).strip_ansi(true);
+            assert_eq!(quoted.to_string(), expected, "{:?}", text);
+            let mut len = 0;
+            quoted.escaped_spans(|span| len = span.input.end.max(len));
+            assert!(len <= text.len());
+
+            if let Ok(text) = core::str::from_utf8(text) {
+                // Some things are only escaped by Quoted::sanitize()
+                let mut whole = AnsiFilter::new(std::string::String::new());
+                whole.write_str(text).unwrap();
+                let whole = whole.into_inner();
+                assert_eq!(Quoted::sanitize(&whole).to_string(), expected, "{:?}", text);
+
+                let mut split = AnsiFilter::new(std::string::String::new());
+                for ch in text.chars() {
+                    split.write_char(ch).unwrap();
+                }
+                assert_eq!(split.into_inner(), whole);
+            }
+        }
+
+        let mut events = std::vec::Vec::new();
+        Quoted::sanitize("\x1B[mx")
+            .strip_ansi(true)
+            .events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(
+            events,
+            [
+                r#"Escape { original: Char('\u{1b}'), rendered: "" }"#,
+                r#"Escape { original: Char('['), rendered: "" }"#,
+                r#"Escape { original: Char('m'), rendered: "" }"#,
+                r#"Literal("x")"#,
+            ]
+        );
+    }
+    #[cfg(all(feature = "sanitize", feature = "std"))]
+    #[test]
+    fn sanitizing_writer() {
+        use core::fmt::Write as _;
+        use std::io::Write as _;
+
+        let text = "caf\u{E9}\tok\n\x1B[31mred\r\u{202E}\u{2067}x\u{2069}\u{1F600}\n\u{9B}2J";
+        let escaped =
+            "caf\u{E9}\tok\n\\x1B[31mred\\r\\u{202E}\\u{2067}x\\u{2069}\u{1F600}\n\\u{9B}2J";
+        let stripped = "caf\u{E9}\tok\nred\\r\\u{202E}\\u{2067}x\\u{2069}\u{1F600}\n";
+
+        for &(strip, expected) in &[(false, escaped), (true, stripped)] {
+            let mut out = SanitizingWriter::new(std::string::String::new()).strip_ansi(strip);
+            for ch in text.chars() {
+                out.write_char(ch).unwrap();
+            }
+            assert_eq!(out.into_inner(), expected);
+
+            // Every way of splitting the bytes in two
+            for split in 0..=text.len() {
+                let mut out = SanitizingIoWriter::new(std::vec::Vec::new()).strip_ansi(strip);
+                out.write_all(&text.as_bytes()[..split]).unwrap();
+                out.write_all(&text.as_bytes()[split..]).unwrap();
+                assert_eq!(out.finish().unwrap(), expected.as_bytes(), "{}", split);
+            }
+        }
+
+        let mut out = SanitizingIoWriter::new(std::vec::Vec::new());
+        out.write_all(b"\xFF\xF0\x9F").unwrap();
+        assert_eq!(out.get_ref(), b"\\xFF");
+        out.write_all(b"\x98\x80\xE2").unwrap();
+        assert_eq!(out.get_ref(), "\\xFF\u{1F600}".as_bytes());
+        assert_eq!(out.finish().unwrap(), "\\xFF\u{1F600}\\xE2".as_bytes());
+
+        let mut out = SanitizingIoWriter::new(std::vec::Vec::new()).strip_ansi(true);
+        out.write_all(b"\x9B31mx\x9D0;t\x9Cy").unwrap();
+        assert_eq!(out.finish().unwrap(), b"xy");
+
+        // A cut-off sequence is only completed by continuation bytes.
+        let text: &[u8] = b"a\xE2\x80\xC3\xA9\xF0\x9F\x98b\xE0\x80\xE2\x82\xAC";
+        let mut whole = SanitizingIoWriter::new(std::vec::Vec::new());
+        whole.write_all(text).unwrap();
+        let whole = whole.finish().unwrap();
+        assert_eq!(
+            whole,
+            "a\\xE2\\x80\u{E9}\\xF0\\x9F\\x98b\\xE0\\x80\u{20AC}".as_bytes()
+        );
+        for size in 1..5 {
+            let mut out = SanitizingIoWriter::new(std::vec::Vec::new());
+            for chunk in text.chunks(size) {
+                out.write_all(chunk).unwrap();
+            }
+            assert_eq!(out.finish().unwrap(), whole, "{}", size);
+        }
+    }
+
+    #[cfg(all(feature = "tracing", feature = "unix", feature = "alloc"))]
+    #[test]
+    fn tracing_field() {
+        let quoted = Quoted::unix("foo bar");
+        assert_eq!(crate::alloc::format!("{:?}", quoted.field()), "'foo bar'");
+        let prepared = Quoted::unix("a\nb").prepared();
+        assert_eq!(crate::alloc::format!("{:?}", prepared.field()), "$'a\\nb'");
+    }
+
+    #[cfg(all(feature = "native", unix))]
+    #[test]
+    fn quoted_path_buf() {
+        use std::error::Error;
+        use std::path::PathBuf;
+
+        let path = QuotedPathBuf::new("foo/bar");
+        assert_eq!(path.to_string(), "foo/bar");
+        assert_eq!(path.clone().force(true).to_string(), "'foo/bar'");
+        assert_eq!(QuotedPathBuf::from("a b").to_string(), "'a b'");
+        assert_eq!(PathBuf::from(path.clone()), PathBuf::from("foo/bar"));
+
+        let inner = std::io::Error::new(std::io::ErrorKind::Other, "oops");
+        let err = QuotedPathBuf::from("x\ny").with_error(inner);
+        assert_eq!(err.to_string(), "$'x\\ny': oops");
+        assert!(err.source().is_none());
+
+        let err = QuotedPathBuf::new("outer").with_error(err);
+        assert_eq!(err.to_string(), "outer: $'x\\ny': oops");
+        assert!(err.source().is_none());
+    }
+
+    #[cfg(all(feature = "native", unix))]
+    #[test]
+    fn error_context() {
+        use std::error::Error;
+        use std::io;
+        use std::path::Path;
+
+        let inner = io::Error::new(io::ErrorKind::NotFound, "not found");
+        let err = crate::error::context(inner, Path::new("a b/c"));
+        assert_eq!(err.to_string(), "'a b/c': not found");
+        assert_eq!(err.error().kind(), io::ErrorKind::NotFound);
+        assert_eq!(err.path().as_path(), Path::new("a b/c"));
+        assert!(err.source().is_none());
+
+        let inner = io::Error::new(io::ErrorKind::Other, "oops");
+        let err = crate::error::context(inner, Path::new("plain"));
+        assert_eq!(err.to_string(), "plain: oops");
+        let boxed: std::boxed::Box<dyn Error + Send + Sync> = err.into();
+        assert_eq!(boxed.to_string(), "plain: oops");
+    }
+
+    #[cfg(all(feature = "clap", feature = "native", unix))]
+    #[test]
+    fn clap_value_parser() {
+        use ::clap::builder::{PathBufValueParser, PossibleValuesParser, StringValueParser};
+        use ::clap::error::ErrorKind;
+        use ::clap::{value_parser, Arg, Command};
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        use crate::clap::QuotedValueParser;
+
+        let cmd = Command::new("prog")
+            .arg(
+                Arg::new("num")
+                    .long("num")
+                    .value_parser(QuotedValueParser::new(value_parser!(u8))),
+            )
+            .arg(
+                Arg::new("name")
+                    .long("name")
+                    .value_parser(QuotedValueParser::new(StringValueParser::new())),
+            )
+            .arg(
+                Arg::new("path")
+                    .long("path")
+                    .value_parser(QuotedValueParser::new(PathBufValueParser::new())),
+            )
+            .arg(
+                Arg::new("color")
+                    .long("color")
+                    .value_parser(QuotedValueParser::new(PossibleValuesParser::new([
+                        "auto", "never",
+                    ]))),
+            );
+
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["prog", "--num", "7", "--path", "a b"])
+            .unwrap();
+        assert_eq!(matches.get_one::<u8>("num"), Some(&7));
+
+        let err = cmd
+            .clone()
+            .try_get_matches_from(["prog", "--num", "x\ny"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err
+            .to_string()
+            .starts_with("error: invalid value $'x\\ny' for '--num <num>': invalid digit"));
+
+        let args = [
+            OsStr::new("prog"),
+            OsStr::new("--name"),
+            OsStr::from_bytes(b"\xFF"),
+        ];
+        let err = cmd.clone().try_get_matches_from(args).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("error: invalid value $'\\xFF' for '--name <name>': invalid UTF-8\n"));
+
+        let err = cmd
+            .clone()
+            .try_get_matches_from(["prog", "--color", "always"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[cfg(all(feature = "serde", feature = "native", unix))]
+    #[test]
+    fn serde_quoted() {
+        use serde::Serialize;
+        use std::path::Path;
+
+        /// serde can serialize strings into a Formatter.
+        struct Ser<T>(T);
+
+        impl<T: Serialize> Display for Ser<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                self.0.serialize(f)
+            }
+        }
+
+        struct Field<'a>(&'a Path);
+
+        impl<'a> Display for Field<'a> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                crate::serde_quoted::serialize(self.0, f)
+            }
+        }
+
+        assert_eq!(Ser(Quoted::unix("a b")).to_string(), "'a b'");
+        assert_eq!(Ser(Quoted::unix("a\nb").prepared()).to_string(), "$'a\\nb'");
+        assert_eq!(Field(Path::new("foo")).to_string(), "'foo'");
+        assert_eq!(Field(Path::new("it's")).to_string(), "\"it's\"");
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_quoted() {
+        use ::arbitrary::{Arbitrary, Unstructured};
+        use std::collections::HashSet;
+        use std::fmt::Write;
+
+        let mut styles = HashSet::new();
+        let mut state = 0x2545_F491_4F6C_DD1D_u64;
+        for _ in 0..2000 {
+            let data: std::vec::Vec<u8> = (0..64)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect();
+            let quoted = Quoted::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+            styles.insert(
+                std::format!("{:?}", quoted.source)
+                    .split('(')
+                    .next()
+                    .unwrap()
+                    .to_string(),
+            );
+            // Some options refuse to write some text, but nothing panics.
+            let mut out = String::new();
+            if write!(out, "{}", quoted).is_ok() {
+                assert_eq!(quoted.display_len(), out.len());
+            }
+        }
+        assert!(styles.len() > 10, "{:?}", styles);
+
+        let style = Style::arbitrary(&mut Unstructured::new(&[0; 8])).unwrap();
+        assert_ne!(style, Style::__NonExhaustive);
+    }
+
+    #[cfg(all(feature = "proptest", feature = "native"))]
+    #[test]
+    fn hostile_strategies() {
+        use crate::testing::strategies;
+        use proptest::strategy::{Strategy, ValueTree};
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::deterministic();
+        runner
+            .run(&strategies::hostile_os_string(), |name| {
+                let quoted = Quoted::native_raw(&name).to_string();
+                assert!(!quoted.chars().any(char::is_control), "{:?}", quoted);
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&strategies::leading_dash(), |name| {
+                assert!(name.starts_with('-'));
+                Ok(())
+            })
+            .unwrap();
+
+        let mut invalid = false;
+        for _ in 0..100 {
+            let bytes = strategies::invalid_utf8()
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            invalid |= std::str::from_utf8(&bytes).is_err();
+        }
+        assert!(invalid);
+    }
+
+    #[cfg(all(feature = "capi", feature = "unix", feature = "windows"))]
+    #[test]
+    #[cfg_attr(feature = "capi", allow(unsafe_code))]
+    fn capi() {
+        use crate::capi::*;
+        use core::ffi::c_char;
+        use core::ptr;
+
+        fn call(
+            f: unsafe extern "C" fn(*const u8, usize, *mut c_char, usize) -> usize,
+            input: &[u8],
+            buf: &mut [u8],
+        ) -> usize {
+            unsafe {
+                f(
+                    input.as_ptr(),
+                    input.len(),
+                    buf.as_mut_ptr().cast(),
+                    buf.len(),
+                )
+            }
+        }
+
+        let mut buf = [0xAA; 16];
+        assert_eq!(call(os_display_quote_unix, b"foo bar", &mut buf), 9);
+        assert_eq!(&buf[..10], b"'foo bar'\0");
+        assert_eq!(call(os_display_maybe_quote_unix, b"foo", &mut buf), 3);
+        assert_eq!(&buf[..4], b"foo\0");
+        assert_eq!(call(os_display_quote_unix, b"\xFF", &mut buf), 7);
+        assert_eq!(&buf[..8], b"$'\\xFF'\0");
+
+        // Too small, the output needs a NUL too.
+        buf = [0xAA; 16];
+        assert_eq!(call(os_display_quote_unix, b"foo bar", &mut buf[..9]), 9);
+        assert_eq!(buf[0], 0);
+        let len = unsafe { os_display_quote_unix(ptr::null(), 0, ptr::null_mut(), 0) };
+        assert_eq!(len, 2);
+
+        assert_eq!(call(os_display_quote_windows, b"a\nb", &mut buf), 6);
+        assert_eq!(&buf[..7], b"\"a`nb\"\0");
+        assert_eq!(call(os_display_maybe_quote_windows, b"ab", &mut buf), 2);
+        assert_eq!(
+            call(os_display_quote_windows, b"\xFF", &mut buf),
+            OS_DISPLAY_ERROR
+        );
+        assert_eq!(buf[0], 0);
+
+        let units = [u16::from(b'a'), 0xD800];
+        let len = unsafe {
+            os_display_quote_windows_utf16(
+                units.as_ptr(),
+                units.len(),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+            )
+        };
+        assert_eq!(&buf[..len], b"\"a`u{D800}\"");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn safe_fast_path() {
+        let texts = [
+            "foo",
+            "Cargo.toml",
+            "src/lib.rs",
+            "-",
+            "--",
+            "-foo",
+            "--foo",
+            "-1",
+            "1",
+            "1d",
+            ".",
+            "..",
+            ".1",
+            ".foo",
+            "_",
+            "/",
+            "/usr/bin",
+            "a-b_c.d",
+            "-.:",
+        ];
+        for &text in &texts {
+            let variants = [
+                Quoted::unix(text),
+                Quoted::unix_raw(text.as_bytes()),
+                Quoted::windows(text),
+                Quoted::windows(text).external(true),
+                Quoted::windows_wtf8(text.as_bytes()),
+            ];
+            for quoted in variants.iter() {
+                for &force in &[false, true] {
+                    let quoted = quoted.force(force);
+                    // A precision takes the slow path.
+                    let slow = std::format!("{:.1000}", quoted);
+                    assert_eq!(quoted.to_string(), slow, "{:?}", text);
+                    let paranoid = quoted.quoting(Quoting::Paranoid);
+                    assert_eq!(
+                        paranoid.to_string(),
+                        std::format!("{:.1000}", paranoid),
+                        "{:?}",
+                        text
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            Quoted::unix("foo/bar.txt").force(false).to_string(),
+            "foo/bar.txt"
+        );
+        assert_eq!(Quoted::windows("1").force(false).to_string(), "'1'");
+        assert_eq!(Quoted::windows("-foo").force(false).to_string(), "'-foo'");
+        assert_eq!(
+            Quoted::unix("foo")
+                .force(false)
+                .extra_unsafe_chars("o")
+                .to_string(),
+            "'foo'"
+        );
+    }
+
+    /// Short text is rendered on the stack first, and again straight into
+    /// the output if it doesn't fit.
+    #[cfg(all(feature = "std", feature = "unix", feature = "windows"))]
+    #[test]
+    fn short_buffer() {
+        use std::string::String;
+
+        let from_events = |quoted: Quoted<'_>| {
+            let mut output = String::new();
+            quoted.events(|event| {
+                output.push_str(match event {
+                    QuoteEvent::OpenQuote(text)
+                    | QuoteEvent::Literal(text)
+                    | QuoteEvent::CloseQuote(text) => text,
+                    QuoteEvent::Escape { rendered, .. } => rendered,
+                })
+            });
+            output
+        };
+
+        for &count in &[0, 1, 10, 42, 127, 128, 200] {
+            for &piece in &["a", "a b", "\x01", "\u{FEFF}", "'\""] {
+                let text = piece.repeat(count);
+                for &quoted in &[
+                    Quoted::unix(&text),
+                    Quoted::windows(&text),
+                    Quoted::windows(&text).highlight(Highlight::default()),
+                ] {
+                    let output = quoted.to_string();
+                    if quoted.opts.highlight.is_none() {
+                        assert_eq!(output, from_events(quoted));
+                    }
+                    assert_eq!(std::format!("{:.10000}", quoted), output);
+                }
+            }
+        }
+
+        // Escapes make this too long for the buffer.
+        let text = "\x01".repeat(127);
+        let output = Quoted::windows(&text).to_string();
+        assert!(output.len() > 512);
+        assert_eq!(output, from_events(Quoted::windows(&text)));
+
+        let quoted = Quoted::unix("a\0b").on_unrepresentable(OnUnrepresentable::Error);
+        let mut output = String::new();
+        assert!(fmt::Write::write_fmt(&mut output, format_args!("{}", quoted)).is_err());
+        assert_eq!(output, "");
+    }
+
+    /// The scan remembers where the first few quotes are, and the writers
+    /// have to search for any after that themselves.
+    #[cfg(all(feature = "std", feature = "unix", feature = "windows"))]
+    #[test]
+    fn many_quotes() {
+        use crate::posix::parse_word;
+        use crate::powershell::parse_argument;
+
+        for count in 0..20 {
+            for &piece in &["'", "a'", "'b ", "\u{2018}'\"", "\"\u{2019}x"] {
+                let text = piece.repeat(count) + "end";
+                let unix = Quoted::unix(&text).to_string();
+                assert_eq!(parse_word(&unix), Ok(text.clone().into_bytes()), "{}", unix);
+                let windows = Quoted::windows(&text).to_string();
+                assert_eq!(
+                    parse_argument(&windows),
+                    Ok(text.encode_utf16().collect()),
+                    "{}",
+                    windows
+                );
+            }
+        }
+
+        let text = "\"'".repeat(10);
+        assert_eq!(
+            Quoted::windows(&text).external(true).to_string(),
+            std::format!("'{}'", "\\\"''".repeat(10))
+        );
+        assert_eq!(
+            Quoted::unix(&"'$".repeat(10)).to_string(),
+            "\\''$'".repeat(10)
+        );
+    }
+
+    #[cfg(all(feature = "bench-corpus", feature = "unix", feature = "windows"))]
+    #[test]
+    fn bench_corpus() {
+        use crate::bench_corpus::*;
+        use crate::posix::parse_word;
+        use crate::powershell::parse_argument;
+
+        for &len in &[0, 1, 7, 100, 1000] {
+            for text in &[long_paths(len), escape_heavy(len), external_args(len)] {
+                assert!(text.len() <= len && text.len() + 4 > len);
+                let unix = Quoted::unix(text).to_string();
+                assert_eq!(parse_word(&unix), Ok(text.clone().into_bytes()), "{}", unix);
+                let windows = Quoted::windows(text).to_string();
+                assert_eq!(
+                    parse_argument(&windows),
+                    Ok(text.encode_utf16().collect()),
+                    "{}",
+                    windows
+                );
+            }
+            let bytes = invalid_utf8(len);
+            assert_eq!(bytes.len(), len);
+            let unix = Quoted::unix_raw(&bytes).to_string();
+            assert_eq!(parse_word(&unix), Ok(bytes), "{}", unix);
+            let units = utf16_surrogates(len);
+            assert_eq!(units.len(), len);
+            let windows = Quoted::windows_raw(&units).to_string();
+            assert_eq!(parse_argument(&windows), Ok(units), "{}", windows);
+        }
+        assert_eq!(escape_heavy(100), escape_heavy(100));
+        assert!(core::str::from_utf8(&invalid_utf8(100)).is_err());
+    }
+
+    #[cfg(all(feature = "sanitize", feature = "std"))]
+    #[test]
+    fn hex_c1() {
+        use crate::{ControlStyle, SanitizingIoWriter, SanitizingWriter};
+        use core::fmt::Write as _;
+        use std::io::Write as _;
+
+        for code in 0x80..0xA0 {
+            let ch = core::char::from_u32(code).unwrap();
+            let text = std::format!("a{}b", ch);
+            let hex = std::format!(r"a\x{:02X}b", code);
+            assert_eq!(
+                Quoted::sanitize(&text).to_string(),
+                std::format!(r"a\u{{{:X}}}b", code)
+            );
+            assert_eq!(Quoted::sanitize(&text).hex_c1(true).to_string(), hex);
+            assert_eq!(
+                Quoted::sanitize_raw(&[b'a', code as u8, b'b']).to_string(),
+                hex
+            );
+            assert_eq!(
+                Quoted::sanitize(&text)
+                    .hex_c1(true)
+                    .control_style(ControlStyle::Caret)
+                    .to_string(),
+                hex
+            );
+
+            let mut out = SanitizingWriter::new(std::string::String::new())
+                .hex_c1(true)
+                .strip_ansi(false);
+            out.write_str(&text).unwrap();
+            assert_eq!(out.into_inner(), hex);
+            let mut out = SanitizingIoWriter::new(std::vec::Vec::new()).hex_c1(true);
+            out.write_all(text.as_bytes()).unwrap();
+            assert_eq!(out.finish().unwrap(), hex.as_bytes());
+        }
+
+        // Only C1 is affected
+        assert_eq!(
+            Quoted::sanitize("\u{7F}\u{A0}\u{AD}")
+                .hex_c1(true)
+                .to_string(),
+            "\\x7F\u{A0}\u{AD}"
+        );
+        assert_eq!(
+            Quoted::sanitize("\u{7F}")
+                .hex_c1(true)
+                .control_style(ControlStyle::Caret)
+                .to_string(),
+            "^?"
+        );
+        // Sequences are still removed completely
+        assert_eq!(
+            Quoted::sanitize("\u{9B}31mred\u{85}")
+                .hex_c1(true)
+                .strip_ansi(true)
+                .to_string(),
+            r"red\x85"
+        );
+    }
+
+    #[cfg(all(feature = "std", feature = "unix", feature = "windows"))]
+    #[test]
+    fn hidden_text() {
+        use crate::posix::parse_word;
+        use crate::powershell::parse_argument;
+
+        let hidden = (0xE0000..=0xE007F)
+            .chain(0xFFF9..=0xFFFB)
+            .filter_map(core::char::from_u32);
+        for ch in hidden {
+            assert!(security::is_hidden_text(ch));
+            assert!(security::is_invisible(ch));
+            let text = std::format!("a{}b", ch);
+            let unix = Quoted::unix(&text).force(false).to_string();
+            assert!(unix.starts_with("$'a\\x"), "{:?}", unix);
+            assert_eq!(parse_word(&unix), Ok(text.clone().into_bytes()));
+            let windows = Quoted::windows(&text).force(false).to_string();
+            assert!(windows.starts_with("\"a`u{"), "{:?}", windows);
+            assert_eq!(parse_argument(&windows), Ok(text.encode_utf16().collect()));
+            // Most tag characters are unassigned.
+            if !is_unknown(ch) {
+                assert_eq!(
+                    Quoted::unix(&text)
+                        .force(false)
+                        .escape_hidden_text(false)
+                        .to_string(),
+                    text
+                );
+                assert_eq!(
+                    Quoted::windows(&text)
+                        .force(false)
+                        .escape_hidden_text(false)
+                        .to_string(),
+                    text
+                );
+            }
+            assert_ne!(crate::escape::unix_char(ch).to_string(), ch.to_string());
+            #[cfg(feature = "html")]
+            assert_eq!(
+                Quoted::html(&text).to_string(),
+                std::format!("a&#x{:X};b", ch as u32)
+            );
+            #[cfg(feature = "sanitize")]
+            {
+                use core::fmt::Write as _;
+
+                let escaped = std::format!("a\\u{{{:X}}}b", ch as u32);
+                assert_eq!(Quoted::sanitize(&text).to_string(), escaped);
+                let mut out = crate::SanitizingWriter::new(std::string::String::new());
+                out.write_str(&text).unwrap();
+                assert_eq!(out.into_inner(), escaped);
+            }
+        }
+
+        // The emoji flag for Scotland
+        let flag = "\u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}";
+        assert_eq!(
+            Quoted::windows(flag).force(false).to_string(),
+            "\"\u{1F3F4}`u{E0067}`u{E0062}`u{E0073}`u{E0063}`u{E0074}`u{E007F}\""
+        );
+        assert!(Quoted::windows(flag)
+            .force(false)
+            .escape_hidden_text(false)
+            .to_string()
+            .contains(flag));
+        // Still escaped with escape_invisible
+        assert!(!Quoted::windows(flag)
+            .force(false)
+            .escape_hidden_text(false)
+            .escape_invisible(true)
+            .to_string()
+            .contains(flag));
+    }
+
+    #[cfg(all(feature = "std", feature = "unix", feature = "windows"))]
+    #[test]
+    fn max_combining() {
+        use crate::posix::parse_word;
+        use crate::powershell::parse_argument;
+
+        let marks = [
+            "\u{300}", "\u{36F}", "\u{1AB0}", "\u{1DFF}", "\u{20E3}", "\u{FE0F}",
+        ];
+        for &mark in &marks {
+            for count in 0..8 {
+                // A short run elsewhere in the text is escaped too.
+                let text = std::format!("e{}x{}", mark, mark.repeat(count));
+                let flood = count > 4;
+                assert_eq!(security::longest_combining_run(&text), count.max(1));
+                let unix = Quoted::unix(&text).force(false).to_string();
+                assert_eq!(parse_word(&unix), Ok(text.clone().into_bytes()));
+                assert_eq!(unix.contains(mark), !flood, "{:?}", unix);
+                let windows = Quoted::windows(&text).force(false).to_string();
+                assert_eq!(parse_argument(&windows), Ok(text.encode_utf16().collect()));
+                assert_eq!(windows.contains(mark), !flood, "{:?}", windows);
+                let units: std::vec::Vec<u16> = text.encode_utf16().collect();
+                assert_eq!(
+                    Quoted::windows_raw(&units).force(false).to_string(),
+                    windows
+                );
+                assert_eq!(
+                    Quoted::unix(&text)
+                        .context(QuoteContext::InsideDoubleQuotes)
+                        .to_string()
+                        .contains(mark),
+                    !flood
+                );
+                assert_eq!(
+                    Quoted::unix(&text)
+                        .fallback(Fallback::PrintfCommand)
+                        .to_string()
+                        .contains(mark),
+                    !flood
+                );
+                #[cfg(feature = "html")]
+                assert_eq!(Quoted::html(&text).to_string().contains(mark), !flood);
+                #[cfg(feature = "sanitize")]
+                assert_eq!(Quoted::sanitize(&text).to_string().contains(mark), !flood);
+
+                let unlimited = Quoted::unix(&text).force(false).max_combining(None);
+                assert!(unlimited.to_string().contains(mark));
+                let strict = Quoted::unix(&text).force(false).max_combining(Some(0));
+                assert!(!strict.to_string().contains(mark));
+            }
+        }
+
+        // Invalid unicode breaks up a run.
+        let mut bytes = "a".as_bytes().to_vec();
+        bytes.extend_from_slice("\u{301}".repeat(3).as_bytes());
+        bytes.push(0xFF);
+        bytes.extend_from_slice("\u{301}".repeat(3).as_bytes());
+        let unix = Quoted::unix_raw(&bytes).to_string();
+        assert!(unix.contains("\u{301}\u{301}\u{301}"), "{:?}", unix);
+        assert_eq!(parse_word(&unix), Ok(bytes));
+
+        let zalgo = "Z\u{351}\u{36B}\u{343}\u{36A}\u{302}";
+        assert_eq!(
+            Quoted::unix(zalgo)
+                .classify()
+                .reasons()
+                .collect::<std::vec::Vec<_>>(),
+            [Reason::Combining]
+        );
+        // Keycaps and emoji presentation are a mark or two.
+        assert_eq!(
+            Quoted::unix("1\u{FE0F}\u{20E3}\u{2764}\u{FE0F}")
+                .force(false)
+                .to_string(),
+            "1\u{FE0F}\u{20E3}\u{2764}\u{FE0F}"
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix"))]
+    #[test]
+    fn max_width() {
+        let quoted = Quoted::unix("foo bar").max_width(6);
+        assert_eq!(quoted.to_string(), "'foo '");
+        assert_eq!(quoted.display_width(), 6);
+        assert_eq!(quoted.display_len(), 6);
+        assert_eq!(std::format!("{:.4}", quoted), "'fo'");
+        assert_eq!(std::format!("{:.10}", quoted), "'foo '");
+        assert_eq!(std::format!("[{:>8}]", quoted), "[  'foo ']");
+        assert_eq!(
+            Quoted::unix("foo").force(false).max_width(2).to_string(),
+            "fo"
+        );
+        assert_eq!(Quoted::unix("foo\nbar").max_width(7).to_string(), r"$'foo'");
+
+        let mut events = std::vec::Vec::new();
+        Quoted::unix("foo bar")
+            .max_width(4)
+            .events(|event| events.push(std::format!("{:?}", event)));
+        assert_eq!(events.len(), 3);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "unicode-width"))]
+    #[test]
+    fn truncate_clusters() {
+        let family = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b";
+        let flags = "\u{1F1F3}\u{1F1F1}\u{1F1E9}\u{1F1EA}";
+        let hangul = "\u{1100}\u{1161}\u{11A8}\u{1100}\u{1161}\u{11A8}";
+        let cut = |text, width| Quoted::unix(text).max_width(width).to_string();
+        assert_eq!(cut(hangul, 4), "'\u{1100}\u{1161}\u{11A8}'");
+        if cfg!(feature = "unicode-segmentation") {
+            assert_eq!(
+                cut(family, 5),
+                "'a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}'"
+            );
+            assert_eq!(cut(family, 4), "'a'");
+            assert_eq!(cut(flags, 5), "'\u{1F1F3}\u{1F1F1}'");
+        } else {
+            assert_eq!(cut(family, 5), "'a\u{1F468}\u{200D}'");
+            assert_eq!(cut(flags, 5), "'\u{1F1F3}\u{1F1F1}\u{1F1E9}'");
+        }
+
+        // The escaped writers pass text on piece by piece.
+        let escaped = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\n";
+        let cluster = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let long = std::format!("\n{}", cluster.repeat(20));
+        let mut quoted = std::vec::Vec::new();
+        for &text in &[escaped, &escaped[..escaped.len() - 1], &long] {
+            quoted.push(Quoted::unix(text));
+            quoted.push(Quoted::unix(text).fallback(Fallback::PrintfCommand));
+            quoted.push(Quoted::unix(text).context(QuoteContext::InsideDoubleQuotes));
+            #[cfg(feature = "windows")]
+            quoted.push(Quoted::windows(text));
+        }
+        for quoted in quoted {
+            for width in 0..60 {
+                let out = quoted.max_width(width).to_string();
+                if cfg!(feature = "unicode-segmentation") {
+                    assert_eq!(
+                        out.matches('\u{1F468}').count(),
+                        out.matches(cluster).count(),
+                        "{:?}",
+                        out
+                    );
+                }
+            }
+        }
+        assert_eq!(cut(escaped, 6), std::format!("$'a{}'", cluster));
+        assert_eq!(cut(escaped, 5), "$'a'");
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "unicode-width"))]
+    #[test]
+    fn ambiguous_width() {
+        let text = "\u{B1}\u{2500}\u{2460}";
+        let narrow = Quoted::unix(text);
+        let wide = Quoted::unix(text).ambiguous_width(Width::Wide);
+        assert_eq!(narrow.display_width(), 5);
+        assert_eq!(wide.display_width(), 8);
+        assert_eq!(narrow.to_string_and_width().width(), 5);
+        assert_eq!(wide.to_string_and_width().width(), 8);
+        assert_eq!(std::format!("[{:>9}]", wide), std::format!("[ '{}']", text));
+        assert_eq!(std::format!("{:.5}", narrow), std::format!("'{}'", text));
+        assert_eq!(std::format!("{:.5}", wide), "'\u{B1}'");
+        assert_eq!(wide.max_width(6).to_string(), "'\u{B1}\u{2500}'");
+        // Wide characters and plain ASCII don't change.
+        let cjk = Quoted::unix("\u{65E5}a").ambiguous_width(Width::Wide);
+        assert_eq!(cjk.display_width(), 5);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "windows"))]
+    #[test]
+    fn strict_unassigned() {
+        for &ch in &['\u{378}', '\u{FFFF}', '\u{E000}', '\u{10FFFD}'] {
+            let text = std::format!("a{}", ch);
+            let unix = Quoted::unix(&text).force(false).to_string();
+            let windows = Quoted::windows(&text).force(false).to_string();
+            if cfg!(feature = "strict-unassigned") {
+                assert!(unix.starts_with("$'a\\x"), "{:?}", unix);
+                assert!(windows.starts_with("\"a`u{"), "{:?}", windows);
+                assert!(Quoted::unix(&text).classify().contains(Reason::Control));
+            } else {
+                assert_eq!(unix, text);
+                assert_eq!(windows, text);
+            }
+        }
+        assert_eq!(
+            Quoted::unix("a\u{1F600}").force(false).to_string(),
+            "a\u{1F600}"
+        );
+        assert_eq!(
+            Quoted::unix("\u{E9}t\u{E9}").force(false).to_string(),
+            "\u{E9}t\u{E9}"
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "unix", feature = "windows"))]
+    #[test]
+    fn shell_chars() {
+        use crate::shell_chars::{unix, windows};
+
+        for byte in b' '..=b'~' {
+            let ch = byte as char;
+            let inner = std::format!("a{}b", ch);
+            let quoted = Quoted::unix(&inner).force(false).to_string();
+            assert_eq!(
+                quoted != inner,
+                unix::SPECIAL_SHELL_CHARS.contains(&byte),
+                "{:?}",
+                ch
+            );
+            let quoted = Quoted::windows(&inner).force(false).to_string();
+            assert_eq!(
+                quoted != inner,
+                windows::SPECIAL_SHELL_CHARS.contains(&byte),
+                "{:?}",
+                ch
+            );
+
+            let leading = std::format!("{}a", ch);
+            let quoted = Quoted::unix(&leading).force(false).to_string();
+            assert_eq!(
+                quoted != leading,
+                unix::SPECIAL_SHELL_CHARS.contains(&byte)
+                    || unix::SPECIAL_SHELL_CHARS_START.contains(&ch),
+                "{:?}",
+                ch
+            );
+            if windows::SPECIAL_SHELL_CHARS_START.contains(&ch) {
+                let quoted = Quoted::windows(&leading).force(false).to_string();
+                assert_ne!(quoted, leading);
+            }
+
+            let double = Quoted::unix(&leading).prefer(QuoteChar::Double).to_string();
+            assert_eq!(
+                double.starts_with('"'),
+                !unix::DOUBLE_UNSAFE.contains(&byte) && byte != b'!',
+                "{:?}",
+                ch
+            );
+        }
+    }
+
+    #[cfg(all(feature = "pyo3", feature = "unix", feature = "windows"))]
+    #[test]
+    fn python() {
+        use pyo3::prelude::*;
+        use pyo3::types::{PyBytes, PyModule};
+
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "os_display").unwrap();
+            crate::python::register(&module).unwrap();
+            let quote = module.getattr("quote").unwrap();
+            let maybe_quote = module.getattr("maybe_quote").unwrap();
+            let call = |f: &Bound<'_, PyAny>, text: Bound<'_, PyAny>, style: &str| {
+                f.call1((text, style))
+                    .and_then(|result| result.extract::<String>())
+            };
+
+            let text = "foo\nbar".into_pyobject(py).unwrap().into_any();
+            assert_eq!(
+                call(&quote, text.clone(), "unix").unwrap(),
+                Quoted::unix("foo\nbar").to_string()
+            );
+            assert_eq!(
+                call(&quote, text, "windows").unwrap(),
+                Quoted::windows("foo\nbar").to_string()
+            );
+            let text = "foo".into_pyobject(py).unwrap().into_any();
+            assert_eq!(call(&maybe_quote, text, "unix").unwrap(), "foo");
+
+            let bytes = PyBytes::new(py, b"\xFF").into_any();
+            assert_eq!(call(&quote, bytes.clone(), "unix").unwrap(), "$'\\xFF'");
+            assert!(call(&quote, bytes, "windows").is_err());
+            let text = "foo".into_pyobject(py).unwrap().into_any();
+            assert!(call(&quote, text, "fish").is_err());
+
+            let styles: std::vec::Vec<String> =
+                module.getattr("STYLES").unwrap().extract().unwrap();
+            assert_eq!(styles, crate::python::STYLES);
+        });
+    }
+
+    #[cfg(all(feature = "defmt", feature = "alloc"))]
+    #[test]
+    fn defmt_format() {
+        // Actually logging needs a defmt global logger, which only exists on
+        // the device.
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<Quoted<'_>>();
+        assert_format::<Prepared<'_>>();
+    }
+
+    #[cfg(all(feature = "valuable", feature = "unix", feature = "alloc"))]
+    #[test]
+    fn valuable_prepared() {
+        use valuable::{Valuable, Value};
+
+        let prepared = Quoted::unix("foo bar")
+            .highlight(Highlight::new().quotes("2"))
+            .prepared();
+        match prepared.as_value() {
+            Value::String(text) => assert_eq!(text, "'foo bar'"),
+            _ => panic!("not a string"),
+        }
+    }
+
+    #[cfg(all(feature = "unix", feature = "sql", feature = "std"))]
+    #[test]
+    fn nul_separated() {
+        fn quote(list: &[u8], style: Style) -> std::vec::Vec<std::string::String> {
+            super::quote_nul_separated(list, style)
+                .unwrap()
+                .map(|entry| entry.to_string())
+                .collect()
+        }
+
+        assert_eq!(quote(b"a\0b c\0", Style::Unix), ["'a'", "'b c'"]);
+        assert_eq!(quote(b"a\0b c", Style::Unix), ["'a'", "'b c'"]);
+        assert_eq!(
+            quote(b"a\0\0\xFF\0", Style::Unix),
+            ["'a'", "''", "$'\\xFF'"]
+        );
+        assert_eq!(quote(b"\0", Style::Unix), ["''"]);
+        assert!(quote(b"", Style::Unix).is_empty());
+        assert_eq!(quote(b"it's\0", Style::Sql), ["'it''s'"]);
+        #[cfg(feature = "windows")]
+        {
+            let err = super::quote_nul_separated(b"a", Style::Powershell).unwrap_err();
+            assert_eq!(err.style(), Style::Powershell);
+            let err = std::io::Error::from(err);
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        }
+
+        let mut out = std::vec::Vec::new();
+        super::write_nul_separated(&mut out, &["a", "", "b c"]).unwrap();
+        assert_eq!(out, b"a\0\0b c\0");
+        let err = super::write_nul_separated(&mut out, &[&b"a\0b"[..]]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows", feature = "std"))]
+    #[test]
+    fn quote_stream() {
+        use std::io::BufReader;
+
+        fn stream(text: &[u8], style: Style, capacity: usize) -> std::string::String {
+            let mut out = std::vec::Vec::new();
+            let reader = BufReader::with_capacity(capacity, text);
+            super::quote_stream(reader, &mut out, style).unwrap();
+            std::string::String::from_utf8(out).unwrap()
+        }
+
+        let texts: &[&[u8]] = &[
+            b"a\x01b",
+            b"\xC3\xA9\x7F1",
+            b"\xE2\x80\xAE\xE2\x82\xACa",
+            b"x\xFF\xFE\xE2\x82\xAC\x01y",
+            b"\x1B\xE2\x82",
+            b"\xF0\x9F\x98\x80\n\xF0\x9F",
+            b"\0A\t\\'\"",
+            b"\xE2\x80\xC3\xA9\x01",
+            b"\xF0\x9F\x98a\xF0\x9F\x98\x80\n",
+            b"\xE0\x80\xE2\x82\xAC\x01",
+        ];
+        for text in texts {
+            let unix = Quoted::unix_raw(text).to_string();
+            let lossy: std::string::String = crate::utf8::chars(text)
+                .map(|ch| ch.unwrap_or(core::char::REPLACEMENT_CHARACTER))
+                .collect();
+            let windows = Quoted::windows(&lossy).to_string();
+            for capacity in 1..8 {
+                assert_eq!(stream(text, Style::Unix, capacity), unix);
+                assert_eq!(stream(text, Style::Powershell, capacity), windows);
+            }
+        }
+
+        let mut out = std::vec::Vec::new();
+        let err = super::quote_stream(&b"a"[..], &mut out, Style::WindowsArgv).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+
+        // Random mixes of lead bytes, continuation bytes and ASCII, so the
+        // reader splits them in every possible place.
+        let alphabet = [b'a', b'\n', 0x80, 0x98, 0xA9, 0xBF, 0xC3, 0xE2, 0xF0, 0xFF];
+        let mut state: u32 = 1;
+        for _ in 0..2000 {
+            let mut text = std::vec::Vec::new();
+            for _ in 0..8 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                text.push(alphabet[(state >> 16) as usize % alphabet.len()]);
+            }
+            let unix = stream(&text, Style::Unix, text.len());
+            let windows = stream(&text, Style::Powershell, text.len());
+            for capacity in 1..5 {
+                assert_eq!(stream(&text, Style::Unix, capacity), unix, "{:?}", text);
+                assert_eq!(
+                    stream(&text, Style::Powershell, capacity),
+                    windows,
+                    "{:?}",
+                    text
+                );
+            }
+        }
+
+        assert_eq!(stream(b"", Style::Unix, 8), "$''");
+        assert_eq!(stream(b"abc", Style::Unix, 1), "$'abc'");
+        assert_eq!(stream(b"a\\", Style::Powershell, 1), "\"a\\\"");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn operator() {
+        for &op in &[
+            "&&", "||", "|", "|&", ";", ";;", "&", "!", "(", ")", "{", "}", "<", ">", ">>", ">|",
+            "<>", "<<", "<<-", "<<<", "2>", "2>>", "2>&1", ">&2", "<&-", "10>&-", "&>", "&>>",
+        ] {
+            assert_eq!(Quoted::operator(Style::Unix, op).to_string(), op);
+        }
+        for &op in &["", "&&&", "a", ">a", "2>&x", "&2>", "2&>", "> ", "$(", "`"] {
+            assert!(!unix::is_operator(op), "{:?}", op);
+        }
+
+        for &op in &[
+            "&&", "||", "|", ";", "&", "(", ")", "{", "}", ">", ">>", "2>", "*>>", "2>&1", "3>&1",
+        ] {
+            assert_eq!(Quoted::operator(Style::Powershell, op).to_string(), op);
+        }
+        for &op in &["", "<", "2>&2", "7>", "**>", "|&", "--%"] {
+            assert!(!windows::is_operator(op), "{:?}", op);
+        }
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    #[should_panic(expected = "not an operator")]
+    fn operator_invalid() {
+        let _ = Quoted::operator(Style::Unix, "; rm -rf /");
+    }
+
+    #[cfg(all(feature = "unix", feature = "alloc"))]
+    #[test]
+    fn operator_remote() {
+        let op = Quoted::operator(Style::Unix, "&&");
+        assert_eq!(op.for_remote(1).to_string(), "'&&'");
+        assert_eq!(op.for_remote(2).to_string(), r#""'&&'""#);
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn extra_chars() {
+        let unix = |text| Quoted::unix(text).force(false);
+        assert_eq!(unix("a=b").extra_safe_chars("=").to_string(), "a=b");
+        assert_eq!(unix("#x").extra_safe_chars("#").to_string(), "#x");
+        assert_eq!(unix("a b").extra_safe_chars("=").to_string(), "'a b'");
+        assert_eq!(unix("a\nb").extra_safe_chars("\n").to_string(), "$'a\\nb'");
+        assert_eq!(unix("a:b").extra_unsafe_chars(":").to_string(), "'a:b'");
+        assert_eq!(
+            unix("a=b")
+                .extra_safe_chars("=")
+                .extra_unsafe_chars("=")
+                .to_string(),
+            "'a=b'"
+        );
+        assert_eq!(
+            unix("a-b.c")
+                .quoting(Quoting::Paranoid)
+                .extra_safe_chars("-")
+                .to_string(),
+            "'a-b.c'"
+        );
+
+        let windows = |text| Quoted::windows(text).force(false);
+        assert_eq!(windows("a,b").extra_safe_chars(",").to_string(), "a,b");
+        assert_eq!(windows("@x").extra_safe_chars("@").to_string(), "@x");
+        assert_eq!(windows("a%b").extra_unsafe_chars("%").to_string(), "'a%b'");
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    #[should_panic(expected = "only ASCII")]
+    fn extra_chars_ascii() {
+        let _ = Quoted::unix("x").extra_safe_chars("\u{E9}");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows", feature = "sql"))]
+    #[test]
+    fn style() {
+        let quote = |style| Quoted::new(style, "it's\n").to_string();
+        assert_eq!(quote(Style::Unix), r"$'it\'s\n'");
+        assert_eq!(quote(Style::Powershell), r#""it's`n""#);
+        assert_eq!(quote(Style::WindowsArgv), "\"it's\n\"");
+        assert_eq!(quote(Style::Sql), "'it''s' || x'0A'");
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[test]
+    fn from_shell_path() {
+        let cases = [
+            ("bash", Some(Style::Unix)),
+            ("/bin/sh", Some(Style::Unix)),
+            ("-zsh", Some(Style::Unix)),
+            ("/usr/local/bin/mksh", Some(Style::Unix)),
+            (r"C:\msys64\usr\bin\bash.exe", Some(Style::Unix)),
+            ("pwsh", Some(Style::Powershell)),
+            (
+                r"C:\WINDOWS\System32\WindowsPowerShell\v1.0\PowerShell.EXE",
+                Some(Style::Powershell),
+            ),
+            ("/opt/microsoft/powershell/7/pwsh", Some(Style::Powershell)),
+            (r"C:\WINDOWS\system32\cmd.exe", None),
+            ("/usr/bin/fish", None),
+            ("/bin/tcsh", None),
+            ("nu", None),
+            ("", None),
+            ("/", None),
+            (".exe", None),
+            ("a-very-long-shell-name", None),
+        ];
+        for &(path, style) in &cases {
+            assert_eq!(Style::from_shell_path(path), style, "{}", path);
+        }
+    }
+
+    #[cfg(feature = "reg")]
+    #[test]
+    fn reg() {
+        let reg = |text| Quoted::reg(text).to_string();
+        assert_eq!(reg(""), r#""""#);
+        assert_eq!(reg("foo bar"), r#""foo bar""#);
+        assert_eq!(reg("%PATH%;$x"), r#""%PATH%;$x""#);
+        assert_eq!(reg(r#"C:\"x""#), r#""C:\\\"x\"""#);
+        assert_eq!(reg("\u{E9}"), "\"\u{E9}\"");
+        assert_eq!(reg("\t"), "hex(1):09,00,00,00");
+        assert_eq!(reg("\\\n"), "hex(1):5c,00,0a,00,00,00");
+        assert_eq!(reg("\u{202E}"), "hex(1):2e,20,00,00");
+        assert_eq!(reg("\u{1F600}\r"), "hex(1):3d,d8,00,de,0d,00,00,00");
+
+        let raw = |units: &[u16]| Quoted::reg_raw(units).to_string();
+        assert_eq!(raw(&[0x61, 0x5C]), r#""a\\""#);
+        assert_eq!(raw(&[0xD83D, 0xDE00]), "\"\u{1F600}\"");
+        assert_eq!(raw(&[0x61, 0xD800]), "hex(1):61,00,00,d8,00,00");
+
+        assert_eq!(
+            std::format!("{:.26}", Quoted::reg("abc\n")),
+            "hex(1):61,00,62,00,00,00"
+        );
+    }
+
+    #[cfg(feature = "sudoers")]
+    #[test]
+    fn sudoers() {
+        let sudoers = |text| Quoted::sudoers(text).to_string();
+        assert_eq!(sudoers(""), r#""""#);
+        assert_eq!(sudoers("/usr/bin/systemctl"), "/usr/bin/systemctl");
+        assert_eq!(sudoers("a b\tc"), "a\\ b\\\tc");
+        assert_eq!(sudoers(r"a,b:c=d\e"), r"a\,b\:c\=d\\e");
+        assert_eq!(sudoers("*?[a]#"), r"\*\?\[a\]\#");
+        assert_eq!(sudoers("^a$"), r"\^a$");
+        assert_eq!(sudoers("a^b$"), "a^b$");
+        assert_eq!(sudoers("'\"$x!"), "'\"$x!");
+        assert_eq!(sudoers("a\nb\0"), "a\u{FFFD}b\u{FFFD}");
+        assert_eq!(std::format!("{:.2}", Quoted::sudoers("a b")), "a");
+
+        assert!(Quoted::sudoers("a\tb").try_render().is_ok());
+        assert!(Quoted::sudoers("a\nb").try_render().is_err());
+        assert!(Quoted::sudoers("").try_render().is_err());
+        assert_eq!(
+            Quoted::sudoers("a\n")
+                .on_unrepresentable(OnUnrepresentable::ReplacementChar)
+                .to_string(),
+            "a\u{FFFD}"
+        );
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn git() {
+        let ignore = |text| Quoted::gitignore(text).to_string();
+        assert_eq!(ignore("build/"), "build/");
+        assert_eq!(ignore("#a#"), r"\#a#");
+        assert_eq!(ignore("!a!"), r"\!a!");
+        assert_eq!(ignore("a b\t"), "a\\ b\\\t");
+        assert_eq!(ignore(r"*?[]\"), r"\*\?\[]\\");
+        assert_eq!(ignore("a\r\n"), "a\u{FFFD}\u{FFFD}");
+        assert_eq!(ignore(""), "");
+        assert!(Quoted::gitignore("").try_render().is_err());
+        assert!(Quoted::gitignore("a\n").try_render().is_err());
+        assert!(Quoted::gitignore("a\t").try_render().is_ok());
+
+        let attributes = |text| Quoted::gitattributes(text).to_string();
+        assert_eq!(attributes("*.png"), r"\*.png");
+        assert_eq!(attributes("!a"), r"\!a");
+        assert_eq!(attributes("[attr]x"), r"\[attr]x");
+        assert_eq!(attributes("\"a"), r#""\"a""#);
+        assert_eq!(attributes("a\"b"), "a\"b");
+        assert_eq!(attributes("! *"), r#""\\! \\*""#);
+        assert_eq!(attributes(r"a b\"), r#""a b\\\\""#);
+        assert_eq!(attributes("a\tb\u{1}\0"), "\"a\\tb\\001\u{FFFD}\"");
+        assert_eq!(attributes(""), r#""""#);
+        assert!(Quoted::gitattributes("").try_render().is_err());
+        assert!(Quoted::gitattributes("a\0").try_render().is_err());
+        assert!(Quoted::gitattributes("a\n").try_render().is_ok());
+
+        let config = |text| Quoted::git_config(text).to_string();
+        assert_eq!(config(""), r#""""#);
+        assert_eq!(config("a b;#c"), r#""a b;#c""#);
+        assert_eq!(config(" a "), r#"" a ""#);
+        assert_eq!(config("\"\\\n\t\u{8}"), r#""\"\\\n\t\b""#);
+        assert_eq!(config("a\rb"), "\"a\u{FFFD}b\"");
+        assert_eq!(
+            Quoted::git_config("origin/main").force(false).to_string(),
+            "origin/main"
+        );
+        assert_eq!(
+            Quoted::git_config("a;b").force(false).to_string(),
+            r#""a;b""#
+        );
+        assert!(Quoted::git_config("a\nb").try_render().is_ok());
+        assert!(Quoted::git_config("a\rb").try_render().is_err());
+        assert_eq!(std::format!("{:.4}", Quoted::git_config("a\nb")), r#""a""#);
+    }
+
+    #[cfg(feature = "cmake")]
+    #[test]
+    fn cmake() {
+        let cmake = |text| Quoted::cmake(text).force(false).to_string();
+        assert_eq!(cmake(""), r#""""#);
+        assert_eq!(cmake("foo/bar-1.txt"), "foo/bar-1.txt");
+        assert_eq!(cmake("a b"), r#""a b""#);
+        assert_eq!(cmake("#x"), "\"#x\"");
+        assert_eq!(cmake("a;b"), r#""a;b""#);
+        assert_eq!(cmake("${x} $ENV{y}"), r#""\${x} \$ENV{y}""#);
+        assert_eq!(cmake(r#"C:\"x""#), r#""C:\\\"x\"""#);
+        assert_eq!(cmake("a\nb\r\t"), r#""a\nb\r\t""#);
+        assert_eq!(cmake("\x1B"), "\"\x1B\"");
+        assert_eq!(
+            Quoted::cmake("a;b\\;c").cmake_list(true).to_string(),
+            r#""a\;b\\\;c""#
+        );
+        assert_eq!(std::format!("{:.4}", Quoted::cmake("a\nb")), r#""a""#);
+    }
+
+    #[cfg(feature = "dockerfile")]
+    #[test]
+    fn dockerfile() {
+        let shell = |text| Quoted::dockerfile(text).force(false).to_string();
+        assert_eq!(shell(""), "''");
+        assert_eq!(shell("foo/bar-1.txt"), "foo/bar-1.txt");
+        assert_eq!(shell("a b"), "'a b'");
+        assert_eq!(shell("$HOME"), "'$HOME'");
+        assert_eq!(shell("it's"), r"'it'\''s'");
+        assert_eq!(shell("'"), r"\'");
+        assert_eq!(shell("a\tb"), r#""$(printf 'a\011b')""#);
+        assert_eq!(shell("-n\n"), r#""$(printf '\055n\012')""#);
+        assert_eq!(shell("50%\\'\n"), r#""$(printf '50%%\\\047\012')""#);
+        assert_eq!(shell("\u{202E}x"), r#""$(printf '\342\200\256x')""#);
+        assert_eq!(
+            std::format!("{:.15}", Quoted::dockerfile("a\nbcdef")),
+            r#""$(printf 'a')""#
+        );
+
+        // Command substitution drops the newline at the end.
+        let quoted = Quoted::dockerfile("a\n");
+        assert_eq!(
+            quoted.try_render().unwrap_err().to_string(),
+            "command substitution would drop the trailing newlines"
+        );
+        assert!(Quoted::dockerfile("a\nb").try_render().is_ok());
+        let quoted = quoted.on_unrepresentable(OnUnrepresentable::Error);
+        let mut out = std::string::String::new();
+        assert!(fmt::Write::write_fmt(&mut out, format_args!("{}", quoted)).is_err());
+        let exec = quoted.dockerfile_form(DockerfileForm::Exec);
+        assert_eq!(exec.try_render().unwrap(), r#""a\n""#);
+
+        let exec = |text| {
+            Quoted::dockerfile(text)
+                .dockerfile_form(DockerfileForm::Exec)
+                .to_string()
+        };
+        assert_eq!(exec(""), r#""""#);
+        assert_eq!(exec("a b"), r#""a b""#);
+        assert_eq!(exec(r#"C:\"x""#), r#""C:\\\"x\"""#);
+        assert_eq!(exec("a\nb\t\r"), r#""a\nb\t\r""#);
+        assert_eq!(exec("\x1B\u{202E}"), r#""\u001B\u202E""#);
+        assert_eq!(
+            Quoted::dockerfile("\u{E0001}")
+                .dockerfile_form(DockerfileForm::Exec)
+                .escape_invisible(true)
+                .to_string(),
+            r#""\uDB40\uDC01""#
+        );
+    }
+
+    #[cfg(feature = "dotenv")]
+    #[test]
+    fn dotenv() {
+        let dotenv = |text| Quoted::dotenv(text).force(false).to_string();
+        assert_eq!(dotenv(""), "''");
+        assert_eq!(dotenv("foo-1.2_3"), "foo-1.2_3");
+        assert_eq!(dotenv("a b"), "'a b'");
+        assert_eq!(dotenv("#x"), "'#x'");
+        assert_eq!(dotenv("$HOME"), "'$HOME'");
+        assert_eq!(dotenv("\u{E9}"), "'\u{E9}'");
+        assert_eq!(dotenv("it's"), r#""it's""#);
+        assert_eq!(dotenv(r"C:\x"), r#""C:\\x""#);
+        assert_eq!(dotenv("a\nb"), r#""a\nb""#);
+        assert_eq!(dotenv("\"$HOME\"\t"), r#""\"\$HOME\"\t""#);
+        assert_eq!(dotenv("\x07\x08\x0B\x0C\r"), r#""\a\b\v\f\r""#);
+        assert_eq!(dotenv("\x1B"), "\"\x1B\"");
+        assert_eq!(
+            super::assign("KEY", Quoted::dotenv("value"))
+                .unwrap()
+                .to_string(),
+            "KEY='value'"
+        );
+    }
+
+    // Checked against systemd-escape --path.
+    #[cfg(feature = "systemd")]
+    #[test]
+    fn systemd_unit() {
+        let unit = |path: &[u8]| Quoted::systemd_unit_raw(path).to_string();
+        assert_eq!(unit(b"/"), "-");
+        assert_eq!(unit(b""), "-");
+        assert_eq!(unit(b"//a//b/"), "a-b");
+        assert_eq!(unit(b"/a/./b"), "a-b");
+        assert_eq!(unit(b"foo/bar"), "foo-bar");
+        assert_eq!(unit(b"/.hidden/.x"), r"\x2ehidden-.x");
+        assert_eq!(unit(b"/./.a"), r"\x2ea");
+        assert_eq!(unit(b"/:_.a"), ":_.a");
+        assert_eq!(
+            unit("/a-b\\c/\u{E9} \u{FC}".as_bytes()),
+            r"a\x2db\x5cc-\xc3\xa9\x20\xc3\xbc"
+        );
+        assert_eq!(unit(b"/x\n\t/y\xFF"), r"x\x0a\x09-y\xff");
+        assert_eq!(
+            std::format!("{:.7}", Quoted::systemd_unit("/ab/c d")),
+            "ab-c"
+        );
+
+        #[cfg(feature = "alloc")]
+        {
+            let path = |name| parse::systemd_unit(name);
+            assert_eq!(path("-").unwrap(), b"/");
+            assert_eq!(path("a-b").unwrap(), b"/a/b");
+            assert_eq!(path(r"a\x2db").unwrap(), b"/a-b");
+            assert_eq!(path(r"a\x2Db").unwrap(), b"/a-b");
+            assert_eq!(path(r"\x2ea").unwrap(), b"/.a");
+            assert_eq!(path(r"\xffb").unwrap(), b"/\xFFb");
+            assert_eq!(path("\u{E9}").unwrap(), "/\u{E9}".as_bytes());
+            for &name in &[
+                "", "a--b", "-a", "a-", r"a\x2f", "..", "a-..-b", r"a\x00", r"a\x2", r"a\y20",
+                r"a\",
+            ] {
+                assert_eq!(path(name), None, "{:?}", name);
+            }
+        }
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn html() {
+        let html = |text| Quoted::html(text).to_string();
+        assert_eq!(html(""), "");
+        assert_eq!(html("foo bar"), "foo bar");
+        assert_eq!(
+            html("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+        assert_eq!(html("a\nb\x1B"), "a&#xA;b&#x1B;");
+        assert_eq!(html("\u{2028}"), "&#x2028;");
+        assert_eq!(html("a\u{200B}b"), "a\u{200B}b");
+        assert_eq!(
+            Quoted::html("a\u{200B}b")
+                .escape_invisible(true)
+                .to_string(),
+            "a&#x200B;b"
+        );
+        assert_eq!(html("\u{202E}a\u{202C}"), "\u{202E}a\u{202C}");
+        assert_eq!(html("a\u{202E}b"), "a&#x202E;b");
+        assert_eq!(
+            Quoted::html("a\u{202E}b")
+                .bidi_policy(BidiPolicy::Isolate)
+                .to_string(),
+            "<bdi>a\u{202E}b</bdi>"
+        );
+        assert_eq!(
+            Quoted::html_raw(b"\xFF\xE2\x80\xAE\xE2\x80\xACx")
+                .bidi_policy(BidiPolicy::Allow)
+                .to_string(),
+            "&#xFFFD;&#x202E;&#x202C;x"
+        );
+        assert_eq!(std::format!("{:.8}", Quoted::html("abc&def")), "abc&amp;");
+        assert_eq!(std::format!("{:.7}", Quoted::html("abc&def")), "abc");
+    }
+
+    #[cfg(feature = "sql")]
+    #[test]
+    fn sql() {
+        let standard = |text| Quoted::sql(text).to_string();
+        assert_eq!(standard(""), "''");
+        assert_eq!(standard("it's"), "'it''s'");
+        assert_eq!(standard(r"x\y"), r"'x\y'");
+        assert_eq!(standard("a\nb"), "'a' || x'0A' || 'b'");
+        assert_eq!(standard("\x1B"), "x'1B'");
+        assert_eq!(
+            standard("a\u{202E}b\u{202C}"),
+            "'a' || x'E280AE' || 'b' || x'E280AC'"
+        );
+        assert_eq!(
+            Quoted::sql_raw(b"caf\xE9\xFF!").to_string(),
+            "'caf' || x'E9FF' || '!'"
+        );
+        assert_eq!(std::format!("{:.8}", Quoted::sql("abc\x1Bdef")), "'abc'");
+
+        let mysql = |text| Quoted::sql(text).sql_dialect(SqlDialect::MySql).to_string();
+        assert_eq!(mysql(""), "''");
+        assert_eq!(mysql("it's"), r"'it\'s'");
+        assert_eq!(mysql(r"x\y"), r"'x\\y'");
+        assert_eq!(mysql("a\nb"), r"'a\nb'");
+        assert_eq!(mysql("\x1B"), "CONCAT(x'1B')");
+        assert_eq!(mysql("\0\x1A\x07"), r"CONCAT('\0\Z', x'07')");
+        assert_eq!(
+            Quoted::sql_raw(b"caf\xE9")
+                .sql_dialect(SqlDialect::MySql)
+                .to_string(),
+            "CONCAT('caf', x'E9')"
+        );
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn here_doc() {
+        let here_doc = |text| Quoted::unix(text).multiline(Multiline::HereDoc).to_string();
+        assert_eq!(here_doc("a\nb\n"), "<<'EOF'\na\nb\nEOF");
+        assert_eq!(here_doc("\n"), "<<'EOF'\n\nEOF");
+        assert_eq!(here_doc("$a 'b'\n\tc\n"), "<<'EOF'\n$a 'b'\n\tc\nEOF");
+        assert_eq!(
+            here_doc("EOF\nEOF1\nEOF3\n"),
+            "<<'EOF2'\nEOF\nEOF1\nEOF3\nEOF2"
+        );
+        assert_eq!(here_doc(" EOF\nEOF \n"), "<<'EOF'\n EOF\nEOF \nEOF");
+        // Anything else is quoted as usual.
+        assert_eq!(here_doc("a\nb"), "$'a\\nb'");
+        assert_eq!(here_doc("a\x1B\n"), "$'a\\x1B\\n'");
+        assert_eq!(here_doc("\u{202E}\n"), "$'\\xE2\\x80\\xAE\\n'");
+        assert_eq!(here_doc("foo"), "'foo'");
+        assert_eq!(
+            Quoted::unix("a\nb\n")
+                .multiline(Multiline::HereDoc)
+                .for_remote(1)
+                .to_string(),
+            r#"'$'\''a\nb\n'\'"#
+        );
+        assert_eq!(
+            super::assign("A", Quoted::unix("a\n").multiline(Multiline::HereDoc))
+                .unwrap()
+                .to_string(),
+            "A=$'a\\n'"
+        );
+        // Truncating keeps the delimiter.
+        assert_eq!(
+            std::format!(
+                "{:.12}",
+                Quoted::unix("abc\ndef\n").multiline(Multiline::HereDoc)
+            ),
+            "<<'EOF'\nab\nEOF"
+        );
+    }
+
+    #[test]
+    fn assign() {
+        fn assign(name: &str, value: Quoted<'_>) -> std::string::String {
+            super::assign(name, value).unwrap().to_string()
+        }
+
+        #[cfg(feature = "unix")]
+        {
+            assert_eq!(assign("A", Quoted::unix("")), "A=''");
+            assert_eq!(
+                assign("_a1", Quoted::unix("x:~y").force(false)),
+                "_a1='x:~y'"
+            );
+            assert_eq!(assign("A", Quoted::unix("a\nb")), "A=$'a\\nb'");
+            assert_eq!(
+                assign("A", Quoted::unix("a b").prepared().as_quoted()),
+                "A='a b'"
+            );
+            for &name in &["", "1A", "A-B", "A B", "\u{E9}"] {
+                assert!(super::assign(name, Quoted::unix("")).is_err(), "{:?}", name);
+            }
+        }
+        #[cfg(feature = "windows")]
+        {
+            let assign = |name, value| assign(name, Quoted::windows(value));
+            assert_eq!(assign("A", "foo"), "$A = 'foo'");
+            assert_eq!(assign("\u{E9}", ""), "$\u{E9} = ''");
+            assert_eq!(assign("a b}`", "x"), "${a b`}``} = 'x'");
+            assert_eq!(assign("", "x"), "${} = 'x'");
+            assert_eq!(
+                super::assign("A", Quoted::windows("").external(true))
+                    .unwrap()
+                    .to_string(),
+                "$A = ''"
+            );
+            assert_eq!(
+                super::assign("A", Quoted::windows("a b").prepared().as_quoted())
+                    .unwrap()
+                    .to_string(),
+                "$A = 'a b'"
+            );
+            let result = std::panic::catch_unwind(|| super::assign("A", Quoted::windows_argv("")));
+            assert!(result.is_err());
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn alias() {
+        #[cfg(feature = "unix")]
+        {
+            let arg = |text| Quoted::unix(text).force(false);
+            let alias = |command: &[Quoted<'_>]| super::alias("a", command).to_string();
+            assert_eq!(alias(&[arg("ls")]), "alias a='ls'");
+            assert_eq!(alias(&[Quoted::unix("ls")]), r#"alias a="'ls'""#);
+            assert_eq!(
+                alias(&[arg("echo"), arg("a\nb"), arg("$x")]),
+                r"alias a='echo $'\''a\nb'\'' '\''$x'\'"
+            );
+            assert_eq!(
+                alias(&[
+                    arg("cat"),
+                    Quoted::unix("a\n").multiline(Multiline::HereDoc)
+                ]),
+                r"alias a='cat $'\''a\n'\'"
+            );
+            #[cfg(feature = "std")]
+            {
+                let command = [arg("it's"), arg("\"$HOME\""), arg("a\\b"), arg("\u{202E}")];
+                let output = alias(&command);
+                let inner = crate::posix::parse_word(&output["alias a=".len()..]).unwrap();
+                assert_eq!(
+                    core::str::from_utf8(&inner).unwrap(),
+                    r#""it's" '"$HOME"' 'a\b' $'\xE2\x80\xAE'"#
+                );
+            }
+        }
+        #[cfg(feature = "windows")]
+        {
+            let command = [Quoted::windows("a'b").force(false), Quoted::windows("$x")];
+            assert_eq!(
+                super::alias("a.b-c_1", &command).to_string(),
+                "function a.b-c_1 { \"a'b\" '$x' @args }"
+            );
+        }
+        #[cfg(all(feature = "unix", feature = "windows"))]
+        {
+            let mixed = [Quoted::unix("a"), Quoted::windows("b")];
+            assert!(std::panic::catch_unwind(|| super::alias("a", &mixed)).is_err());
+        }
+        #[cfg(feature = "unix")]
+        {
+            let command = [Quoted::unix("a")];
+            for &name in &["", "-a", "a b", "a=b", "a/b", "$a"] {
+                let result = std::panic::catch_unwind(|| super::alias(name, &command));
+                assert!(result.is_err(), "{:?}", name);
+            }
+            assert!(std::panic::catch_unwind(|| super::alias("a", &[])).is_err());
+        }
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn crontab() {
+        /// Undo cron's escaping the way Vixie cron and cronie do, and stop at
+        /// the first `%` like they do.
+        fn cron(line: &str) -> std::string::String {
+            let mut command = std::string::String::new();
+            let mut escaped = false;
+            for ch in line.chars() {
+                if escaped {
+                    if ch == '%' {
+                        command.pop();
+                    }
+                    escaped = false;
+                } else if ch == '%' {
+                    break;
+                } else {
+                    escaped = ch == '\\';
+                }
+                command.push(ch);
+            }
+            command
+        }
+
+        let cron_quote = |text| Quoted::unix(text).crontab(true).to_string();
+        assert_eq!(cron_quote("100%"), r"'100\%'");
+        assert_eq!(cron_quote("a\n%"), r"$'a\n\%'");
+        assert_eq!(cron_quote(r"a\%"), r"$'a\\\%'");
+        assert_eq!(
+            Quoted::unix("%").force(false).crontab(true).to_string(),
+            r"\%"
+        );
+        assert_eq!(
+            Quoted::unix("%\n")
+                .crontab(true)
+                .fallback(Fallback::PrintfCommand)
+                .to_string(),
+            r"printf '\%\%\n'"
+        );
+        assert_eq!(
+            Quoted::unix("a%\n")
+                .crontab(true)
+                .multiline(Multiline::HereDoc)
+                .to_string(),
+            r"$'a\%\n'"
+        );
+        assert_eq!(
+            Quoted::unix("a b%").crontab(true).for_remote(1).to_string(),
+            r#""'a b\%'""#
+        );
+        assert_eq!(Quoted::unix("100%").crontab(false).to_string(), "'100%'");
+        #[cfg(feature = "windows")]
+        assert_eq!(Quoted::windows("100%").crontab(true).to_string(), "'100%'");
+
+        #[cfg(feature = "std")]
+        {
+            let inputs = [
+                "%",
+                "%%",
+                "100% done",
+                r"a\%",
+                r"\\%",
+                r"\%\%",
+                "it's 50%",
+                "'%",
+                "%'",
+                "\"%\"",
+                "a\n%",
+                "%\u{202E}",
+                "$HOME%",
+                "~%",
+            ];
+            for &input in &inputs {
+                for &force in &[true, false] {
+                    for &prefer in &[QuoteChar::Single, QuoteChar::Double] {
+                        let output = Quoted::unix(input)
+                            .force(force)
+                            .prefer(prefer)
+                            .crontab(true)
+                            .to_string();
+                        let word = crate::posix::parse_word(&cron(&output));
+                        assert_eq!(
+                            word.as_ref().map(|w| &w[..]),
+                            Ok(input.as_bytes()),
+                            "{}",
+                            output
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn export() {
+        #[cfg(feature = "unix")]
+        {
+            let export = |name, value| super::export(name, Quoted::unix(value));
+            assert_eq!(export("A", "").unwrap().to_string(), "export A=''");
+            assert_eq!(
+                export("_a1", "a\nb").unwrap().to_string(),
+                "export _a1=$'a\\nb'"
+            );
+            for &name in &["", "1A", "A-B", "A B", "\u{E9}", "A=B"] {
+                assert!(export(name, "").is_err(), "{:?}", name);
+            }
+        }
+        #[cfg(feature = "windows")]
+        {
+            let export = |name, value| super::export(name, Quoted::windows(value));
+            assert_eq!(export("A", "foo").unwrap().to_string(), "$env:A = 'foo'");
+            assert_eq!(
+                export("\u{E9}", "").unwrap().to_string(),
+                "$env:\u{E9} = ''"
+            );
+            assert_eq!(export("a:b", "").unwrap().to_string(), "${env:a:b} = ''");
+            assert_eq!(
+                export("a b}`", "x").unwrap().to_string(),
+                "${env:a b`}``} = 'x'"
+            );
+            assert_eq!(
+                export("A", "x").unwrap().to_string(),
+                super::assign("env:A", Quoted::windows("x"))
+                    .unwrap()
+                    .to_string()
+            );
+            for &name in &["", "A=B", "A\0"] {
+                assert!(export(name, "").is_err(), "{:?}", name);
+            }
+        }
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn iex_safe() {
+        let iex = |quoted: Quoted| quoted.iex_safe(true).to_string();
+        assert_eq!(iex(Quoted::windows("foo")), "'foo'");
+        assert_eq!(iex(Quoted::windows("$a`b")), "'`$a``b'");
+        assert_eq!(iex(Quoted::windows("a'\n")), "`\"a'``n`\"");
+        assert_eq!(iex(Quoted::windows("a\u{201C}")), "'a`\u{201C}'");
+        assert_eq!(iex(Quoted::windows("a'$")), "'a''`$'");
+        assert_eq!(iex(Quoted::windows("\"").external(true)), "'\\`\"'");
+        assert_eq!(
+            iex(Quoted::windows_raw(&[0xD800]).expression(true)),
+            "(`\"`\" + [char]0xD800)"
+        );
+        assert_eq!(
+            iex(Quoted::windows("\u{1F600}\u{E0001}")
+                .escape_invisible(true)
+                .expression(true)
+                .context(QuoteContext::InsideDoubleQuotes)),
+            "\u{1F600}`$([char]0xDB40)`$([char]0xDC01)"
+        );
+        #[cfg(feature = "unix")]
+        assert_eq!(iex(Quoted::unix("$a")), "'$a'");
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn expression() {
+        let raw = |units: &[u16]| Quoted::windows_raw(units).expression(true).to_string();
+        assert_eq!(raw(&[0xD800]), r#"("" + [char]0xD800)"#);
+        assert_eq!(
+            raw(&[0x61, 0xD800, 0xDC00, 0x62, 0x5C, 0xDFFF, 0x5C]),
+            "(\"a\u{10000}b\\\" + [char]0xDFFF + \"\\\")"
+        );
+        assert_eq!(
+            Quoted::windows("\x1B\n").expression(true).to_string(),
+            r#"("" + [char]0x001B + "`n")"#
+        );
+        assert_eq!(
+            Quoted::windows("\u{E0001}")
+                .escape_invisible(true)
+                .expression(true)
+                .to_string(),
+            r#"("" + [char]0xDB40 + [char]0xDC01)"#
+        );
+        assert_eq!(
+            Quoted::windows_raw(&[0x61, 0xD800])
+                .expression(true)
+                .context(QuoteContext::InsideDoubleQuotes)
+                .to_string(),
+            "a$([char]0xD800)"
+        );
+        // Casts are kept whole, and the parentheses are closed.
+        assert_eq!(
+            std::format!(
+                "{:.19}",
+                Quoted::windows_raw(&[0x61, 0xD800, 0x62]).expression(true)
+            ),
+            r#"("a")"#
+        );
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn windows_argv() {
+        /// Parse a command line argument like the Microsoft C runtime.
+        fn parse(line: &str) -> std::vec::Vec<String> {
+            let mut args = std::vec::Vec::new();
+            let mut arg = None;
+            let mut in_quotes = false;
+            let mut chars = line.chars().peekable();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    ' ' | '\t' | '\n' | '\x0b' if !in_quotes => {
+                        args.extend(arg.take());
+                    }
+                    '\\' => {
+                        let mut count = 1;
+                        while chars.peek() == Some(&'\\') {
+                            chars.next();
+                            count += 1;
+                        }
+                        let arg = arg.get_or_insert_with(String::new);
+                        if chars.peek() == Some(&'"') {
+                            arg.extend(std::iter::repeat('\\').take(count / 2));
+                            if count % 2 == 1 {
+                                chars.next();
+                                arg.push('"');
+                            }
+                        } else {
+                            arg.extend(std::iter::repeat('\\').take(count));
+                        }
+                    }
+                    '"' => {
+                        arg.get_or_insert_with(String::new);
+                        in_quotes = !in_quotes;
+                    }
+                    ch => arg.get_or_insert_with(String::new).push(ch),
+                }
+            }
+            args.extend(arg);
+            args
+        }
+
+        let cases: &[(&str, &str, &str)] = &[
+            ("", "\"\"", "\"\""),
+            ("foo", "\"foo\"", "foo"),
+            ("foo bar", "\"foo bar\"", "\"foo bar\""),
+            ("a\\b", "\"a\\b\"", "a\\b"),
+            ("a\\", "\"a\\\\\"", "a\\"),
+            ("a\\ b\\", "\"a\\ b\\\\\"", "\"a\\ b\\\\\""),
+            ("\"", "\"\\\"\"", "\"\\\"\""),
+            ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+            ("$`'\n", "\"$`'\n\"", "\"$`'\n\""),
+        ];
+        for &(text, forced, unforced) in cases {
+            let quoted = Quoted::windows_argv(text);
+            assert_eq!(quoted.to_string(), forced);
+            assert_eq!(quoted.force(false).to_string(), unforced);
+            for line in &[forced, unforced] {
+                let line = std::format!("prog {} {} end", line, line);
+                assert_eq!(parse(&line), ["prog", text, text, "end"]);
+            }
+        }
+
+        let inside = Quoted::windows_argv("a \"b\" c\\").context(QuoteContext::InsideDoubleQuotes);
+        assert_eq!(inside.to_string(), "a \\\"b\\\" c\\\\");
+        assert_eq!(
+            parse(&std::format!("\"--arg={}\"", inside)),
+            ["--arg=a \"b\" c\\"]
+        );
+        assert_eq!(
+            Quoted::windows_argv("a b").for_remote(1).to_string(),
+            "\"\\\"a b\\\"\""
+        );
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn cmd_context() {
+        /// Undo cmd.exe's escaping for a single argument, and fail if it
+        /// would do anything else with it.
+        fn cmd(line: &str, context: CmdContext) -> String {
+            let mut line = line.to_string();
+            if context != CmdContext::Interactive {
+                assert!(!line.replace("%%", "").contains('%'), "{}", line);
+                line = line.replace("%%", "%");
+            }
+            let mut rounds = 1;
+            if context == CmdContext::DelayedExpansion && line.contains('!') {
+                rounds = 2;
+            }
+            for round in 0..rounds {
+                let mut unescaped = String::new();
+                let mut chars = line.chars();
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '^' => unescaped.extend(chars.next()),
+                        '!' if round == 1 => panic!("unescaped ! in {}", line),
+                        '&' | '|' | '<' | '>' | '(' | ')' | '"' if round == 0 => {
+                            panic!("unescaped {} in {}", ch, line)
+                        }
+                        '%' if round == 0 && context == CmdContext::Interactive => {
+                            panic!("unescaped % in {}", line)
+                        }
+                        _ => unescaped.push(ch),
+                    }
+                }
+                line = unescaped;
+            }
+            line
+        }
+
+        let arg = |text, context| Quoted::windows_argv(text).cmd_context(context).to_string();
+        assert_eq!(arg("a b", CmdContext::Interactive), r#"^"a b^""#);
+        assert_eq!(arg("%PATH%", CmdContext::Interactive), r#"^"^%PATH^%^""#);
+        assert_eq!(arg("%PATH%", CmdContext::BatchFile), r#"^"%%PATH%%^""#);
+        assert_eq!(arg("a^b!", CmdContext::BatchFile), r#"^"a^^b!^""#);
+        assert_eq!(
+            arg("a^b!", CmdContext::DelayedExpansion),
+            r#"^"a^^^^b^^!^""#
+        );
+        assert_eq!(arg("a^b", CmdContext::DelayedExpansion), r#"^"a^^b^""#);
+        assert_eq!(
+            arg(r#"\"(x)"#, CmdContext::Interactive),
+            r#"^"\\\^"^(x^)^""#
+        );
+        assert_eq!(
+            Quoted::windows_argv("a&b")
+                .force(false)
+                .cmd_context(CmdContext::Interactive)
+                .to_string(),
+            "a^&b"
+        );
+        assert_eq!(arg("a\nb", CmdContext::BatchFile), "^\"a\u{FFFD}b^\"");
+        assert!(Quoted::windows_argv("a\nb")
+            .cmd_context(CmdContext::BatchFile)
+            .try_render()
+            .is_err());
+        assert!(Quoted::windows_argv("a\nb").try_render().is_ok());
+        // The caller's quotes would change what cmd.exe does with carets.
+        assert_eq!(
+            Quoted::windows_argv("a&b")
+                .context(QuoteContext::InsideDoubleQuotes)
+                .cmd_context(CmdContext::Interactive)
+                .to_string(),
+            "a&b"
+        );
+
+        let texts = [
+            "",
+            "a b",
+            "%PATH%",
+            "100%",
+            "!x!",
+            "a^b",
+            "a^!b",
+            "^",
+            "!",
+            "\"",
+            "a\\\"b\\",
+            "x & y | z",
+            "<in> (out)",
+            "%%",
+            "^^!!",
+        ];
+        let contexts = [
+            CmdContext::Interactive,
+            CmdContext::BatchFile,
+            CmdContext::DelayedExpansion,
+        ];
+        for &text in &texts {
+            for &context in &contexts {
+                for &force in &[true, false] {
+                    let output = Quoted::windows_argv(text)
+                        .force(force)
+                        .cmd_context(context)
+                        .to_string();
+                    // cmd.exe should pass on what windows_argv() wrote.
+                    assert_eq!(
+                        cmd(&output, context),
+                        Quoted::windows_argv(text).force(force).to_string(),
+                        "{:?} {:?}",
+                        text,
+                        context
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn for_remote() {
+        let remote = |text, levels| Quoted::unix(text).for_remote(levels).to_string();
+        assert_eq!(remote("foo bar", 0), "'foo bar'");
+        assert_eq!(remote("foo bar", 1), r#""'foo bar'""#);
+        assert_eq!(remote("foo bar", 2), r#"'"'\''foo bar'\''"'"#);
+        assert_eq!(remote("foo", 1), "\"'foo'\"");
+        assert_eq!(
+            Quoted::unix("foo").force(false).for_remote(3).to_string(),
+            "foo"
+        );
+        assert_eq!(remote("a\nb", 1), r#"'$'\''a\nb'\'"#);
+        // Isolating only works for the outermost level.
+        assert_eq!(
+            Quoted::unix("\u{2067}")
+                .bidi_policy(BidiPolicy::Isolate)
+                .for_remote(1)
+                .to_string(),
+            r#"'$'\''\xE2\x81\xA7'\'"#
+        );
+        assert_eq!(
+            Quoted::unix("foo bar").for_remote(1).prepared().to_string(),
+            r#""'foo bar'""#
+        );
+        // A prepared Quoted is replayed as it is.
+        let prepared = Quoted::unix("a b").prepared();
+        assert_eq!(prepared.as_quoted().for_remote(1).to_string(), "'a b'");
+        assert_eq!(prepared.as_quoted().for_remote(3).to_string(), "'a b'");
+        assert_eq!(prepared.as_quoted().glob_safe(true).to_string(), "'a b'");
+    }
+
+    #[test]
+    fn inside_double_quotes() {
+        #[cfg(feature = "unix")]
+        {
+            let unix = |text| {
+                Quoted::unix(text)
+                    .context(QuoteContext::InsideDoubleQuotes)
+                    .to_string()
+            };
+            assert_eq!(unix(""), "");
+            assert_eq!(unix("foo bar"), "foo bar");
+            assert_eq!(unix("'~!*"), "'~!*");
+            assert_eq!(unix(r#"$a `b` "c" \d"#), r#"\$a \`b\` \"c\" \\d"#);
+            assert_eq!(unix("a\nb"), r#"a"$'\n'"b"#);
+            assert_eq!(unix("\n\x1B1"), r#""$'\n\x1B'"1"#);
+            assert_eq!(unix("a\u{202E}"), r#"a"$'\xE2\x80\xAE'""#);
+            assert_eq!(
+                Quoted::unix_raw(b"a\xFFb")
+                    .context(QuoteContext::InsideDoubleQuotes)
+                    .to_string(),
+                r#"a"$'\xFF'"b"#
+            );
+            // The detour is cut off as a whole.
+            assert_eq!(
+                std::format!(
+                    "{:.9}",
+                    Quoted::unix("ab\ncd").context(QuoteContext::InsideDoubleQuotes)
+                ),
+                r#"ab"$'\n'""#
+            );
+            assert_eq!(
+                std::format!(
+                    "{:.6}",
+                    Quoted::unix("ab\ncd").context(QuoteContext::InsideDoubleQuotes)
+                ),
+                "ab"
+            );
+        }
+        #[cfg(feature = "windows")]
+        {
+            let windows = |text| {
+                Quoted::windows(text)
+                    .context(QuoteContext::InsideDoubleQuotes)
+                    .to_string()
+            };
+            assert_eq!(windows(""), "");
+            assert_eq!(windows("foo bar"), "foo bar");
+            assert_eq!(windows("$a `b` \\"), "`$a ``b`` \\");
+            assert_eq!(windows("a\nb"), "a`nb");
+            assert_eq!(windows("\u{201C}"), "`\u{201C}");
+            assert_eq!(
+                Quoted::windows("a\"b")
+                    .external(true)
+                    .context(QuoteContext::InsideDoubleQuotes)
+                    .to_string(),
+                "a\\`\"b"
+            );
+            assert_eq!(
+                Quoted::windows_raw(&[0xD800])
+                    .context(QuoteContext::InsideDoubleQuotes)
+                    .to_string(),
+                "`u{D800}"
+            );
+        }
+    }
+
+    #[test]
+    fn escape_chars() {
+        // A lone character that needs an escape is written as just that
+        // escape inside the quotes.
+        for &ch in &['\0', '\n', '\t', '\r', '\x1B', '\x7F', '\u{85}', '\u{202E}'] {
+            let mut buf = [0; 4];
+            let text = ch.encode_utf8(&mut buf);
+            #[cfg(feature = "unix")]
+            assert_eq!(
+                Quoted::unix(text).to_string(),
+                std::format!("$'{}'", escape::unix_char(ch))
+            );
+            #[cfg(feature = "windows")]
+            assert_eq!(
+                Quoted::windows(text).to_string(),
+                std::format!("\"{}\"", escape::powershell_char(ch))
+            );
+        }
+        #[cfg(feature = "unix")]
+        assert_eq!(
+            Quoted::unix_raw(b"\xFF").to_string(),
+            std::format!("$'{}'", escape::unix_byte(0xFF))
+        );
+        #[cfg(feature = "windows")]
+        assert_eq!(
+            Quoted::windows_raw(&[0xD800]).to_string(),
+            std::format!("\"{}\"", escape::powershell_unit(0xD800))
+        );
+    }
+
+    #[test]
+    fn unicode_tables() {
+        for table in &[
+            tables::SPACE_SEPARATOR,
+            tables::LINE_PARAGRAPH_SEPARATOR,
+            tables::FORMAT,
+            tables::WHITE_SPACE,
+        ] {
+            for pair in table.windows(2) {
+                assert!(pair[0].1 < pair[1].0);
+            }
+            for &(first, last) in table.iter() {
+                assert!(first <= last);
+            }
+        }
+        // White_Space hasn't changed in a long time, so this shouldn't
+        // depend on the compiler.
+        for ch in (0..=0x10FFFF).filter_map(core::char::from_u32) {
+            assert_eq!(
+                tables::contains(tables::WHITE_SPACE, ch),
+                ch.is_whitespace(),
+                "{:?}",
+                ch
+            );
+        }
+        assert!(UNICODE_VERSION >= (14, 0, 0));
+    }
 
     #[cfg(feature = "unix")]
     #[test]
-    fn unix() {
+    fn render_into() {
+        let mut buf = [0; 32];
         for &(orig, expected) in UNIX_ALWAYS.iter().chain(BOTH_ALWAYS) {
-            assert_eq!(Quoted::unix(orig).to_string(), expected);
-        }
-        for &(orig, expected) in UNIX_MAYBE.iter().chain(BOTH_MAYBE) {
-            assert_eq!(Quoted::unix(orig).force(false).to_string(), expected);
+            let len = Quoted::unix(orig).render_into(&mut buf).unwrap();
+            assert_eq!(&buf[..len], expected.as_bytes());
+            if len > 0 {
+                let err = Quoted::unix(orig)
+                    .render_into(&mut buf[..len - 1])
+                    .unwrap_err();
+                assert_eq!(err.required(), len);
+            }
         }
-        for &(orig, expected) in UNIX_RAW {
-            assert_eq!(Quoted::unix_raw(orig).to_string(), expected);
+        let len = Quoted::unix("a\nb").render_into(&mut []);
+        assert_eq!(len, Err(CapacityError { required: 7 }));
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[cfg(not(feature = "unicode-width"))]
+    #[test]
+    fn without_unicode_width() {
+        assert_eq!(Quoted::unix("\u{E9}").force(false).to_string(), "'\u{E9}'");
+        assert_eq!(
+            Quoted::unix("\u{200B}").force(false).to_string(),
+            "'\u{200B}'"
+        );
+        assert_eq!(Quoted::unix("foo").force(false).to_string(), "foo");
+        assert_eq!(Quoted::unix("a\u{E9}").force(false).to_string(), "a\u{E9}");
+        assert_eq!(Quoted::windows("\u{65E5}\u{672C}").display_width(), 4);
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows"))]
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn display_width() {
+        const CASES: &[(&str, usize, usize)] = &[
+            ("foo", 5, 5),
+            ("", 2, 2),
+            ("\u{65E5}\u{672C}", 6, 6),
+            ("a\u{301}", 3, 3),
+            ("\n", 5, 4),
+            ("\u{FEFF}", 2, 2),
+            ("\u{85}", 11, 8),
+        ];
+        for &(orig, unix, windows) in CASES {
+            assert_eq!(Quoted::unix(orig).display_width(), unix);
+            assert_eq!(Quoted::windows(orig).display_width(), windows);
         }
-        let bidi_ok = nest_bidi(16);
         assert_eq!(
-            Quoted::unix(&bidi_ok).to_string(),
-            "'".to_string() + &bidi_ok + "'"
+            Quoted::unix("foo")
+                .highlight(Highlight::new().quotes("2"))
+                .hyperlink("https://example.com")
+                .display_width(),
+            5
         );
-        let bidi_too_deep = nest_bidi(17);
-        assert!(Quoted::unix(&bidi_too_deep).to_string().starts_with('$'));
     }
 
-    const WINDOWS_ALWAYS: &[(&str, &str)] = &[
-        (r#"foo\bar"#, r#"'foo\bar'"#),
-        (r#"can'"t"#, r#"'can''"t'"#),
-        (r#"can'$t"#, r#"'can''$t'"#),
-        ("foo\nb\ta\r\\\0`r", r#""foo`nb`ta`r\`0``r""#),
-        ("foo\x02", r#""foo`u{02}""#),
-        (r#"'$''"#, r#"'''$'''''"#),
-    ];
-    const WINDOWS_MAYBE: &[(&str, &str)] = &[
-        ("--%", "'--%'"),
-        ("--ok", "--ok"),
-        ("—x", "'—x'"),
-        ("a,b", "'a,b'"),
-        ("a\\b", "a\\b"),
-        ("‘", r#""‘""#),
-        (r#"‘""#, r#"''‘"'"#),
-        ("„\0", r#""`„`0""#),
-        ("\t", r#""`t""#),
-        ("\r", r#""`r""#),
-        ("\u{85}", r#""`u{85}""#),
-        ("\u{2028}", r#""`u{2028}""#),
-        (
-            "user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}",
-            r#""user`u{202E} `u{2066}// Check if admin`u{2069} `u{2066}""#,
-        ),
-    ];
-    const WINDOWS_RAW: &[(&[u16], &str)] = &[(&[b'x' as u16, 0xD800], r#""x`u{D800}""#)];
-    const WINDOWS_EXTERNAL: &[(&str, &str)] = &[
-        ("", r#"'""'"#),
-        (r#"\""#, r#"'\\\"'"#),
-        (r#"\\""#, r#"'\\\\\"'"#),
-        (r#"\x\""#, r#"'\x\\\"'"#),
-        (r#"\x\"'""#, r#"'\x\\\"''\"'"#),
-        ("\n\\\"", r#""`n\\\`"""#),
-        ("\n\\\\\"", r#""`n\\\\\`"""#),
-        ("\n\\x\\\"", r#""`n\x\\\`"""#),
-        ("\n\\x\\\"'\"", r#""`n\x\\\`"'\`"""#),
-        ("-x:", "'-x:'"),
-        ("-x.x", "'-x.x'"),
-        ("--%", r#"'"--%"'"#),
-        ("--ok", "--ok"),
-    ];
-    const WINDOWS_INTERNAL: &[(&str, &str)] = &[
-        ("", "''"),
-        (r#"can'"t"#, r#"'can''"t'"#),
-        ("-x", "'-x'"),
-        ("—x", "'—x'"),
-        ("‘\"", r#"''‘"'"#),
-        ("--%", "'--%'"),
-        ("--ok", "--ok"),
-    ];
+    #[cfg(all(feature = "unix", feature = "windows", feature = "alloc"))]
+    #[cfg(all(feature = "html", feature = "systemd", feature = "reg"))]
+    #[test]
+    fn try_render() {
+        let ok = |quoted: Quoted<'_>| assert_eq!(quoted.try_render().unwrap(), quoted.to_string());
+        let err = |quoted: Quoted<'_>| assert!(quoted.try_render().is_err(), "{}", quoted);
 
-    #[cfg(feature = "windows")]
+        ok(Quoted::unix("a\nb"));
+        ok(Quoted::unix_raw(b"\xFF"));
+        ok(Quoted::unix("\x01").fallback(Fallback::PrintfCommand));
+        err(Quoted::unix("a\0b"));
+        err(Quoted::unix_raw(b"\xFF\0"));
+        err(Quoted::unix("\0").fallback(Fallback::PrintfCommand));
+        err(Quoted::unix("\0").for_remote(1));
+
+        ok(Quoted::windows("a\0b"));
+        ok(Quoted::windows_raw(&[0xD800, 0]));
+        err(Quoted::windows("a\0b").external(true));
+        err(Quoted::windows_raw(&[0xD800, 0]).external(true));
+        err(Quoted::windows_argv("a\0b"));
+        ok(Quoted::windows_wtf8(b"\xED\xA0\x80\0"));
+        err(Quoted::windows_wtf8(b"\xED\xA0\x80\0").external(true));
+        err(Quoted::windows_wtf8(b"\xFF"));
+
+        ok(Quoted::html_raw(b"a\0b"));
+        err(Quoted::html_raw(b"caf\xE9"));
+        ok(Quoted::systemd_unit_raw(b"/mnt/\xFF"));
+        err(Quoted::systemd_unit("/mnt/\0"));
+        ok(Quoted::reg("a\nb"));
+        err(Quoted::reg("a\0b"));
+        err(Quoted::reg_raw(&[0x61, 0]));
+
+        // Only the outermost level is cut off, so the inner ones stay valid.
+        #[cfg(feature = "sudoers")]
+        ok(Quoted::sudoers("~")
+            .for_remote(2)
+            .max_width(0)
+            .on_unrepresentable(OnUnrepresentable::Error));
+
+        let error = Quoted::unix("\0").try_render().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "a command-line argument can't contain a NUL character"
+        );
+    }
+
+    /// Quote lots of made-up text and check that a shell would turn it back
+    /// into the original.
+    #[cfg(feature = "std")]
+    #[cfg(feature = "unix")]
     #[test]
-    fn windows() {
-        for &(orig, expected) in WINDOWS_ALWAYS.iter().chain(BOTH_ALWAYS) {
-            assert_eq!(Quoted::windows(orig).to_string(), expected);
-        }
-        for &(orig, expected) in WINDOWS_MAYBE.iter().chain(BOTH_MAYBE) {
-            assert_eq!(Quoted::windows(orig).force(false).to_string(), expected);
-        }
-        for &(orig, expected) in WINDOWS_RAW {
-            assert_eq!(Quoted::windows_raw(orig).to_string(), expected);
-        }
-        for &(orig, expected) in WINDOWS_EXTERNAL {
+    fn unix_round_trip() {
+        use crate::posix::parse_word;
+
+        // Pieces that are likely to trip something up, especially next to
+        // each other.
+        const PIECES: &[&[u8]] = &[
+            b"a",
+            b"Z",
+            b"0",
+            b"9",
+            b"f",
+            b"-",
+            b"_",
+            b".",
+            b"/",
+            b",",
+            b":",
+            b"@",
+            b"%",
+            b"+",
+            b" ",
+            b"\t",
+            b"\n",
+            b"\r",
+            b"\x01",
+            b"\x1B",
+            b"\x7F",
+            b"'",
+            b"\"",
+            b"\\",
+            b"$",
+            b"`",
+            b"!",
+            b"#",
+            b"~",
+            b"=",
+            b"*",
+            b"?",
+            b"[",
+            b"]",
+            b"{",
+            b"}",
+            b"(",
+            b")",
+            b"|",
+            b"&",
+            b";",
+            b"<",
+            b">",
+            b"^",
+            b"\xC3\xA9",
+            b"\xE2\x80\x8B",
+            b"\xE2\x80\xAE",
+            b"\xCC\x81",
+            b"\xC2\x85",
+            b"\xEF\xBB\xBF",
+            b"\xF0\x9F\x98\x80",
+            b"\xFF",
+            b"\xC3",
+            b"\xE2\x80",
+            b"\xED\xA0\x80",
+        ];
+
+        // xorshift, to get the same cases every time without a dependency.
+        let mut state: u32 = 0x2545_F491;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as usize % bound
+        };
+
+        let check = |quoted: Quoted<'_>, bytes: &[u8]| {
+            let output = quoted.to_string();
             assert_eq!(
-                Quoted::windows(orig)
-                    .force(false)
-                    .external(true)
-                    .to_string(),
-                expected
+                parse_word(&output).as_ref().map(|word| &word[..]),
+                Ok(bytes),
+                "{:?} -> {}",
+                String::from_utf8_lossy(bytes),
+                output
             );
+        };
+
+        for _ in 0..5000 {
+            let mut bytes = std::vec::Vec::new();
+            for _ in 0..next(8) {
+                bytes.extend_from_slice(PIECES[next(PIECES.len())]);
+            }
+            for &force in &[true, false] {
+                check(Quoted::unix_raw(&bytes).force(force), &bytes);
+                check(
+                    Quoted::unix_raw(&bytes)
+                        .force(force)
+                        .quoting(Quoting::Minimal),
+                    &bytes,
+                );
+                check(
+                    Quoted::unix_raw(&bytes)
+                        .force(force)
+                        .quoting(Quoting::Paranoid),
+                    &bytes,
+                );
+                if let Ok(text) = core::str::from_utf8(&bytes) {
+                    check(Quoted::unix(text).force(force), &bytes);
+                }
+            }
         }
-        for &(orig, expected) in WINDOWS_INTERNAL {
+    }
+
+    /// The PowerShell version of unix_round_trip.
+    #[cfg(feature = "std")]
+    #[cfg(feature = "windows")]
+    #[test]
+    fn powershell_round_trip() {
+        use crate::powershell::parse_argument;
+
+        const PIECES: &[&str] = &[
+            "a",
+            "Z",
+            "0",
+            "9",
+            "f",
+            "-",
+            "_",
+            ".",
+            "/",
+            "\\",
+            ":",
+            "%",
+            "+",
+            " ",
+            "\t",
+            "\n",
+            "\r",
+            "\0",
+            "\x01",
+            "\x1B",
+            "\x7F",
+            "'",
+            "\"",
+            "`",
+            "$",
+            "@",
+            "#",
+            "~",
+            "!",
+            "=",
+            "*",
+            "?",
+            "[",
+            "]",
+            "{",
+            "}",
+            "(",
+            ")",
+            "|",
+            "&",
+            ";",
+            "<",
+            ">",
+            ",",
+            "\u{E9}",
+            "\u{A0}",
+            "\u{85}",
+            "\u{2013}",
+            "\u{2018}",
+            "\u{2019}",
+            "\u{201A}",
+            "\u{201C}",
+            "\u{201D}",
+            "\u{201E}",
+            "\u{200B}",
+            "\u{202E}",
+            "\u{2068}",
+            "\u{301}",
+            "\u{FEFF}",
+            "\u{1F600}",
+            "--%",
+        ];
+
+        let mut state: u32 = 0x2545_F491;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as usize % bound
+        };
+
+        let check = |quoted: Quoted<'_>, units: &[u16]| {
+            let output = quoted.to_string();
             assert_eq!(
-                Quoted::windows(orig)
-                    .force(false)
-                    .external(false)
-                    .to_string(),
-                expected
+                parse_argument(&output).as_ref().map(|arg| &arg[..]),
+                Ok(units),
+                "{:?} -> {}",
+                String::from_utf16_lossy(units),
+                output
             );
+        };
+
+        for _ in 0..5000 {
+            let mut units = std::vec::Vec::new();
+            for _ in 0..next(8) {
+                match next(PIECES.len() + 2) {
+                    // Unpaired surrogates
+                    0 => units.push(0xD800),
+                    1 => units.push(0xDC00),
+                    n => units.extend(PIECES[n - 2].encode_utf16()),
+                }
+            }
+            for &force in &[true, false] {
+                check(Quoted::windows_raw(&units).force(force), &units);
+                check(
+                    Quoted::windows_raw(&units)
+                        .force(force)
+                        .prefer(QuoteChar::Double),
+                    &units,
+                );
+                if let Ok(text) = String::from_utf16(&units) {
+                    check(Quoted::windows(&text).force(force), &units);
+                }
+            }
         }
-        let bidi_ok = nest_bidi(16);
+    }
+
+    #[cfg(feature = "testkit")]
+    #[cfg(feature = "unix")]
+    #[test]
+    fn testkit() {
+        let bash = match crate::testkit::Shell::new(&mut std::process::Command::new("bash")) {
+            Ok(bash) => bash,
+            // Not every machine has bash.
+            Err(_) => return,
+        };
+        for &text in &[
+            &b"foo"[..],
+            b"",
+            b"my file",
+            b"\xFF\n'$x'\\",
+            b"\xE2\x80\xAE",
+        ] {
+            assert_eq!(bash.roundtrip(Quoted::unix_raw(text)), text);
+            assert_eq!(bash.roundtrip(Quoted::unix_raw(text).force(false)), text);
+        }
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows", feature = "alloc"))]
+    #[test]
+    fn on_unrepresentable() {
+        use std::fmt::Write;
+
+        let replace = |quoted: Quoted<'_>| {
+            quoted
+                .on_unrepresentable(OnUnrepresentable::ReplacementChar)
+                .to_string()
+        };
+        let fail = |quoted: Quoted<'_>| {
+            let quoted = quoted.on_unrepresentable(OnUnrepresentable::Error);
+            write!(std::string::String::new(), "{}", quoted).is_err()
+        };
+
+        assert_eq!(replace(Quoted::unix("a\0b")), "'a\u{FFFD}b'");
+        assert_eq!(replace(Quoted::unix_raw(b"a\0\xFF")), "$'a\u{FFFD}\\xFF'");
+        assert_eq!(replace(Quoted::unix("a\nb")), "$'a\\nb'");
+        assert!(fail(Quoted::unix("a\0b")));
+        assert!(!fail(Quoted::unix("a\nb")));
+
+        assert_eq!(replace(Quoted::windows("a\0b")), "\"a`0b\"");
         assert_eq!(
-            Quoted::windows(&bidi_ok).to_string(),
-            "'".to_string() + &bidi_ok + "'"
+            replace(Quoted::windows("a\0b").external(true)),
+            "'a\u{FFFD}b'"
         );
-        let bidi_too_deep = nest_bidi(17);
-        assert!(Quoted::windows(&bidi_too_deep).to_string().contains('`'));
+        assert_eq!(
+            replace(Quoted::windows_raw(&[0x61, 0]).external(true)),
+            "'a\u{FFFD}'"
+        );
+        assert_eq!(
+            replace(Quoted::windows_wtf8(b"\0\xFF").external(true)),
+            "\"\u{FFFD}\u{FFFD}\""
+        );
+        assert!(fail(Quoted::windows_wtf8(b"\xFF")));
+        assert!(!fail(Quoted::windows("a\0b")));
+        assert!(fail(Quoted::windows_argv("a\0b")));
+
+        // The policy doesn't change what try_render() reports.
+        let quoted = Quoted::unix("\0").on_unrepresentable(OnUnrepresentable::ReplacementChar);
+        assert!(quoted.try_render().is_err());
+    }
+
+    #[cfg(all(feature = "unix", feature = "windows", feature = "alloc"))]
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn to_string_and_width() {
+        for &orig in &["foo", "", "\u{65E5}\u{672C}", "a\u{301}", "\n", "\u{85}"] {
+            for &quoted in &[Quoted::unix(orig), Quoted::windows(orig).force(false)] {
+                let measured = quoted.to_string_and_width();
+                assert_eq!(measured.as_str(), quoted.to_string());
+                assert_eq!(measured.width(), quoted.display_width());
+                assert_eq!(measured.to_string(), quoted.to_string());
+                assert_eq!(
+                    std::format!("{:>12}", measured),
+                    std::format!("{:>12}", quoted)
+                );
+                assert_eq!(
+                    std::format!("{:-^13}", measured),
+                    std::format!("{:-^13}", quoted)
+                );
+            }
+        }
+
+        let quoted = Quoted::unix("foo")
+            .highlight(Highlight::new().quotes("2"))
+            .hyperlink("https://example.com");
+        let measured = quoted.to_string_and_width();
+        assert_eq!(measured.width(), 5);
+        assert_eq!(measured.as_str(), quoted.to_string());
+        assert_eq!(std::format!("{:.2}", measured), measured.as_str());
+        assert_eq!(measured.into_string(), quoted.to_string());
+    }
+
+    #[cfg(feature = "unix")]
+    #[test]
+    fn hyperlink() {
+        assert_eq!(
+            Quoted::unix("a\nb")
+                .hyperlink("https://example.com/a b\x1B\u{E9}")
+                .to_string(),
+            "\x1B]8;;https://example.com/a%20b%1B%C3%A9\x1B\\$'a\\nb'\x1B]8;;\x1B\\"
+        );
+        assert_eq!(
+            Quoted::unix("x")
+                .hyperlink("file:///x")
+                .highlight(Highlight::new().quotes("2"))
+                .to_string(),
+            "\x1B]8;;file:///x\x1B\\\x1B[2m'\x1B[0mx\x1B[2m'\x1B[0m\x1B]8;;\x1B\\"
+        );
+    }
+
+    #[cfg(feature = "native")]
+    #[cfg(unix)]
+    #[test]
+    fn native_raw_linked() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/tmp/a b/\xFF%"));
+        assert_eq!(
+            Quoted::native_raw_linked(path).to_string(),
+            "\x1B]8;;file:///tmp/a%20b/%FF%25\x1B\\$'/tmp/a b/\\xFF%'\x1B]8;;\x1B\\"
+        );
+
+        // Relative paths are resolved against the current directory.
+        let dir = std::env::current_dir().unwrap();
+        let dir = dir.to_str().unwrap();
+        if dir
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"/-._".contains(&byte))
+        {
+            assert_eq!(
+                Quoted::native_raw_linked(Path::new("foo")).to_string(),
+                std::format!("\x1B]8;;file://{}/foo\x1B\\'foo'\x1B]8;;\x1B\\", dir)
+            );
+        }
     }
 
     #[cfg(feature = "native")]
@@ -637,17 +8644,22 @@ mod tests {
     #[cfg(any(unix, target_os = "wasi"))]
     #[test]
     fn native() {
-        #[cfg(unix)]
-        use std::os::unix::ffi::OsStrExt;
-        #[cfg(target_os = "wasi")]
-        use std::os::wasi::ffi::OsStrExt;
-
         assert_eq!("'\"".quote().to_string(), r#"\''"'"#);
         assert_eq!("x\0".quote().to_string(), r#"$'x\x00'"#);
-        assert_eq!(
-            OsStr::from_bytes(b"x\xFF").quote().to_string(),
-            r#"$'x\xFF'"#
-        );
+
+        // There's no safe way to construct an invalid OsStr on WASI preview 2.
+        #[cfg(any(unix, all(target_os = "wasi", not(target_env = "p2"))))]
+        {
+            #[cfg(unix)]
+            use std::os::unix::ffi::OsStrExt;
+            #[cfg(target_os = "wasi")]
+            use std::os::wasi::ffi::OsStrExt;
+
+            assert_eq!(
+                OsStr::from_bytes(b"x\xFF").quote().to_string(),
+                r#"$'x\xFF'"#
+            );
+        }
     }
 
     #[cfg(feature = "native")]
@@ -676,6 +8688,141 @@ mod tests {
         Cow::Borrowed(Path::new("foo")).quote();
     }
 
+    #[cfg(feature = "native")]
+    #[test]
+    fn quotable_generic() {
+        use std::borrow::ToOwned;
+        use std::boxed::Box;
+        use std::ffi::OsString;
+        use std::path::PathBuf;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        fn quote<Q: Quotable>(val: Q) -> String {
+            val.quote().to_string()
+        }
+
+        fn maybe_quote<Q: Quotable>(val: Q) -> String {
+            val.maybe_quote().to_string()
+        }
+
+        assert_eq!(quote("foo"), "'foo'");
+        let nested: &&str = &"foo";
+        assert_eq!(quote(nested), "'foo'");
+        assert_eq!(quote("foo".to_owned()), "'foo'");
+        assert_eq!(quote(OsString::from("foo")), "'foo'");
+        assert_eq!(quote(PathBuf::from("foo")), "'foo'");
+        assert_eq!(quote(Box::<str>::from("foo")), "'foo'");
+        assert_eq!(quote(Rc::<Path>::from(Path::new("foo"))), "'foo'");
+        assert_eq!(quote(Arc::<OsStr>::from(OsStr::new("foo"))), "'foo'");
+        assert_eq!(maybe_quote(Box::new("foo".to_owned())), "foo");
+        let path = PathBuf::from("foo bar");
+        assert_eq!(maybe_quote(&path), "'foo bar'");
+        assert_eq!(maybe_quote(path), "'foo bar'");
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn quote_to_string() {
+        use std::path::PathBuf;
+
+        assert_eq!("foo".to_quoted_string(), "'foo'");
+        assert_eq!("foo".to_maybe_quoted_string(), "foo");
+        assert_eq!(Path::new("a b").to_maybe_quoted_string(), "'a b'");
+        assert_eq!(PathBuf::from("foo").to_maybe_quoted_string(), "foo");
+
+        let mut buf = String::from("foo: ");
+        "bar".quote_into(&mut buf);
+        buf.push_str(", ");
+        OsStr::new("baz").quote_into(&mut buf);
+        assert_eq!(buf, "foo: 'bar', 'baz'");
+    }
+
+    #[cfg(feature = "native")]
+    #[cfg(os_display_quoted_literal)]
+    #[test]
+    fn quoted_literal() {
+        macro_rules! check {
+            ($($text:expr),* $(,)?) => {
+                $(
+                    assert_eq!(
+                        quoted_literal!($text),
+                        Quoted::native($text).force(false).to_string(),
+                        "{:?}",
+                        $text,
+                    );
+                )*
+            };
+        }
+
+        check!(
+            "", "foo", "foo bar", "-", "--", "-a", "--a", "--%", "-a:b", "~", "a~", "#a", "a#",
+            "!", "a!", "@a", "a@", "1", "a1", ".1", ".a", "./foo", "can't", "'", "''", "'a'",
+            "a'b'c", "'$'", "a''b", "$HOME", "a\\b", "a\"b", "\"", "`", "a`b'", "'\"", "a b'c",
+            "=", "a=b", "%", "^", "{}", "{", "a,b", "a;b", "*", "?", "[a]", "(a)", "a|b", "a&b",
+            "<>", "a/b", "C:\\foo", "can't!", "a b!",
+        );
+        check!(
+            " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~"
+        );
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn quote_components() {
+        let path = Path::new("foo bar/baz");
+        let mut components = path.components();
+        assert_eq!(components.next().unwrap().quote().to_string(), "'foo bar'");
+        assert_eq!(components.next().unwrap().maybe_quote().to_string(), "baz");
+        assert_eq!(
+            path.components().maybe_quote().to_string(),
+            path.maybe_quote().to_string()
+        );
+
+        #[cfg(not(windows))]
+        {
+            let path = Path::new("/foo bar/./baz/../$x/");
+            assert_eq!(
+                QuotedComponents::new(path).to_string(),
+                "/'foo bar'/baz/../'$x'"
+            );
+            assert_eq!(
+                QuotedComponents::new(path).force(true).to_string(),
+                "/'foo bar'/'baz'/'..'/'$x'"
+            );
+            assert_eq!(
+                QuotedComponents::new(Path::new("./a b")).to_string(),
+                "./'a b'"
+            );
+            assert_eq!(QuotedComponents::new(Path::new("/")).to_string(), "/");
+            assert_eq!(QuotedComponents::new(Path::new("")).to_string(), "");
+        }
+
+        #[cfg(windows)]
+        {
+            let path = Path::new(r"C:\foo bar\baz");
+            assert_eq!(QuotedComponents::new(path).to_string(), r"C:\'foo bar'\baz");
+        }
+    }
+
+    #[cfg(feature = "native")]
+    #[cfg(unix)]
+    #[test]
+    fn quote_bytes() {
+        use std::borrow::Cow;
+        use std::vec::Vec;
+
+        fn quote_generic<T: Quotable + ?Sized>(val: &T) -> String {
+            val.quote().to_string()
+        }
+
+        assert_eq!(b"foo"[..].quote().to_string(), "'foo'");
+        assert_eq!(b"foo bar"[..].maybe_quote().to_string(), "'foo bar'");
+        assert_eq!(quote_generic(&b"x\xFF"[..]), r#"$'x\xFF'"#);
+        assert_eq!(quote_generic(&Vec::from(&b"x\xFF"[..])), r#"$'x\xFF'"#);
+        assert_eq!(quote_generic(&Cow::Borrowed(&b"x\xFF"[..])), r#"$'x\xFF'"#);
+    }
+
     fn nest_bidi(n: usize) -> String {
         let mut out = String::new();
         for _ in 0..n {
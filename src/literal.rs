@@ -0,0 +1,243 @@
+//! Quoting at compile time, for [`quoted_literal!`](crate::quoted_literal).
+//!
+//! This is not public API, it's only reachable so that the macro can use it.
+//!
+//! It's a stripped-down version of `write()` in the native style that only
+//! knows about printable ASCII. The tests check that it gives the same output.
+
+#[cfg(not(windows))]
+use crate::shell_chars::unix::{DOUBLE_UNSAFE, SPECIAL_SHELL_CHARS, SPECIAL_SHELL_CHARS_START};
+#[cfg(windows)]
+use crate::shell_chars::windows::{DOUBLE_UNSAFE, SPECIAL_SHELL_CHARS, SPECIAL_SHELL_CHARS_START};
+
+/// Quote a string literal at compile time, if necessary.
+///
+/// This gives the same output as [`Quotable::maybe_quote`](crate::Quotable::maybe_quote),
+/// but as a `&'static str` that's built by the compiler. It only supports
+/// printable ASCII, anything else is a compile error.
+///
+/// ```
+/// use os_display::quoted_literal;
+///
+/// const FLAG: &str = quoted_literal!("--color");
+/// # #[cfg(unix)]
+/// assert_eq!(quoted_literal!("foo bar"), "'foo bar'");
+/// # #[cfg(unix)]
+/// assert_eq!(FLAG, "--color");
+/// ```
+///
+/// ```compile_fail
+/// use os_display::quoted_literal;
+///
+/// let _ = quoted_literal!("foo\nbar");
+/// ```
+///
+/// # Optional
+/// This requires the `native` feature and Rust 1.63 or newer.
+#[macro_export]
+macro_rules! quoted_literal {
+    ($text:expr) => {{
+        const TEXT: &str = $text;
+        const LEN: usize = $crate::literal::quoted_len(TEXT);
+        const BYTES: [u8; LEN] = $crate::literal::quote::<LEN>(TEXT);
+        const QUOTED: &str = match ::core::str::from_utf8(&BYTES) {
+            Ok(quoted) => quoted,
+            Err(_) => panic!("quoted_literal! produced invalid UTF-8"),
+        };
+        QUOTED
+    }};
+}
+
+#[derive(Copy, Clone)]
+enum Quoting {
+    Bare,
+    Single,
+    Double,
+    SingleEscaped,
+}
+
+const fn contains(set: &[u8], byte: u8) -> bool {
+    let mut i = 0;
+    while i < set.len() {
+        if set[i] == byte {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn starts_special(first: u8) -> bool {
+    let mut i = 0;
+    while i < SPECIAL_SHELL_CHARS_START.len() {
+        if SPECIAL_SHELL_CHARS_START[i] == first as char {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// PowerShell's extra rules for the start of a bare string. See `write()`
+/// in windows.rs for the details.
+#[cfg(windows)]
+const fn starts_windows_special(text: &[u8]) -> bool {
+    let first = text[0];
+    let second = if text.len() > 1 { text[1] } else { 0 };
+    first.is_ascii_digit()
+        || (first == b'.' && second.is_ascii_digit())
+        || (first == b'-' && text.len() > 1 && second != b'-')
+}
+
+#[cfg(not(windows))]
+const fn starts_windows_special(_text: &[u8]) -> bool {
+    false
+}
+
+const fn quoting(text: &[u8]) -> Quoting {
+    let mut is_single_safe = true;
+    let mut is_double_safe = true;
+    let mut requires_quote = text.is_empty();
+    if !requires_quote {
+        requires_quote = starts_special(text[0]) || starts_windows_special(text);
+    }
+    if cfg!(windows) && text.len() == 3 && text[0] == b'-' && text[1] == b'-' && text[2] == b'%' {
+        return Quoting::Single;
+    }
+
+    let mut i = 0;
+    while i < text.len() {
+        let byte = text[i];
+        if byte < b' ' || byte > b'~' {
+            panic!("quoted_literal! only supports printable ASCII");
+        }
+        if byte == b'\'' {
+            is_single_safe = false;
+        }
+        // Interactive shells expand history with ! inside double quotes.
+        if contains(DOUBLE_UNSAFE, byte) || (byte == b'!' && !cfg!(windows)) {
+            is_double_safe = false;
+        }
+        if contains(SPECIAL_SHELL_CHARS, byte) {
+            requires_quote = true;
+        }
+        i += 1;
+    }
+
+    if !requires_quote {
+        Quoting::Bare
+    } else if is_single_safe {
+        Quoting::Single
+    } else if is_double_safe {
+        Quoting::Double
+    } else {
+        Quoting::SingleEscaped
+    }
+}
+
+#[doc(hidden)]
+pub const fn quoted_len(text: &str) -> usize {
+    let text = text.as_bytes();
+    match quoting(text) {
+        Quoting::Bare => text.len(),
+        Quoting::Single | Quoting::Double => text.len() + 2,
+        Quoting::SingleEscaped => {
+            let mut len = 0;
+            let mut i = 0;
+            while i < text.len() {
+                if text[i] == b'\'' {
+                    // Unix: \'
+                    // Windows: ''
+                    len += 2;
+                } else {
+                    len += 1;
+                }
+                i += 1;
+            }
+            if cfg!(windows) {
+                len + 2
+            } else {
+                len + 2 * count_chunks(text)
+            }
+        }
+    }
+}
+
+/// The number of non-empty runs between single quotes.
+const fn count_chunks(text: &[u8]) -> usize {
+    let mut count = 0;
+    let mut in_chunk = false;
+    let mut i = 0;
+    while i < text.len() {
+        if text[i] == b'\'' {
+            in_chunk = false;
+        } else if !in_chunk {
+            in_chunk = true;
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[doc(hidden)]
+pub const fn quote<const N: usize>(text: &str) -> [u8; N] {
+    let text = text.as_bytes();
+    let mut out = [0; N];
+    let mut pos = 0;
+    let quoting = quoting(text);
+    let quote = match quoting {
+        Quoting::Bare => None,
+        Quoting::Double => Some(b'"'),
+        Quoting::Single | Quoting::SingleEscaped => Some(b'\''),
+    };
+    let escape_quotes = matches!(quoting, Quoting::SingleEscaped);
+
+    if let Some(quote) = quote {
+        if !(escape_quotes && !cfg!(windows) && text[0] == b'\'') {
+            out[pos] = quote;
+            pos += 1;
+        }
+    }
+    let mut i = 0;
+    while i < text.len() {
+        let byte = text[i];
+        if escape_quotes && byte == b'\'' {
+            if cfg!(windows) {
+                // PowerShell escapes quotes by doubling them.
+                out[pos] = b'\'';
+                out[pos + 1] = b'\'';
+                pos += 2;
+            } else {
+                // bash has no escapes inside single quotes, so we close the
+                // quotes around the chunk before and open new ones after.
+                if i > 0 && text[i - 1] != b'\'' {
+                    out[pos] = b'\'';
+                    pos += 1;
+                }
+                out[pos] = b'\\';
+                out[pos + 1] = b'\'';
+                pos += 2;
+                if i + 1 < text.len() && text[i + 1] != b'\'' {
+                    out[pos] = b'\'';
+                    pos += 1;
+                }
+            }
+        } else {
+            out[pos] = byte;
+            pos += 1;
+        }
+        i += 1;
+    }
+    if let Some(quote) = quote {
+        if !(escape_quotes && !cfg!(windows) && text[text.len() - 1] == b'\'') {
+            out[pos] = quote;
+            pos += 1;
+        }
+    }
+
+    if pos != N {
+        panic!("quoted_literal! miscounted");
+    }
+    out
+}
@@ -0,0 +1,88 @@
+//! Recording quoted text in structured logs, for the `tracing`, `valuable`
+//! and `defmt` features.
+
+#[cfg(feature = "tracing")]
+use tracing_core::field::{self, DisplayValue};
+
+#[cfg(feature = "alloc")]
+use crate::Prepared;
+#[cfg(any(feature = "tracing", feature = "defmt"))]
+use crate::Quoted;
+
+#[cfg(feature = "tracing")]
+impl<'a> Quoted<'a> {
+    /// Record the text as a [`tracing`](https://docs.rs/tracing) field.
+    ///
+    /// This is short for `tracing::field::display(quoted)`. The text is
+    /// quoted when the event is recorded, not when it's created, and
+    /// nothing is allocated.
+    ///
+    /// ```
+    /// use os_display::Quoted;
+    ///
+    /// # #[cfg(feature = "unix")] {
+    /// // tracing::info!(path = Quoted::unix(path).field(), "opened file");
+    /// let value = Quoted::unix("foo bar").field();
+    /// assert_eq!(format!("{:?}", value), "'foo bar'");
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    /// This requires the `tracing` feature.
+    pub fn field(self) -> DisplayValue<Self> {
+        field::display(self)
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[cfg(feature = "alloc")]
+impl<'a> Prepared<'a> {
+    /// Record the prepared output as a [`tracing`](https://docs.rs/tracing)
+    /// field, like [`Quoted::field`].
+    ///
+    /// # Optional
+    /// This requires the `tracing` and `alloc` features.
+    pub fn field(&self) -> DisplayValue<&Self> {
+        field::display(self)
+    }
+}
+
+/// A prepared value is a string. A [`Quoted`](crate::Quoted) can't be
+/// [`Valuable`](valuable::Valuable) because its output doesn't exist until
+/// it's formatted.
+///
+/// Highlighting and hyperlinks are left out, only the text is recorded.
+#[cfg(feature = "valuable")]
+#[cfg(feature = "alloc")]
+impl<'a> valuable::Valuable for Prepared<'a> {
+    fn as_value(&self) -> valuable::Value<'_> {
+        valuable::Value::String(self.text())
+    }
+
+    fn visit(&self, visit: &mut dyn valuable::Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+/// The text is formatted on the device with [`core::fmt`], like any other
+/// [`Display2Format`](defmt::Display2Format), so it's sent as a string
+/// rather than compressed.
+///
+/// # Optional
+/// This requires the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl<'a> defmt::Format for Quoted<'a> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{}", defmt::Display2Format(self))
+    }
+}
+
+/// # Optional
+/// This requires the `defmt` and `alloc` features.
+#[cfg(feature = "defmt")]
+#[cfg(feature = "alloc")]
+impl<'a> defmt::Format for Prepared<'a> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{}", defmt::Display2Format(self))
+    }
+}
@@ -0,0 +1,233 @@
+//! Lists of names separated by NUL bytes, like the output of `find -print0`,
+//! for [`quote_nul_separated`] and [`write_nul_separated`].
+
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+use crate::{Kind, Quoted, Style};
+
+/// Quote each entry in a list of entries separated by NUL bytes, like the
+/// output of `find -print0` or `git ls-files -z`.
+///
+/// ```
+/// # #[cfg(feature = "unix")] {
+/// use os_display::{quote_nul_separated, Style};
+///
+/// let list = b"foo.txt\0my file.txt\0";
+/// let quoted: Vec<String> = quote_nul_separated(list, Style::Unix)
+///     .unwrap()
+///     .map(|entry| entry.force(false).to_string())
+///     .collect();
+/// assert_eq!(quoted, ["foo.txt", "'my file.txt'"]);
+/// # }
+/// ```
+///
+/// A NUL at the very end terminates the last entry rather than starting an
+/// empty one, so lists with and without it give the same entries. Empty
+/// entries anywhere else are kept.
+///
+/// The entries are bytes, so only styles that can quote bytes are supported:
+/// [`Style::Unix`], [`Style::Sql`], [`Style::Html`] and
+/// [`Style::SystemdUnit`]. For PowerShell, decode the entries and use
+/// [`Quoted::windows`].
+///
+/// # Errors
+/// If `style` isn't one of the supported styles. The error converts to an
+/// `io::Error` of kind `InvalidInput`.
+///
+/// # Optional
+/// This requires the `unix`, `sql`, `html` or `systemd` feature, or the
+/// `native` feature on Unix.
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+pub fn quote_nul_separated(
+    list: &[u8],
+    style: Style,
+) -> Result<NulSeparated<'_>, UnsupportedStyleError> {
+    #[allow(unreachable_patterns)]
+    match style {
+        #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+        Style::Unix => (),
+        #[cfg(feature = "sql")]
+        Style::Sql => (),
+        #[cfg(feature = "html")]
+        Style::Html => (),
+        #[cfg(feature = "systemd")]
+        Style::SystemdUnit => (),
+        _ => return Err(UnsupportedStyleError { style }),
+    }
+    Ok(NulSeparated { list, style })
+}
+
+/// The error from [`quote_nul_separated`] for a style that can't quote bytes.
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnsupportedStyleError {
+    style: Style,
+}
+
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+impl UnsupportedStyleError {
+    /// The style that was asked for.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+}
+
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+impl Display for UnsupportedStyleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "style {:?} can't quote bytes", self.style)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+impl std::error::Error for UnsupportedStyleError {}
+
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+impl From<UnsupportedStyleError> for io::Error {
+    fn from(err: UnsupportedStyleError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// An iterator over the quoted entries in a list, made by
+/// [`quote_nul_separated`].
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+#[derive(Debug, Clone)]
+pub struct NulSeparated<'a> {
+    list: &'a [u8],
+    style: Style,
+}
+
+#[cfg(any(
+    feature = "unix",
+    all(feature = "native", unix),
+    feature = "sql",
+    feature = "html",
+    feature = "systemd"
+))]
+impl<'a> Iterator for NulSeparated<'a> {
+    type Item = Quoted<'a>;
+
+    fn next(&mut self) -> Option<Quoted<'a>> {
+        if self.list.is_empty() {
+            return None;
+        }
+        let entry = match self.list.iter().position(|&byte| byte == 0) {
+            Some(end) => {
+                let entry = &self.list[..end];
+                self.list = &self.list[end + 1..];
+                entry
+            }
+            None => {
+                let entry = self.list;
+                self.list = &[];
+                entry
+            }
+        };
+        Some(Quoted::from_kind(match self.style {
+            #[cfg(any(feature = "unix", all(feature = "native", unix)))]
+            Style::Unix => Kind::UnixRaw(entry),
+            #[cfg(feature = "sql")]
+            Style::Sql => Kind::Sql(entry),
+            #[cfg(feature = "html")]
+            Style::Html => Kind::Html(entry),
+            #[cfg(feature = "systemd")]
+            Style::SystemdUnit => Kind::SystemdUnit(entry),
+            // quote_nul_separated() already checked the style.
+            _ => unreachable!(),
+        }))
+    }
+}
+
+/// Write entries as a list separated by NUL bytes, the inverse of
+/// [`quote_nul_separated`] for feeding them to `xargs -0` and the like.
+///
+/// Each entry is followed by a NUL, including the last one.
+///
+/// # Errors
+/// If writing fails, or with [`io::ErrorKind::InvalidInput`] if an entry
+/// contains a NUL byte itself. Entries before that one have been written
+/// by then.
+///
+/// # Optional
+/// This requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn write_nul_separated<W, I>(mut writer: W, entries: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    for entry in entries {
+        let entry = entry.as_ref();
+        if entry.contains(&0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "entry contains a NUL byte",
+            ));
+        }
+        writer.write_all(entry)?;
+        writer.write_all(b"\0")?;
+    }
+    Ok(())
+}
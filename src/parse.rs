@@ -0,0 +1,66 @@
+//! Turning output back into the original text.
+//!
+//! This only covers the formats that are meant to be read by programs
+//! rather than shells.
+
+use crate::alloc::vec::Vec;
+
+/// Turn a unit name made by [`Quoted::systemd_unit`](crate::Quoted::systemd_unit)
+/// back into a path, like `systemd-escape --unescape --path`.
+///
+/// A suffix like `.mount` has to be removed first.
+///
+/// ```
+/// use os_display::parse;
+///
+/// assert_eq!(parse::systemd_unit(r"home-my\x2dfiles").unwrap(), b"/home/my-files");
+/// assert_eq!(parse::systemd_unit("-").unwrap(), b"/");
+/// assert_eq!(parse::systemd_unit("home--files"), None);
+/// ```
+///
+/// Returns `None` if the name has an invalid escape or doesn't stand for an
+/// absolute path without empty, `.` and `..` components, which systemd
+/// refuses too. Paths that contain a NUL byte are also refused.
+///
+/// # Optional
+/// This requires the `systemd` and `alloc` features.
+pub fn systemd_unit(name: &str) -> Option<Vec<u8>> {
+    let mut path = Vec::with_capacity(name.len() + 1);
+    path.push(b'/');
+    if name == "-" {
+        return Some(path);
+    }
+    let mut bytes = name.bytes();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'-' => path.push(b'/'),
+            b'\\' => {
+                if bytes.next() != Some(b'x') {
+                    return None;
+                }
+                let high = hex_digit(bytes.next()?)?;
+                let low = hex_digit(bytes.next()?)?;
+                path.push(high << 4 | low);
+            }
+            _ => path.push(byte),
+        }
+    }
+    if is_normalized(&path) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Check an absolute path for empty, `.` and `..` components (which includes
+/// a trailing slash) and NUL bytes.
+fn is_normalized(path: &[u8]) -> bool {
+    !path.contains(&0)
+        && path[1..]
+            .split(|&byte| byte == b'/')
+            .all(|component| !component.is_empty() && component != b"." && component != b"..")
+}
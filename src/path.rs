@@ -0,0 +1,174 @@
+//! Owned paths for error types, [`QuotedPathBuf`] and [`PathError`].
+
+use core::fmt::{self, Display, Formatter};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::Quoted;
+
+/// An owned path that's quoted when it's displayed.
+///
+/// A [`Quoted`] borrows its text, so an error type can't keep one around.
+/// Storing the path in this wrapper instead delays the quoting until the
+/// error is actually printed, if it ever is.
+///
+/// It uses [`Quotable::maybe_quote`](crate::Quotable::maybe_quote)
+/// semantics: quotes are only added if they're needed.
+///
+/// ```
+/// use os_display::QuotedPathBuf;
+///
+/// #[derive(Debug)]
+/// struct NotFound {
+///     path: QuotedPathBuf,
+/// }
+///
+/// impl std::fmt::Display for NotFound {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "{}: not found", self.path)
+///     }
+/// }
+///
+/// # #[cfg(unix)] {
+/// let err = NotFound { path: "my file.txt".into() };
+/// assert_eq!(err.to_string(), "'my file.txt': not found");
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `native` and `std` features.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuotedPathBuf {
+    path: PathBuf,
+    force_quote: bool,
+}
+
+impl QuotedPathBuf {
+    /// Store a path to quote with the default style for the platform.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        QuotedPathBuf {
+            path: path.into(),
+            force_quote: false,
+        }
+    }
+
+    /// Always add quotes, like [`Quotable::quote`](crate::Quotable::quote).
+    /// Defaults to `false`.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force_quote = force;
+        self
+    }
+
+    /// Get a [`Quoted`] for the path, to use its other options.
+    pub fn quoted(&self) -> Quoted<'_> {
+        Quoted::native_raw(self.path.as_os_str()).force(self.force_quote)
+    }
+
+    /// The path itself.
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Take the path back out.
+    pub fn into_path_buf(self) -> PathBuf {
+        self.path
+    }
+
+    /// Attach an error that happened while working with the path.
+    ///
+    /// ```
+    /// use os_display::QuotedPathBuf;
+    ///
+    /// let path = QuotedPathBuf::new("/nonexistent/foo");
+    /// let err = std::fs::read(path.as_path()).map_err(|err| path.with_error(err));
+    /// # #[cfg(unix)]
+    /// assert!(err.unwrap_err().to_string().starts_with("/nonexistent/foo: "));
+    /// ```
+    pub fn with_error<E>(self, error: E) -> PathError<E> {
+        PathError { path: self, error }
+    }
+}
+
+impl Display for QuotedPathBuf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.quoted().fmt(f)
+    }
+}
+
+impl AsRef<Path> for QuotedPathBuf {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl From<PathBuf> for QuotedPathBuf {
+    fn from(path: PathBuf) -> Self {
+        QuotedPathBuf::new(path)
+    }
+}
+
+impl<'a> From<&'a Path> for QuotedPathBuf {
+    fn from(path: &'a Path) -> Self {
+        QuotedPathBuf::new(path)
+    }
+}
+
+impl<'a> From<&'a str> for QuotedPathBuf {
+    fn from(path: &'a str) -> Self {
+        QuotedPathBuf::new(path)
+    }
+}
+
+impl From<QuotedPathBuf> for PathBuf {
+    fn from(path: QuotedPathBuf) -> Self {
+        path.path
+    }
+}
+
+/// An error with the path it happened to, from
+/// [`QuotedPathBuf::with_error`].
+///
+/// It's displayed as the quoted path, a colon and the error, which is the
+/// same layout as most command line tools. Its [`source`](Error::source) is
+/// the source of the original error, since the original error's message is
+/// already included, so that a chain of causes doesn't repeat it.
+///
+/// It works with `?` in functions that return an `anyhow::Error` or a
+/// `Box<dyn Error>`.
+///
+/// # Optional
+/// This requires the `native` and `std` features.
+#[derive(Debug, Clone)]
+pub struct PathError<E> {
+    path: QuotedPathBuf,
+    error: E,
+}
+
+impl<E> PathError<E> {
+    /// The path that the error happened to.
+    pub fn path(&self) -> &QuotedPathBuf {
+        &self.path
+    }
+
+    /// The original error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Split into the path and the original error.
+    pub fn into_parts(self) -> (QuotedPathBuf, E) {
+        (self.path, self.error)
+    }
+}
+
+impl<E: Display> Display for PathError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for PathError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
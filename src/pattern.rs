@@ -0,0 +1,85 @@
+//! Escaping of pattern syntax, for [`Quoted::glob_safe`](crate::Quoted::glob_safe)
+//! and [`Quoted::as_regex`](crate::Quoted::as_regex).
+//!
+//! This happens before quoting, the quoting then has to preserve the escapes.
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+
+/// The kind of pattern that the text is turned into.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Pattern {
+    Glob,
+    Regex,
+}
+
+/// Characters with a special meaning in a glob pattern in a POSIX shell.
+/// Braces aren't among them, but bash expands them in many of the same places.
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+const UNIX_SPECIAL: &[u8] = b"*?[]{}\\";
+
+/// Characters with a special meaning in a PowerShell wildcard pattern.
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+const POWERSHELL_SPECIAL: &[u8] = b"*?[]`";
+
+/// Characters with a special meaning in POSIX extended regular expressions.
+/// PCRE, .NET and most other flavors agree on these, and escaping them with a
+/// backslash works everywhere.
+const REGEX_SPECIAL: &[u8] = b".[]()*+?{}|^$\\";
+
+/// Put `escape` before every special character.
+fn escape<T: Copy + PartialEq + From<u8>>(items: &[T], special: &[u8], escape: u8) -> Vec<T> {
+    let mut escaped = Vec::with_capacity(items.len());
+    for &item in items {
+        if special.iter().any(|&byte| T::from(byte) == item) {
+            escaped.push(T::from(escape));
+        }
+        escaped.push(item);
+    }
+    escaped
+}
+
+/// Only ASCII is inserted before ASCII, so UTF-8 stays valid.
+fn escape_str(text: &str, special: &[u8], escape_char: u8) -> String {
+    String::from_utf8(escape(text.as_bytes(), special, escape_char)).unwrap()
+}
+
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+fn unix_syntax(pattern: Pattern) -> (&'static [u8], u8) {
+    match pattern {
+        Pattern::Glob => (UNIX_SPECIAL, b'\\'),
+        Pattern::Regex => (REGEX_SPECIAL, b'\\'),
+    }
+}
+
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+fn powershell_syntax(pattern: Pattern) -> (&'static [u8], u8) {
+    match pattern {
+        Pattern::Glob => (POWERSHELL_SPECIAL, b'`'),
+        Pattern::Regex => (REGEX_SPECIAL, b'\\'),
+    }
+}
+
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+pub(crate) fn unix_str(text: &str, pattern: Pattern) -> String {
+    let (special, escape_char) = unix_syntax(pattern);
+    escape_str(text, special, escape_char)
+}
+
+#[cfg(any(feature = "unix", all(feature = "native", unix)))]
+pub(crate) fn unix_bytes(bytes: &[u8], pattern: Pattern) -> Vec<u8> {
+    let (special, escape_char) = unix_syntax(pattern);
+    escape(bytes, special, escape_char)
+}
+
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+pub(crate) fn powershell_str(text: &str, pattern: Pattern) -> String {
+    let (special, escape_char) = powershell_syntax(pattern);
+    escape_str(text, special, escape_char)
+}
+
+#[cfg(any(feature = "windows", all(feature = "native", feature = "std", windows)))]
+pub(crate) fn powershell_units(units: &[u16], pattern: Pattern) -> Vec<u16> {
+    let (special, escape_char) = powershell_syntax(pattern);
+    escape(units, special, escape_char)
+}
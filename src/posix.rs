@@ -0,0 +1,224 @@
+//! A small POSIX shell tokenizer, so tests can check that bash/ksh output
+//! stands for the original text without running a real shell.
+//!
+//! It covers what a single argument may contain: bare characters,
+//! backslashes, single quotes, double quotes and bash's `$'...'`. Anything
+//! the shell would do something with instead of passing it on, like a
+//! variable, a glob or a second word, is an error. So is a command
+//! substitution, which rules out [`Fallback::PrintfCommand`](crate::Fallback).
+//!
+//! The fuzz targets remain the real check, this only keeps them honest on
+//! machines that don't have all those shells installed.
+
+use std::vec::Vec;
+
+/// Why input isn't a single word that the shell passes on as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseError {
+    /// A character that splits words or starts an operator, like a space
+    /// or `;`.
+    Separator(char),
+    /// A character that the shell would expand, like `$` or `*`.
+    Expansion(char),
+    /// The input ended inside quotes or after a backslash.
+    Unterminated,
+}
+
+/// Parse a single shell word and return the bytes it stands for.
+pub(crate) fn parse_word(input: &str) -> Result<Vec<u8>, ParseError> {
+    let mut word = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut at_start = true;
+    let mut equals = false;
+    let mut open_brace = false;
+    let mut close_brace = false;
+    while let Some(ch) = chars.next() {
+        match ch {
+            ' ' | '\t' | '\n' | '|' | '&' | ';' | '<' | '>' | '(' | ')' => {
+                return Err(ParseError::Separator(ch))
+            }
+            '\\' => match chars.next() {
+                // A line continuation disappears entirely.
+                Some('\n') => (),
+                Some(ch) => push_char(&mut word, ch),
+                None => return Err(ParseError::Unterminated),
+            },
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(ch) => push_char(&mut word, ch),
+                    None => return Err(ParseError::Unterminated),
+                }
+            },
+            '"' => parse_double(&mut chars, &mut word)?,
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                parse_ansi_c(&mut chars, &mut word)?;
+            }
+            // Variables and command substitution, and globs.
+            '$' | '`' | '*' | '?' | '[' => return Err(ParseError::Expansion(ch)),
+            // A comment, history expansion and zsh's `=cmd` expansion.
+            '#' | '!' | '=' if at_start => return Err(ParseError::Expansion(ch)),
+            // Tilde expansion, which bash also does after the `=` in
+            // something that looks like an assignment.
+            '~' if at_start || equals => return Err(ParseError::Expansion(ch)),
+            _ => {
+                equals |= ch == '=';
+                open_brace |= ch == '{';
+                close_brace |= ch == '}';
+                push_char(&mut word, ch);
+            }
+        }
+        at_start = false;
+    }
+    // Brace expansion needs both braces. It's more picky than that, but
+    // there's no need to be precise.
+    if open_brace && close_brace {
+        return Err(ParseError::Expansion('{'));
+    }
+    Ok(word)
+}
+
+type Chars<'a> = core::iter::Peekable<core::str::Chars<'a>>;
+
+fn push_char(word: &mut Vec<u8>, ch: char) {
+    let mut buf = [0; 4];
+    word.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+}
+
+/// Inside double quotes a backslash only escapes characters that would
+/// otherwise mean something.
+fn parse_double(chars: &mut Chars<'_>, word: &mut Vec<u8>) -> Result<(), ParseError> {
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(()),
+            Some('\\') => match chars.next() {
+                Some('\n') => (),
+                Some(ch @ '"') | Some(ch @ '\\') | Some(ch @ '$') | Some(ch @ '`') => {
+                    push_char(word, ch)
+                }
+                Some(ch) => {
+                    word.push(b'\\');
+                    push_char(word, ch);
+                }
+                None => return Err(ParseError::Unterminated),
+            },
+            Some(ch @ '$') | Some(ch @ '`') => return Err(ParseError::Expansion(ch)),
+            Some(ch) => push_char(word, ch),
+            None => return Err(ParseError::Unterminated),
+        }
+    }
+}
+
+/// bash's `$'...'`, after the opening quote. Unknown escapes are kept
+/// as-is, backslash included, like bash does.
+fn parse_ansi_c(chars: &mut Chars<'_>, word: &mut Vec<u8>) -> Result<(), ParseError> {
+    loop {
+        let ch = match chars.next() {
+            Some('\'') => return Ok(()),
+            Some('\\') => chars.next().ok_or(ParseError::Unterminated)?,
+            Some(ch) => {
+                push_char(word, ch);
+                continue;
+            }
+            None => return Err(ParseError::Unterminated),
+        };
+        match ch {
+            'a' => word.push(0x07),
+            'b' => word.push(0x08),
+            'e' | 'E' => word.push(0x1B),
+            'f' => word.push(0x0C),
+            'n' => word.push(b'\n'),
+            'r' => word.push(b'\r'),
+            't' => word.push(b'\t'),
+            'v' => word.push(0x0B),
+            '\\' | '\'' | '"' | '?' => word.push(ch as u8),
+            '0'..='7' => {
+                let value = digits(chars, 8, 2, ch.to_digit(8).unwrap());
+                word.push(value as u8);
+            }
+            'x' => match chars.peek().and_then(|ch| ch.to_digit(16)) {
+                Some(first) => {
+                    chars.next();
+                    word.push(digits(chars, 16, 1, first) as u8);
+                }
+                None => word.extend_from_slice(b"\\x"),
+            },
+            'u' | 'U' => {
+                let max = if ch == 'u' { 4 } else { 8 };
+                match chars.peek().and_then(|ch| ch.to_digit(16)) {
+                    Some(first) => {
+                        chars.next();
+                        let value = digits(chars, 16, max - 1, first);
+                        let ch = core::char::from_u32(value)
+                            .unwrap_or(core::char::REPLACEMENT_CHARACTER);
+                        push_char(word, ch);
+                    }
+                    None => {
+                        word.push(b'\\');
+                        push_char(word, ch);
+                    }
+                }
+            }
+            'c' => {
+                let ch = chars.next().ok_or(ParseError::Unterminated)?;
+                word.push(ch.to_ascii_uppercase() as u8 ^ 0x40);
+            }
+            _ => {
+                word.push(b'\\');
+                push_char(word, ch);
+            }
+        }
+    }
+}
+
+/// Read up to `max` more digits and add them to `value`.
+fn digits(chars: &mut Chars<'_>, radix: u32, max: usize, mut value: u32) -> u32 {
+    for _ in 0..max {
+        match chars.peek().and_then(|ch| ch.to_digit(radix)) {
+            Some(digit) => {
+                chars.next();
+                value = value * radix + digit;
+            }
+            None => break,
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_word() {
+        let ok = |input: &str, expected: &[u8]| assert_eq!(parse_word(input).unwrap(), expected);
+        ok("foo", b"foo");
+        ok("", b"");
+        ok("''", b"");
+        ok("a#b", b"a#b");
+        ok("a!~", b"a!~");
+        ok("a={", b"a={");
+        ok(r"a\ b", b"a b");
+        ok("'a b'\"c d\"", b"a bc d");
+        ok(r#""\$\a\"""#, b"$\\a\"");
+        ok(r"$'\x41\x4g\101é\e\'\q'", b"A\x04gA\xC3\xA9\x1B'\\q");
+        ok(r"$'\xFFF'", b"\xFFF");
+        ok(r"$'\cA'", b"\x01");
+
+        let err = |input: &str, expected: ParseError| assert_eq!(parse_word(input), Err(expected));
+        err("a b", ParseError::Separator(' '));
+        err("a;", ParseError::Separator(';'));
+        err("$foo", ParseError::Expansion('$'));
+        err("\"$foo\"", ParseError::Expansion('$'));
+        err("*.txt", ParseError::Expansion('*'));
+        err("~/foo", ParseError::Expansion('~'));
+        err("#foo", ParseError::Expansion('#'));
+        err("a=~", ParseError::Expansion('~'));
+        err("a{b,c}", ParseError::Expansion('{'));
+        err("\"$(printf 'a')\"", ParseError::Expansion('$'));
+        err("'foo", ParseError::Unterminated);
+        err("$'foo\\'", ParseError::Unterminated);
+        err("foo\\", ParseError::Unterminated);
+    }
+}
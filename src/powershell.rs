@@ -0,0 +1,226 @@
+//! A small PowerShell tokenizer, so tests can check that PowerShell output
+//! stands for the original text without running PowerShell. It's the
+//! counterpart of [`posix`](crate::posix).
+//!
+//! It covers what a single argument may contain: bare characters, `'...'`
+//! and `"..."` with backtick escapes, including the typographic quotes that
+//! PowerShell treats the same way. Anything that PowerShell would do
+//! something with instead of passing it on is an error. So are the
+//! concatenations made by [`Quoted::expression`](crate::Quoted::expression).
+//!
+//! Arguments for external commands go through another round of parsing in
+//! the command that receives them, which this doesn't attempt.
+
+use std::vec::Vec;
+
+/// Why input isn't a single argument that PowerShell passes on as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseError {
+    /// A character that splits arguments or starts an operator, like a
+    /// space or `;`, or anything after a quoted argument.
+    Separator(char),
+    /// A character that PowerShell would expand, like `$`.
+    Expansion(char),
+    /// The input ended inside quotes or after a backtick.
+    Unterminated,
+}
+
+/// Parse a single argument and return the UTF-16 it stands for.
+pub(crate) fn parse_argument(input: &str) -> Result<Vec<u16>, ParseError> {
+    // The stop-parsing token.
+    if input == "--%" {
+        return Err(ParseError::Expansion('%'));
+    }
+    let mut arg = Vec::new();
+    let mut chars = input.chars().peekable();
+    match chars.peek() {
+        // A splat, a comment, home directory expansion and whatever a lone
+        // `!` does.
+        Some(&ch @ '@') | Some(&ch @ '#') | Some(&ch @ '~') | Some(&ch @ '!') => {
+            return Err(ParseError::Expansion(ch))
+        }
+        // If an argument starts with a quote then it ends with the quote,
+        // and anything after it is the next argument.
+        Some(&ch) if is_single_quote(ch) || is_double_quote(ch) => {
+            chars.next();
+            if is_single_quote(ch) {
+                parse_single(&mut chars, &mut arg)?;
+            } else {
+                parse_double(&mut chars, &mut arg)?;
+            }
+            return match chars.next() {
+                Some(ch) => Err(ParseError::Separator(ch)),
+                None => Ok(arg),
+            };
+        }
+        _ => (),
+    }
+    while let Some(ch) = chars.next() {
+        match ch {
+            _ if is_whitespace(ch) => return Err(ParseError::Separator(ch)),
+            '\n' | '|' | '&' | ';' | '<' | '>' | '(' | ')' | '{' | '}' | ',' => {
+                return Err(ParseError::Separator(ch))
+            }
+            '$' | '`' | '*' | '?' | '[' => return Err(ParseError::Expansion(ch)),
+            // Further on, quotes are part of the same argument.
+            _ if is_single_quote(ch) => parse_single(&mut chars, &mut arg)?,
+            _ if is_double_quote(ch) => parse_double(&mut chars, &mut arg)?,
+            _ => push_char(&mut arg, ch),
+        }
+    }
+    Ok(arg)
+}
+
+type Chars<'a> = core::iter::Peekable<core::str::Chars<'a>>;
+
+fn push_char(arg: &mut Vec<u16>, ch: char) {
+    let mut buf = [0; 2];
+    arg.extend_from_slice(ch.encode_utf16(&mut buf));
+}
+
+/// After the opening quote. A quote is escaped by doubling it, and it's the
+/// second one that's kept.
+fn parse_single(chars: &mut Chars<'_>, arg: &mut Vec<u16>) -> Result<(), ParseError> {
+    loop {
+        match chars.next() {
+            Some(ch) if is_single_quote(ch) => match chars.peek() {
+                Some(&next) if is_single_quote(next) => {
+                    chars.next();
+                    push_char(arg, next);
+                }
+                _ => return Ok(()),
+            },
+            Some(ch) => push_char(arg, ch),
+            None => return Err(ParseError::Unterminated),
+        }
+    }
+}
+
+/// After the opening quote. Quotes can be doubled here too.
+fn parse_double(chars: &mut Chars<'_>, arg: &mut Vec<u16>) -> Result<(), ParseError> {
+    loop {
+        match chars.next() {
+            Some(ch) if is_double_quote(ch) => match chars.peek() {
+                Some(&next) if is_double_quote(next) => {
+                    chars.next();
+                    push_char(arg, next);
+                }
+                _ => return Ok(()),
+            },
+            Some('`') => parse_backtick(chars, arg)?,
+            Some('$') => return Err(ParseError::Expansion('$')),
+            Some(ch) => push_char(arg, ch),
+            None => return Err(ParseError::Unterminated),
+        }
+    }
+}
+
+/// An escape sequence inside `"..."`, after the backtick. Other characters
+/// are kept without the backtick.
+fn parse_backtick(chars: &mut Chars<'_>, arg: &mut Vec<u16>) -> Result<(), ParseError> {
+    let ch = chars.next().ok_or(ParseError::Unterminated)?;
+    let unit = match ch {
+        '0' => 0,
+        'a' => 0x07,
+        'b' => 0x08,
+        'e' => 0x1B,
+        'f' => 0x0C,
+        'n' => 0x0A,
+        'r' => 0x0D,
+        't' => 0x09,
+        'v' => 0x0B,
+        'u' if chars.peek() == Some(&'{') => {
+            chars.next();
+            let mut value = 0;
+            let mut len = 0;
+            loop {
+                match chars.next() {
+                    Some('}') if len > 0 => break,
+                    Some(ch) if len < 6 && ch.is_ascii_hexdigit() => {
+                        value = value * 16 + ch.to_digit(16).unwrap();
+                        len += 1;
+                    }
+                    Some(_) => return Err(ParseError::Expansion('u')),
+                    None => return Err(ParseError::Unterminated),
+                }
+            }
+            // Surrogates are allowed, they just end up unpaired.
+            match core::char::from_u32(value) {
+                Some(ch) => push_char(arg, ch),
+                None if value < 0x10000 => arg.push(value as u16),
+                None => return Err(ParseError::Expansion('u')),
+            }
+            return Ok(());
+        }
+        _ => {
+            push_char(arg, ch);
+            return Ok(());
+        }
+    };
+    arg.push(unit);
+    Ok(())
+}
+
+/// The whitespace that splits arguments. Written out separately from
+/// windows.rs, so that a mistake there doesn't slip through.
+fn is_whitespace(ch: char) -> bool {
+    match ch {
+        '\t' | '\x0B' | '\x0C' | '\r' | '\u{85}' | '\u{A0}' => true,
+        _ => ch.is_whitespace() && ch != '\n',
+    }
+}
+
+fn is_single_quote(ch: char) -> bool {
+    match ch {
+        '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => true,
+        _ => false,
+    }
+}
+
+fn is_double_quote(ch: char) -> bool {
+    match ch {
+        '"' | '\u{201C}' | '\u{201D}' | '\u{201E}' => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str) -> Vec<u16> {
+        text.encode_utf16().collect()
+    }
+
+    #[test]
+    fn test_parse_argument() {
+        let ok = |input: &str, expected: &str| {
+            assert_eq!(parse_argument(input).unwrap(), utf16(expected))
+        };
+        ok("foo", "foo");
+        ok("''", "");
+        ok(r"C:\foo", r"C:\foo");
+        ok("'a b'", "a b");
+        ok("'it''s'", "it's");
+        ok("'it'\u{2019}s'", "it\u{2019}s");
+        ok("\u{2018}a'", "a");
+        ok("\"a`$b`\"c\"\"d\"", "a$b\"c\"d");
+        ok("\"`n`t`0`u{E9}`q\"", "\n\t\0\u{E9}q");
+        ok("foo'bar'\"baz\"", "foobarbaz");
+        assert_eq!(parse_argument("\"`u{D800}\"").unwrap(), [0xD800]);
+
+        let err =
+            |input: &str, expected: ParseError| assert_eq!(parse_argument(input), Err(expected));
+        err("a b", ParseError::Separator(' '));
+        err("a\u{A0}b", ParseError::Separator('\u{A0}'));
+        err("a,b", ParseError::Separator(','));
+        err("'a'b", ParseError::Separator('b'));
+        err("$foo", ParseError::Expansion('$'));
+        err("\"$foo\"", ParseError::Expansion('$'));
+        err("@foo", ParseError::Expansion('@'));
+        err("--%", ParseError::Expansion('%'));
+        err("(\"a\" + [char]0xD800)", ParseError::Separator('('));
+        err("'foo", ParseError::Unterminated);
+        err("\"foo`\"", ParseError::Unterminated);
+    }
+}
@@ -0,0 +1,178 @@
+//! Output that's worked out once and then formatted many times.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::alloc::string::String;
+use crate::alloc::vec::Vec;
+
+use crate::hyperlink::Link;
+use crate::sink::Sink;
+use crate::{ControlStyle, Kind, Options, Original, Quoted};
+
+/// A [`Quoted`] that has already decided how to quote and escape its text.
+///
+/// Formatting a `Quoted` scans the text every time. That's usually fine, but
+/// a value that's kept around and formatted over and over again (like a path
+/// inside an error) can do the work up front with [`Quoted::prepared`].
+///
+/// All the options have to be set before preparing. Format specifiers like
+/// `{:>30}` and `{:.20}` still work.
+///
+/// ```
+/// use os_display::Quoted;
+///
+/// # #[cfg(feature = "unix")] {
+/// let prepared = Quoted::unix("foo\nbar").prepared();
+/// assert_eq!(prepared.to_string(), "$'foo\\nbar'");
+/// assert_eq!(prepared.as_quoted().display_len(), 11);
+/// # }
+/// ```
+///
+/// # Optional
+/// This requires the `alloc` feature.
+#[derive(Debug, Clone)]
+pub struct Prepared<'a> {
+    recording: Recording,
+    opts: Options,
+    link: Option<Link<'a>>,
+}
+
+impl<'a> Prepared<'a> {
+    pub(crate) fn new(quoted: &Quoted<'a>) -> Self {
+        let mut recording = Recording {
+            text: String::new(),
+            pieces: Vec::new(),
+            powershell: quoted.source.is_powershell(),
+        };
+        // This can't fail, our Sink impl doesn't produce errors.
+        let _ = quoted.write(&mut recording);
+        recording.text.shrink_to_fit();
+        recording.pieces.shrink_to_fit();
+        Prepared {
+            recording,
+            // The recording already has every level of quoting.
+            opts: Options {
+                remote: 0,
+                pattern: None,
+                control_style: ControlStyle::Escape,
+                #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+                iex_safe: false,
+                #[cfg(feature = "windows")]
+                cmd_context: None,
+                #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+                crontab: false,
+                ..quoted.opts
+            },
+            link: quoted.link,
+        }
+    }
+
+    /// The output without highlighting or a hyperlink.
+    #[cfg(feature = "valuable")]
+    pub(crate) fn text(&self) -> &str {
+        &self.recording.text
+    }
+
+    /// Get a [`Quoted`] that replays the prepared output, to use its methods.
+    ///
+    /// The output is already quoted, so options like [`Quoted::for_remote`]
+    /// don't change it.
+    pub fn as_quoted(&self) -> Quoted<'_> {
+        Quoted {
+            source: Kind::Prepared(&self.recording),
+            opts: self.opts,
+            link: self.link,
+        }
+    }
+}
+
+impl<'a> Display for Prepared<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.as_quoted().fmt(f)
+    }
+}
+
+/// The output of a writer, split up into pieces.
+#[derive(Debug, Clone)]
+pub(crate) struct Recording {
+    text: String,
+    pieces: Vec<Piece>,
+    /// Whether the output is PowerShell syntax.
+    powershell: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Piece {
+    kind: PieceKind,
+    /// Where the piece ends in the text. It starts where the last one ended.
+    end: usize,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum PieceKind {
+    Open,
+    Close,
+    Literal,
+    Escape(Original),
+}
+
+impl Recording {
+    fn push(&mut self, kind: PieceKind, text: &str) {
+        self.text.push_str(text);
+        let end = self.text.len();
+        if let (PieceKind::Literal, Some(last)) = (kind, self.pieces.last_mut()) {
+            if let PieceKind::Literal = last.kind {
+                last.end = end;
+                return;
+            }
+        }
+        self.pieces.push(Piece { kind, end });
+    }
+
+    pub(crate) fn is_powershell(&self) -> bool {
+        self.powershell
+    }
+
+    /// The length of the recorded output.
+    pub(crate) fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Pass the recorded output on to another sink.
+    pub(crate) fn replay(&self, out: &mut dyn Sink) -> fmt::Result {
+        let mut start = 0;
+        for piece in &self.pieces {
+            let text = &self.text[start..piece.end];
+            match piece.kind {
+                PieceKind::Open => out.open(text)?,
+                PieceKind::Close => out.close(text)?,
+                PieceKind::Literal => out.literal(text)?,
+                PieceKind::Escape(original) => out.escape(original, text)?,
+            }
+            start = piece.end;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for Recording {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.push(PieceKind::Open, text);
+        Ok(())
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.push(PieceKind::Close, text);
+        Ok(())
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.push(PieceKind::Literal, text);
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        self.push(PieceKind::Escape(original), rendered);
+        Ok(())
+    }
+}
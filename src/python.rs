@@ -0,0 +1,176 @@
+//! Python bindings, for the `pyo3` feature.
+//!
+//! This makes a Python module with two functions:
+//!
+//! ```python
+//! import os_display
+//!
+//! os_display.quote("foo bar")                  # "'foo bar'" on Unix
+//! os_display.maybe_quote("foo", style="unix")  # "foo"
+//! os_display.quote(b"\xff", style="unix")      # "$'\\xFF'"
+//! os_display.STYLES                            # ("unix", "windows", ...)
+//! ```
+//!
+//! The style is the name of a [`Quoted`] constructor, like `"unix"` or
+//! `"git_config"`, and defaults to `"native"` if the `native` feature is
+//! enabled. Only the styles whose features are enabled are available, and
+//! [`STYLES`] lists them. The text can be `str` or `bytes`. `bytes` don't
+//! have to be valid UTF-8 for the `"unix"` style (or for `"native"` on
+//! Unix), for the other styles they're decoded first.
+//!
+//! To build an extension module, use
+//! `cargo rustc --release --features pyo3 --crate-type cdylib` and rename the
+//! library to `os_display.so` (or `os_display.pyd` on Windows), or call
+//! [`register`] from the `#[pymodule]` of your own crate to add the functions
+//! to it. The output is exactly the same as from Rust.
+//!
+//! # Optional
+//! This requires the `pyo3` and `std` features.
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use crate::{Quoted, Style};
+
+/// The names of the styles that are enabled, `"native"` first and then
+/// in the same order as [`Style`].
+pub const STYLES: &[&str] = &[
+    #[cfg(feature = "native")]
+    "native",
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    "unix",
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    "windows",
+    #[cfg(feature = "windows")]
+    "windows_argv",
+    #[cfg(feature = "sql")]
+    "sql",
+    #[cfg(feature = "html")]
+    "html",
+    #[cfg(feature = "systemd")]
+    "systemd_unit",
+    #[cfg(feature = "dotenv")]
+    "dotenv",
+    #[cfg(feature = "dockerfile")]
+    "dockerfile",
+    #[cfg(feature = "cmake")]
+    "cmake",
+    #[cfg(feature = "reg")]
+    "reg",
+    #[cfg(feature = "sudoers")]
+    "sudoers",
+    #[cfg(feature = "git")]
+    "gitignore",
+    #[cfg(feature = "git")]
+    "gitattributes",
+    #[cfg(feature = "git")]
+    "git_config",
+    #[cfg(feature = "sanitize")]
+    "sanitize",
+];
+
+fn style(name: &str) -> PyResult<Style> {
+    Ok(match name {
+        #[cfg(feature = "native")]
+        "native" => Style::native(),
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        "unix" => Style::Unix,
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        "windows" => Style::Powershell,
+        #[cfg(feature = "windows")]
+        "windows_argv" => Style::WindowsArgv,
+        #[cfg(feature = "sql")]
+        "sql" => Style::Sql,
+        #[cfg(feature = "html")]
+        "html" => Style::Html,
+        #[cfg(feature = "systemd")]
+        "systemd_unit" => Style::SystemdUnit,
+        #[cfg(feature = "dotenv")]
+        "dotenv" => Style::Dotenv,
+        #[cfg(feature = "dockerfile")]
+        "dockerfile" => Style::Dockerfile,
+        #[cfg(feature = "cmake")]
+        "cmake" => Style::Cmake,
+        #[cfg(feature = "reg")]
+        "reg" => Style::Reg,
+        #[cfg(feature = "sudoers")]
+        "sudoers" => Style::Sudoers,
+        #[cfg(feature = "git")]
+        "gitignore" => Style::GitIgnore,
+        #[cfg(feature = "git")]
+        "gitattributes" => Style::GitAttributes,
+        #[cfg(feature = "git")]
+        "git_config" => Style::GitConfig,
+        #[cfg(feature = "sanitize")]
+        "sanitize" => Style::Sanitize,
+        _ => {
+            return Err(PyValueError::new_err(std::format!(
+                "unknown style {:?}, expected one of {:?}",
+                name,
+                STYLES
+            )))
+        }
+    })
+}
+
+/// A `str` or `bytes` argument.
+#[derive(FromPyObject)]
+enum Text {
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+fn format(text: Text, style_name: Option<&str>, force: bool) -> PyResult<String> {
+    let style = match style_name {
+        Some(name) => style(name)?,
+        #[cfg(feature = "native")]
+        None => Style::native(),
+        #[cfg(not(feature = "native"))]
+        None => return Err(PyValueError::new_err("a style is required")),
+    };
+    let text = match text {
+        Text::Str(text) => text,
+        Text::Bytes(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+            Err(err) if style == Style::Unix => {
+                return Ok(Quoted::unix_raw(err.as_bytes()).force(force).to_string())
+            }
+            Err(err) => return Err(PyValueError::new_err(err.to_string())),
+        },
+    };
+    Ok(Quoted::new(style, &text).force(force).to_string())
+}
+
+/// Quote text, adding quotes even if they're not needed.
+#[pyfunction]
+#[pyo3(signature = (text, style = None))]
+fn quote(text: Text, style: Option<&str>) -> PyResult<String> {
+    format(text, style, true)
+}
+
+/// Quote text, adding quotes only if they're needed.
+#[pyfunction]
+#[pyo3(signature = (text, style = None))]
+fn maybe_quote(text: Text, style: Option<&str>) -> PyResult<String> {
+    format(text, style, false)
+}
+
+/// Add `quote`, `maybe_quote` and `STYLES` to a Python module.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(quote, module)?)?;
+    module.add_function(wrap_pyfunction!(maybe_quote, module)?)?;
+    module.add("STYLES", PyTuple::new(module.py(), STYLES)?)?;
+    Ok(())
+}
+
+/// The `os_display` extension module.
+#[pymodule]
+#[pyo3(name = "os_display")]
+fn os_display_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    register(module)
+}
@@ -0,0 +1,99 @@
+//! String values in `.reg` files, for [`Quoted::reg`](crate::Quoted::reg).
+
+use core::char::{decode_utf16, DecodeUtf16Error, REPLACEMENT_CHARACTER};
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::{Options, Original};
+
+/// Whether the value can't be written as a quoted string, with `None` for
+/// unpaired surrogates. A string can't span lines, and regedit has no
+/// escapes other than `\\` and `\"`.
+fn needs_hex<I>(mut chars: I, opts: &Options) -> bool
+where
+    I: Iterator<Item = Option<char>> + Clone,
+{
+    let escapes = opts.text_escapes(chars.clone());
+    chars.any(|ch| match ch {
+        Some(ch) => opts.escapes(ch) || escapes.contains(ch),
+        None => true,
+    })
+}
+
+pub(crate) fn write_str(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if needs_hex(text.chars().map(Some), opts) {
+        return write_hex(out, decode_utf16(text.encode_utf16()));
+    }
+
+    out.open("\"")?;
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        let escape = match ch {
+            '\\' => "\\\\",
+            '"' => "\\\"",
+            _ => continue,
+        };
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        out.escape(Original::Char(ch), escape)?;
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    out.close("\"")
+}
+
+pub(crate) fn write_units(out: &mut dyn Sink, units: &[u16], opts: &Options) -> fmt::Result {
+    if needs_hex(decode_utf16(units.iter().cloned()).map(Result::ok), opts) {
+        return write_hex(out, decode_utf16(units.iter().cloned()));
+    }
+
+    out.open("\"")?;
+    for ch in decode_utf16(units.iter().cloned()) {
+        // We checked for unpaired surrogates already.
+        let ch = ch.unwrap_or(REPLACEMENT_CHARACTER);
+        match ch {
+            '\\' => out.escape(Original::Char(ch), "\\\\")?,
+            '"' => out.escape(Original::Char(ch), "\\\"")?,
+            _ => out.literal(ch.encode_utf8(&mut [0; 4]))?,
+        }
+    }
+    out.close("\"")
+}
+
+/// A `REG_SZ` value written as little-endian UTF-16, with the terminating
+/// null. Every character is an escape, so that truncation can't split one.
+fn write_hex<I>(out: &mut dyn Sink, chars: I) -> fmt::Result
+where
+    I: Iterator<Item = Result<char, DecodeUtf16Error>>,
+{
+    out.open("hex(1):")?;
+    for ch in chars {
+        match ch {
+            Ok(ch) => {
+                let mut buf = [0; 2];
+                let units = ch.encode_utf16(&mut buf);
+                out.escape_fmt(Original::Char(ch), format_args!("{}", Hex(units)))?;
+            }
+            Err(err) => {
+                let unit = err.unpaired_surrogate();
+                out.escape_fmt(Original::Unit(unit), format_args!("{}", Hex(&[unit])))?;
+            }
+        }
+    }
+    out.close("00,00")
+}
+
+/// Formats code units as comma-terminated bytes, like `61,00,`.
+struct Hex<'a>(&'a [u16]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for unit in self.0 {
+            write!(f, "{:02x},{:02x},", unit & 0xFF, unit >> 8)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,538 @@
+//! Text that's only meant to be displayed, for
+//! [`Quoted::sanitize`](crate::Quoted::sanitize).
+
+use core::fmt::{self, Write};
+use core::str::from_utf8;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::security::{self, Bidi};
+#[cfg(feature = "std")]
+use crate::sink::{fmt_error, IoWrite};
+use crate::sink::{Plain, Sink};
+use crate::utf8::chars;
+#[cfg(feature = "std")]
+use crate::utf8::Pending;
+use crate::util::utf8_chunks;
+use crate::{Options, Original};
+
+/// This works like the HTML writer, except that nothing else needs escaping
+/// and the escapes only have to be readable.
+pub(crate) fn write(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    let text_escapes = opts.text_escapes(chars(text));
+
+    let mut escape_bidi = true;
+    let mut isolate = false;
+    if let Ok(text) = from_utf8(text) {
+        if text.chars().any(security::is_bidi) {
+            match security::apply_bidi_policy(text, opts.bidi) {
+                Bidi::Ok => escape_bidi = false,
+                Bidi::Unterminated => {
+                    escape_bidi = false;
+                    isolate = true;
+                }
+                Bidi::Suspicious => (),
+            }
+        }
+    }
+
+    let mut escaper = Escaper::new(opts.strip_ansi, opts.hex_c1);
+    let escapes = |ch| {
+        crate::requires_escape(ch)
+            || (escape_bidi && security::is_bidi(ch))
+            || (opts.escape_hidden_text && security::is_hidden_text(ch))
+            || (opts.escape_invisible && security::is_invisible(ch))
+            || text_escapes.contains(ch)
+    };
+
+    if isolate {
+        out.open("\u{2068}")?;
+    }
+    for chunk in utf8_chunks(text) {
+        match chunk {
+            Ok(chunk) => escaper.text(out, chunk, escapes)?,
+            Err(byte) => escaper.byte(out, byte)?,
+        }
+    }
+    if isolate {
+        out.close("\u{2069}")?;
+    }
+    Ok(())
+}
+
+/// What the writers escape. They don't see all of the text at once, so all
+/// bidi control characters are escaped. Line breaks and tabs are left alone,
+/// since they're likely part of the output itself and not of a single value.
+fn escapes_in_stream(ch: char) -> bool {
+    (crate::requires_escape(ch) && ch != '\n' && ch != '\t')
+        || security::is_bidi(ch)
+        || security::is_hidden_text(ch)
+}
+
+/// Escapes text a piece at a time, keeping track of ANSI escape sequences
+/// that span pieces if they're stripped.
+#[derive(Debug, Clone)]
+struct Escaper {
+    ansi: Option<Parser>,
+    hex_c1: bool,
+}
+
+impl Escaper {
+    fn new(strip_ansi: bool, hex_c1: bool) -> Self {
+        Escaper {
+            ansi: if strip_ansi {
+                Some(Parser::new())
+            } else {
+                None
+            },
+            hex_c1,
+        }
+    }
+
+    /// Stripped characters are passed on as escapes without any text, so
+    /// that they're still accounted for.
+    fn strip(&mut self, code: u32) -> bool {
+        match self.ansi {
+            Some(ref mut parser) => parser.feed(code) == Feed::Sequence,
+            None => false,
+        }
+    }
+
+    fn text(
+        &mut self,
+        out: &mut dyn Sink,
+        text: &str,
+        escapes: impl Fn(char) -> bool,
+    ) -> fmt::Result {
+        let mut start = 0;
+        for (index, ch) in text.char_indices() {
+            let stripped = self.strip(ch.into());
+            if !stripped && !escapes(ch) {
+                continue;
+            }
+            if start < index {
+                out.literal(&text[start..index])?;
+            }
+            start = index + ch.len_utf8();
+            match ch {
+                _ if stripped => out.escape(Original::Char(ch), "")?,
+                '\t' => out.escape(Original::Char(ch), "\\t")?,
+                '\n' => out.escape(Original::Char(ch), "\\n")?,
+                '\r' => out.escape(Original::Char(ch), "\\r")?,
+                // The rest of C0, and DEL
+                '\0'..='\x7F' => {
+                    out.escape_fmt(Original::Char(ch), format_args!("\\x{:02X}", ch as u32))?
+                }
+                // C1, written like a lone byte from sanitize_raw()
+                '\u{80}'..='\u{9F}' if self.hex_c1 => {
+                    out.escape_fmt(Original::Char(ch), format_args!("\\x{:02X}", ch as u32))?
+                }
+                _ => out.escape_fmt(Original::Char(ch), format_args!("\\u{{{:X}}}", ch as u32))?,
+            }
+        }
+        if start < text.len() {
+            out.literal(&text[start..])?;
+        }
+        Ok(())
+    }
+
+    /// A byte that's not part of valid UTF-8. From 0x80 to 0x9F these could
+    /// be read as C1 controls by a terminal that doesn't expect UTF-8.
+    fn byte(&mut self, out: &mut dyn Sink, byte: u8) -> fmt::Result {
+        if self.strip(byte.into()) {
+            out.escape(Original::Byte(byte), "")
+        } else {
+            out.escape_fmt(Original::Byte(byte), format_args!("\\x{:02X}", byte))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Ground,
+    /// After an ESC.
+    Escape,
+    /// After an ESC and one or more intermediate bytes.
+    Intermediate,
+    /// A CSI sequence, like `ESC [ 31 m`.
+    Csi,
+    /// A string like an OSC or DCS sequence, which lasts until a terminator.
+    String,
+    /// An ESC inside a string, which may be the start of an ST terminator.
+    StringEscape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Feed {
+    Text,
+    Sequence,
+}
+
+/// Recognizes ANSI escape sequences one character at a time, following
+/// ECMA-48 and the way terminals parse them.
+///
+/// It's fed code points, or bytes that aren't part of valid UTF-8, which
+/// are treated the same way. That makes a lone `0x9B` byte an 8-bit CSI,
+/// just like `U+009B`.
+#[derive(Debug, Clone)]
+pub(crate) struct Parser {
+    state: State,
+}
+
+impl Parser {
+    pub(crate) fn new() -> Self {
+        Parser {
+            state: State::Ground,
+        }
+    }
+
+    /// Whether a character is part of an escape sequence.
+    ///
+    /// A sequence that's interrupted by a character that can't be part of it
+    /// ends right there, and the character is looked at on its own. A line
+    /// break ends a string too, so that one that's never terminated can't
+    /// swallow the rest of a log.
+    pub(crate) fn feed(&mut self, code: u32) -> Feed {
+        let next = match (self.state, code) {
+            (State::Ground, 0x1B) => State::Escape,
+            (State::Ground, 0x9B) => State::Csi,
+            (State::Ground, 0x90) | (State::Ground, 0x98) | (State::Ground, 0x9D) => State::String,
+            (State::Ground, 0x9E) | (State::Ground, 0x9F) => State::String,
+            (State::Ground, _) => return Feed::Text,
+
+            (State::Escape, 0x5B) => State::Csi,
+            // OSC, DCS, SOS, PM and APC
+            (State::Escape, 0x5D) | (State::Escape, 0x50) | (State::Escape, 0x58) => State::String,
+            (State::Escape, 0x5E) | (State::Escape, 0x5F) => State::String,
+            (State::Escape, 0x20..=0x2F) | (State::Intermediate, 0x20..=0x2F) => {
+                State::Intermediate
+            }
+            (State::Escape, 0x30..=0x7E) | (State::Intermediate, 0x30..=0x7E) => State::Ground,
+
+            (State::Csi, 0x20..=0x3F) => State::Csi,
+            (State::Csi, 0x40..=0x7E) => State::Ground,
+
+            (State::String, 0x07) | (State::String, 0x9C) => State::Ground,
+            (State::String, 0x1B) => State::StringEscape,
+            (State::String, 0x0A) => return self.interrupt(code),
+            (State::String, _) => State::String,
+            (State::StringEscape, 0x5C) => State::Ground,
+            // Any other ESC ends the string and starts a new sequence.
+            (State::StringEscape, _) => {
+                self.state = State::Escape;
+                return self.feed(code);
+            }
+
+            _ => return self.interrupt(code),
+        };
+        self.state = next;
+        Feed::Sequence
+    }
+
+    fn interrupt(&mut self, code: u32) -> Feed {
+        self.state = State::Ground;
+        self.feed(code)
+    }
+}
+
+/// A writer that removes ANSI escape sequences from everything that's
+/// written through it, like colors, cursor movement, window titles and
+/// hyperlinks. Everything else is passed on as it is.
+///
+/// This recognizes complete sequences, so nothing is left of them. That
+/// includes the 8-bit forms that start with a C1 control character, like
+/// `U+009B` for CSI. Sequences can be split over several writes.
+///
+/// ```
+/// use std::fmt::Write;
+/// use os_display::AnsiFilter;
+///
+/// let mut filter = AnsiFilter::new(String::new());
+/// write!(filter, "\x1B[1;31merror:\x1B[0m disk \x1B]0;full").unwrap();
+/// write!(filter, "\x07full").unwrap();
+/// assert_eq!(filter.into_inner(), "error: disk full");
+/// ```
+///
+/// Lone control characters are left alone. Use [`Quoted::sanitize`]
+/// with [`Quoted::strip_ansi`] to escape those too.
+///
+/// [`Quoted::sanitize`]: crate::Quoted::sanitize
+/// [`Quoted::strip_ansi`]: crate::Quoted::strip_ansi
+///
+/// # Optional
+/// This requires the optional `sanitize` feature.
+#[derive(Debug, Clone)]
+pub struct AnsiFilter<W> {
+    inner: W,
+    parser: Parser,
+}
+
+impl<W: Write> AnsiFilter<W> {
+    /// Wrap a writer.
+    pub fn new(inner: W) -> Self {
+        AnsiFilter {
+            inner,
+            parser: Parser::new(),
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Take out the underlying writer. The rest of an unfinished sequence
+    /// would have been removed too.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for AnsiFilter<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let mut start = 0;
+        for (index, ch) in text.char_indices() {
+            if self.parser.feed(ch.into()) == Feed::Text {
+                continue;
+            }
+            if start < index {
+                self.inner.write_str(&text[start..index])?;
+            }
+            start = index + ch.len_utf8();
+        }
+        if start < text.len() {
+            self.inner.write_str(&text[start..])?;
+        }
+        Ok(())
+    }
+}
+
+/// A writer that makes everything that's written through it safe to print,
+/// like [`Quoted::sanitize`] does for a single value.
+///
+/// This is for wrapping the output of a whole program, or a log, instead of
+/// quoting each value that goes into it.
+///
+/// ```
+/// use std::fmt::Write;
+/// use os_display::SanitizingWriter;
+///
+/// let mut out = SanitizingWriter::new(String::new());
+/// writeln!(out, "Opened {}", "evil\x1B]0;pwned\x07.txt").unwrap();
+/// writeln!(out, "Done").unwrap();
+/// assert_eq!(out.into_inner(), "Opened evil\\x1B]0;pwned\\x07.txt\nDone\n");
+/// ```
+///
+/// Unlike with [`Quoted::sanitize`], line breaks and tabs are passed on as
+/// they are, since they're likely part of the output itself. Carriage returns
+/// are still escaped. All bidi control characters are escaped, because it's
+/// not possible to tell in advance whether they'll be terminated.
+///
+/// [`SanitizingWriter::strip_ansi`] removes ANSI escape sequences, even if
+/// they're split over several writes.
+///
+/// [`Quoted::sanitize`]: crate::Quoted::sanitize
+///
+/// # Optional
+/// This requires the optional `sanitize` feature.
+#[derive(Debug, Clone)]
+pub struct SanitizingWriter<W> {
+    inner: W,
+    escaper: Escaper,
+}
+
+impl<W: Write> SanitizingWriter<W> {
+    /// Wrap a writer.
+    pub fn new(inner: W) -> Self {
+        SanitizingWriter {
+            inner,
+            escaper: Escaper::new(false, false),
+        }
+    }
+
+    /// Toggle removal of ANSI escape sequences, like
+    /// [`Quoted::strip_ansi`](crate::Quoted::strip_ansi).
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    /// use os_display::SanitizingWriter;
+    ///
+    /// let mut out = SanitizingWriter::new(String::new()).strip_ansi(true);
+    /// write!(out, "\x1B[31").unwrap();
+    /// write!(out, "mred\x1B[0m\r").unwrap();
+    /// assert_eq!(out.into_inner(), r"red\r");
+    /// ```
+    ///
+    /// Defaults to `false`.
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.escaper = Escaper::new(strip, self.escaper.hex_c1);
+        self
+    }
+
+    /// Toggle writing C1 control characters as `\x85` instead of `\u{85}`,
+    /// like [`Quoted::hex_c1`](crate::Quoted::hex_c1).
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    /// use os_display::SanitizingWriter;
+    ///
+    /// let mut out = SanitizingWriter::new(String::new()).hex_c1(true);
+    /// write!(out, "\u{9B}31mred\x7F").unwrap();
+    /// assert_eq!(out.into_inner(), r"\x9B31mred\x7F");
+    /// ```
+    ///
+    /// Defaults to `false`.
+    pub fn hex_c1(mut self, hex: bool) -> Self {
+        self.escaper.hex_c1 = hex;
+        self
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Take out the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for SanitizingWriter<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.escaper
+            .text(&mut Plain(&mut self.inner), text, escapes_in_stream)
+    }
+}
+
+/// Like [`SanitizingWriter`], for an [`io::Write`](std::io::Write).
+///
+/// Bytes that aren't part of valid UTF-8 are escaped like with
+/// [`Quoted::sanitize_raw`](crate::Quoted::sanitize_raw). A UTF-8 sequence
+/// that's split over several writes is held back until the rest comes in.
+///
+/// ```
+/// use std::io::Write;
+/// use os_display::SanitizingIoWriter;
+///
+/// let mut out = SanitizingIoWriter::new(Vec::new());
+/// out.write_all(b"caf\xC3").unwrap();
+/// out.write_all(b"\xA9 \xFF\x1B[2J\n").unwrap();
+/// assert_eq!(out.finish().unwrap(), b"caf\xC3\xA9 \\xFF\\x1B[2J\n");
+/// ```
+///
+/// The writer gets many small writes, so it's worth buffering.
+///
+/// # Optional
+/// This requires the optional `sanitize` feature and the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SanitizingIoWriter<W> {
+    inner: W,
+    escaper: Escaper,
+    pending: Pending,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> SanitizingIoWriter<W> {
+    /// Wrap a writer.
+    pub fn new(inner: W) -> Self {
+        SanitizingIoWriter {
+            inner,
+            escaper: Escaper::new(false, false),
+            pending: Pending::default(),
+        }
+    }
+
+    /// Toggle removal of ANSI escape sequences, like
+    /// [`Quoted::strip_ansi`](crate::Quoted::strip_ansi). That includes the
+    /// 8-bit forms that start with a lone byte like `0x9B`.
+    ///
+    /// Defaults to `false`.
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.escaper = Escaper::new(strip, self.escaper.hex_c1);
+        self
+    }
+
+    /// Toggle writing C1 control characters as `\x85` instead of `\u{85}`,
+    /// like [`Quoted::hex_c1`](crate::Quoted::hex_c1). Lone bytes in the
+    /// same range are always written that way.
+    ///
+    /// Defaults to `false`.
+    pub fn hex_c1(mut self, hex: bool) -> Self {
+        self.escaper.hex_c1 = hex;
+        self
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Escape the start of a UTF-8 sequence that's still held back, if any,
+    /// and take out the underlying writer.
+    ///
+    /// # Errors
+    /// If writing fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut pending = self.pending;
+        pending.finish(|bytes| self.write_bytes(bytes))?;
+        Ok(self.inner)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let mut writer = IoWrite {
+            inner: &mut self.inner,
+            error: None,
+        };
+        let mut result = Ok(());
+        for chunk in utf8_chunks(bytes) {
+            result = match chunk {
+                Ok(chunk) => self
+                    .escaper
+                    .text(&mut Plain(&mut writer), chunk, escapes_in_stream),
+                Err(byte) => self.escaper.byte(&mut Plain(&mut writer), byte),
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+        match writer.error {
+            Some(err) => Err(err),
+            None => result.map_err(fmt_error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> io::Write for SanitizingIoWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The bytes that are held back have to be taken out while the rest
+        // is written.
+        let mut pending = self.pending;
+        let result = pending.push(buf, |bytes| self.write_bytes(bytes));
+        self.pending = pending;
+        result.map(|()| buf.len())
+    }
+
+    /// Flush the underlying writer. The start of a UTF-8 sequence that's
+    /// still held back stays there.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
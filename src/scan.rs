@@ -0,0 +1,253 @@
+//! Helpers for getting through long runs of boring text quickly.
+
+#[cfg(feature = "alloc")]
+use crate::alloc::vec::Vec;
+
+/// Find the length of the prefix that consists of plain bytes.
+///
+/// Bytes are checked sixteen at a time. In that form the check compiles to
+/// branchless code (and often SIMD), which is much faster than going through
+/// the text char by char. Only ASCII bytes should be considered plain, so the
+/// prefix always ends on a char boundary.
+#[inline]
+pub(crate) fn plain_prefix(bytes: &[u8], is_plain: impl Fn(u8) -> bool) -> usize {
+    let mut pos = 0;
+    for chunk in bytes.chunks_exact(16) {
+        if !chunk.iter().fold(true, |acc, &byte| acc & is_plain(byte)) {
+            break;
+        }
+        pos += 16;
+    }
+    match bytes[pos..].iter().position(|&byte| !is_plain(byte)) {
+        Some(index) => pos + index,
+        None => bytes.len(),
+    }
+}
+
+/// Flags for the per-byte tables that the Unix and PowerShell styles use to
+/// classify ASCII. One lookup answers every question the scan has about a
+/// byte, instead of a `contains()` for each set of characters.
+pub(crate) mod class {
+    /// Has a special meaning outside quotes, so the text needs quotes.
+    pub(crate) const SPECIAL: u8 = 1 << 0;
+    /// Is interpreted inside double quotes.
+    pub(crate) const DOUBLE_UNSAFE: u8 = 1 << 1;
+    /// `'`
+    pub(crate) const SINGLE_QUOTE: u8 = 1 << 2;
+    /// `"`
+    pub(crate) const DOUBLE_QUOTE: u8 = 1 << 3;
+    /// A control character, which has to be escaped.
+    pub(crate) const CONTROL: u8 = 1 << 4;
+    /// Part of a non-ASCII character, which has to be decoded first.
+    pub(crate) const NON_ASCII: u8 = 1 << 5;
+
+    /// The lowest of the flags that matter even once we know that the text
+    /// needs quotes. Every flag but [`SPECIAL`] is at least this, so that can
+    /// be checked with a comparison, which is faster in a tight loop than a
+    /// mask.
+    pub(crate) const ALWAYS: u8 = DOUBLE_UNSAFE;
+}
+
+/// Check whether a non-ASCII character might matter to the Unix or PowerShell
+/// scan: C1 controls, whitespace and separators, bidi controls, curly quotes,
+/// combining marks and hidden text. Other characters only matter if invisible
+/// characters are escaped.
+///
+/// This is a few comparisons, so the tables only have to be searched for the
+/// rare characters that fall in these ranges. With the `strict-unassigned`
+/// feature any character might be unassigned, so they all have to be checked.
+#[inline]
+pub(crate) fn is_notable(ch: char) -> bool {
+    if cfg!(feature = "strict-unassigned") {
+        return true;
+    }
+    // Most CJK and Hangul falls in the gap between the two halves.
+    if ch <= '\u{3000}' {
+        match ch {
+            '\u{80}'..='\u{A0}'
+            | '\u{300}'..='\u{36F}'
+            | '\u{1680}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{2000}'..='\u{206F}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{2800}'
+            | '\u{3000}' => true,
+            _ => false,
+        }
+    } else {
+        match ch {
+            '\u{FE00}'..='\u{FE2F}' | '\u{FFF9}'..='\u{FFFB}' | '\u{E0000}'..='\u{E01EF}' => true,
+            _ => false,
+        }
+    }
+}
+
+/// Bytes that never need quotes in any position, for the Unix and PowerShell
+/// styles: `A-Z`, `a-z`, `0-9`, `.`, `_`, `/` and `-`.
+#[rustfmt::skip]
+static SAFE: [bool; 256] = {
+    const F: bool = false;
+    const T: bool = true;
+    [
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // 00..0F
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // 10..1F
+        F, F, F, F, F, F, F, F, F, F, F, F, F, T, T, T, // 20..2F
+        T, T, T, T, T, T, T, T, T, T, F, F, F, F, F, F, // 30..3F
+        F, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, // 40..4F
+        T, T, T, T, T, T, T, T, T, T, T, F, F, F, F, T, // 50..5F
+        F, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, // 60..6F
+        T, T, T, T, T, T, T, T, T, T, T, F, F, F, F, F, // 70..7F
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // 80..8F
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // 90..9F
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // A0..AF
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // B0..BF
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // C0..CF
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // D0..DF
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // E0..EF
+        F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, F, // F0..FF
+    ]
+};
+
+/// Check whether a non-empty string consists only of [`SAFE`] bytes.
+///
+/// Most file names look like this, and they're written as-is by the Unix and
+/// PowerShell styles (give or take the first character), so checking for
+/// them up front skips all of the usual machinery.
+#[inline]
+pub(crate) fn is_safe(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && plain_prefix(bytes, |byte| SAFE[byte as usize]) == bytes.len()
+}
+
+/// How many positions [`Marks`] remembers without `alloc`.
+#[cfg(not(feature = "alloc"))]
+const MAX_MARKS: usize = 8;
+
+/// The positions of the quotes that the scan in `write()` came across, so
+/// that the writer doesn't have to search the text for them a second time.
+///
+/// With `alloc` they're all kept, and the writer only goes back to the text
+/// to copy out the pieces between them. Without it only the first few are
+/// kept, to stay on the stack. After that the writer searches the rest of the
+/// text itself, starting after the last one.
+#[derive(Debug, Clone)]
+pub(crate) struct Marks {
+    #[cfg(feature = "alloc")]
+    positions: Vec<usize>,
+    #[cfg(not(feature = "alloc"))]
+    positions: [usize; MAX_MARKS],
+    #[cfg(not(feature = "alloc"))]
+    len: usize,
+    #[cfg(not(feature = "alloc"))]
+    overflow: bool,
+}
+
+impl Marks {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new() -> Self {
+        Marks {
+            positions: Vec::new(),
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn new() -> Self {
+        Marks {
+            positions: [0; MAX_MARKS],
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    /// Remember the position of a character. Positions have to be added in
+    /// order, and every character that [`find`](Marks::find) is asked about
+    /// has to be added.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn push(&mut self, pos: usize) {
+        self.positions.push(pos);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[inline]
+    pub(crate) fn push(&mut self, pos: usize) {
+        if self.len < MAX_MARKS {
+            self.positions[self.len] = pos;
+            self.len += 1;
+        } else {
+            self.overflow = true;
+        }
+    }
+
+    /// Like `text.match_indices(pattern)`, but only searching the part of the
+    /// text that the marks don't cover.
+    pub(crate) fn find<'m, 't: 'm, P>(
+        &'m self,
+        text: &'t str,
+        pattern: P,
+    ) -> impl Iterator<Item = (usize, &'t str)> + 'm
+    where
+        P: Fn(char) -> bool + Copy + 't,
+    {
+        let marked = self.marked().iter().map(move |&pos| {
+            let len = text[pos..].chars().next().map_or(0, char::len_utf8);
+            (pos, &text[pos..pos + len])
+        });
+        let marked = marked.filter(move |&(_, found)| found.chars().next().map_or(false, pattern));
+        let rest = self.unmarked(text).map(move |start| {
+            text[start..]
+                .match_indices(pattern)
+                .map(move |(index, found)| (start + index, found))
+        });
+        marked.chain(rest.into_iter().flatten())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn marked(&self) -> &[usize] {
+        &self.positions
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn marked(&self) -> &[usize] {
+        &self.positions[..self.len]
+    }
+
+    /// Where the part of the text that has to be searched starts, if any.
+    #[cfg(feature = "alloc")]
+    fn unmarked(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn unmarked(&self, text: &str) -> Option<usize> {
+        if !self.overflow {
+            return None;
+        }
+        let start = self.positions[MAX_MARKS - 1];
+        Some(start + text[start..].chars().next().map_or(0, char::len_utf8))
+    }
+}
+
+/// The longest run of adjacent characters that the scan in `write()` came
+/// across, for counting combining marks that are stacked together.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Run {
+    longest: usize,
+    current: usize,
+    end: usize,
+}
+
+impl Run {
+    /// Count a character that starts at `pos` and is `len` bytes long.
+    /// Characters have to be added in order.
+    #[inline]
+    pub(crate) fn push(&mut self, pos: usize, len: usize) {
+        self.current = if pos == self.end { self.current + 1 } else { 1 };
+        self.end = pos + len;
+        self.longest = self.longest.max(self.current);
+    }
+
+    pub(crate) fn longest(&self) -> usize {
+        self.longest
+    }
+}
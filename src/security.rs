@@ -0,0 +1,503 @@
+//! The checks used to protect against misleading text.
+//!
+//! These are the exact heuristics applied by [`Quoted`](crate::Quoted). They're
+//! exposed so that other tools (linters, code review bots) can flag the same
+//! strings without reimplementing them.
+
+use core::iter::FusedIterator;
+use core::str::CharIndices;
+
+use crate::classify::char_reason;
+use crate::util::{utf8_chunks, Utf8Chunks};
+use crate::{BidiPolicy, Reason};
+
+/// Check whether a character is an explicit bidirectional formatting character
+/// that can affect text outside itself.
+///
+/// These are the two ranges in PropList.txt:
+/// - LEFT-TO-RIGHT EMBEDDING..RIGHT-TO-LEFT OVERRIDE (`U+202A..U+202E`)
+/// - LEFT-TO-RIGHT ISOLATE..POP DIRECTIONAL ISOLATE (`U+2066..U+2069`)
+///
+/// Implicit marks like `U+200F RIGHT-TO-LEFT MARK` are not included. They
+/// behave like ordinary right-to-left characters.
+///
+/// ```
+/// use os_display::security::is_bidi;
+///
+/// assert!(is_bidi('\u{202E}'));
+/// assert!(!is_bidi('\u{200F}'));
+/// assert!(!is_bidi('a'));
+/// ```
+pub fn is_bidi(ch: char) -> bool {
+    match ch {
+        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => true,
+        _ => false,
+    }
+}
+
+/// Check whether a character can carry text that isn't shown.
+///
+/// This includes:
+/// - Tag characters (`U+E0000..U+E007F`), which mirror ASCII but are
+///   invisible. Their only legitimate use is in a few emoji flags, like
+///   the one for Scotland.
+/// - Interlinear annotation controls (`U+FFF9..U+FFFB`), which mark text
+///   as an annotation that most programs don't display.
+///
+/// Both can smuggle instructions past a reader, and some terminals print
+/// them as nothing at all. They're escaped by default, see
+/// [`Quoted::escape_hidden_text`](crate::Quoted::escape_hidden_text).
+///
+/// ```
+/// use os_display::security::is_hidden_text;
+///
+/// assert!(is_hidden_text('\u{E0041}'));
+/// assert!(is_hidden_text('\u{FFF9}'));
+/// assert!(!is_hidden_text('\u{200B}'));
+/// assert!(!is_hidden_text('A'));
+/// ```
+pub fn is_hidden_text(ch: char) -> bool {
+    match ch {
+        '\u{E0000}'..='\u{E007F}' | '\u{FFF9}'..='\u{FFFB}' => true,
+        _ => false,
+    }
+}
+
+/// Check whether a character is invisible, or can invisibly change the
+/// rendering of its neighbors.
+///
+/// This includes:
+/// - Zero-width spaces and joiners, like `U+200B ZERO WIDTH SPACE` and
+///   `U+200D ZERO WIDTH JOINER`
+/// - Other format characters, like `U+00AD SOFT HYPHEN`, `U+2060 WORD JOINER`
+///   and `U+FEFF ZERO WIDTH NO-BREAK SPACE`
+/// - Bidi control characters, including implicit marks like
+///   `U+200F RIGHT-TO-LEFT MARK`
+/// - Variation selectors
+/// - Tag characters
+/// - Fillers that are rendered as blank space, like `U+3164 HANGUL FILLER`
+///
+/// Combining marks are not included even though they're zero-width, because
+/// they're visible on top of the preceding character.
+///
+/// The format characters are a hardcoded list. With the `unicode-format`
+/// feature every character in the general category Cf (format) is included
+/// instead, according to [`UNICODE_VERSION`](crate::UNICODE_VERSION). That
+/// covers newly assigned ones without waiting for this list to be updated,
+/// but it also covers a few that are visible, like `U+0600 ARABIC NUMBER SIGN`.
+///
+/// ```
+/// use os_display::security::is_invisible;
+///
+/// assert!(is_invisible('\u{200B}'));
+/// assert!(is_invisible('\u{FE0F}'));
+/// assert!(!is_invisible('\u{0301}'));
+/// assert!(!is_invisible(' '));
+/// ```
+pub fn is_invisible(ch: char) -> bool {
+    match ch {
+        '\u{00AD}'
+        | '\u{034F}'
+        | '\u{061C}'
+        | '\u{115F}'
+        | '\u{1160}'
+        | '\u{17B4}'
+        | '\u{17B5}'
+        | '\u{180B}'..='\u{180F}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'..='\u{206F}'
+        | '\u{3164}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FEFF}'
+        | '\u{FFA0}'
+        | '\u{FFF9}'..='\u{FFFB}'
+        | '\u{1BCA0}'..='\u{1BCA3}'
+        | '\u{1D173}'..='\u{1D17A}'
+        | '\u{E0000}'..='\u{E007F}'
+        | '\u{E0100}'..='\u{E01EF}' => true,
+        #[cfg(feature = "unicode-format")]
+        _ if crate::tables::contains(crate::tables::FORMAT, ch) => true,
+        _ => false,
+    }
+}
+
+/// Check whether a codepoint is unassigned in
+/// [`UNICODE_VERSION`](crate::UNICODE_VERSION), including noncharacters like
+/// `U+FFFF`.
+///
+/// Terminals give these a width of one column, but they may be assigned as
+/// zero-width characters or combining marks later on. They're only escaped
+/// with the `strict-unassigned` feature.
+///
+/// ```
+/// use os_display::security::is_unassigned;
+///
+/// assert!(is_unassigned('\u{378}'));
+/// assert!(is_unassigned('\u{FFFF}'));
+/// assert!(!is_unassigned('\u{1F600}'));
+/// assert!(!is_unassigned('a'));
+/// ```
+pub fn is_unassigned(ch: char) -> bool {
+    !ch.is_ascii() && crate::tables::contains(crate::tables::UNASSIGNED, ch)
+}
+
+/// Check whether a codepoint is reserved for private use, like icons in
+/// patched fonts. What they look like depends entirely on the font.
+///
+/// They're only escaped with the `strict-unassigned` feature.
+///
+/// ```
+/// use os_display::security::is_private_use;
+///
+/// assert!(is_private_use('\u{E000}'));
+/// assert!(is_private_use('\u{10FFFD}'));
+/// assert!(!is_private_use('\u{378}'));
+/// ```
+pub fn is_private_use(ch: char) -> bool {
+    !ch.is_ascii() && crate::tables::contains(crate::tables::PRIVATE_USE, ch)
+}
+
+/// Check whether a character is a combining mark that can be stacked on any
+/// letter, or a variation selector.
+///
+/// These are the characters that "zalgo" text piles on top of each other,
+/// until a single name covers several lines of output. This includes:
+/// - The blocks of combining diacritical marks that aren't tied to a script
+///   (`U+0300..U+036F`, `U+1AB0..U+1AFF`, `U+1DC0..U+1DFF`)
+/// - Combining marks for symbols (`U+20D0..U+20FF`)
+/// - Combining half marks (`U+FE20..U+FE2F`)
+/// - Variation selectors (`U+FE00..U+FE0F`, `U+E0100..U+E01EF`)
+///
+/// Marks that belong to a script, like Arabic vowel signs or Devanagari
+/// viramas, are not included. They can legitimately appear a few at a time.
+///
+/// ```
+/// use os_display::security::is_combining;
+///
+/// assert!(is_combining('\u{0301}'));
+/// assert!(is_combining('\u{FE0F}'));
+/// assert!(!is_combining('\u{064E}'));
+/// assert!(!is_combining('e'));
+/// ```
+pub fn is_combining(ch: char) -> bool {
+    match ch {
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{E0100}'..='\u{E01EF}' => true,
+        _ => false,
+    }
+}
+
+/// Find the length of the longest run of [`is_combining`] characters in the
+/// text.
+///
+/// Text in any language needs a few at most: Vietnamese has two marks on
+/// some letters if it's decomposed. Anything much longer is an attempt to
+/// mess up the layout of whatever the text is shown in. See
+/// [`Quoted::max_combining`](crate::Quoted::max_combining).
+///
+/// ```
+/// use os_display::security::longest_combining_run;
+///
+/// assert_eq!(longest_combining_run("cafe\u{301}"), 1);
+/// assert_eq!(longest_combining_run("Z\u{351}\u{36B}\u{343}\u{36A}\u{302}a"), 5);
+/// assert_eq!(longest_combining_run("plain"), 0);
+/// ```
+pub fn longest_combining_run(text: &str) -> usize {
+    combining_run(text.chars().map(Some))
+}
+
+/// [`longest_combining_run`] for text that may contain invalid unicode,
+/// which is represented by `None`.
+pub(crate) fn combining_run(chars: impl Iterator<Item = Option<char>>) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in chars {
+        match ch {
+            Some(ch) if is_combining(ch) => {
+                current += 1;
+                longest = longest.max(current);
+            }
+            _ => current = 0,
+        }
+    }
+    longest
+}
+
+/// Find the printable ASCII character that a character can be confused with.
+///
+/// This uses a subset of the Unicode
+/// [confusables data](https://www.unicode.org/reports/tr39/#Confusable_Detection):
+/// single codepoints that look like a single ASCII character.
+///
+/// ```
+/// use os_display::security::ascii_confusable;
+///
+/// assert_eq!(ascii_confusable('\u{0430}'), Some('a')); // CYRILLIC SMALL LETTER A
+/// assert_eq!(ascii_confusable('\u{03BF}'), Some('o')); // GREEK SMALL LETTER OMICRON
+/// assert_eq!(ascii_confusable('a'), None);
+/// assert_eq!(ascii_confusable('\u{00E9}'), None);
+/// ```
+///
+/// # Optional
+/// This requires the optional `confusables` feature.
+#[cfg(feature = "confusables")]
+pub fn ascii_confusable(ch: char) -> Option<char> {
+    crate::confusables::CONFUSABLES
+        .binary_search_by_key(&ch, |&(source, _)| source)
+        .ok()
+        .map(|index| crate::confusables::CONFUSABLES[index].1)
+}
+
+/// Check whether text looks like an attempt to spoof ASCII.
+///
+/// That's the case if it contains characters that are confusable with ASCII
+/// (see [`ascii_confusable`]) and all of the other characters are ASCII.
+///
+/// ```
+/// use os_display::security::is_ascii_spoof;
+///
+/// assert!(is_ascii_spoof("p\u{0430}ypal.com"));
+/// assert!(!is_ascii_spoof("paypal.com"));
+/// // Probably just Russian
+/// assert!(!is_ascii_spoof("\u{043F}\u{0440}\u{0438}\u{0432}\u{0435}\u{0442}"));
+/// ```
+///
+/// # Optional
+/// This requires the optional `confusables` feature.
+#[cfg(feature = "confusables")]
+pub fn is_ascii_spoof(text: &str) -> bool {
+    spoofs_ascii(text.chars().map(Some))
+}
+
+/// Like [`is_ascii_spoof`], with `None` for invalid unicode.
+#[cfg(feature = "confusables")]
+pub(crate) fn spoofs_ascii(chars: impl Iterator<Item = Option<char>>) -> bool {
+    let mut found = false;
+    for ch in chars {
+        match ch {
+            Some(ch) if ch.is_ascii() => (),
+            Some(ch) if ascii_confusable(ch).is_some() => found = true,
+            _ => return false,
+        }
+    }
+    found
+}
+
+/// Find the characters in text that could make it misleading or unsafe to
+/// print, without quoting it.
+///
+/// This yields the byte offset of each character together with the
+/// character and a [`Reason`]:
+/// - [`Reason::Control`] for control characters and line separators (see
+///   [`Quoted`](crate::Quoted))
+/// - [`Reason::Bidi`] for bidi control characters (see [`is_bidi`])
+/// - [`Reason::ZeroWidth`] for other invisible characters (see
+///   [`is_invisible`])
+/// - [`Reason::Confusable`] for characters that look like ASCII, with the
+///   `confusables` feature (see `ascii_confusable`)
+/// - [`Reason::LeadingDash`] for a `-` at the start, which a program could
+///   take as an option
+/// - [`Reason::InvalidUnicode`] for each byte that isn't valid UTF-8, with
+///   `U+FFFD REPLACEMENT CHARACTER` as the character
+///
+/// Shell syntax like spaces and `$` doesn't count, since it's harmless when
+/// the text is quoted properly.
+///
+/// ```
+/// use os_display::security::dangerous_chars;
+/// use os_display::Reason;
+///
+/// let found: Vec<_> = dangerous_chars("-rf\u{202E}\n").collect();
+/// assert_eq!(
+///     found,
+///     [
+///         (0, '-', Reason::LeadingDash),
+///         (3, '\u{202E}', Reason::Bidi),
+///         (6, '\n', Reason::Control),
+///     ],
+/// );
+///
+/// let found: Vec<_> = dangerous_chars(b"caf\xE9").collect();
+/// assert_eq!(found, [(3, '\u{FFFD}', Reason::InvalidUnicode)]);
+///
+/// assert_eq!(dangerous_chars("my file.txt").count(), 0);
+/// ```
+///
+/// On Unix an `OsStr` can be checked with
+/// [`OsStrExt::as_bytes`](https://doc.rust-lang.org/std/os/unix/ffi/trait.OsStrExt.html#tymethod.as_bytes).
+pub fn dangerous_chars<T: AsRef<[u8]> + ?Sized>(text: &T) -> DangerousChars<'_> {
+    let bytes = text.as_ref();
+    DangerousChars {
+        chunks: utf8_chunks(bytes),
+        chars: "".char_indices(),
+        start: 0,
+        len: bytes.len(),
+    }
+}
+
+/// An iterator over the problematic characters in text, made by
+/// [`dangerous_chars`].
+#[derive(Debug, Clone)]
+pub struct DangerousChars<'a> {
+    chunks: Utf8Chunks<'a>,
+    /// The valid chunk that's being searched.
+    chars: CharIndices<'a>,
+    /// Where that chunk starts.
+    start: usize,
+    len: usize,
+}
+
+impl<'a> Iterator for DangerousChars<'a> {
+    type Item = (usize, char, Reason);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((index, ch)) = self.chars.next() {
+                let offset = self.start + index;
+                let reason = match char_reason(ch) {
+                    Some(reason) => reason,
+                    None if offset == 0 && ch == '-' => Reason::LeadingDash,
+                    None => continue,
+                };
+                return Some((offset, ch, reason));
+            }
+            let chunk = self.chunks.next()?;
+            let end = self.len - self.chunks.as_bytes().len();
+            match chunk {
+                Ok(text) => {
+                    self.start = end - text.len();
+                    self.chars = text.char_indices();
+                }
+                Err(_) => {
+                    return Some((
+                        end - 1,
+                        core::char::REPLACEMENT_CHARACTER,
+                        Reason::InvalidUnicode,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for DangerousChars<'a> {}
+
+/// Check whether text uses bidi in a potentially problematic way.
+///
+/// This is the case if the explicit formatting characters (see [`is_bidi`])
+/// aren't properly paired up, or if they're nested more than 16 levels deep.
+/// Such text can rearrange the text that comes after it, which is known as a
+/// [*Trojan Source*](https://trojansource.codes/) attack.
+///
+/// The text is treated as a single paragraph, so newlines don't reset the
+/// state like they do in
+/// [UAX #9](https://www.unicode.org/reports/tr9/tr9-42.html). That errs on
+/// the side of caution. (`Quoted` always escapes text with newlines anyway.)
+///
+/// A mismatched terminator is considered suspicious even though UAX #9 says
+/// how to resolve it.
+///
+/// ```
+/// use os_display::security::is_suspicious_bidi;
+///
+/// assert!(!is_suspicious_bidi("abc"));
+/// assert!(!is_suspicious_bidi("\u{2067}abc\u{2069}"));
+/// assert!(is_suspicious_bidi("user\u{202E} \u{2066}// Check if admin\u{2069} \u{2066}"));
+/// ```
+pub fn is_suspicious_bidi(text: &str) -> bool {
+    check_bidi(text) != Bidi::Ok
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Bidi {
+    Ok,
+    /// Some formatting characters are never terminated, but the text can be
+    /// made harmless by wrapping it in an isolate.
+    Unterminated,
+    Suspicious,
+}
+
+#[inline(never)]
+pub(crate) fn check_bidi(text: &str) -> Bidi {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Kind {
+        Formatting,
+        Isolate,
+    }
+    const STACK_SIZE: usize = 16;
+    // Can't use a Vec because of no_std
+    let mut stack: [Option<Kind>; STACK_SIZE] = [None; STACK_SIZE];
+    let mut pos = 0;
+    for ch in text.chars() {
+        match ch {
+            '\u{202A}' | '\u{202B}' | '\u{202D}' | '\u{202E}' => {
+                if pos >= STACK_SIZE {
+                    // Suspicious amount of nesting.
+                    return Bidi::Suspicious;
+                }
+                stack[pos] = Some(Kind::Formatting);
+                pos += 1;
+            }
+            '\u{202C}' => {
+                if pos == 0 {
+                    // Unpaired terminator.
+                    // Not necessarily dangerous, but suspicious and
+                    // could disrupt preceding text.
+                    return Bidi::Suspicious;
+                }
+                pos -= 1;
+                if stack[pos] != Some(Kind::Formatting) {
+                    // Terminator doesn't match.
+                    // UAX #9 says to pop the stack until we find a match.
+                    // But we'll keep things simple and cautious.
+                    return Bidi::Suspicious;
+                }
+            }
+            '\u{2066}' | '\u{2067}' | '\u{2068}' => {
+                if pos >= STACK_SIZE {
+                    return Bidi::Suspicious;
+                }
+                stack[pos] = Some(Kind::Isolate);
+                pos += 1;
+            }
+            '\u{2069}' => {
+                if pos == 0 {
+                    return Bidi::Suspicious;
+                }
+                pos -= 1;
+                if stack[pos] != Some(Kind::Isolate) {
+                    return Bidi::Suspicious;
+                }
+            }
+            _ => (),
+        }
+    }
+    if pos != 0 {
+        // A POP DIRECTIONAL ISOLATE terminates any unclosed formatting up to
+        // and including the matching isolate initiator.
+        Bidi::Unterminated
+    } else {
+        Bidi::Ok
+    }
+}
+
+/// Check text that contains bidi control characters against a policy.
+///
+/// `Bidi::Suspicious` means it has to be escaped, `Bidi::Unterminated` means
+/// it has to be isolated.
+pub(crate) fn apply_bidi_policy(text: &str, policy: BidiPolicy) -> Bidi {
+    match policy {
+        BidiPolicy::Escape => match check_bidi(text) {
+            Bidi::Ok => Bidi::Ok,
+            _ => Bidi::Suspicious,
+        },
+        BidiPolicy::Allow => Bidi::Ok,
+        BidiPolicy::Isolate => check_bidi(text),
+    }
+}
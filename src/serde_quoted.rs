@@ -0,0 +1,61 @@
+//! Serializing quoted text with [`serde`](https://docs.rs/serde).
+//!
+//! [`Quoted`] and [`Prepared`](crate::Prepared) serialize as strings, the
+//! same as their [`Display`](core::fmt::Display) output.
+//!
+//! To quote a path field without changing its type, use this module with
+//! `#[serde(with = "os_display::serde_quoted")]`:
+//!
+//! ```ignore
+//! use std::path::PathBuf;
+//!
+//! #[derive(serde::Serialize)]
+//! struct Report {
+//!     #[serde(with = "os_display::serde_quoted")]
+//!     path: PathBuf,
+//! }
+//! ```
+//!
+//! The output is meant to be pasted into a shell, it can't be deserialized
+//! back into a path.
+//!
+//! # Optional
+//! This requires the `serde` feature.
+
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "alloc")]
+use crate::Prepared;
+use crate::Quoted;
+
+impl<'a> Serialize for Quoted<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serialize for Prepared<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serialize a path as a quoted string, like
+/// [`Quotable::quote`](crate::Quotable::quote).
+///
+/// # Optional
+/// This requires the `serde`, `native` and `std` features.
+#[cfg(feature = "native")]
+#[cfg(feature = "std")]
+pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+where
+    P: AsRef<Path> + ?Sized,
+    S: Serializer,
+{
+    serializer.collect_str(&Quoted::native_raw(path.as_ref().as_os_str()))
+}
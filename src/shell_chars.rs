@@ -0,0 +1,88 @@
+//! The ASCII characters that the quoting styles treat specially.
+//!
+//! These are the same sets that the quoting code uses, so a program that
+//! refuses to create files with awkward names can stay in sync with what
+//! gets quoted.
+//!
+//! ```
+//! use os_display::shell_chars::unix;
+//!
+//! fn is_awkward(name: &str) -> bool {
+//!     name.bytes().any(|byte| unix::SPECIAL_SHELL_CHARS.contains(&byte))
+//!         || name.starts_with(unix::SPECIAL_SHELL_CHARS_START)
+//! }
+//!
+//! assert!(is_awkward("foo bar"));
+//! assert!(is_awkward("~foo"));
+//! assert!(!is_awkward("foo~"));
+//! ```
+//!
+//! They're not the whole story. Control characters, unicode whitespace and
+//! some other non-ASCII characters need quotes or escapes too, and
+//! [`Quoting::Minimal`](crate::Quoting::Minimal) leaves some of these
+//! characters alone. Use [`Quoted::classify`](crate::Quoted::classify) to
+//! find out what happens to a particular string.
+
+// The scans in unix.rs and windows.rs use a table with the same sets, and
+// their tests check that they match.
+
+/// Characters in the bash/ksh style, for [`Quoted::unix`](crate::Quoted::unix).
+pub mod unix {
+    // I don't know why % is in the POSIX list. GNU doesn't quote it either.
+
+    /// Characters with a special meaning anywhere in an argument, including
+    /// the space. The text is quoted if it contains any of them.
+    ///
+    /// These are mostly the characters from
+    /// [POSIX](https://pubs.opengroup.org/onlinepubs/9699919799/utilities/V3_chap02.html#tag_18_02).
+    /// zsh and fish have trouble with standalone `{}`, and `^` was used for
+    /// piping in old shells.
+    pub const SPECIAL_SHELL_CHARS: &[u8] = b"|&;<>()$`\\\"'*?[]=^{} ";
+
+    /// Characters with a special meaning at the start of an argument: `~`
+    /// expands a home directory, `#` starts a comment and `!` expands the
+    /// shell history in interactive shells.
+    pub const SPECIAL_SHELL_CHARS_START: &[char] = &['~', '#', '!'];
+
+    /// Characters that are interpreted inside a double-quoted string, so
+    /// double quotes can't be used for text that contains them.
+    ///
+    /// Interactive shells also expand the history with `!` in double quotes.
+    /// It can't be escaped there, so text with a `!` isn't put in double
+    /// quotes either, but it's left out of this set because a backslash in
+    /// front of it doesn't help.
+    // Quotes and backslashes are easier to read one at a time.
+    #[allow(clippy::byte_char_slices)]
+    pub const DOUBLE_UNSAFE: &[u8] = &[b'"', b'`', b'$', b'\\'];
+}
+
+/// Characters in the PowerShell style, for
+/// [`Quoted::windows`](crate::Quoted::windows).
+pub mod windows {
+    // I'm not too familiar with PowerShell, much of this is based on
+    // experimentation rather than documentation or deep understanding.
+    // I have noticed that ~?*[] only get expanded in some contexts, so watch
+    // out for that if doing your own tests.
+    // Get-ChildItem seems unwilling to quote anything so it doesn't help.
+    // Tildes are unclear: In PowerShell on Linux, quoting a tilde keeps it
+    // from expanding if passed to an external program, but not if passed to
+    // Get-ChildItem.
+
+    /// Characters with a special meaning anywhere in an argument, including
+    /// the space. The text is quoted if it contains any of them.
+    ///
+    /// The backslash isn't special, since it's used in file paths.
+    pub const SPECIAL_SHELL_CHARS: &[u8] = b"|&;<>()$`\"'*?[]=,{} ";
+
+    /// Characters with a special meaning at the start of an argument.
+    ///
+    /// Text that starts with a digit or a `-` may be quoted too, depending on
+    /// what follows.
+    pub const SPECIAL_SHELL_CHARS_START: &[char] = &['~', '#', '@', '!'];
+
+    /// Characters that are interpreted inside a double-quoted string, so
+    /// double quotes can't be used for text that contains them.
+    // The quote and the backtick are easier to read one at a time.
+    #[allow(clippy::byte_char_slices)]
+    pub const DOUBLE_UNSAFE: &[u8] = &[b'"', b'`', b'$'];
+}
@@ -0,0 +1,620 @@
+//! The writers don't write to a `fmt::Write` directly. They tell a `Sink` what
+//! each piece of the output means, so that it can be decorated.
+
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+use std::io;
+
+use crate::width::{clusters, str_width};
+
+use crate::{ControlStyle, EscapedSpan, Highlight, Original, QuoteEvent, Width};
+
+/// A destination for quoted output.
+pub(crate) trait Sink {
+    /// The start of a quoted section, or other syntax that isn't part of the
+    /// content.
+    fn open(&mut self, text: &str) -> fmt::Result;
+
+    /// The end of a quoted section.
+    fn close(&mut self, text: &str) -> fmt::Result;
+
+    /// Content that stands for itself.
+    fn literal(&mut self, text: &str) -> fmt::Result;
+
+    /// A single complete escape sequence, like `\n` or `\xFF`.
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result;
+
+    /// Format an escape sequence and pass it on in one piece.
+    fn escape_fmt(&mut self, original: Original, args: fmt::Arguments<'_>) -> fmt::Result {
+        let mut buf = Buf::new();
+        buf.write_fmt(args)?;
+        self.escape(original, buf.as_str())
+    }
+}
+
+/// Writes everything as it is.
+pub(crate) struct Plain<'a>(pub(crate) &'a mut dyn Write);
+
+impl<'a> Sink for Plain<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.0.write_str(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.0.write_str(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.0.write_str(text)
+    }
+
+    fn escape(&mut self, _original: Original, rendered: &str) -> fmt::Result {
+        self.0.write_str(rendered)
+    }
+}
+
+/// This makes `write!` work, for output that doesn't fit any other category.
+impl<'a> Write for dyn Sink + 'a {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.literal(text)
+    }
+}
+
+/// Enough room for the longest escape sequence (two `$([char]0xXXXX)` casts),
+/// even with backticks added by `Quoted::iex_safe`, and for the start of a
+/// here-document.
+pub(crate) struct Buf {
+    bytes: [u8; 40],
+    len: usize,
+}
+
+impl Buf {
+    pub(crate) fn new() -> Self {
+        Buf {
+            bytes: [0; 40],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // We only ever copy whole strs in here.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl Write for Buf {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Collects characters that are written as-is one at a time, so that they
+/// reach the sink in one piece. [`Truncate`] can only keep a grapheme cluster
+/// together if it sees all of it at once.
+///
+/// [`flush`](Literals::flush) has to be called before anything else is
+/// written. If the buffer fills up everything but the last cluster is passed
+/// on, so only clusters that are longer than the buffer get split.
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+pub(crate) struct Literals {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+impl Literals {
+    pub(crate) fn new() -> Self {
+        Literals {
+            bytes: [0; 64],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // We only ever copy whole chars in here.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+
+    pub(crate) fn push(&mut self, out: &mut dyn Sink, ch: char) -> fmt::Result {
+        if self.len + ch.len_utf8() > self.bytes.len() {
+            let last = clusters(self.as_str()).last().map_or(0, str::len);
+            let end = if last < self.len {
+                self.len - last
+            } else {
+                self.len
+            };
+            out.literal(&self.as_str()[..end])?;
+            for index in end..self.len {
+                self.bytes[index - end] = self.bytes[index];
+            }
+            self.len -= end;
+        }
+        self.len += ch.encode_utf8(&mut self.bytes[self.len..]).len();
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self, out: &mut dyn Sink) -> fmt::Result {
+        if self.len > 0 {
+            out.literal(self.as_str())?;
+            self.len = 0;
+        }
+        Ok(())
+    }
+}
+
+/// Text shorter than this is rendered into a [`ShortBuf`] before it's
+/// written, see `Quoted::write_buffered()`.
+pub(crate) const SHORT_INPUT: usize = 128;
+
+/// Room for the output for a [`SHORT_INPUT`], unless it has a lot of escapes.
+pub(crate) struct ShortBuf {
+    bytes: [u8; 256],
+    len: usize,
+    overflowed: bool,
+}
+
+impl ShortBuf {
+    pub(crate) fn new() -> Self {
+        ShortBuf {
+            bytes: [0; 256],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // We only ever copy whole strs in here.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+
+    /// Whether a write failed because the buffer was full.
+    pub(crate) fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+impl Write for ShortBuf {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        if end > self.bytes.len() {
+            self.overflowed = true;
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Wraps quotes and escapes in ANSI SGR sequences.
+///
+/// Consecutive pieces with the same style share a single sequence, and the
+/// sequences only ever appear between pieces. [`Highlighter::finish`] must be
+/// called at the end to reset the style.
+pub(crate) struct Highlighter<'a> {
+    f: &'a mut dyn Write,
+    colors: Highlight,
+    active: Option<&'static str>,
+}
+
+impl<'a> Highlighter<'a> {
+    pub(crate) fn new(f: &'a mut dyn Write, colors: Highlight) -> Self {
+        Highlighter {
+            f,
+            colors,
+            active: None,
+        }
+    }
+
+    fn set(&mut self, params: Option<&'static str>) -> fmt::Result {
+        if self.active != params {
+            if self.active.is_some() {
+                self.f.write_str("\x1B[0m")?;
+            }
+            if let Some(params) = params {
+                write!(self.f, "\x1B[{}m", params)?;
+            }
+            self.active = params;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> fmt::Result {
+        self.set(None)
+    }
+}
+
+impl<'a> Sink for Highlighter<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        let params = self.colors.quotes;
+        self.set(params)?;
+        self.f.write_str(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.open(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.set(None)?;
+        self.f.write_str(text)
+    }
+
+    fn escape(&mut self, _original: Original, rendered: &str) -> fmt::Result {
+        let params = self.colors.escapes;
+        self.set(params)?;
+        self.f.write_str(rendered)
+    }
+}
+
+/// Passes everything on to a callback, for [`Quoted::events`](crate::Quoted::events).
+pub(crate) struct Events<F>(pub(crate) F);
+
+impl<F: FnMut(QuoteEvent<'_>)> Sink for Events<F> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        (self.0)(QuoteEvent::OpenQuote(text));
+        Ok(())
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        (self.0)(QuoteEvent::CloseQuote(text));
+        Ok(())
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        (self.0)(QuoteEvent::Literal(text));
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        (self.0)(QuoteEvent::Escape { original, rendered });
+        Ok(())
+    }
+}
+
+/// Reports where the escapes are, in the original text and in the output.
+///
+/// This relies on the writers passing the original text on in order, as
+/// literals and escapes, with everything else in open() and close().
+pub(crate) struct Spans<F> {
+    callback: F,
+    /// Whether the original text is measured in UTF-16 code units.
+    wide: bool,
+    input: usize,
+    output: usize,
+}
+
+impl<F: FnMut(EscapedSpan)> Spans<F> {
+    pub(crate) fn new(callback: F, wide: bool) -> Self {
+        Spans {
+            callback,
+            wide,
+            input: 0,
+            output: 0,
+        }
+    }
+}
+
+impl<F: FnMut(EscapedSpan)> Sink for Spans<F> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.output += text.len();
+        Ok(())
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.output += text.len();
+        Ok(())
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.input += if self.wide {
+            text.encode_utf16().count()
+        } else {
+            text.len()
+        };
+        self.output += text.len();
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        let len = match original {
+            Original::Char(ch) if self.wide => ch.len_utf16(),
+            Original::Char(ch) => ch.len_utf8(),
+            // An unpaired surrogate in WTF-8.
+            Original::Unit(_) if !self.wide => 3,
+            Original::Byte(_) | Original::Unit(_) => 1,
+        };
+        (self.callback)(EscapedSpan {
+            original,
+            input: self.input..self.input + len,
+            output: self.output..self.output + rendered.len(),
+        });
+        self.input += len;
+        self.output += rendered.len();
+        Ok(())
+    }
+}
+
+/// Adds up the width of everything in terminal columns.
+pub(crate) struct Columns {
+    pub(crate) width: usize,
+    ambiguous: Width,
+}
+
+impl Columns {
+    pub(crate) fn new(ambiguous: Width) -> Self {
+        Columns {
+            width: 0,
+            ambiguous,
+        }
+    }
+}
+
+impl Sink for Columns {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.literal(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.literal(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.width += str_width(text, self.ambiguous);
+        Ok(())
+    }
+
+    fn escape(&mut self, _original: Original, rendered: &str) -> fmt::Result {
+        self.literal(rendered)
+    }
+}
+
+/// Passes everything on while adding up its width, for
+/// [`Quoted::to_string_and_width`](crate::Quoted::to_string_and_width).
+pub(crate) struct Measure<'a> {
+    inner: &'a mut dyn Sink,
+    pub(crate) width: usize,
+    ambiguous: Width,
+}
+
+impl<'a> Measure<'a> {
+    pub(crate) fn new(inner: &'a mut dyn Sink, ambiguous: Width) -> Self {
+        Measure {
+            inner,
+            width: 0,
+            ambiguous,
+        }
+    }
+}
+
+impl<'a> Sink for Measure<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.width += str_width(text, self.ambiguous);
+        self.inner.open(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.width += str_width(text, self.ambiguous);
+        self.inner.close(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.width += str_width(text, self.ambiguous);
+        self.inner.literal(text)
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        self.width += str_width(rendered, self.ambiguous);
+        self.inner.escape(original, rendered)
+    }
+}
+
+/// Cuts the output off after a number of columns, for `{:.N}`.
+///
+/// Escape sequences are kept whole and every quote that's let through is
+/// closed, so the output is still valid. Room for the closing quote is set
+/// aside as soon as a quote is opened.
+pub(crate) struct Truncate<'a> {
+    inner: &'a mut dyn Sink,
+    /// The columns that are left, not counting the ones set aside.
+    room: usize,
+    /// Whether something didn't fit, after which we let nothing else through.
+    full: bool,
+    /// The number of quotes that were opened after we ran out of room. Their
+    /// closing quotes have to be dropped too.
+    dropped: usize,
+    ambiguous: Width,
+}
+
+impl<'a> Truncate<'a> {
+    pub(crate) fn new(inner: &'a mut dyn Sink, width: usize, ambiguous: Width) -> Self {
+        Truncate {
+            inner,
+            room: width,
+            full: false,
+            dropped: 0,
+            ambiguous,
+        }
+    }
+}
+
+/// The width of the quote that will close `open`.
+fn closing_width(open: &str, ambiguous: Width) -> usize {
+    let str_width = |text| str_width(text, ambiguous);
+    match open {
+        // ANSI-C quoting, closed by a plain single quote.
+        "$'" => 1,
+        // The same, after leaving a double-quoted string.
+        "\"$'" => 2,
+        // A PowerShell string that's concatenated to an expression.
+        " + \"" => 1,
+        // A printf command, closed by the end of its format string.
+        "printf '" => 1,
+        // The same, in a command substitution.
+        "\"$(printf '" => 3,
+        // SQL hex literals and concatenated strings, closed by a single quote.
+        "x'" | " || '" | " || x'" | ", '" | ", x'" => 1,
+        // CONCAT() in MySQL.
+        "CONCAT(" => 1,
+        // A hex value in a .reg file, closed by a null terminator.
+        "hex(1):" => 5,
+        // An HTML element.
+        "<bdi>" => str_width("</bdi>"),
+        // A here-document, closed by a newline and the delimiter.
+        _ if open.starts_with("<<'") => str_width("\n") + str_width(&open[3..open.len() - 2]),
+        // Everything else is closed by its mirror image.
+        _ => str_width(open),
+    }
+}
+
+impl<'a> Sink for Truncate<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        let needed = str_width(text, self.ambiguous) + closing_width(text, self.ambiguous);
+        if self.full || needed > self.room {
+            self.full = true;
+            self.dropped += 1;
+            return Ok(());
+        }
+        self.room -= needed;
+        self.inner.open(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        if self.dropped > 0 {
+            self.dropped -= 1;
+            return Ok(());
+        }
+        // We already made room for this one.
+        self.inner.close(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        if self.full {
+            return Ok(());
+        }
+        let width = str_width(text, self.ambiguous);
+        if width <= self.room {
+            self.room -= width;
+            return self.inner.literal(text);
+        }
+        self.full = true;
+        let mut end = 0;
+        for cluster in clusters(text) {
+            let width = str_width(cluster, self.ambiguous);
+            if width > self.room {
+                break;
+            }
+            self.room -= width;
+            end += cluster.len();
+        }
+        self.inner.literal(&text[..end])
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        let width = str_width(rendered, self.ambiguous);
+        if self.full || width > self.room {
+            self.full = true;
+            return Ok(());
+        }
+        self.room -= width;
+        self.inner.escape(original, rendered)
+    }
+}
+
+/// Renders escaped ASCII control characters differently, for
+/// [`Quoted::control_style`](crate::Quoted::control_style).
+pub(crate) struct Controls<'a> {
+    pub(crate) out: &'a mut dyn Sink,
+    pub(crate) style: ControlStyle,
+}
+
+impl<'a> Sink for Controls<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.out.open(text)
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.out.close(text)
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        self.out.literal(text)
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        let ch = match original {
+            Original::Char(ch) if ch.is_ascii_control() => ch,
+            _ => return self.out.escape(original, rendered),
+        };
+        match self.style {
+            ControlStyle::Escape => self.out.escape(original, rendered),
+            ControlStyle::Caret => {
+                // ^@ for NUL through ^_ for 0x1F, and ^? for DEL
+                let caret = (ch as u8 ^ 0x40) as char;
+                self.out.escape_fmt(original, format_args!("^{}", caret))
+            }
+            ControlStyle::Picture => {
+                // The Control Pictures block has the C0 controls in order,
+                // with DEL after them.
+                let picture = match ch {
+                    '\x7F' => '\u{2421}',
+                    _ => core::char::from_u32(0x2400 + ch as u32).unwrap(),
+                };
+                self.out.escape(original, picture.encode_utf8(&mut [0; 4]))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+pub(crate) fn fmt_error(_: fmt::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "formatter error")
+}
+
+/// Lets a `fmt::Write` sink write to an `io::Write`, holding on to the error
+/// if that fails.
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+pub(crate) struct IoWrite<W> {
+    pub(crate) inner: W,
+    pub(crate) error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+impl<W: io::Write> fmt::Write for IoWrite<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.inner.write_all(text.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
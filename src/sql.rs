@@ -0,0 +1,160 @@
+//! SQL string literals, for [`Quoted::sql`](crate::Quoted::sql).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::utf8::chars;
+use crate::util::utf8_chunks;
+use crate::{Options, Original, SqlDialect};
+
+/// The part of the literal we're in.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Piece {
+    /// Nothing was written yet.
+    Start,
+    /// Inside `'...'`.
+    Quoted,
+    /// Inside `x'...'`.
+    Hex,
+}
+
+struct Writer<'a> {
+    out: &'a mut dyn Sink,
+    dialect: SqlDialect,
+    piece: Piece,
+}
+
+impl<'a> Writer<'a> {
+    /// Switch to another piece, concatenating it to the previous one.
+    fn enter(&mut self, piece: Piece) -> fmt::Result {
+        if self.piece == piece {
+            return Ok(());
+        }
+        let first = self.piece == Piece::Start;
+        if !first {
+            self.out.close("'")?;
+        }
+        let open = match (piece, first, self.dialect) {
+            (Piece::Quoted, true, _) => "'",
+            (Piece::Hex, true, _) => "x'",
+            (Piece::Quoted, false, SqlDialect::Standard) => " || '",
+            (Piece::Hex, false, SqlDialect::Standard) => " || x'",
+            (Piece::Quoted, false, SqlDialect::MySql) => ", '",
+            (Piece::Hex, false, SqlDialect::MySql) => ", x'",
+            (Piece::Start, _, _) => unreachable!(),
+        };
+        self.piece = piece;
+        self.out.open(open)
+    }
+
+    fn hex(&mut self, original: Original, bytes: &[u8]) -> fmt::Result {
+        self.enter(Piece::Hex)?;
+        self.out
+            .escape_fmt(original, format_args!("{}", Hex(bytes)))
+    }
+
+    fn finish(&mut self) -> fmt::Result {
+        if self.piece == Piece::Start {
+            self.enter(Piece::Quoted)?;
+        }
+        self.out.close("'")
+    }
+}
+
+pub(crate) fn write(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    let dialect = opts.sql_dialect;
+    let escapes = opts.text_escapes(chars(text));
+    let hex = |ch: char| opts.escapes(ch) || escapes.contains(ch);
+
+    // MySQL's || is a logical OR, so the pieces are joined with CONCAT().
+    let concat = dialect == SqlDialect::MySql
+        && utf8_chunks(text).any(|chunk| match chunk {
+            Ok(chunk) => chunk
+                .chars()
+                .any(|ch| hex(ch) && mysql_escape(ch).is_none()),
+            Err(_) => true,
+        });
+    if concat {
+        out.open("CONCAT(")?;
+    }
+
+    let mut writer = Writer {
+        out,
+        dialect,
+        piece: Piece::Start,
+    };
+    for chunk in utf8_chunks(text) {
+        match chunk {
+            Ok(chunk) => {
+                let mut start = 0;
+                for (index, ch) in chunk.char_indices() {
+                    let escape = match dialect {
+                        SqlDialect::MySql => match ch {
+                            '\'' => Some("\\'"),
+                            '\\' => Some("\\\\"),
+                            _ if hex(ch) => mysql_escape(ch),
+                            _ => None,
+                        },
+                        SqlDialect::Standard if ch == '\'' => Some("''"),
+                        SqlDialect::Standard => None,
+                    };
+                    if escape.is_none() && !hex(ch) {
+                        continue;
+                    }
+                    if start < index {
+                        writer.enter(Piece::Quoted)?;
+                        writer.out.literal(&chunk[start..index])?;
+                    }
+                    start = index + ch.len_utf8();
+                    match escape {
+                        Some(escape) => {
+                            writer.enter(Piece::Quoted)?;
+                            writer.out.escape(Original::Char(ch), escape)?;
+                        }
+                        None => {
+                            let bytes = &chunk.as_bytes()[index..start];
+                            writer.hex(Original::Char(ch), bytes)?;
+                        }
+                    }
+                }
+                if start < chunk.len() {
+                    writer.enter(Piece::Quoted)?;
+                    writer.out.literal(&chunk[start..])?;
+                }
+            }
+            Err(byte) => writer.hex(Original::Byte(byte), &[byte])?,
+        }
+    }
+    writer.finish()?;
+
+    if concat {
+        writer.out.close(")")?;
+    }
+    Ok(())
+}
+
+/// The backslash escapes MySQL understands, for characters that would
+/// otherwise go in a hex literal.
+fn mysql_escape(ch: char) -> Option<&'static str> {
+    match ch {
+        '\0' => Some("\\0"),
+        '\u{8}' => Some("\\b"),
+        '\t' => Some("\\t"),
+        '\n' => Some("\\n"),
+        '\r' => Some("\\r"),
+        '\u{1A}' => Some("\\Z"),
+        _ => None,
+    }
+}
+
+/// Formats bytes as pairs of hex digits.
+struct Hex<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
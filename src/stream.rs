@@ -0,0 +1,111 @@
+//! Quoting for input that doesn't have to fit in memory, for [`quote_stream`].
+
+use core::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::sink::{fmt_error, IoWrite, Plain, Sink};
+use crate::utf8::Pending;
+use crate::{Quoted, Style, TextEscapes};
+
+/// Quote everything from a reader as a single string, without reading it
+/// all into memory first.
+///
+/// ```
+/// # #[cfg(feature = "unix")] {
+/// use os_display::{quote_stream, Style};
+///
+/// let mut out = Vec::new();
+/// quote_stream(&b"one\ntwo"[..], &mut out, Style::Unix).unwrap();
+/// assert_eq!(out, b"$'one\\ntwo'");
+/// # }
+/// ```
+///
+/// Since the input can't be checked in advance the output always uses the
+/// escaped form of the style, `$'...'` for bash/ksh and `"..."` for
+/// PowerShell, even if plain quotes or no quotes would have done. Other than
+/// that it's the same as quoting all of the input at once, no matter where
+/// the reader splits it.
+///
+/// Invalid UTF-8 is escaped byte by byte for bash/ksh. PowerShell strings
+/// can't contain it, so there each invalid byte is replaced by U+FFFD.
+///
+/// The writer gets many small writes, so it's worth buffering.
+///
+/// # Errors
+/// If reading or writing fails. Whatever was written up to that point is
+/// left unterminated.
+///
+/// With [`io::ErrorKind::InvalidInput`] if `style` isn't [`Style::Unix`] or
+/// [`Style::Powershell`]. Nothing is read or written then.
+///
+/// # Optional
+/// This requires the `std` feature.
+pub fn quote_stream<R: BufRead, W: Write>(reader: R, writer: W, style: Style) -> io::Result<()> {
+    let mut writer = IoWrite {
+        inner: writer,
+        error: None,
+    };
+    let result = quote(reader, &mut Plain(&mut writer), style);
+    match writer.error {
+        Some(err) => Err(err),
+        None => result,
+    }
+}
+
+fn quote(reader: impl BufRead, out: &mut dyn Sink, style: Style) -> io::Result<()> {
+    let opts = Quoted::new(style, "").opts;
+    // There's no way to tell whether the text as a whole looks like ASCII,
+    // so confusables aren't escaped, and neither are floods of combining
+    // marks.
+    #[allow(unreachable_patterns)]
+    match style {
+        #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+        Style::Unix => {
+            let mut writer = crate::unix::EscapedWriter::new(out, &opts, TextEscapes::default())
+                .map_err(fmt_error)?;
+            read_utf8(reader, |chunk| writer.write(out, chunk))?;
+            writer.finish(out).map_err(fmt_error)
+        }
+        #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+        Style::Powershell => {
+            let mut writer = crate::windows::EscapedWriter::new(out, &opts, TextEscapes::default())
+                .map_err(fmt_error)?;
+            read_utf8(reader, |chunk| {
+                let chars = crate::util::utf8_chunks(chunk).flat_map(|chunk| {
+                    let (chunk, invalid) = match chunk {
+                        Ok(chunk) => (chunk, None),
+                        Err(_) => ("", Some(Ok(core::char::REPLACEMENT_CHARACTER))),
+                    };
+                    chunk.chars().map(Ok).chain(invalid)
+                });
+                writer.write(out, chars)
+            })?;
+            writer.finish(out).map_err(fmt_error)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a shell style",
+        )),
+    }
+}
+
+/// Pass everything from the reader on in chunks that don't end in the middle
+/// of a UTF-8 sequence. A sequence that's cut off by the end of the reader's
+/// buffer is held back until the rest comes in.
+fn read_utf8(mut reader: impl BufRead, mut f: impl FnMut(&[u8]) -> fmt::Result) -> io::Result<()> {
+    let mut pending = Pending::default();
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.is_empty() {
+            break;
+        }
+        pending.push(buf, &mut f).map_err(fmt_error)?;
+        let len = buf.len();
+        reader.consume(len);
+    }
+    pending.finish(f).map_err(fmt_error)
+}
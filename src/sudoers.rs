@@ -0,0 +1,58 @@
+//! Command specifications in a sudoers file, for
+//! [`Quoted::sudoers`](crate::Quoted::sudoers).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::Original;
+
+/// Characters that get a backslash. `,:=\` and whitespace are part of the
+/// sudoers syntax, `#` can start a comment and the rest are wildcards, which
+/// sudo matches against the command and its arguments.
+fn is_special(ch: char) -> bool {
+    match ch {
+        ',' | ':' | '=' | '\\' | ' ' | '\t' | '#' => true,
+        '*' | '?' | '[' | ']' => true,
+        _ => false,
+    }
+}
+
+/// Control characters other than tabs can't be written at all, a newline
+/// would even end the entry.
+pub(crate) fn is_forbidden(ch: char) -> bool {
+    ch.is_control() && ch != '\t'
+}
+
+/// There's nothing to quote with, so everything is backslash-escaped.
+///
+/// Forbidden characters are replaced by U+FFFD so that the entry can't
+/// match them.
+pub(crate) fn write(out: &mut dyn Sink, text: &str) -> fmt::Result {
+    if text.is_empty() {
+        // This means "no arguments" in place of the arguments, which is
+        // the closest we can get.
+        return out.open("\"\"");
+    }
+    let mut start = 0;
+    for (index, ch) in text.char_indices() {
+        // An argument that starts with ^ and ends with $ would be a
+        // regular expression.
+        let backslash = is_special(ch) || (index == 0 && ch == '^');
+        if !backslash && !is_forbidden(ch) {
+            continue;
+        }
+        if start < index {
+            out.literal(&text[start..index])?;
+        }
+        start = index + ch.len_utf8();
+        if backslash {
+            out.escape_fmt(Original::Char(ch), format_args!("\\{}", ch))?;
+        } else {
+            out.escape(Original::Char(ch), "\u{FFFD}")?;
+        }
+    }
+    if start < text.len() {
+        out.literal(&text[start..])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Unit names for paths, for [`Quoted::systemd_unit`](crate::Quoted::systemd_unit).
+
+use core::fmt;
+
+use crate::sink::Sink;
+use crate::util::utf8_chunks;
+use crate::Original;
+
+/// Whether a byte can appear in the name as it is. `-` and `\` are valid in
+/// unit names too, but they have to be escaped because they're used as
+/// escapes themselves.
+fn is_plain(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b':' | b'_' | b'.' => true,
+        _ => false,
+    }
+}
+
+/// This follows `unit_name_path_escape()` from systemd: the path is
+/// simplified, the slashes at either end are dropped, other slashes become
+/// dashes and everything else that's special is written as `\xNN`.
+pub(crate) fn write(out: &mut dyn Sink, path: &[u8]) -> fmt::Result {
+    let mut first = true;
+    for component in path.split(|&byte| byte == b'/') {
+        if component.is_empty() || component == b"." {
+            continue;
+        }
+        if first {
+            // Unit names shouldn't start with a dot, like hidden files.
+            if component[0] == b'.' {
+                out.escape(Original::Char('.'), "\\x2e")?;
+                write_component(out, &component[1..])?;
+            } else {
+                write_component(out, component)?;
+            }
+            first = false;
+        } else {
+            out.literal("-")?;
+            write_component(out, component)?;
+        }
+    }
+    if first {
+        // The root directory.
+        out.literal("-")?;
+    }
+    Ok(())
+}
+
+fn write_component(out: &mut dyn Sink, component: &[u8]) -> fmt::Result {
+    for chunk in utf8_chunks(component) {
+        match chunk {
+            Ok(chunk) => {
+                let mut start = 0;
+                for (index, ch) in chunk.char_indices() {
+                    if ch.is_ascii() && is_plain(ch as u8) {
+                        continue;
+                    }
+                    if start < index {
+                        out.literal(&chunk[start..index])?;
+                    }
+                    start = index + ch.len_utf8();
+                    let bytes = &chunk.as_bytes()[index..start];
+                    out.escape_fmt(Original::Char(ch), format_args!("{}", Escapes(bytes)))?;
+                }
+                if start < chunk.len() {
+                    out.literal(&chunk[start..])?;
+                }
+            }
+            Err(byte) => out.escape_fmt(Original::Byte(byte), format_args!("\\x{:02x}", byte))?,
+        }
+    }
+    Ok(())
+}
+
+/// Formats bytes as a series of `\xnn` escapes, in lowercase like systemd.
+struct Escapes<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Escapes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "\\x{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2359 @@
+// This file is generated by scripts/gen_tables.py. Do not edit it by hand.
+
+//! Unicode data, for the version that's selected by the `unicodeNN` features.
+//!
+//! Each table is a sorted list of inclusive ranges.
+
+// Which tables are used depends on the quoting styles that are enabled.
+#![allow(dead_code)]
+
+#[cfg(feature = "unicode14")]
+#[rustfmt::skip]
+mod data {
+    pub(crate) const UNICODE_VERSION: (u8, u8, u8) = (14, 0, 0);
+
+    pub(crate) const SPACE_SEPARATOR: &[(char, char)] = &[
+        ('\u{20}', '\u{20}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+
+    pub(crate) const LINE_PARAGRAPH_SEPARATOR: &[(char, char)] = &[
+        ('\u{2028}', '\u{2029}'),
+    ];
+
+    pub(crate) const FORMAT: &[(char, char)] = &[
+        ('\u{AD}', '\u{AD}'),
+        ('\u{600}', '\u{605}'),
+        ('\u{61C}', '\u{61C}'),
+        ('\u{6DD}', '\u{6DD}'),
+        ('\u{70F}', '\u{70F}'),
+        ('\u{890}', '\u{891}'),
+        ('\u{8E2}', '\u{8E2}'),
+        ('\u{180E}', '\u{180E}'),
+        ('\u{200B}', '\u{200F}'),
+        ('\u{202A}', '\u{202E}'),
+        ('\u{2060}', '\u{2064}'),
+        ('\u{2066}', '\u{206F}'),
+        ('\u{FEFF}', '\u{FEFF}'),
+        ('\u{FFF9}', '\u{FFFB}'),
+        ('\u{110BD}', '\u{110BD}'),
+        ('\u{110CD}', '\u{110CD}'),
+        ('\u{13430}', '\u{13438}'),
+        ('\u{1BCA0}', '\u{1BCA3}'),
+        ('\u{1D173}', '\u{1D17A}'),
+        ('\u{E0001}', '\u{E0001}'),
+        ('\u{E0020}', '\u{E007F}'),
+    ];
+
+    pub(crate) const PRIVATE_USE: &[(char, char)] = &[
+        ('\u{E000}', '\u{F8FF}'),
+        ('\u{F0000}', '\u{FFFFD}'),
+        ('\u{100000}', '\u{10FFFD}'),
+    ];
+
+    pub(crate) const UNASSIGNED: &[(char, char)] = &[
+        ('\u{378}', '\u{379}'),
+        ('\u{380}', '\u{383}'),
+        ('\u{38B}', '\u{38B}'),
+        ('\u{38D}', '\u{38D}'),
+        ('\u{3A2}', '\u{3A2}'),
+        ('\u{530}', '\u{530}'),
+        ('\u{557}', '\u{558}'),
+        ('\u{58B}', '\u{58C}'),
+        ('\u{590}', '\u{590}'),
+        ('\u{5C8}', '\u{5CF}'),
+        ('\u{5EB}', '\u{5EE}'),
+        ('\u{5F5}', '\u{5FF}'),
+        ('\u{70E}', '\u{70E}'),
+        ('\u{74B}', '\u{74C}'),
+        ('\u{7B2}', '\u{7BF}'),
+        ('\u{7FB}', '\u{7FC}'),
+        ('\u{82E}', '\u{82F}'),
+        ('\u{83F}', '\u{83F}'),
+        ('\u{85C}', '\u{85D}'),
+        ('\u{85F}', '\u{85F}'),
+        ('\u{86B}', '\u{86F}'),
+        ('\u{88F}', '\u{88F}'),
+        ('\u{892}', '\u{897}'),
+        ('\u{984}', '\u{984}'),
+        ('\u{98D}', '\u{98E}'),
+        ('\u{991}', '\u{992}'),
+        ('\u{9A9}', '\u{9A9}'),
+        ('\u{9B1}', '\u{9B1}'),
+        ('\u{9B3}', '\u{9B5}'),
+        ('\u{9BA}', '\u{9BB}'),
+        ('\u{9C5}', '\u{9C6}'),
+        ('\u{9C9}', '\u{9CA}'),
+        ('\u{9CF}', '\u{9D6}'),
+        ('\u{9D8}', '\u{9DB}'),
+        ('\u{9DE}', '\u{9DE}'),
+        ('\u{9E4}', '\u{9E5}'),
+        ('\u{9FF}', '\u{A00}'),
+        ('\u{A04}', '\u{A04}'),
+        ('\u{A0B}', '\u{A0E}'),
+        ('\u{A11}', '\u{A12}'),
+        ('\u{A29}', '\u{A29}'),
+        ('\u{A31}', '\u{A31}'),
+        ('\u{A34}', '\u{A34}'),
+        ('\u{A37}', '\u{A37}'),
+        ('\u{A3A}', '\u{A3B}'),
+        ('\u{A3D}', '\u{A3D}'),
+        ('\u{A43}', '\u{A46}'),
+        ('\u{A49}', '\u{A4A}'),
+        ('\u{A4E}', '\u{A50}'),
+        ('\u{A52}', '\u{A58}'),
+        ('\u{A5D}', '\u{A5D}'),
+        ('\u{A5F}', '\u{A65}'),
+        ('\u{A77}', '\u{A80}'),
+        ('\u{A84}', '\u{A84}'),
+        ('\u{A8E}', '\u{A8E}'),
+        ('\u{A92}', '\u{A92}'),
+        ('\u{AA9}', '\u{AA9}'),
+        ('\u{AB1}', '\u{AB1}'),
+        ('\u{AB4}', '\u{AB4}'),
+        ('\u{ABA}', '\u{ABB}'),
+        ('\u{AC6}', '\u{AC6}'),
+        ('\u{ACA}', '\u{ACA}'),
+        ('\u{ACE}', '\u{ACF}'),
+        ('\u{AD1}', '\u{ADF}'),
+        ('\u{AE4}', '\u{AE5}'),
+        ('\u{AF2}', '\u{AF8}'),
+        ('\u{B00}', '\u{B00}'),
+        ('\u{B04}', '\u{B04}'),
+        ('\u{B0D}', '\u{B0E}'),
+        ('\u{B11}', '\u{B12}'),
+        ('\u{B29}', '\u{B29}'),
+        ('\u{B31}', '\u{B31}'),
+        ('\u{B34}', '\u{B34}'),
+        ('\u{B3A}', '\u{B3B}'),
+        ('\u{B45}', '\u{B46}'),
+        ('\u{B49}', '\u{B4A}'),
+        ('\u{B4E}', '\u{B54}'),
+        ('\u{B58}', '\u{B5B}'),
+        ('\u{B5E}', '\u{B5E}'),
+        ('\u{B64}', '\u{B65}'),
+        ('\u{B78}', '\u{B81}'),
+        ('\u{B84}', '\u{B84}'),
+        ('\u{B8B}', '\u{B8D}'),
+        ('\u{B91}', '\u{B91}'),
+        ('\u{B96}', '\u{B98}'),
+        ('\u{B9B}', '\u{B9B}'),
+        ('\u{B9D}', '\u{B9D}'),
+        ('\u{BA0}', '\u{BA2}'),
+        ('\u{BA5}', '\u{BA7}'),
+        ('\u{BAB}', '\u{BAD}'),
+        ('\u{BBA}', '\u{BBD}'),
+        ('\u{BC3}', '\u{BC5}'),
+        ('\u{BC9}', '\u{BC9}'),
+        ('\u{BCE}', '\u{BCF}'),
+        ('\u{BD1}', '\u{BD6}'),
+        ('\u{BD8}', '\u{BE5}'),
+        ('\u{BFB}', '\u{BFF}'),
+        ('\u{C0D}', '\u{C0D}'),
+        ('\u{C11}', '\u{C11}'),
+        ('\u{C29}', '\u{C29}'),
+        ('\u{C3A}', '\u{C3B}'),
+        ('\u{C45}', '\u{C45}'),
+        ('\u{C49}', '\u{C49}'),
+        ('\u{C4E}', '\u{C54}'),
+        ('\u{C57}', '\u{C57}'),
+        ('\u{C5B}', '\u{C5C}'),
+        ('\u{C5E}', '\u{C5F}'),
+        ('\u{C64}', '\u{C65}'),
+        ('\u{C70}', '\u{C76}'),
+        ('\u{C8D}', '\u{C8D}'),
+        ('\u{C91}', '\u{C91}'),
+        ('\u{CA9}', '\u{CA9}'),
+        ('\u{CB4}', '\u{CB4}'),
+        ('\u{CBA}', '\u{CBB}'),
+        ('\u{CC5}', '\u{CC5}'),
+        ('\u{CC9}', '\u{CC9}'),
+        ('\u{CCE}', '\u{CD4}'),
+        ('\u{CD7}', '\u{CDC}'),
+        ('\u{CDF}', '\u{CDF}'),
+        ('\u{CE4}', '\u{CE5}'),
+        ('\u{CF0}', '\u{CF0}'),
+        ('\u{CF3}', '\u{CFF}'),
+        ('\u{D0D}', '\u{D0D}'),
+        ('\u{D11}', '\u{D11}'),
+        ('\u{D45}', '\u{D45}'),
+        ('\u{D49}', '\u{D49}'),
+        ('\u{D50}', '\u{D53}'),
+        ('\u{D64}', '\u{D65}'),
+        ('\u{D80}', '\u{D80}'),
+        ('\u{D84}', '\u{D84}'),
+        ('\u{D97}', '\u{D99}'),
+        ('\u{DB2}', '\u{DB2}'),
+        ('\u{DBC}', '\u{DBC}'),
+        ('\u{DBE}', '\u{DBF}'),
+        ('\u{DC7}', '\u{DC9}'),
+        ('\u{DCB}', '\u{DCE}'),
+        ('\u{DD5}', '\u{DD5}'),
+        ('\u{DD7}', '\u{DD7}'),
+        ('\u{DE0}', '\u{DE5}'),
+        ('\u{DF0}', '\u{DF1}'),
+        ('\u{DF5}', '\u{E00}'),
+        ('\u{E3B}', '\u{E3E}'),
+        ('\u{E5C}', '\u{E80}'),
+        ('\u{E83}', '\u{E83}'),
+        ('\u{E85}', '\u{E85}'),
+        ('\u{E8B}', '\u{E8B}'),
+        ('\u{EA4}', '\u{EA4}'),
+        ('\u{EA6}', '\u{EA6}'),
+        ('\u{EBE}', '\u{EBF}'),
+        ('\u{EC5}', '\u{EC5}'),
+        ('\u{EC7}', '\u{EC7}'),
+        ('\u{ECE}', '\u{ECF}'),
+        ('\u{EDA}', '\u{EDB}'),
+        ('\u{EE0}', '\u{EFF}'),
+        ('\u{F48}', '\u{F48}'),
+        ('\u{F6D}', '\u{F70}'),
+        ('\u{F98}', '\u{F98}'),
+        ('\u{FBD}', '\u{FBD}'),
+        ('\u{FCD}', '\u{FCD}'),
+        ('\u{FDB}', '\u{FFF}'),
+        ('\u{10C6}', '\u{10C6}'),
+        ('\u{10C8}', '\u{10CC}'),
+        ('\u{10CE}', '\u{10CF}'),
+        ('\u{1249}', '\u{1249}'),
+        ('\u{124E}', '\u{124F}'),
+        ('\u{1257}', '\u{1257}'),
+        ('\u{1259}', '\u{1259}'),
+        ('\u{125E}', '\u{125F}'),
+        ('\u{1289}', '\u{1289}'),
+        ('\u{128E}', '\u{128F}'),
+        ('\u{12B1}', '\u{12B1}'),
+        ('\u{12B6}', '\u{12B7}'),
+        ('\u{12BF}', '\u{12BF}'),
+        ('\u{12C1}', '\u{12C1}'),
+        ('\u{12C6}', '\u{12C7}'),
+        ('\u{12D7}', '\u{12D7}'),
+        ('\u{1311}', '\u{1311}'),
+        ('\u{1316}', '\u{1317}'),
+        ('\u{135B}', '\u{135C}'),
+        ('\u{137D}', '\u{137F}'),
+        ('\u{139A}', '\u{139F}'),
+        ('\u{13F6}', '\u{13F7}'),
+        ('\u{13FE}', '\u{13FF}'),
+        ('\u{169D}', '\u{169F}'),
+        ('\u{16F9}', '\u{16FF}'),
+        ('\u{1716}', '\u{171E}'),
+        ('\u{1737}', '\u{173F}'),
+        ('\u{1754}', '\u{175F}'),
+        ('\u{176D}', '\u{176D}'),
+        ('\u{1771}', '\u{1771}'),
+        ('\u{1774}', '\u{177F}'),
+        ('\u{17DE}', '\u{17DF}'),
+        ('\u{17EA}', '\u{17EF}'),
+        ('\u{17FA}', '\u{17FF}'),
+        ('\u{181A}', '\u{181F}'),
+        ('\u{1879}', '\u{187F}'),
+        ('\u{18AB}', '\u{18AF}'),
+        ('\u{18F6}', '\u{18FF}'),
+        ('\u{191F}', '\u{191F}'),
+        ('\u{192C}', '\u{192F}'),
+        ('\u{193C}', '\u{193F}'),
+        ('\u{1941}', '\u{1943}'),
+        ('\u{196E}', '\u{196F}'),
+        ('\u{1975}', '\u{197F}'),
+        ('\u{19AC}', '\u{19AF}'),
+        ('\u{19CA}', '\u{19CF}'),
+        ('\u{19DB}', '\u{19DD}'),
+        ('\u{1A1C}', '\u{1A1D}'),
+        ('\u{1A5F}', '\u{1A5F}'),
+        ('\u{1A7D}', '\u{1A7E}'),
+        ('\u{1A8A}', '\u{1A8F}'),
+        ('\u{1A9A}', '\u{1A9F}'),
+        ('\u{1AAE}', '\u{1AAF}'),
+        ('\u{1ACF}', '\u{1AFF}'),
+        ('\u{1B4D}', '\u{1B4F}'),
+        ('\u{1B7F}', '\u{1B7F}'),
+        ('\u{1BF4}', '\u{1BFB}'),
+        ('\u{1C38}', '\u{1C3A}'),
+        ('\u{1C4A}', '\u{1C4C}'),
+        ('\u{1C89}', '\u{1C8F}'),
+        ('\u{1CBB}', '\u{1CBC}'),
+        ('\u{1CC8}', '\u{1CCF}'),
+        ('\u{1CFB}', '\u{1CFF}'),
+        ('\u{1F16}', '\u{1F17}'),
+        ('\u{1F1E}', '\u{1F1F}'),
+        ('\u{1F46}', '\u{1F47}'),
+        ('\u{1F4E}', '\u{1F4F}'),
+        ('\u{1F58}', '\u{1F58}'),
+        ('\u{1F5A}', '\u{1F5A}'),
+        ('\u{1F5C}', '\u{1F5C}'),
+        ('\u{1F5E}', '\u{1F5E}'),
+        ('\u{1F7E}', '\u{1F7F}'),
+        ('\u{1FB5}', '\u{1FB5}'),
+        ('\u{1FC5}', '\u{1FC5}'),
+        ('\u{1FD4}', '\u{1FD5}'),
+        ('\u{1FDC}', '\u{1FDC}'),
+        ('\u{1FF0}', '\u{1FF1}'),
+        ('\u{1FF5}', '\u{1FF5}'),
+        ('\u{1FFF}', '\u{1FFF}'),
+        ('\u{2065}', '\u{2065}'),
+        ('\u{2072}', '\u{2073}'),
+        ('\u{208F}', '\u{208F}'),
+        ('\u{209D}', '\u{209F}'),
+        ('\u{20C1}', '\u{20CF}'),
+        ('\u{20F1}', '\u{20FF}'),
+        ('\u{218C}', '\u{218F}'),
+        ('\u{2427}', '\u{243F}'),
+        ('\u{244B}', '\u{245F}'),
+        ('\u{2B74}', '\u{2B75}'),
+        ('\u{2B96}', '\u{2B96}'),
+        ('\u{2CF4}', '\u{2CF8}'),
+        ('\u{2D26}', '\u{2D26}'),
+        ('\u{2D28}', '\u{2D2C}'),
+        ('\u{2D2E}', '\u{2D2F}'),
+        ('\u{2D68}', '\u{2D6E}'),
+        ('\u{2D71}', '\u{2D7E}'),
+        ('\u{2D97}', '\u{2D9F}'),
+        ('\u{2DA7}', '\u{2DA7}'),
+        ('\u{2DAF}', '\u{2DAF}'),
+        ('\u{2DB7}', '\u{2DB7}'),
+        ('\u{2DBF}', '\u{2DBF}'),
+        ('\u{2DC7}', '\u{2DC7}'),
+        ('\u{2DCF}', '\u{2DCF}'),
+        ('\u{2DD7}', '\u{2DD7}'),
+        ('\u{2DDF}', '\u{2DDF}'),
+        ('\u{2E5E}', '\u{2E7F}'),
+        ('\u{2E9A}', '\u{2E9A}'),
+        ('\u{2EF4}', '\u{2EFF}'),
+        ('\u{2FD6}', '\u{2FEF}'),
+        ('\u{2FFC}', '\u{2FFF}'),
+        ('\u{3040}', '\u{3040}'),
+        ('\u{3097}', '\u{3098}'),
+        ('\u{3100}', '\u{3104}'),
+        ('\u{3130}', '\u{3130}'),
+        ('\u{318F}', '\u{318F}'),
+        ('\u{31E4}', '\u{31EF}'),
+        ('\u{321F}', '\u{321F}'),
+        ('\u{A48D}', '\u{A48F}'),
+        ('\u{A4C7}', '\u{A4CF}'),
+        ('\u{A62C}', '\u{A63F}'),
+        ('\u{A6F8}', '\u{A6FF}'),
+        ('\u{A7CB}', '\u{A7CF}'),
+        ('\u{A7D2}', '\u{A7D2}'),
+        ('\u{A7D4}', '\u{A7D4}'),
+        ('\u{A7DA}', '\u{A7F1}'),
+        ('\u{A82D}', '\u{A82F}'),
+        ('\u{A83A}', '\u{A83F}'),
+        ('\u{A878}', '\u{A87F}'),
+        ('\u{A8C6}', '\u{A8CD}'),
+        ('\u{A8DA}', '\u{A8DF}'),
+        ('\u{A954}', '\u{A95E}'),
+        ('\u{A97D}', '\u{A97F}'),
+        ('\u{A9CE}', '\u{A9CE}'),
+        ('\u{A9DA}', '\u{A9DD}'),
+        ('\u{A9FF}', '\u{A9FF}'),
+        ('\u{AA37}', '\u{AA3F}'),
+        ('\u{AA4E}', '\u{AA4F}'),
+        ('\u{AA5A}', '\u{AA5B}'),
+        ('\u{AAC3}', '\u{AADA}'),
+        ('\u{AAF7}', '\u{AB00}'),
+        ('\u{AB07}', '\u{AB08}'),
+        ('\u{AB0F}', '\u{AB10}'),
+        ('\u{AB17}', '\u{AB1F}'),
+        ('\u{AB27}', '\u{AB27}'),
+        ('\u{AB2F}', '\u{AB2F}'),
+        ('\u{AB6C}', '\u{AB6F}'),
+        ('\u{ABEE}', '\u{ABEF}'),
+        ('\u{ABFA}', '\u{ABFF}'),
+        ('\u{D7A4}', '\u{D7AF}'),
+        ('\u{D7C7}', '\u{D7CA}'),
+        ('\u{D7FC}', '\u{D7FF}'),
+        ('\u{FA6E}', '\u{FA6F}'),
+        ('\u{FADA}', '\u{FAFF}'),
+        ('\u{FB07}', '\u{FB12}'),
+        ('\u{FB18}', '\u{FB1C}'),
+        ('\u{FB37}', '\u{FB37}'),
+        ('\u{FB3D}', '\u{FB3D}'),
+        ('\u{FB3F}', '\u{FB3F}'),
+        ('\u{FB42}', '\u{FB42}'),
+        ('\u{FB45}', '\u{FB45}'),
+        ('\u{FBC3}', '\u{FBD2}'),
+        ('\u{FD90}', '\u{FD91}'),
+        ('\u{FDC8}', '\u{FDCE}'),
+        ('\u{FDD0}', '\u{FDEF}'),
+        ('\u{FE1A}', '\u{FE1F}'),
+        ('\u{FE53}', '\u{FE53}'),
+        ('\u{FE67}', '\u{FE67}'),
+        ('\u{FE6C}', '\u{FE6F}'),
+        ('\u{FE75}', '\u{FE75}'),
+        ('\u{FEFD}', '\u{FEFE}'),
+        ('\u{FF00}', '\u{FF00}'),
+        ('\u{FFBF}', '\u{FFC1}'),
+        ('\u{FFC8}', '\u{FFC9}'),
+        ('\u{FFD0}', '\u{FFD1}'),
+        ('\u{FFD8}', '\u{FFD9}'),
+        ('\u{FFDD}', '\u{FFDF}'),
+        ('\u{FFE7}', '\u{FFE7}'),
+        ('\u{FFEF}', '\u{FFF8}'),
+        ('\u{FFFE}', '\u{FFFF}'),
+        ('\u{1000C}', '\u{1000C}'),
+        ('\u{10027}', '\u{10027}'),
+        ('\u{1003B}', '\u{1003B}'),
+        ('\u{1003E}', '\u{1003E}'),
+        ('\u{1004E}', '\u{1004F}'),
+        ('\u{1005E}', '\u{1007F}'),
+        ('\u{100FB}', '\u{100FF}'),
+        ('\u{10103}', '\u{10106}'),
+        ('\u{10134}', '\u{10136}'),
+        ('\u{1018F}', '\u{1018F}'),
+        ('\u{1019D}', '\u{1019F}'),
+        ('\u{101A1}', '\u{101CF}'),
+        ('\u{101FE}', '\u{1027F}'),
+        ('\u{1029D}', '\u{1029F}'),
+        ('\u{102D1}', '\u{102DF}'),
+        ('\u{102FC}', '\u{102FF}'),
+        ('\u{10324}', '\u{1032C}'),
+        ('\u{1034B}', '\u{1034F}'),
+        ('\u{1037B}', '\u{1037F}'),
+        ('\u{1039E}', '\u{1039E}'),
+        ('\u{103C4}', '\u{103C7}'),
+        ('\u{103D6}', '\u{103FF}'),
+        ('\u{1049E}', '\u{1049F}'),
+        ('\u{104AA}', '\u{104AF}'),
+        ('\u{104D4}', '\u{104D7}'),
+        ('\u{104FC}', '\u{104FF}'),
+        ('\u{10528}', '\u{1052F}'),
+        ('\u{10564}', '\u{1056E}'),
+        ('\u{1057B}', '\u{1057B}'),
+        ('\u{1058B}', '\u{1058B}'),
+        ('\u{10593}', '\u{10593}'),
+        ('\u{10596}', '\u{10596}'),
+        ('\u{105A2}', '\u{105A2}'),
+        ('\u{105B2}', '\u{105B2}'),
+        ('\u{105BA}', '\u{105BA}'),
+        ('\u{105BD}', '\u{105FF}'),
+        ('\u{10737}', '\u{1073F}'),
+        ('\u{10756}', '\u{1075F}'),
+        ('\u{10768}', '\u{1077F}'),
+        ('\u{10786}', '\u{10786}'),
+        ('\u{107B1}', '\u{107B1}'),
+        ('\u{107BB}', '\u{107FF}'),
+        ('\u{10806}', '\u{10807}'),
+        ('\u{10809}', '\u{10809}'),
+        ('\u{10836}', '\u{10836}'),
+        ('\u{10839}', '\u{1083B}'),
+        ('\u{1083D}', '\u{1083E}'),
+        ('\u{10856}', '\u{10856}'),
+        ('\u{1089F}', '\u{108A6}'),
+        ('\u{108B0}', '\u{108DF}'),
+        ('\u{108F3}', '\u{108F3}'),
+        ('\u{108F6}', '\u{108FA}'),
+        ('\u{1091C}', '\u{1091E}'),
+        ('\u{1093A}', '\u{1093E}'),
+        ('\u{10940}', '\u{1097F}'),
+        ('\u{109B8}', '\u{109BB}'),
+        ('\u{109D0}', '\u{109D1}'),
+        ('\u{10A04}', '\u{10A04}'),
+        ('\u{10A07}', '\u{10A0B}'),
+        ('\u{10A14}', '\u{10A14}'),
+        ('\u{10A18}', '\u{10A18}'),
+        ('\u{10A36}', '\u{10A37}'),
+        ('\u{10A3B}', '\u{10A3E}'),
+        ('\u{10A49}', '\u{10A4F}'),
+        ('\u{10A59}', '\u{10A5F}'),
+        ('\u{10AA0}', '\u{10ABF}'),
+        ('\u{10AE7}', '\u{10AEA}'),
+        ('\u{10AF7}', '\u{10AFF}'),
+        ('\u{10B36}', '\u{10B38}'),
+        ('\u{10B56}', '\u{10B57}'),
+        ('\u{10B73}', '\u{10B77}'),
+        ('\u{10B92}', '\u{10B98}'),
+        ('\u{10B9D}', '\u{10BA8}'),
+        ('\u{10BB0}', '\u{10BFF}'),
+        ('\u{10C49}', '\u{10C7F}'),
+        ('\u{10CB3}', '\u{10CBF}'),
+        ('\u{10CF3}', '\u{10CF9}'),
+        ('\u{10D28}', '\u{10D2F}'),
+        ('\u{10D3A}', '\u{10E5F}'),
+        ('\u{10E7F}', '\u{10E7F}'),
+        ('\u{10EAA}', '\u{10EAA}'),
+        ('\u{10EAE}', '\u{10EAF}'),
+        ('\u{10EB2}', '\u{10EFF}'),
+        ('\u{10F28}', '\u{10F2F}'),
+        ('\u{10F5A}', '\u{10F6F}'),
+        ('\u{10F8A}', '\u{10FAF}'),
+        ('\u{10FCC}', '\u{10FDF}'),
+        ('\u{10FF7}', '\u{10FFF}'),
+        ('\u{1104E}', '\u{11051}'),
+        ('\u{11076}', '\u{1107E}'),
+        ('\u{110C3}', '\u{110CC}'),
+        ('\u{110CE}', '\u{110CF}'),
+        ('\u{110E9}', '\u{110EF}'),
+        ('\u{110FA}', '\u{110FF}'),
+        ('\u{11135}', '\u{11135}'),
+        ('\u{11148}', '\u{1114F}'),
+        ('\u{11177}', '\u{1117F}'),
+        ('\u{111E0}', '\u{111E0}'),
+        ('\u{111F5}', '\u{111FF}'),
+        ('\u{11212}', '\u{11212}'),
+        ('\u{1123F}', '\u{1127F}'),
+        ('\u{11287}', '\u{11287}'),
+        ('\u{11289}', '\u{11289}'),
+        ('\u{1128E}', '\u{1128E}'),
+        ('\u{1129E}', '\u{1129E}'),
+        ('\u{112AA}', '\u{112AF}'),
+        ('\u{112EB}', '\u{112EF}'),
+        ('\u{112FA}', '\u{112FF}'),
+        ('\u{11304}', '\u{11304}'),
+        ('\u{1130D}', '\u{1130E}'),
+        ('\u{11311}', '\u{11312}'),
+        ('\u{11329}', '\u{11329}'),
+        ('\u{11331}', '\u{11331}'),
+        ('\u{11334}', '\u{11334}'),
+        ('\u{1133A}', '\u{1133A}'),
+        ('\u{11345}', '\u{11346}'),
+        ('\u{11349}', '\u{1134A}'),
+        ('\u{1134E}', '\u{1134F}'),
+        ('\u{11351}', '\u{11356}'),
+        ('\u{11358}', '\u{1135C}'),
+        ('\u{11364}', '\u{11365}'),
+        ('\u{1136D}', '\u{1136F}'),
+        ('\u{11375}', '\u{113FF}'),
+        ('\u{1145C}', '\u{1145C}'),
+        ('\u{11462}', '\u{1147F}'),
+        ('\u{114C8}', '\u{114CF}'),
+        ('\u{114DA}', '\u{1157F}'),
+        ('\u{115B6}', '\u{115B7}'),
+        ('\u{115DE}', '\u{115FF}'),
+        ('\u{11645}', '\u{1164F}'),
+        ('\u{1165A}', '\u{1165F}'),
+        ('\u{1166D}', '\u{1167F}'),
+        ('\u{116BA}', '\u{116BF}'),
+        ('\u{116CA}', '\u{116FF}'),
+        ('\u{1171B}', '\u{1171C}'),
+        ('\u{1172C}', '\u{1172F}'),
+        ('\u{11747}', '\u{117FF}'),
+        ('\u{1183C}', '\u{1189F}'),
+        ('\u{118F3}', '\u{118FE}'),
+        ('\u{11907}', '\u{11908}'),
+        ('\u{1190A}', '\u{1190B}'),
+        ('\u{11914}', '\u{11914}'),
+        ('\u{11917}', '\u{11917}'),
+        ('\u{11936}', '\u{11936}'),
+        ('\u{11939}', '\u{1193A}'),
+        ('\u{11947}', '\u{1194F}'),
+        ('\u{1195A}', '\u{1199F}'),
+        ('\u{119A8}', '\u{119A9}'),
+        ('\u{119D8}', '\u{119D9}'),
+        ('\u{119E5}', '\u{119FF}'),
+        ('\u{11A48}', '\u{11A4F}'),
+        ('\u{11AA3}', '\u{11AAF}'),
+        ('\u{11AF9}', '\u{11BFF}'),
+        ('\u{11C09}', '\u{11C09}'),
+        ('\u{11C37}', '\u{11C37}'),
+        ('\u{11C46}', '\u{11C4F}'),
+        ('\u{11C6D}', '\u{11C6F}'),
+        ('\u{11C90}', '\u{11C91}'),
+        ('\u{11CA8}', '\u{11CA8}'),
+        ('\u{11CB7}', '\u{11CFF}'),
+        ('\u{11D07}', '\u{11D07}'),
+        ('\u{11D0A}', '\u{11D0A}'),
+        ('\u{11D37}', '\u{11D39}'),
+        ('\u{11D3B}', '\u{11D3B}'),
+        ('\u{11D3E}', '\u{11D3E}'),
+        ('\u{11D48}', '\u{11D4F}'),
+        ('\u{11D5A}', '\u{11D5F}'),
+        ('\u{11D66}', '\u{11D66}'),
+        ('\u{11D69}', '\u{11D69}'),
+        ('\u{11D8F}', '\u{11D8F}'),
+        ('\u{11D92}', '\u{11D92}'),
+        ('\u{11D99}', '\u{11D9F}'),
+        ('\u{11DAA}', '\u{11EDF}'),
+        ('\u{11EF9}', '\u{11FAF}'),
+        ('\u{11FB1}', '\u{11FBF}'),
+        ('\u{11FF2}', '\u{11FFE}'),
+        ('\u{1239A}', '\u{123FF}'),
+        ('\u{1246F}', '\u{1246F}'),
+        ('\u{12475}', '\u{1247F}'),
+        ('\u{12544}', '\u{12F8F}'),
+        ('\u{12FF3}', '\u{12FFF}'),
+        ('\u{1342F}', '\u{1342F}'),
+        ('\u{13439}', '\u{143FF}'),
+        ('\u{14647}', '\u{167FF}'),
+        ('\u{16A39}', '\u{16A3F}'),
+        ('\u{16A5F}', '\u{16A5F}'),
+        ('\u{16A6A}', '\u{16A6D}'),
+        ('\u{16ABF}', '\u{16ABF}'),
+        ('\u{16ACA}', '\u{16ACF}'),
+        ('\u{16AEE}', '\u{16AEF}'),
+        ('\u{16AF6}', '\u{16AFF}'),
+        ('\u{16B46}', '\u{16B4F}'),
+        ('\u{16B5A}', '\u{16B5A}'),
+        ('\u{16B62}', '\u{16B62}'),
+        ('\u{16B78}', '\u{16B7C}'),
+        ('\u{16B90}', '\u{16E3F}'),
+        ('\u{16E9B}', '\u{16EFF}'),
+        ('\u{16F4B}', '\u{16F4E}'),
+        ('\u{16F88}', '\u{16F8E}'),
+        ('\u{16FA0}', '\u{16FDF}'),
+        ('\u{16FE5}', '\u{16FEF}'),
+        ('\u{16FF2}', '\u{16FFF}'),
+        ('\u{187F8}', '\u{187FF}'),
+        ('\u{18CD6}', '\u{18CFF}'),
+        ('\u{18D09}', '\u{1AFEF}'),
+        ('\u{1AFF4}', '\u{1AFF4}'),
+        ('\u{1AFFC}', '\u{1AFFC}'),
+        ('\u{1AFFF}', '\u{1AFFF}'),
+        ('\u{1B123}', '\u{1B14F}'),
+        ('\u{1B153}', '\u{1B163}'),
+        ('\u{1B168}', '\u{1B16F}'),
+        ('\u{1B2FC}', '\u{1BBFF}'),
+        ('\u{1BC6B}', '\u{1BC6F}'),
+        ('\u{1BC7D}', '\u{1BC7F}'),
+        ('\u{1BC89}', '\u{1BC8F}'),
+        ('\u{1BC9A}', '\u{1BC9B}'),
+        ('\u{1BCA4}', '\u{1CEFF}'),
+        ('\u{1CF2E}', '\u{1CF2F}'),
+        ('\u{1CF47}', '\u{1CF4F}'),
+        ('\u{1CFC4}', '\u{1CFFF}'),
+        ('\u{1D0F6}', '\u{1D0FF}'),
+        ('\u{1D127}', '\u{1D128}'),
+        ('\u{1D1EB}', '\u{1D1FF}'),
+        ('\u{1D246}', '\u{1D2DF}'),
+        ('\u{1D2F4}', '\u{1D2FF}'),
+        ('\u{1D357}', '\u{1D35F}'),
+        ('\u{1D379}', '\u{1D3FF}'),
+        ('\u{1D455}', '\u{1D455}'),
+        ('\u{1D49D}', '\u{1D49D}'),
+        ('\u{1D4A0}', '\u{1D4A1}'),
+        ('\u{1D4A3}', '\u{1D4A4}'),
+        ('\u{1D4A7}', '\u{1D4A8}'),
+        ('\u{1D4AD}', '\u{1D4AD}'),
+        ('\u{1D4BA}', '\u{1D4BA}'),
+        ('\u{1D4BC}', '\u{1D4BC}'),
+        ('\u{1D4C4}', '\u{1D4C4}'),
+        ('\u{1D506}', '\u{1D506}'),
+        ('\u{1D50B}', '\u{1D50C}'),
+        ('\u{1D515}', '\u{1D515}'),
+        ('\u{1D51D}', '\u{1D51D}'),
+        ('\u{1D53A}', '\u{1D53A}'),
+        ('\u{1D53F}', '\u{1D53F}'),
+        ('\u{1D545}', '\u{1D545}'),
+        ('\u{1D547}', '\u{1D549}'),
+        ('\u{1D551}', '\u{1D551}'),
+        ('\u{1D6A6}', '\u{1D6A7}'),
+        ('\u{1D7CC}', '\u{1D7CD}'),
+        ('\u{1DA8C}', '\u{1DA9A}'),
+        ('\u{1DAA0}', '\u{1DAA0}'),
+        ('\u{1DAB0}', '\u{1DEFF}'),
+        ('\u{1DF1F}', '\u{1DFFF}'),
+        ('\u{1E007}', '\u{1E007}'),
+        ('\u{1E019}', '\u{1E01A}'),
+        ('\u{1E022}', '\u{1E022}'),
+        ('\u{1E025}', '\u{1E025}'),
+        ('\u{1E02B}', '\u{1E0FF}'),
+        ('\u{1E12D}', '\u{1E12F}'),
+        ('\u{1E13E}', '\u{1E13F}'),
+        ('\u{1E14A}', '\u{1E14D}'),
+        ('\u{1E150}', '\u{1E28F}'),
+        ('\u{1E2AF}', '\u{1E2BF}'),
+        ('\u{1E2FA}', '\u{1E2FE}'),
+        ('\u{1E300}', '\u{1E7DF}'),
+        ('\u{1E7E7}', '\u{1E7E7}'),
+        ('\u{1E7EC}', '\u{1E7EC}'),
+        ('\u{1E7EF}', '\u{1E7EF}'),
+        ('\u{1E7FF}', '\u{1E7FF}'),
+        ('\u{1E8C5}', '\u{1E8C6}'),
+        ('\u{1E8D7}', '\u{1E8FF}'),
+        ('\u{1E94C}', '\u{1E94F}'),
+        ('\u{1E95A}', '\u{1E95D}'),
+        ('\u{1E960}', '\u{1EC70}'),
+        ('\u{1ECB5}', '\u{1ED00}'),
+        ('\u{1ED3E}', '\u{1EDFF}'),
+        ('\u{1EE04}', '\u{1EE04}'),
+        ('\u{1EE20}', '\u{1EE20}'),
+        ('\u{1EE23}', '\u{1EE23}'),
+        ('\u{1EE25}', '\u{1EE26}'),
+        ('\u{1EE28}', '\u{1EE28}'),
+        ('\u{1EE33}', '\u{1EE33}'),
+        ('\u{1EE38}', '\u{1EE38}'),
+        ('\u{1EE3A}', '\u{1EE3A}'),
+        ('\u{1EE3C}', '\u{1EE41}'),
+        ('\u{1EE43}', '\u{1EE46}'),
+        ('\u{1EE48}', '\u{1EE48}'),
+        ('\u{1EE4A}', '\u{1EE4A}'),
+        ('\u{1EE4C}', '\u{1EE4C}'),
+        ('\u{1EE50}', '\u{1EE50}'),
+        ('\u{1EE53}', '\u{1EE53}'),
+        ('\u{1EE55}', '\u{1EE56}'),
+        ('\u{1EE58}', '\u{1EE58}'),
+        ('\u{1EE5A}', '\u{1EE5A}'),
+        ('\u{1EE5C}', '\u{1EE5C}'),
+        ('\u{1EE5E}', '\u{1EE5E}'),
+        ('\u{1EE60}', '\u{1EE60}'),
+        ('\u{1EE63}', '\u{1EE63}'),
+        ('\u{1EE65}', '\u{1EE66}'),
+        ('\u{1EE6B}', '\u{1EE6B}'),
+        ('\u{1EE73}', '\u{1EE73}'),
+        ('\u{1EE78}', '\u{1EE78}'),
+        ('\u{1EE7D}', '\u{1EE7D}'),
+        ('\u{1EE7F}', '\u{1EE7F}'),
+        ('\u{1EE8A}', '\u{1EE8A}'),
+        ('\u{1EE9C}', '\u{1EEA0}'),
+        ('\u{1EEA4}', '\u{1EEA4}'),
+        ('\u{1EEAA}', '\u{1EEAA}'),
+        ('\u{1EEBC}', '\u{1EEEF}'),
+        ('\u{1EEF2}', '\u{1EFFF}'),
+        ('\u{1F02C}', '\u{1F02F}'),
+        ('\u{1F094}', '\u{1F09F}'),
+        ('\u{1F0AF}', '\u{1F0B0}'),
+        ('\u{1F0C0}', '\u{1F0C0}'),
+        ('\u{1F0D0}', '\u{1F0D0}'),
+        ('\u{1F0F6}', '\u{1F0FF}'),
+        ('\u{1F1AE}', '\u{1F1E5}'),
+        ('\u{1F203}', '\u{1F20F}'),
+        ('\u{1F23C}', '\u{1F23F}'),
+        ('\u{1F249}', '\u{1F24F}'),
+        ('\u{1F252}', '\u{1F25F}'),
+        ('\u{1F266}', '\u{1F2FF}'),
+        ('\u{1F6D8}', '\u{1F6DC}'),
+        ('\u{1F6ED}', '\u{1F6EF}'),
+        ('\u{1F6FD}', '\u{1F6FF}'),
+        ('\u{1F774}', '\u{1F77F}'),
+        ('\u{1F7D9}', '\u{1F7DF}'),
+        ('\u{1F7EC}', '\u{1F7EF}'),
+        ('\u{1F7F1}', '\u{1F7FF}'),
+        ('\u{1F80C}', '\u{1F80F}'),
+        ('\u{1F848}', '\u{1F84F}'),
+        ('\u{1F85A}', '\u{1F85F}'),
+        ('\u{1F888}', '\u{1F88F}'),
+        ('\u{1F8AE}', '\u{1F8AF}'),
+        ('\u{1F8B2}', '\u{1F8FF}'),
+        ('\u{1FA54}', '\u{1FA5F}'),
+        ('\u{1FA6E}', '\u{1FA6F}'),
+        ('\u{1FA75}', '\u{1FA77}'),
+        ('\u{1FA7D}', '\u{1FA7F}'),
+        ('\u{1FA87}', '\u{1FA8F}'),
+        ('\u{1FAAD}', '\u{1FAAF}'),
+        ('\u{1FABB}', '\u{1FABF}'),
+        ('\u{1FAC6}', '\u{1FACF}'),
+        ('\u{1FADA}', '\u{1FADF}'),
+        ('\u{1FAE8}', '\u{1FAEF}'),
+        ('\u{1FAF7}', '\u{1FAFF}'),
+        ('\u{1FB93}', '\u{1FB93}'),
+        ('\u{1FBCB}', '\u{1FBEF}'),
+        ('\u{1FBFA}', '\u{1FFFF}'),
+        ('\u{2A6E0}', '\u{2A6FF}'),
+        ('\u{2B739}', '\u{2B73F}'),
+        ('\u{2B81E}', '\u{2B81F}'),
+        ('\u{2CEA2}', '\u{2CEAF}'),
+        ('\u{2EBE1}', '\u{2F7FF}'),
+        ('\u{2FA1E}', '\u{2FFFF}'),
+        ('\u{3134B}', '\u{E0000}'),
+        ('\u{E0002}', '\u{E001F}'),
+        ('\u{E0080}', '\u{E00FF}'),
+        ('\u{E01F0}', '\u{EFFFF}'),
+        ('\u{FFFFE}', '\u{FFFFF}'),
+        ('\u{10FFFE}', '\u{10FFFF}'),
+    ];
+
+    pub(crate) const WHITE_SPACE: &[(char, char)] = &[
+        ('\u{9}', '\u{D}'),
+        ('\u{20}', '\u{20}'),
+        ('\u{85}', '\u{85}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{2028}', '\u{2029}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+}
+
+#[cfg(all(feature = "unicode15", not(feature = "unicode14")))]
+#[rustfmt::skip]
+mod data {
+    pub(crate) const UNICODE_VERSION: (u8, u8, u8) = (15, 1, 0);
+
+    pub(crate) const SPACE_SEPARATOR: &[(char, char)] = &[
+        ('\u{20}', '\u{20}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+
+    pub(crate) const LINE_PARAGRAPH_SEPARATOR: &[(char, char)] = &[
+        ('\u{2028}', '\u{2029}'),
+    ];
+
+    pub(crate) const FORMAT: &[(char, char)] = &[
+        ('\u{AD}', '\u{AD}'),
+        ('\u{600}', '\u{605}'),
+        ('\u{61C}', '\u{61C}'),
+        ('\u{6DD}', '\u{6DD}'),
+        ('\u{70F}', '\u{70F}'),
+        ('\u{890}', '\u{891}'),
+        ('\u{8E2}', '\u{8E2}'),
+        ('\u{180E}', '\u{180E}'),
+        ('\u{200B}', '\u{200F}'),
+        ('\u{202A}', '\u{202E}'),
+        ('\u{2060}', '\u{2064}'),
+        ('\u{2066}', '\u{206F}'),
+        ('\u{FEFF}', '\u{FEFF}'),
+        ('\u{FFF9}', '\u{FFFB}'),
+        ('\u{110BD}', '\u{110BD}'),
+        ('\u{110CD}', '\u{110CD}'),
+        ('\u{13430}', '\u{1343F}'),
+        ('\u{1BCA0}', '\u{1BCA3}'),
+        ('\u{1D173}', '\u{1D17A}'),
+        ('\u{E0001}', '\u{E0001}'),
+        ('\u{E0020}', '\u{E007F}'),
+    ];
+
+    pub(crate) const PRIVATE_USE: &[(char, char)] = &[
+        ('\u{E000}', '\u{F8FF}'),
+        ('\u{F0000}', '\u{FFFFD}'),
+        ('\u{100000}', '\u{10FFFD}'),
+    ];
+
+    pub(crate) const UNASSIGNED: &[(char, char)] = &[
+        ('\u{378}', '\u{379}'),
+        ('\u{380}', '\u{383}'),
+        ('\u{38B}', '\u{38B}'),
+        ('\u{38D}', '\u{38D}'),
+        ('\u{3A2}', '\u{3A2}'),
+        ('\u{530}', '\u{530}'),
+        ('\u{557}', '\u{558}'),
+        ('\u{58B}', '\u{58C}'),
+        ('\u{590}', '\u{590}'),
+        ('\u{5C8}', '\u{5CF}'),
+        ('\u{5EB}', '\u{5EE}'),
+        ('\u{5F5}', '\u{5FF}'),
+        ('\u{70E}', '\u{70E}'),
+        ('\u{74B}', '\u{74C}'),
+        ('\u{7B2}', '\u{7BF}'),
+        ('\u{7FB}', '\u{7FC}'),
+        ('\u{82E}', '\u{82F}'),
+        ('\u{83F}', '\u{83F}'),
+        ('\u{85C}', '\u{85D}'),
+        ('\u{85F}', '\u{85F}'),
+        ('\u{86B}', '\u{86F}'),
+        ('\u{88F}', '\u{88F}'),
+        ('\u{892}', '\u{897}'),
+        ('\u{984}', '\u{984}'),
+        ('\u{98D}', '\u{98E}'),
+        ('\u{991}', '\u{992}'),
+        ('\u{9A9}', '\u{9A9}'),
+        ('\u{9B1}', '\u{9B1}'),
+        ('\u{9B3}', '\u{9B5}'),
+        ('\u{9BA}', '\u{9BB}'),
+        ('\u{9C5}', '\u{9C6}'),
+        ('\u{9C9}', '\u{9CA}'),
+        ('\u{9CF}', '\u{9D6}'),
+        ('\u{9D8}', '\u{9DB}'),
+        ('\u{9DE}', '\u{9DE}'),
+        ('\u{9E4}', '\u{9E5}'),
+        ('\u{9FF}', '\u{A00}'),
+        ('\u{A04}', '\u{A04}'),
+        ('\u{A0B}', '\u{A0E}'),
+        ('\u{A11}', '\u{A12}'),
+        ('\u{A29}', '\u{A29}'),
+        ('\u{A31}', '\u{A31}'),
+        ('\u{A34}', '\u{A34}'),
+        ('\u{A37}', '\u{A37}'),
+        ('\u{A3A}', '\u{A3B}'),
+        ('\u{A3D}', '\u{A3D}'),
+        ('\u{A43}', '\u{A46}'),
+        ('\u{A49}', '\u{A4A}'),
+        ('\u{A4E}', '\u{A50}'),
+        ('\u{A52}', '\u{A58}'),
+        ('\u{A5D}', '\u{A5D}'),
+        ('\u{A5F}', '\u{A65}'),
+        ('\u{A77}', '\u{A80}'),
+        ('\u{A84}', '\u{A84}'),
+        ('\u{A8E}', '\u{A8E}'),
+        ('\u{A92}', '\u{A92}'),
+        ('\u{AA9}', '\u{AA9}'),
+        ('\u{AB1}', '\u{AB1}'),
+        ('\u{AB4}', '\u{AB4}'),
+        ('\u{ABA}', '\u{ABB}'),
+        ('\u{AC6}', '\u{AC6}'),
+        ('\u{ACA}', '\u{ACA}'),
+        ('\u{ACE}', '\u{ACF}'),
+        ('\u{AD1}', '\u{ADF}'),
+        ('\u{AE4}', '\u{AE5}'),
+        ('\u{AF2}', '\u{AF8}'),
+        ('\u{B00}', '\u{B00}'),
+        ('\u{B04}', '\u{B04}'),
+        ('\u{B0D}', '\u{B0E}'),
+        ('\u{B11}', '\u{B12}'),
+        ('\u{B29}', '\u{B29}'),
+        ('\u{B31}', '\u{B31}'),
+        ('\u{B34}', '\u{B34}'),
+        ('\u{B3A}', '\u{B3B}'),
+        ('\u{B45}', '\u{B46}'),
+        ('\u{B49}', '\u{B4A}'),
+        ('\u{B4E}', '\u{B54}'),
+        ('\u{B58}', '\u{B5B}'),
+        ('\u{B5E}', '\u{B5E}'),
+        ('\u{B64}', '\u{B65}'),
+        ('\u{B78}', '\u{B81}'),
+        ('\u{B84}', '\u{B84}'),
+        ('\u{B8B}', '\u{B8D}'),
+        ('\u{B91}', '\u{B91}'),
+        ('\u{B96}', '\u{B98}'),
+        ('\u{B9B}', '\u{B9B}'),
+        ('\u{B9D}', '\u{B9D}'),
+        ('\u{BA0}', '\u{BA2}'),
+        ('\u{BA5}', '\u{BA7}'),
+        ('\u{BAB}', '\u{BAD}'),
+        ('\u{BBA}', '\u{BBD}'),
+        ('\u{BC3}', '\u{BC5}'),
+        ('\u{BC9}', '\u{BC9}'),
+        ('\u{BCE}', '\u{BCF}'),
+        ('\u{BD1}', '\u{BD6}'),
+        ('\u{BD8}', '\u{BE5}'),
+        ('\u{BFB}', '\u{BFF}'),
+        ('\u{C0D}', '\u{C0D}'),
+        ('\u{C11}', '\u{C11}'),
+        ('\u{C29}', '\u{C29}'),
+        ('\u{C3A}', '\u{C3B}'),
+        ('\u{C45}', '\u{C45}'),
+        ('\u{C49}', '\u{C49}'),
+        ('\u{C4E}', '\u{C54}'),
+        ('\u{C57}', '\u{C57}'),
+        ('\u{C5B}', '\u{C5C}'),
+        ('\u{C5E}', '\u{C5F}'),
+        ('\u{C64}', '\u{C65}'),
+        ('\u{C70}', '\u{C76}'),
+        ('\u{C8D}', '\u{C8D}'),
+        ('\u{C91}', '\u{C91}'),
+        ('\u{CA9}', '\u{CA9}'),
+        ('\u{CB4}', '\u{CB4}'),
+        ('\u{CBA}', '\u{CBB}'),
+        ('\u{CC5}', '\u{CC5}'),
+        ('\u{CC9}', '\u{CC9}'),
+        ('\u{CCE}', '\u{CD4}'),
+        ('\u{CD7}', '\u{CDC}'),
+        ('\u{CDF}', '\u{CDF}'),
+        ('\u{CE4}', '\u{CE5}'),
+        ('\u{CF0}', '\u{CF0}'),
+        ('\u{CF4}', '\u{CFF}'),
+        ('\u{D0D}', '\u{D0D}'),
+        ('\u{D11}', '\u{D11}'),
+        ('\u{D45}', '\u{D45}'),
+        ('\u{D49}', '\u{D49}'),
+        ('\u{D50}', '\u{D53}'),
+        ('\u{D64}', '\u{D65}'),
+        ('\u{D80}', '\u{D80}'),
+        ('\u{D84}', '\u{D84}'),
+        ('\u{D97}', '\u{D99}'),
+        ('\u{DB2}', '\u{DB2}'),
+        ('\u{DBC}', '\u{DBC}'),
+        ('\u{DBE}', '\u{DBF}'),
+        ('\u{DC7}', '\u{DC9}'),
+        ('\u{DCB}', '\u{DCE}'),
+        ('\u{DD5}', '\u{DD5}'),
+        ('\u{DD7}', '\u{DD7}'),
+        ('\u{DE0}', '\u{DE5}'),
+        ('\u{DF0}', '\u{DF1}'),
+        ('\u{DF5}', '\u{E00}'),
+        ('\u{E3B}', '\u{E3E}'),
+        ('\u{E5C}', '\u{E80}'),
+        ('\u{E83}', '\u{E83}'),
+        ('\u{E85}', '\u{E85}'),
+        ('\u{E8B}', '\u{E8B}'),
+        ('\u{EA4}', '\u{EA4}'),
+        ('\u{EA6}', '\u{EA6}'),
+        ('\u{EBE}', '\u{EBF}'),
+        ('\u{EC5}', '\u{EC5}'),
+        ('\u{EC7}', '\u{EC7}'),
+        ('\u{ECF}', '\u{ECF}'),
+        ('\u{EDA}', '\u{EDB}'),
+        ('\u{EE0}', '\u{EFF}'),
+        ('\u{F48}', '\u{F48}'),
+        ('\u{F6D}', '\u{F70}'),
+        ('\u{F98}', '\u{F98}'),
+        ('\u{FBD}', '\u{FBD}'),
+        ('\u{FCD}', '\u{FCD}'),
+        ('\u{FDB}', '\u{FFF}'),
+        ('\u{10C6}', '\u{10C6}'),
+        ('\u{10C8}', '\u{10CC}'),
+        ('\u{10CE}', '\u{10CF}'),
+        ('\u{1249}', '\u{1249}'),
+        ('\u{124E}', '\u{124F}'),
+        ('\u{1257}', '\u{1257}'),
+        ('\u{1259}', '\u{1259}'),
+        ('\u{125E}', '\u{125F}'),
+        ('\u{1289}', '\u{1289}'),
+        ('\u{128E}', '\u{128F}'),
+        ('\u{12B1}', '\u{12B1}'),
+        ('\u{12B6}', '\u{12B7}'),
+        ('\u{12BF}', '\u{12BF}'),
+        ('\u{12C1}', '\u{12C1}'),
+        ('\u{12C6}', '\u{12C7}'),
+        ('\u{12D7}', '\u{12D7}'),
+        ('\u{1311}', '\u{1311}'),
+        ('\u{1316}', '\u{1317}'),
+        ('\u{135B}', '\u{135C}'),
+        ('\u{137D}', '\u{137F}'),
+        ('\u{139A}', '\u{139F}'),
+        ('\u{13F6}', '\u{13F7}'),
+        ('\u{13FE}', '\u{13FF}'),
+        ('\u{169D}', '\u{169F}'),
+        ('\u{16F9}', '\u{16FF}'),
+        ('\u{1716}', '\u{171E}'),
+        ('\u{1737}', '\u{173F}'),
+        ('\u{1754}', '\u{175F}'),
+        ('\u{176D}', '\u{176D}'),
+        ('\u{1771}', '\u{1771}'),
+        ('\u{1774}', '\u{177F}'),
+        ('\u{17DE}', '\u{17DF}'),
+        ('\u{17EA}', '\u{17EF}'),
+        ('\u{17FA}', '\u{17FF}'),
+        ('\u{181A}', '\u{181F}'),
+        ('\u{1879}', '\u{187F}'),
+        ('\u{18AB}', '\u{18AF}'),
+        ('\u{18F6}', '\u{18FF}'),
+        ('\u{191F}', '\u{191F}'),
+        ('\u{192C}', '\u{192F}'),
+        ('\u{193C}', '\u{193F}'),
+        ('\u{1941}', '\u{1943}'),
+        ('\u{196E}', '\u{196F}'),
+        ('\u{1975}', '\u{197F}'),
+        ('\u{19AC}', '\u{19AF}'),
+        ('\u{19CA}', '\u{19CF}'),
+        ('\u{19DB}', '\u{19DD}'),
+        ('\u{1A1C}', '\u{1A1D}'),
+        ('\u{1A5F}', '\u{1A5F}'),
+        ('\u{1A7D}', '\u{1A7E}'),
+        ('\u{1A8A}', '\u{1A8F}'),
+        ('\u{1A9A}', '\u{1A9F}'),
+        ('\u{1AAE}', '\u{1AAF}'),
+        ('\u{1ACF}', '\u{1AFF}'),
+        ('\u{1B4D}', '\u{1B4F}'),
+        ('\u{1B7F}', '\u{1B7F}'),
+        ('\u{1BF4}', '\u{1BFB}'),
+        ('\u{1C38}', '\u{1C3A}'),
+        ('\u{1C4A}', '\u{1C4C}'),
+        ('\u{1C89}', '\u{1C8F}'),
+        ('\u{1CBB}', '\u{1CBC}'),
+        ('\u{1CC8}', '\u{1CCF}'),
+        ('\u{1CFB}', '\u{1CFF}'),
+        ('\u{1F16}', '\u{1F17}'),
+        ('\u{1F1E}', '\u{1F1F}'),
+        ('\u{1F46}', '\u{1F47}'),
+        ('\u{1F4E}', '\u{1F4F}'),
+        ('\u{1F58}', '\u{1F58}'),
+        ('\u{1F5A}', '\u{1F5A}'),
+        ('\u{1F5C}', '\u{1F5C}'),
+        ('\u{1F5E}', '\u{1F5E}'),
+        ('\u{1F7E}', '\u{1F7F}'),
+        ('\u{1FB5}', '\u{1FB5}'),
+        ('\u{1FC5}', '\u{1FC5}'),
+        ('\u{1FD4}', '\u{1FD5}'),
+        ('\u{1FDC}', '\u{1FDC}'),
+        ('\u{1FF0}', '\u{1FF1}'),
+        ('\u{1FF5}', '\u{1FF5}'),
+        ('\u{1FFF}', '\u{1FFF}'),
+        ('\u{2065}', '\u{2065}'),
+        ('\u{2072}', '\u{2073}'),
+        ('\u{208F}', '\u{208F}'),
+        ('\u{209D}', '\u{209F}'),
+        ('\u{20C1}', '\u{20CF}'),
+        ('\u{20F1}', '\u{20FF}'),
+        ('\u{218C}', '\u{218F}'),
+        ('\u{2427}', '\u{243F}'),
+        ('\u{244B}', '\u{245F}'),
+        ('\u{2B74}', '\u{2B75}'),
+        ('\u{2B96}', '\u{2B96}'),
+        ('\u{2CF4}', '\u{2CF8}'),
+        ('\u{2D26}', '\u{2D26}'),
+        ('\u{2D28}', '\u{2D2C}'),
+        ('\u{2D2E}', '\u{2D2F}'),
+        ('\u{2D68}', '\u{2D6E}'),
+        ('\u{2D71}', '\u{2D7E}'),
+        ('\u{2D97}', '\u{2D9F}'),
+        ('\u{2DA7}', '\u{2DA7}'),
+        ('\u{2DAF}', '\u{2DAF}'),
+        ('\u{2DB7}', '\u{2DB7}'),
+        ('\u{2DBF}', '\u{2DBF}'),
+        ('\u{2DC7}', '\u{2DC7}'),
+        ('\u{2DCF}', '\u{2DCF}'),
+        ('\u{2DD7}', '\u{2DD7}'),
+        ('\u{2DDF}', '\u{2DDF}'),
+        ('\u{2E5E}', '\u{2E7F}'),
+        ('\u{2E9A}', '\u{2E9A}'),
+        ('\u{2EF4}', '\u{2EFF}'),
+        ('\u{2FD6}', '\u{2FEF}'),
+        ('\u{3040}', '\u{3040}'),
+        ('\u{3097}', '\u{3098}'),
+        ('\u{3100}', '\u{3104}'),
+        ('\u{3130}', '\u{3130}'),
+        ('\u{318F}', '\u{318F}'),
+        ('\u{31E4}', '\u{31EE}'),
+        ('\u{321F}', '\u{321F}'),
+        ('\u{A48D}', '\u{A48F}'),
+        ('\u{A4C7}', '\u{A4CF}'),
+        ('\u{A62C}', '\u{A63F}'),
+        ('\u{A6F8}', '\u{A6FF}'),
+        ('\u{A7CB}', '\u{A7CF}'),
+        ('\u{A7D2}', '\u{A7D2}'),
+        ('\u{A7D4}', '\u{A7D4}'),
+        ('\u{A7DA}', '\u{A7F1}'),
+        ('\u{A82D}', '\u{A82F}'),
+        ('\u{A83A}', '\u{A83F}'),
+        ('\u{A878}', '\u{A87F}'),
+        ('\u{A8C6}', '\u{A8CD}'),
+        ('\u{A8DA}', '\u{A8DF}'),
+        ('\u{A954}', '\u{A95E}'),
+        ('\u{A97D}', '\u{A97F}'),
+        ('\u{A9CE}', '\u{A9CE}'),
+        ('\u{A9DA}', '\u{A9DD}'),
+        ('\u{A9FF}', '\u{A9FF}'),
+        ('\u{AA37}', '\u{AA3F}'),
+        ('\u{AA4E}', '\u{AA4F}'),
+        ('\u{AA5A}', '\u{AA5B}'),
+        ('\u{AAC3}', '\u{AADA}'),
+        ('\u{AAF7}', '\u{AB00}'),
+        ('\u{AB07}', '\u{AB08}'),
+        ('\u{AB0F}', '\u{AB10}'),
+        ('\u{AB17}', '\u{AB1F}'),
+        ('\u{AB27}', '\u{AB27}'),
+        ('\u{AB2F}', '\u{AB2F}'),
+        ('\u{AB6C}', '\u{AB6F}'),
+        ('\u{ABEE}', '\u{ABEF}'),
+        ('\u{ABFA}', '\u{ABFF}'),
+        ('\u{D7A4}', '\u{D7AF}'),
+        ('\u{D7C7}', '\u{D7CA}'),
+        ('\u{D7FC}', '\u{D7FF}'),
+        ('\u{FA6E}', '\u{FA6F}'),
+        ('\u{FADA}', '\u{FAFF}'),
+        ('\u{FB07}', '\u{FB12}'),
+        ('\u{FB18}', '\u{FB1C}'),
+        ('\u{FB37}', '\u{FB37}'),
+        ('\u{FB3D}', '\u{FB3D}'),
+        ('\u{FB3F}', '\u{FB3F}'),
+        ('\u{FB42}', '\u{FB42}'),
+        ('\u{FB45}', '\u{FB45}'),
+        ('\u{FBC3}', '\u{FBD2}'),
+        ('\u{FD90}', '\u{FD91}'),
+        ('\u{FDC8}', '\u{FDCE}'),
+        ('\u{FDD0}', '\u{FDEF}'),
+        ('\u{FE1A}', '\u{FE1F}'),
+        ('\u{FE53}', '\u{FE53}'),
+        ('\u{FE67}', '\u{FE67}'),
+        ('\u{FE6C}', '\u{FE6F}'),
+        ('\u{FE75}', '\u{FE75}'),
+        ('\u{FEFD}', '\u{FEFE}'),
+        ('\u{FF00}', '\u{FF00}'),
+        ('\u{FFBF}', '\u{FFC1}'),
+        ('\u{FFC8}', '\u{FFC9}'),
+        ('\u{FFD0}', '\u{FFD1}'),
+        ('\u{FFD8}', '\u{FFD9}'),
+        ('\u{FFDD}', '\u{FFDF}'),
+        ('\u{FFE7}', '\u{FFE7}'),
+        ('\u{FFEF}', '\u{FFF8}'),
+        ('\u{FFFE}', '\u{FFFF}'),
+        ('\u{1000C}', '\u{1000C}'),
+        ('\u{10027}', '\u{10027}'),
+        ('\u{1003B}', '\u{1003B}'),
+        ('\u{1003E}', '\u{1003E}'),
+        ('\u{1004E}', '\u{1004F}'),
+        ('\u{1005E}', '\u{1007F}'),
+        ('\u{100FB}', '\u{100FF}'),
+        ('\u{10103}', '\u{10106}'),
+        ('\u{10134}', '\u{10136}'),
+        ('\u{1018F}', '\u{1018F}'),
+        ('\u{1019D}', '\u{1019F}'),
+        ('\u{101A1}', '\u{101CF}'),
+        ('\u{101FE}', '\u{1027F}'),
+        ('\u{1029D}', '\u{1029F}'),
+        ('\u{102D1}', '\u{102DF}'),
+        ('\u{102FC}', '\u{102FF}'),
+        ('\u{10324}', '\u{1032C}'),
+        ('\u{1034B}', '\u{1034F}'),
+        ('\u{1037B}', '\u{1037F}'),
+        ('\u{1039E}', '\u{1039E}'),
+        ('\u{103C4}', '\u{103C7}'),
+        ('\u{103D6}', '\u{103FF}'),
+        ('\u{1049E}', '\u{1049F}'),
+        ('\u{104AA}', '\u{104AF}'),
+        ('\u{104D4}', '\u{104D7}'),
+        ('\u{104FC}', '\u{104FF}'),
+        ('\u{10528}', '\u{1052F}'),
+        ('\u{10564}', '\u{1056E}'),
+        ('\u{1057B}', '\u{1057B}'),
+        ('\u{1058B}', '\u{1058B}'),
+        ('\u{10593}', '\u{10593}'),
+        ('\u{10596}', '\u{10596}'),
+        ('\u{105A2}', '\u{105A2}'),
+        ('\u{105B2}', '\u{105B2}'),
+        ('\u{105BA}', '\u{105BA}'),
+        ('\u{105BD}', '\u{105FF}'),
+        ('\u{10737}', '\u{1073F}'),
+        ('\u{10756}', '\u{1075F}'),
+        ('\u{10768}', '\u{1077F}'),
+        ('\u{10786}', '\u{10786}'),
+        ('\u{107B1}', '\u{107B1}'),
+        ('\u{107BB}', '\u{107FF}'),
+        ('\u{10806}', '\u{10807}'),
+        ('\u{10809}', '\u{10809}'),
+        ('\u{10836}', '\u{10836}'),
+        ('\u{10839}', '\u{1083B}'),
+        ('\u{1083D}', '\u{1083E}'),
+        ('\u{10856}', '\u{10856}'),
+        ('\u{1089F}', '\u{108A6}'),
+        ('\u{108B0}', '\u{108DF}'),
+        ('\u{108F3}', '\u{108F3}'),
+        ('\u{108F6}', '\u{108FA}'),
+        ('\u{1091C}', '\u{1091E}'),
+        ('\u{1093A}', '\u{1093E}'),
+        ('\u{10940}', '\u{1097F}'),
+        ('\u{109B8}', '\u{109BB}'),
+        ('\u{109D0}', '\u{109D1}'),
+        ('\u{10A04}', '\u{10A04}'),
+        ('\u{10A07}', '\u{10A0B}'),
+        ('\u{10A14}', '\u{10A14}'),
+        ('\u{10A18}', '\u{10A18}'),
+        ('\u{10A36}', '\u{10A37}'),
+        ('\u{10A3B}', '\u{10A3E}'),
+        ('\u{10A49}', '\u{10A4F}'),
+        ('\u{10A59}', '\u{10A5F}'),
+        ('\u{10AA0}', '\u{10ABF}'),
+        ('\u{10AE7}', '\u{10AEA}'),
+        ('\u{10AF7}', '\u{10AFF}'),
+        ('\u{10B36}', '\u{10B38}'),
+        ('\u{10B56}', '\u{10B57}'),
+        ('\u{10B73}', '\u{10B77}'),
+        ('\u{10B92}', '\u{10B98}'),
+        ('\u{10B9D}', '\u{10BA8}'),
+        ('\u{10BB0}', '\u{10BFF}'),
+        ('\u{10C49}', '\u{10C7F}'),
+        ('\u{10CB3}', '\u{10CBF}'),
+        ('\u{10CF3}', '\u{10CF9}'),
+        ('\u{10D28}', '\u{10D2F}'),
+        ('\u{10D3A}', '\u{10E5F}'),
+        ('\u{10E7F}', '\u{10E7F}'),
+        ('\u{10EAA}', '\u{10EAA}'),
+        ('\u{10EAE}', '\u{10EAF}'),
+        ('\u{10EB2}', '\u{10EFC}'),
+        ('\u{10F28}', '\u{10F2F}'),
+        ('\u{10F5A}', '\u{10F6F}'),
+        ('\u{10F8A}', '\u{10FAF}'),
+        ('\u{10FCC}', '\u{10FDF}'),
+        ('\u{10FF7}', '\u{10FFF}'),
+        ('\u{1104E}', '\u{11051}'),
+        ('\u{11076}', '\u{1107E}'),
+        ('\u{110C3}', '\u{110CC}'),
+        ('\u{110CE}', '\u{110CF}'),
+        ('\u{110E9}', '\u{110EF}'),
+        ('\u{110FA}', '\u{110FF}'),
+        ('\u{11135}', '\u{11135}'),
+        ('\u{11148}', '\u{1114F}'),
+        ('\u{11177}', '\u{1117F}'),
+        ('\u{111E0}', '\u{111E0}'),
+        ('\u{111F5}', '\u{111FF}'),
+        ('\u{11212}', '\u{11212}'),
+        ('\u{11242}', '\u{1127F}'),
+        ('\u{11287}', '\u{11287}'),
+        ('\u{11289}', '\u{11289}'),
+        ('\u{1128E}', '\u{1128E}'),
+        ('\u{1129E}', '\u{1129E}'),
+        ('\u{112AA}', '\u{112AF}'),
+        ('\u{112EB}', '\u{112EF}'),
+        ('\u{112FA}', '\u{112FF}'),
+        ('\u{11304}', '\u{11304}'),
+        ('\u{1130D}', '\u{1130E}'),
+        ('\u{11311}', '\u{11312}'),
+        ('\u{11329}', '\u{11329}'),
+        ('\u{11331}', '\u{11331}'),
+        ('\u{11334}', '\u{11334}'),
+        ('\u{1133A}', '\u{1133A}'),
+        ('\u{11345}', '\u{11346}'),
+        ('\u{11349}', '\u{1134A}'),
+        ('\u{1134E}', '\u{1134F}'),
+        ('\u{11351}', '\u{11356}'),
+        ('\u{11358}', '\u{1135C}'),
+        ('\u{11364}', '\u{11365}'),
+        ('\u{1136D}', '\u{1136F}'),
+        ('\u{11375}', '\u{113FF}'),
+        ('\u{1145C}', '\u{1145C}'),
+        ('\u{11462}', '\u{1147F}'),
+        ('\u{114C8}', '\u{114CF}'),
+        ('\u{114DA}', '\u{1157F}'),
+        ('\u{115B6}', '\u{115B7}'),
+        ('\u{115DE}', '\u{115FF}'),
+        ('\u{11645}', '\u{1164F}'),
+        ('\u{1165A}', '\u{1165F}'),
+        ('\u{1166D}', '\u{1167F}'),
+        ('\u{116BA}', '\u{116BF}'),
+        ('\u{116CA}', '\u{116FF}'),
+        ('\u{1171B}', '\u{1171C}'),
+        ('\u{1172C}', '\u{1172F}'),
+        ('\u{11747}', '\u{117FF}'),
+        ('\u{1183C}', '\u{1189F}'),
+        ('\u{118F3}', '\u{118FE}'),
+        ('\u{11907}', '\u{11908}'),
+        ('\u{1190A}', '\u{1190B}'),
+        ('\u{11914}', '\u{11914}'),
+        ('\u{11917}', '\u{11917}'),
+        ('\u{11936}', '\u{11936}'),
+        ('\u{11939}', '\u{1193A}'),
+        ('\u{11947}', '\u{1194F}'),
+        ('\u{1195A}', '\u{1199F}'),
+        ('\u{119A8}', '\u{119A9}'),
+        ('\u{119D8}', '\u{119D9}'),
+        ('\u{119E5}', '\u{119FF}'),
+        ('\u{11A48}', '\u{11A4F}'),
+        ('\u{11AA3}', '\u{11AAF}'),
+        ('\u{11AF9}', '\u{11AFF}'),
+        ('\u{11B0A}', '\u{11BFF}'),
+        ('\u{11C09}', '\u{11C09}'),
+        ('\u{11C37}', '\u{11C37}'),
+        ('\u{11C46}', '\u{11C4F}'),
+        ('\u{11C6D}', '\u{11C6F}'),
+        ('\u{11C90}', '\u{11C91}'),
+        ('\u{11CA8}', '\u{11CA8}'),
+        ('\u{11CB7}', '\u{11CFF}'),
+        ('\u{11D07}', '\u{11D07}'),
+        ('\u{11D0A}', '\u{11D0A}'),
+        ('\u{11D37}', '\u{11D39}'),
+        ('\u{11D3B}', '\u{11D3B}'),
+        ('\u{11D3E}', '\u{11D3E}'),
+        ('\u{11D48}', '\u{11D4F}'),
+        ('\u{11D5A}', '\u{11D5F}'),
+        ('\u{11D66}', '\u{11D66}'),
+        ('\u{11D69}', '\u{11D69}'),
+        ('\u{11D8F}', '\u{11D8F}'),
+        ('\u{11D92}', '\u{11D92}'),
+        ('\u{11D99}', '\u{11D9F}'),
+        ('\u{11DAA}', '\u{11EDF}'),
+        ('\u{11EF9}', '\u{11EFF}'),
+        ('\u{11F11}', '\u{11F11}'),
+        ('\u{11F3B}', '\u{11F3D}'),
+        ('\u{11F5A}', '\u{11FAF}'),
+        ('\u{11FB1}', '\u{11FBF}'),
+        ('\u{11FF2}', '\u{11FFE}'),
+        ('\u{1239A}', '\u{123FF}'),
+        ('\u{1246F}', '\u{1246F}'),
+        ('\u{12475}', '\u{1247F}'),
+        ('\u{12544}', '\u{12F8F}'),
+        ('\u{12FF3}', '\u{12FFF}'),
+        ('\u{13456}', '\u{143FF}'),
+        ('\u{14647}', '\u{167FF}'),
+        ('\u{16A39}', '\u{16A3F}'),
+        ('\u{16A5F}', '\u{16A5F}'),
+        ('\u{16A6A}', '\u{16A6D}'),
+        ('\u{16ABF}', '\u{16ABF}'),
+        ('\u{16ACA}', '\u{16ACF}'),
+        ('\u{16AEE}', '\u{16AEF}'),
+        ('\u{16AF6}', '\u{16AFF}'),
+        ('\u{16B46}', '\u{16B4F}'),
+        ('\u{16B5A}', '\u{16B5A}'),
+        ('\u{16B62}', '\u{16B62}'),
+        ('\u{16B78}', '\u{16B7C}'),
+        ('\u{16B90}', '\u{16E3F}'),
+        ('\u{16E9B}', '\u{16EFF}'),
+        ('\u{16F4B}', '\u{16F4E}'),
+        ('\u{16F88}', '\u{16F8E}'),
+        ('\u{16FA0}', '\u{16FDF}'),
+        ('\u{16FE5}', '\u{16FEF}'),
+        ('\u{16FF2}', '\u{16FFF}'),
+        ('\u{187F8}', '\u{187FF}'),
+        ('\u{18CD6}', '\u{18CFF}'),
+        ('\u{18D09}', '\u{1AFEF}'),
+        ('\u{1AFF4}', '\u{1AFF4}'),
+        ('\u{1AFFC}', '\u{1AFFC}'),
+        ('\u{1AFFF}', '\u{1AFFF}'),
+        ('\u{1B123}', '\u{1B131}'),
+        ('\u{1B133}', '\u{1B14F}'),
+        ('\u{1B153}', '\u{1B154}'),
+        ('\u{1B156}', '\u{1B163}'),
+        ('\u{1B168}', '\u{1B16F}'),
+        ('\u{1B2FC}', '\u{1BBFF}'),
+        ('\u{1BC6B}', '\u{1BC6F}'),
+        ('\u{1BC7D}', '\u{1BC7F}'),
+        ('\u{1BC89}', '\u{1BC8F}'),
+        ('\u{1BC9A}', '\u{1BC9B}'),
+        ('\u{1BCA4}', '\u{1CEFF}'),
+        ('\u{1CF2E}', '\u{1CF2F}'),
+        ('\u{1CF47}', '\u{1CF4F}'),
+        ('\u{1CFC4}', '\u{1CFFF}'),
+        ('\u{1D0F6}', '\u{1D0FF}'),
+        ('\u{1D127}', '\u{1D128}'),
+        ('\u{1D1EB}', '\u{1D1FF}'),
+        ('\u{1D246}', '\u{1D2BF}'),
+        ('\u{1D2D4}', '\u{1D2DF}'),
+        ('\u{1D2F4}', '\u{1D2FF}'),
+        ('\u{1D357}', '\u{1D35F}'),
+        ('\u{1D379}', '\u{1D3FF}'),
+        ('\u{1D455}', '\u{1D455}'),
+        ('\u{1D49D}', '\u{1D49D}'),
+        ('\u{1D4A0}', '\u{1D4A1}'),
+        ('\u{1D4A3}', '\u{1D4A4}'),
+        ('\u{1D4A7}', '\u{1D4A8}'),
+        ('\u{1D4AD}', '\u{1D4AD}'),
+        ('\u{1D4BA}', '\u{1D4BA}'),
+        ('\u{1D4BC}', '\u{1D4BC}'),
+        ('\u{1D4C4}', '\u{1D4C4}'),
+        ('\u{1D506}', '\u{1D506}'),
+        ('\u{1D50B}', '\u{1D50C}'),
+        ('\u{1D515}', '\u{1D515}'),
+        ('\u{1D51D}', '\u{1D51D}'),
+        ('\u{1D53A}', '\u{1D53A}'),
+        ('\u{1D53F}', '\u{1D53F}'),
+        ('\u{1D545}', '\u{1D545}'),
+        ('\u{1D547}', '\u{1D549}'),
+        ('\u{1D551}', '\u{1D551}'),
+        ('\u{1D6A6}', '\u{1D6A7}'),
+        ('\u{1D7CC}', '\u{1D7CD}'),
+        ('\u{1DA8C}', '\u{1DA9A}'),
+        ('\u{1DAA0}', '\u{1DAA0}'),
+        ('\u{1DAB0}', '\u{1DEFF}'),
+        ('\u{1DF1F}', '\u{1DF24}'),
+        ('\u{1DF2B}', '\u{1DFFF}'),
+        ('\u{1E007}', '\u{1E007}'),
+        ('\u{1E019}', '\u{1E01A}'),
+        ('\u{1E022}', '\u{1E022}'),
+        ('\u{1E025}', '\u{1E025}'),
+        ('\u{1E02B}', '\u{1E02F}'),
+        ('\u{1E06E}', '\u{1E08E}'),
+        ('\u{1E090}', '\u{1E0FF}'),
+        ('\u{1E12D}', '\u{1E12F}'),
+        ('\u{1E13E}', '\u{1E13F}'),
+        ('\u{1E14A}', '\u{1E14D}'),
+        ('\u{1E150}', '\u{1E28F}'),
+        ('\u{1E2AF}', '\u{1E2BF}'),
+        ('\u{1E2FA}', '\u{1E2FE}'),
+        ('\u{1E300}', '\u{1E4CF}'),
+        ('\u{1E4FA}', '\u{1E7DF}'),
+        ('\u{1E7E7}', '\u{1E7E7}'),
+        ('\u{1E7EC}', '\u{1E7EC}'),
+        ('\u{1E7EF}', '\u{1E7EF}'),
+        ('\u{1E7FF}', '\u{1E7FF}'),
+        ('\u{1E8C5}', '\u{1E8C6}'),
+        ('\u{1E8D7}', '\u{1E8FF}'),
+        ('\u{1E94C}', '\u{1E94F}'),
+        ('\u{1E95A}', '\u{1E95D}'),
+        ('\u{1E960}', '\u{1EC70}'),
+        ('\u{1ECB5}', '\u{1ED00}'),
+        ('\u{1ED3E}', '\u{1EDFF}'),
+        ('\u{1EE04}', '\u{1EE04}'),
+        ('\u{1EE20}', '\u{1EE20}'),
+        ('\u{1EE23}', '\u{1EE23}'),
+        ('\u{1EE25}', '\u{1EE26}'),
+        ('\u{1EE28}', '\u{1EE28}'),
+        ('\u{1EE33}', '\u{1EE33}'),
+        ('\u{1EE38}', '\u{1EE38}'),
+        ('\u{1EE3A}', '\u{1EE3A}'),
+        ('\u{1EE3C}', '\u{1EE41}'),
+        ('\u{1EE43}', '\u{1EE46}'),
+        ('\u{1EE48}', '\u{1EE48}'),
+        ('\u{1EE4A}', '\u{1EE4A}'),
+        ('\u{1EE4C}', '\u{1EE4C}'),
+        ('\u{1EE50}', '\u{1EE50}'),
+        ('\u{1EE53}', '\u{1EE53}'),
+        ('\u{1EE55}', '\u{1EE56}'),
+        ('\u{1EE58}', '\u{1EE58}'),
+        ('\u{1EE5A}', '\u{1EE5A}'),
+        ('\u{1EE5C}', '\u{1EE5C}'),
+        ('\u{1EE5E}', '\u{1EE5E}'),
+        ('\u{1EE60}', '\u{1EE60}'),
+        ('\u{1EE63}', '\u{1EE63}'),
+        ('\u{1EE65}', '\u{1EE66}'),
+        ('\u{1EE6B}', '\u{1EE6B}'),
+        ('\u{1EE73}', '\u{1EE73}'),
+        ('\u{1EE78}', '\u{1EE78}'),
+        ('\u{1EE7D}', '\u{1EE7D}'),
+        ('\u{1EE7F}', '\u{1EE7F}'),
+        ('\u{1EE8A}', '\u{1EE8A}'),
+        ('\u{1EE9C}', '\u{1EEA0}'),
+        ('\u{1EEA4}', '\u{1EEA4}'),
+        ('\u{1EEAA}', '\u{1EEAA}'),
+        ('\u{1EEBC}', '\u{1EEEF}'),
+        ('\u{1EEF2}', '\u{1EFFF}'),
+        ('\u{1F02C}', '\u{1F02F}'),
+        ('\u{1F094}', '\u{1F09F}'),
+        ('\u{1F0AF}', '\u{1F0B0}'),
+        ('\u{1F0C0}', '\u{1F0C0}'),
+        ('\u{1F0D0}', '\u{1F0D0}'),
+        ('\u{1F0F6}', '\u{1F0FF}'),
+        ('\u{1F1AE}', '\u{1F1E5}'),
+        ('\u{1F203}', '\u{1F20F}'),
+        ('\u{1F23C}', '\u{1F23F}'),
+        ('\u{1F249}', '\u{1F24F}'),
+        ('\u{1F252}', '\u{1F25F}'),
+        ('\u{1F266}', '\u{1F2FF}'),
+        ('\u{1F6D8}', '\u{1F6DB}'),
+        ('\u{1F6ED}', '\u{1F6EF}'),
+        ('\u{1F6FD}', '\u{1F6FF}'),
+        ('\u{1F777}', '\u{1F77A}'),
+        ('\u{1F7DA}', '\u{1F7DF}'),
+        ('\u{1F7EC}', '\u{1F7EF}'),
+        ('\u{1F7F1}', '\u{1F7FF}'),
+        ('\u{1F80C}', '\u{1F80F}'),
+        ('\u{1F848}', '\u{1F84F}'),
+        ('\u{1F85A}', '\u{1F85F}'),
+        ('\u{1F888}', '\u{1F88F}'),
+        ('\u{1F8AE}', '\u{1F8AF}'),
+        ('\u{1F8B2}', '\u{1F8FF}'),
+        ('\u{1FA54}', '\u{1FA5F}'),
+        ('\u{1FA6E}', '\u{1FA6F}'),
+        ('\u{1FA7D}', '\u{1FA7F}'),
+        ('\u{1FA89}', '\u{1FA8F}'),
+        ('\u{1FABE}', '\u{1FABE}'),
+        ('\u{1FAC6}', '\u{1FACD}'),
+        ('\u{1FADC}', '\u{1FADF}'),
+        ('\u{1FAE9}', '\u{1FAEF}'),
+        ('\u{1FAF9}', '\u{1FAFF}'),
+        ('\u{1FB93}', '\u{1FB93}'),
+        ('\u{1FBCB}', '\u{1FBEF}'),
+        ('\u{1FBFA}', '\u{1FFFF}'),
+        ('\u{2A6E0}', '\u{2A6FF}'),
+        ('\u{2B73A}', '\u{2B73F}'),
+        ('\u{2B81E}', '\u{2B81F}'),
+        ('\u{2CEA2}', '\u{2CEAF}'),
+        ('\u{2EBE1}', '\u{2EBEF}'),
+        ('\u{2EE5E}', '\u{2F7FF}'),
+        ('\u{2FA1E}', '\u{2FFFF}'),
+        ('\u{3134B}', '\u{3134F}'),
+        ('\u{323B0}', '\u{E0000}'),
+        ('\u{E0002}', '\u{E001F}'),
+        ('\u{E0080}', '\u{E00FF}'),
+        ('\u{E01F0}', '\u{EFFFF}'),
+        ('\u{FFFFE}', '\u{FFFFF}'),
+        ('\u{10FFFE}', '\u{10FFFF}'),
+    ];
+
+    pub(crate) const WHITE_SPACE: &[(char, char)] = &[
+        ('\u{9}', '\u{D}'),
+        ('\u{20}', '\u{20}'),
+        ('\u{85}', '\u{85}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{2028}', '\u{2029}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+}
+
+#[cfg(not(any(feature = "unicode14", feature = "unicode15")))]
+#[rustfmt::skip]
+mod data {
+    pub(crate) const UNICODE_VERSION: (u8, u8, u8) = (16, 0, 0);
+
+    pub(crate) const SPACE_SEPARATOR: &[(char, char)] = &[
+        ('\u{20}', '\u{20}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+
+    pub(crate) const LINE_PARAGRAPH_SEPARATOR: &[(char, char)] = &[
+        ('\u{2028}', '\u{2029}'),
+    ];
+
+    pub(crate) const FORMAT: &[(char, char)] = &[
+        ('\u{AD}', '\u{AD}'),
+        ('\u{600}', '\u{605}'),
+        ('\u{61C}', '\u{61C}'),
+        ('\u{6DD}', '\u{6DD}'),
+        ('\u{70F}', '\u{70F}'),
+        ('\u{890}', '\u{891}'),
+        ('\u{8E2}', '\u{8E2}'),
+        ('\u{180E}', '\u{180E}'),
+        ('\u{200B}', '\u{200F}'),
+        ('\u{202A}', '\u{202E}'),
+        ('\u{2060}', '\u{2064}'),
+        ('\u{2066}', '\u{206F}'),
+        ('\u{FEFF}', '\u{FEFF}'),
+        ('\u{FFF9}', '\u{FFFB}'),
+        ('\u{110BD}', '\u{110BD}'),
+        ('\u{110CD}', '\u{110CD}'),
+        ('\u{13430}', '\u{1343F}'),
+        ('\u{1BCA0}', '\u{1BCA3}'),
+        ('\u{1D173}', '\u{1D17A}'),
+        ('\u{E0001}', '\u{E0001}'),
+        ('\u{E0020}', '\u{E007F}'),
+    ];
+
+    pub(crate) const PRIVATE_USE: &[(char, char)] = &[
+        ('\u{E000}', '\u{F8FF}'),
+        ('\u{F0000}', '\u{FFFFD}'),
+        ('\u{100000}', '\u{10FFFD}'),
+    ];
+
+    pub(crate) const UNASSIGNED: &[(char, char)] = &[
+        ('\u{378}', '\u{379}'),
+        ('\u{380}', '\u{383}'),
+        ('\u{38B}', '\u{38B}'),
+        ('\u{38D}', '\u{38D}'),
+        ('\u{3A2}', '\u{3A2}'),
+        ('\u{530}', '\u{530}'),
+        ('\u{557}', '\u{558}'),
+        ('\u{58B}', '\u{58C}'),
+        ('\u{590}', '\u{590}'),
+        ('\u{5C8}', '\u{5CF}'),
+        ('\u{5EB}', '\u{5EE}'),
+        ('\u{5F5}', '\u{5FF}'),
+        ('\u{70E}', '\u{70E}'),
+        ('\u{74B}', '\u{74C}'),
+        ('\u{7B2}', '\u{7BF}'),
+        ('\u{7FB}', '\u{7FC}'),
+        ('\u{82E}', '\u{82F}'),
+        ('\u{83F}', '\u{83F}'),
+        ('\u{85C}', '\u{85D}'),
+        ('\u{85F}', '\u{85F}'),
+        ('\u{86B}', '\u{86F}'),
+        ('\u{88F}', '\u{88F}'),
+        ('\u{892}', '\u{896}'),
+        ('\u{984}', '\u{984}'),
+        ('\u{98D}', '\u{98E}'),
+        ('\u{991}', '\u{992}'),
+        ('\u{9A9}', '\u{9A9}'),
+        ('\u{9B1}', '\u{9B1}'),
+        ('\u{9B3}', '\u{9B5}'),
+        ('\u{9BA}', '\u{9BB}'),
+        ('\u{9C5}', '\u{9C6}'),
+        ('\u{9C9}', '\u{9CA}'),
+        ('\u{9CF}', '\u{9D6}'),
+        ('\u{9D8}', '\u{9DB}'),
+        ('\u{9DE}', '\u{9DE}'),
+        ('\u{9E4}', '\u{9E5}'),
+        ('\u{9FF}', '\u{A00}'),
+        ('\u{A04}', '\u{A04}'),
+        ('\u{A0B}', '\u{A0E}'),
+        ('\u{A11}', '\u{A12}'),
+        ('\u{A29}', '\u{A29}'),
+        ('\u{A31}', '\u{A31}'),
+        ('\u{A34}', '\u{A34}'),
+        ('\u{A37}', '\u{A37}'),
+        ('\u{A3A}', '\u{A3B}'),
+        ('\u{A3D}', '\u{A3D}'),
+        ('\u{A43}', '\u{A46}'),
+        ('\u{A49}', '\u{A4A}'),
+        ('\u{A4E}', '\u{A50}'),
+        ('\u{A52}', '\u{A58}'),
+        ('\u{A5D}', '\u{A5D}'),
+        ('\u{A5F}', '\u{A65}'),
+        ('\u{A77}', '\u{A80}'),
+        ('\u{A84}', '\u{A84}'),
+        ('\u{A8E}', '\u{A8E}'),
+        ('\u{A92}', '\u{A92}'),
+        ('\u{AA9}', '\u{AA9}'),
+        ('\u{AB1}', '\u{AB1}'),
+        ('\u{AB4}', '\u{AB4}'),
+        ('\u{ABA}', '\u{ABB}'),
+        ('\u{AC6}', '\u{AC6}'),
+        ('\u{ACA}', '\u{ACA}'),
+        ('\u{ACE}', '\u{ACF}'),
+        ('\u{AD1}', '\u{ADF}'),
+        ('\u{AE4}', '\u{AE5}'),
+        ('\u{AF2}', '\u{AF8}'),
+        ('\u{B00}', '\u{B00}'),
+        ('\u{B04}', '\u{B04}'),
+        ('\u{B0D}', '\u{B0E}'),
+        ('\u{B11}', '\u{B12}'),
+        ('\u{B29}', '\u{B29}'),
+        ('\u{B31}', '\u{B31}'),
+        ('\u{B34}', '\u{B34}'),
+        ('\u{B3A}', '\u{B3B}'),
+        ('\u{B45}', '\u{B46}'),
+        ('\u{B49}', '\u{B4A}'),
+        ('\u{B4E}', '\u{B54}'),
+        ('\u{B58}', '\u{B5B}'),
+        ('\u{B5E}', '\u{B5E}'),
+        ('\u{B64}', '\u{B65}'),
+        ('\u{B78}', '\u{B81}'),
+        ('\u{B84}', '\u{B84}'),
+        ('\u{B8B}', '\u{B8D}'),
+        ('\u{B91}', '\u{B91}'),
+        ('\u{B96}', '\u{B98}'),
+        ('\u{B9B}', '\u{B9B}'),
+        ('\u{B9D}', '\u{B9D}'),
+        ('\u{BA0}', '\u{BA2}'),
+        ('\u{BA5}', '\u{BA7}'),
+        ('\u{BAB}', '\u{BAD}'),
+        ('\u{BBA}', '\u{BBD}'),
+        ('\u{BC3}', '\u{BC5}'),
+        ('\u{BC9}', '\u{BC9}'),
+        ('\u{BCE}', '\u{BCF}'),
+        ('\u{BD1}', '\u{BD6}'),
+        ('\u{BD8}', '\u{BE5}'),
+        ('\u{BFB}', '\u{BFF}'),
+        ('\u{C0D}', '\u{C0D}'),
+        ('\u{C11}', '\u{C11}'),
+        ('\u{C29}', '\u{C29}'),
+        ('\u{C3A}', '\u{C3B}'),
+        ('\u{C45}', '\u{C45}'),
+        ('\u{C49}', '\u{C49}'),
+        ('\u{C4E}', '\u{C54}'),
+        ('\u{C57}', '\u{C57}'),
+        ('\u{C5B}', '\u{C5C}'),
+        ('\u{C5E}', '\u{C5F}'),
+        ('\u{C64}', '\u{C65}'),
+        ('\u{C70}', '\u{C76}'),
+        ('\u{C8D}', '\u{C8D}'),
+        ('\u{C91}', '\u{C91}'),
+        ('\u{CA9}', '\u{CA9}'),
+        ('\u{CB4}', '\u{CB4}'),
+        ('\u{CBA}', '\u{CBB}'),
+        ('\u{CC5}', '\u{CC5}'),
+        ('\u{CC9}', '\u{CC9}'),
+        ('\u{CCE}', '\u{CD4}'),
+        ('\u{CD7}', '\u{CDC}'),
+        ('\u{CDF}', '\u{CDF}'),
+        ('\u{CE4}', '\u{CE5}'),
+        ('\u{CF0}', '\u{CF0}'),
+        ('\u{CF4}', '\u{CFF}'),
+        ('\u{D0D}', '\u{D0D}'),
+        ('\u{D11}', '\u{D11}'),
+        ('\u{D45}', '\u{D45}'),
+        ('\u{D49}', '\u{D49}'),
+        ('\u{D50}', '\u{D53}'),
+        ('\u{D64}', '\u{D65}'),
+        ('\u{D80}', '\u{D80}'),
+        ('\u{D84}', '\u{D84}'),
+        ('\u{D97}', '\u{D99}'),
+        ('\u{DB2}', '\u{DB2}'),
+        ('\u{DBC}', '\u{DBC}'),
+        ('\u{DBE}', '\u{DBF}'),
+        ('\u{DC7}', '\u{DC9}'),
+        ('\u{DCB}', '\u{DCE}'),
+        ('\u{DD5}', '\u{DD5}'),
+        ('\u{DD7}', '\u{DD7}'),
+        ('\u{DE0}', '\u{DE5}'),
+        ('\u{DF0}', '\u{DF1}'),
+        ('\u{DF5}', '\u{E00}'),
+        ('\u{E3B}', '\u{E3E}'),
+        ('\u{E5C}', '\u{E80}'),
+        ('\u{E83}', '\u{E83}'),
+        ('\u{E85}', '\u{E85}'),
+        ('\u{E8B}', '\u{E8B}'),
+        ('\u{EA4}', '\u{EA4}'),
+        ('\u{EA6}', '\u{EA6}'),
+        ('\u{EBE}', '\u{EBF}'),
+        ('\u{EC5}', '\u{EC5}'),
+        ('\u{EC7}', '\u{EC7}'),
+        ('\u{ECF}', '\u{ECF}'),
+        ('\u{EDA}', '\u{EDB}'),
+        ('\u{EE0}', '\u{EFF}'),
+        ('\u{F48}', '\u{F48}'),
+        ('\u{F6D}', '\u{F70}'),
+        ('\u{F98}', '\u{F98}'),
+        ('\u{FBD}', '\u{FBD}'),
+        ('\u{FCD}', '\u{FCD}'),
+        ('\u{FDB}', '\u{FFF}'),
+        ('\u{10C6}', '\u{10C6}'),
+        ('\u{10C8}', '\u{10CC}'),
+        ('\u{10CE}', '\u{10CF}'),
+        ('\u{1249}', '\u{1249}'),
+        ('\u{124E}', '\u{124F}'),
+        ('\u{1257}', '\u{1257}'),
+        ('\u{1259}', '\u{1259}'),
+        ('\u{125E}', '\u{125F}'),
+        ('\u{1289}', '\u{1289}'),
+        ('\u{128E}', '\u{128F}'),
+        ('\u{12B1}', '\u{12B1}'),
+        ('\u{12B6}', '\u{12B7}'),
+        ('\u{12BF}', '\u{12BF}'),
+        ('\u{12C1}', '\u{12C1}'),
+        ('\u{12C6}', '\u{12C7}'),
+        ('\u{12D7}', '\u{12D7}'),
+        ('\u{1311}', '\u{1311}'),
+        ('\u{1316}', '\u{1317}'),
+        ('\u{135B}', '\u{135C}'),
+        ('\u{137D}', '\u{137F}'),
+        ('\u{139A}', '\u{139F}'),
+        ('\u{13F6}', '\u{13F7}'),
+        ('\u{13FE}', '\u{13FF}'),
+        ('\u{169D}', '\u{169F}'),
+        ('\u{16F9}', '\u{16FF}'),
+        ('\u{1716}', '\u{171E}'),
+        ('\u{1737}', '\u{173F}'),
+        ('\u{1754}', '\u{175F}'),
+        ('\u{176D}', '\u{176D}'),
+        ('\u{1771}', '\u{1771}'),
+        ('\u{1774}', '\u{177F}'),
+        ('\u{17DE}', '\u{17DF}'),
+        ('\u{17EA}', '\u{17EF}'),
+        ('\u{17FA}', '\u{17FF}'),
+        ('\u{181A}', '\u{181F}'),
+        ('\u{1879}', '\u{187F}'),
+        ('\u{18AB}', '\u{18AF}'),
+        ('\u{18F6}', '\u{18FF}'),
+        ('\u{191F}', '\u{191F}'),
+        ('\u{192C}', '\u{192F}'),
+        ('\u{193C}', '\u{193F}'),
+        ('\u{1941}', '\u{1943}'),
+        ('\u{196E}', '\u{196F}'),
+        ('\u{1975}', '\u{197F}'),
+        ('\u{19AC}', '\u{19AF}'),
+        ('\u{19CA}', '\u{19CF}'),
+        ('\u{19DB}', '\u{19DD}'),
+        ('\u{1A1C}', '\u{1A1D}'),
+        ('\u{1A5F}', '\u{1A5F}'),
+        ('\u{1A7D}', '\u{1A7E}'),
+        ('\u{1A8A}', '\u{1A8F}'),
+        ('\u{1A9A}', '\u{1A9F}'),
+        ('\u{1AAE}', '\u{1AAF}'),
+        ('\u{1ACF}', '\u{1AFF}'),
+        ('\u{1B4D}', '\u{1B4D}'),
+        ('\u{1BF4}', '\u{1BFB}'),
+        ('\u{1C38}', '\u{1C3A}'),
+        ('\u{1C4A}', '\u{1C4C}'),
+        ('\u{1C8B}', '\u{1C8F}'),
+        ('\u{1CBB}', '\u{1CBC}'),
+        ('\u{1CC8}', '\u{1CCF}'),
+        ('\u{1CFB}', '\u{1CFF}'),
+        ('\u{1F16}', '\u{1F17}'),
+        ('\u{1F1E}', '\u{1F1F}'),
+        ('\u{1F46}', '\u{1F47}'),
+        ('\u{1F4E}', '\u{1F4F}'),
+        ('\u{1F58}', '\u{1F58}'),
+        ('\u{1F5A}', '\u{1F5A}'),
+        ('\u{1F5C}', '\u{1F5C}'),
+        ('\u{1F5E}', '\u{1F5E}'),
+        ('\u{1F7E}', '\u{1F7F}'),
+        ('\u{1FB5}', '\u{1FB5}'),
+        ('\u{1FC5}', '\u{1FC5}'),
+        ('\u{1FD4}', '\u{1FD5}'),
+        ('\u{1FDC}', '\u{1FDC}'),
+        ('\u{1FF0}', '\u{1FF1}'),
+        ('\u{1FF5}', '\u{1FF5}'),
+        ('\u{1FFF}', '\u{1FFF}'),
+        ('\u{2065}', '\u{2065}'),
+        ('\u{2072}', '\u{2073}'),
+        ('\u{208F}', '\u{208F}'),
+        ('\u{209D}', '\u{209F}'),
+        ('\u{20C1}', '\u{20CF}'),
+        ('\u{20F1}', '\u{20FF}'),
+        ('\u{218C}', '\u{218F}'),
+        ('\u{242A}', '\u{243F}'),
+        ('\u{244B}', '\u{245F}'),
+        ('\u{2B74}', '\u{2B75}'),
+        ('\u{2B96}', '\u{2B96}'),
+        ('\u{2CF4}', '\u{2CF8}'),
+        ('\u{2D26}', '\u{2D26}'),
+        ('\u{2D28}', '\u{2D2C}'),
+        ('\u{2D2E}', '\u{2D2F}'),
+        ('\u{2D68}', '\u{2D6E}'),
+        ('\u{2D71}', '\u{2D7E}'),
+        ('\u{2D97}', '\u{2D9F}'),
+        ('\u{2DA7}', '\u{2DA7}'),
+        ('\u{2DAF}', '\u{2DAF}'),
+        ('\u{2DB7}', '\u{2DB7}'),
+        ('\u{2DBF}', '\u{2DBF}'),
+        ('\u{2DC7}', '\u{2DC7}'),
+        ('\u{2DCF}', '\u{2DCF}'),
+        ('\u{2DD7}', '\u{2DD7}'),
+        ('\u{2DDF}', '\u{2DDF}'),
+        ('\u{2E5E}', '\u{2E7F}'),
+        ('\u{2E9A}', '\u{2E9A}'),
+        ('\u{2EF4}', '\u{2EFF}'),
+        ('\u{2FD6}', '\u{2FEF}'),
+        ('\u{3040}', '\u{3040}'),
+        ('\u{3097}', '\u{3098}'),
+        ('\u{3100}', '\u{3104}'),
+        ('\u{3130}', '\u{3130}'),
+        ('\u{318F}', '\u{318F}'),
+        ('\u{31E6}', '\u{31EE}'),
+        ('\u{321F}', '\u{321F}'),
+        ('\u{A48D}', '\u{A48F}'),
+        ('\u{A4C7}', '\u{A4CF}'),
+        ('\u{A62C}', '\u{A63F}'),
+        ('\u{A6F8}', '\u{A6FF}'),
+        ('\u{A7CE}', '\u{A7CF}'),
+        ('\u{A7D2}', '\u{A7D2}'),
+        ('\u{A7D4}', '\u{A7D4}'),
+        ('\u{A7DD}', '\u{A7F1}'),
+        ('\u{A82D}', '\u{A82F}'),
+        ('\u{A83A}', '\u{A83F}'),
+        ('\u{A878}', '\u{A87F}'),
+        ('\u{A8C6}', '\u{A8CD}'),
+        ('\u{A8DA}', '\u{A8DF}'),
+        ('\u{A954}', '\u{A95E}'),
+        ('\u{A97D}', '\u{A97F}'),
+        ('\u{A9CE}', '\u{A9CE}'),
+        ('\u{A9DA}', '\u{A9DD}'),
+        ('\u{A9FF}', '\u{A9FF}'),
+        ('\u{AA37}', '\u{AA3F}'),
+        ('\u{AA4E}', '\u{AA4F}'),
+        ('\u{AA5A}', '\u{AA5B}'),
+        ('\u{AAC3}', '\u{AADA}'),
+        ('\u{AAF7}', '\u{AB00}'),
+        ('\u{AB07}', '\u{AB08}'),
+        ('\u{AB0F}', '\u{AB10}'),
+        ('\u{AB17}', '\u{AB1F}'),
+        ('\u{AB27}', '\u{AB27}'),
+        ('\u{AB2F}', '\u{AB2F}'),
+        ('\u{AB6C}', '\u{AB6F}'),
+        ('\u{ABEE}', '\u{ABEF}'),
+        ('\u{ABFA}', '\u{ABFF}'),
+        ('\u{D7A4}', '\u{D7AF}'),
+        ('\u{D7C7}', '\u{D7CA}'),
+        ('\u{D7FC}', '\u{D7FF}'),
+        ('\u{FA6E}', '\u{FA6F}'),
+        ('\u{FADA}', '\u{FAFF}'),
+        ('\u{FB07}', '\u{FB12}'),
+        ('\u{FB18}', '\u{FB1C}'),
+        ('\u{FB37}', '\u{FB37}'),
+        ('\u{FB3D}', '\u{FB3D}'),
+        ('\u{FB3F}', '\u{FB3F}'),
+        ('\u{FB42}', '\u{FB42}'),
+        ('\u{FB45}', '\u{FB45}'),
+        ('\u{FBC3}', '\u{FBD2}'),
+        ('\u{FD90}', '\u{FD91}'),
+        ('\u{FDC8}', '\u{FDCE}'),
+        ('\u{FDD0}', '\u{FDEF}'),
+        ('\u{FE1A}', '\u{FE1F}'),
+        ('\u{FE53}', '\u{FE53}'),
+        ('\u{FE67}', '\u{FE67}'),
+        ('\u{FE6C}', '\u{FE6F}'),
+        ('\u{FE75}', '\u{FE75}'),
+        ('\u{FEFD}', '\u{FEFE}'),
+        ('\u{FF00}', '\u{FF00}'),
+        ('\u{FFBF}', '\u{FFC1}'),
+        ('\u{FFC8}', '\u{FFC9}'),
+        ('\u{FFD0}', '\u{FFD1}'),
+        ('\u{FFD8}', '\u{FFD9}'),
+        ('\u{FFDD}', '\u{FFDF}'),
+        ('\u{FFE7}', '\u{FFE7}'),
+        ('\u{FFEF}', '\u{FFF8}'),
+        ('\u{FFFE}', '\u{FFFF}'),
+        ('\u{1000C}', '\u{1000C}'),
+        ('\u{10027}', '\u{10027}'),
+        ('\u{1003B}', '\u{1003B}'),
+        ('\u{1003E}', '\u{1003E}'),
+        ('\u{1004E}', '\u{1004F}'),
+        ('\u{1005E}', '\u{1007F}'),
+        ('\u{100FB}', '\u{100FF}'),
+        ('\u{10103}', '\u{10106}'),
+        ('\u{10134}', '\u{10136}'),
+        ('\u{1018F}', '\u{1018F}'),
+        ('\u{1019D}', '\u{1019F}'),
+        ('\u{101A1}', '\u{101CF}'),
+        ('\u{101FE}', '\u{1027F}'),
+        ('\u{1029D}', '\u{1029F}'),
+        ('\u{102D1}', '\u{102DF}'),
+        ('\u{102FC}', '\u{102FF}'),
+        ('\u{10324}', '\u{1032C}'),
+        ('\u{1034B}', '\u{1034F}'),
+        ('\u{1037B}', '\u{1037F}'),
+        ('\u{1039E}', '\u{1039E}'),
+        ('\u{103C4}', '\u{103C7}'),
+        ('\u{103D6}', '\u{103FF}'),
+        ('\u{1049E}', '\u{1049F}'),
+        ('\u{104AA}', '\u{104AF}'),
+        ('\u{104D4}', '\u{104D7}'),
+        ('\u{104FC}', '\u{104FF}'),
+        ('\u{10528}', '\u{1052F}'),
+        ('\u{10564}', '\u{1056E}'),
+        ('\u{1057B}', '\u{1057B}'),
+        ('\u{1058B}', '\u{1058B}'),
+        ('\u{10593}', '\u{10593}'),
+        ('\u{10596}', '\u{10596}'),
+        ('\u{105A2}', '\u{105A2}'),
+        ('\u{105B2}', '\u{105B2}'),
+        ('\u{105BA}', '\u{105BA}'),
+        ('\u{105BD}', '\u{105BF}'),
+        ('\u{105F4}', '\u{105FF}'),
+        ('\u{10737}', '\u{1073F}'),
+        ('\u{10756}', '\u{1075F}'),
+        ('\u{10768}', '\u{1077F}'),
+        ('\u{10786}', '\u{10786}'),
+        ('\u{107B1}', '\u{107B1}'),
+        ('\u{107BB}', '\u{107FF}'),
+        ('\u{10806}', '\u{10807}'),
+        ('\u{10809}', '\u{10809}'),
+        ('\u{10836}', '\u{10836}'),
+        ('\u{10839}', '\u{1083B}'),
+        ('\u{1083D}', '\u{1083E}'),
+        ('\u{10856}', '\u{10856}'),
+        ('\u{1089F}', '\u{108A6}'),
+        ('\u{108B0}', '\u{108DF}'),
+        ('\u{108F3}', '\u{108F3}'),
+        ('\u{108F6}', '\u{108FA}'),
+        ('\u{1091C}', '\u{1091E}'),
+        ('\u{1093A}', '\u{1093E}'),
+        ('\u{10940}', '\u{1097F}'),
+        ('\u{109B8}', '\u{109BB}'),
+        ('\u{109D0}', '\u{109D1}'),
+        ('\u{10A04}', '\u{10A04}'),
+        ('\u{10A07}', '\u{10A0B}'),
+        ('\u{10A14}', '\u{10A14}'),
+        ('\u{10A18}', '\u{10A18}'),
+        ('\u{10A36}', '\u{10A37}'),
+        ('\u{10A3B}', '\u{10A3E}'),
+        ('\u{10A49}', '\u{10A4F}'),
+        ('\u{10A59}', '\u{10A5F}'),
+        ('\u{10AA0}', '\u{10ABF}'),
+        ('\u{10AE7}', '\u{10AEA}'),
+        ('\u{10AF7}', '\u{10AFF}'),
+        ('\u{10B36}', '\u{10B38}'),
+        ('\u{10B56}', '\u{10B57}'),
+        ('\u{10B73}', '\u{10B77}'),
+        ('\u{10B92}', '\u{10B98}'),
+        ('\u{10B9D}', '\u{10BA8}'),
+        ('\u{10BB0}', '\u{10BFF}'),
+        ('\u{10C49}', '\u{10C7F}'),
+        ('\u{10CB3}', '\u{10CBF}'),
+        ('\u{10CF3}', '\u{10CF9}'),
+        ('\u{10D28}', '\u{10D2F}'),
+        ('\u{10D3A}', '\u{10D3F}'),
+        ('\u{10D66}', '\u{10D68}'),
+        ('\u{10D86}', '\u{10D8D}'),
+        ('\u{10D90}', '\u{10E5F}'),
+        ('\u{10E7F}', '\u{10E7F}'),
+        ('\u{10EAA}', '\u{10EAA}'),
+        ('\u{10EAE}', '\u{10EAF}'),
+        ('\u{10EB2}', '\u{10EC1}'),
+        ('\u{10EC5}', '\u{10EFB}'),
+        ('\u{10F28}', '\u{10F2F}'),
+        ('\u{10F5A}', '\u{10F6F}'),
+        ('\u{10F8A}', '\u{10FAF}'),
+        ('\u{10FCC}', '\u{10FDF}'),
+        ('\u{10FF7}', '\u{10FFF}'),
+        ('\u{1104E}', '\u{11051}'),
+        ('\u{11076}', '\u{1107E}'),
+        ('\u{110C3}', '\u{110CC}'),
+        ('\u{110CE}', '\u{110CF}'),
+        ('\u{110E9}', '\u{110EF}'),
+        ('\u{110FA}', '\u{110FF}'),
+        ('\u{11135}', '\u{11135}'),
+        ('\u{11148}', '\u{1114F}'),
+        ('\u{11177}', '\u{1117F}'),
+        ('\u{111E0}', '\u{111E0}'),
+        ('\u{111F5}', '\u{111FF}'),
+        ('\u{11212}', '\u{11212}'),
+        ('\u{11242}', '\u{1127F}'),
+        ('\u{11287}', '\u{11287}'),
+        ('\u{11289}', '\u{11289}'),
+        ('\u{1128E}', '\u{1128E}'),
+        ('\u{1129E}', '\u{1129E}'),
+        ('\u{112AA}', '\u{112AF}'),
+        ('\u{112EB}', '\u{112EF}'),
+        ('\u{112FA}', '\u{112FF}'),
+        ('\u{11304}', '\u{11304}'),
+        ('\u{1130D}', '\u{1130E}'),
+        ('\u{11311}', '\u{11312}'),
+        ('\u{11329}', '\u{11329}'),
+        ('\u{11331}', '\u{11331}'),
+        ('\u{11334}', '\u{11334}'),
+        ('\u{1133A}', '\u{1133A}'),
+        ('\u{11345}', '\u{11346}'),
+        ('\u{11349}', '\u{1134A}'),
+        ('\u{1134E}', '\u{1134F}'),
+        ('\u{11351}', '\u{11356}'),
+        ('\u{11358}', '\u{1135C}'),
+        ('\u{11364}', '\u{11365}'),
+        ('\u{1136D}', '\u{1136F}'),
+        ('\u{11375}', '\u{1137F}'),
+        ('\u{1138A}', '\u{1138A}'),
+        ('\u{1138C}', '\u{1138D}'),
+        ('\u{1138F}', '\u{1138F}'),
+        ('\u{113B6}', '\u{113B6}'),
+        ('\u{113C1}', '\u{113C1}'),
+        ('\u{113C3}', '\u{113C4}'),
+        ('\u{113C6}', '\u{113C6}'),
+        ('\u{113CB}', '\u{113CB}'),
+        ('\u{113D6}', '\u{113D6}'),
+        ('\u{113D9}', '\u{113E0}'),
+        ('\u{113E3}', '\u{113FF}'),
+        ('\u{1145C}', '\u{1145C}'),
+        ('\u{11462}', '\u{1147F}'),
+        ('\u{114C8}', '\u{114CF}'),
+        ('\u{114DA}', '\u{1157F}'),
+        ('\u{115B6}', '\u{115B7}'),
+        ('\u{115DE}', '\u{115FF}'),
+        ('\u{11645}', '\u{1164F}'),
+        ('\u{1165A}', '\u{1165F}'),
+        ('\u{1166D}', '\u{1167F}'),
+        ('\u{116BA}', '\u{116BF}'),
+        ('\u{116CA}', '\u{116CF}'),
+        ('\u{116E4}', '\u{116FF}'),
+        ('\u{1171B}', '\u{1171C}'),
+        ('\u{1172C}', '\u{1172F}'),
+        ('\u{11747}', '\u{117FF}'),
+        ('\u{1183C}', '\u{1189F}'),
+        ('\u{118F3}', '\u{118FE}'),
+        ('\u{11907}', '\u{11908}'),
+        ('\u{1190A}', '\u{1190B}'),
+        ('\u{11914}', '\u{11914}'),
+        ('\u{11917}', '\u{11917}'),
+        ('\u{11936}', '\u{11936}'),
+        ('\u{11939}', '\u{1193A}'),
+        ('\u{11947}', '\u{1194F}'),
+        ('\u{1195A}', '\u{1199F}'),
+        ('\u{119A8}', '\u{119A9}'),
+        ('\u{119D8}', '\u{119D9}'),
+        ('\u{119E5}', '\u{119FF}'),
+        ('\u{11A48}', '\u{11A4F}'),
+        ('\u{11AA3}', '\u{11AAF}'),
+        ('\u{11AF9}', '\u{11AFF}'),
+        ('\u{11B0A}', '\u{11BBF}'),
+        ('\u{11BE2}', '\u{11BEF}'),
+        ('\u{11BFA}', '\u{11BFF}'),
+        ('\u{11C09}', '\u{11C09}'),
+        ('\u{11C37}', '\u{11C37}'),
+        ('\u{11C46}', '\u{11C4F}'),
+        ('\u{11C6D}', '\u{11C6F}'),
+        ('\u{11C90}', '\u{11C91}'),
+        ('\u{11CA8}', '\u{11CA8}'),
+        ('\u{11CB7}', '\u{11CFF}'),
+        ('\u{11D07}', '\u{11D07}'),
+        ('\u{11D0A}', '\u{11D0A}'),
+        ('\u{11D37}', '\u{11D39}'),
+        ('\u{11D3B}', '\u{11D3B}'),
+        ('\u{11D3E}', '\u{11D3E}'),
+        ('\u{11D48}', '\u{11D4F}'),
+        ('\u{11D5A}', '\u{11D5F}'),
+        ('\u{11D66}', '\u{11D66}'),
+        ('\u{11D69}', '\u{11D69}'),
+        ('\u{11D8F}', '\u{11D8F}'),
+        ('\u{11D92}', '\u{11D92}'),
+        ('\u{11D99}', '\u{11D9F}'),
+        ('\u{11DAA}', '\u{11EDF}'),
+        ('\u{11EF9}', '\u{11EFF}'),
+        ('\u{11F11}', '\u{11F11}'),
+        ('\u{11F3B}', '\u{11F3D}'),
+        ('\u{11F5B}', '\u{11FAF}'),
+        ('\u{11FB1}', '\u{11FBF}'),
+        ('\u{11FF2}', '\u{11FFE}'),
+        ('\u{1239A}', '\u{123FF}'),
+        ('\u{1246F}', '\u{1246F}'),
+        ('\u{12475}', '\u{1247F}'),
+        ('\u{12544}', '\u{12F8F}'),
+        ('\u{12FF3}', '\u{12FFF}'),
+        ('\u{13456}', '\u{1345F}'),
+        ('\u{143FB}', '\u{143FF}'),
+        ('\u{14647}', '\u{160FF}'),
+        ('\u{1613A}', '\u{167FF}'),
+        ('\u{16A39}', '\u{16A3F}'),
+        ('\u{16A5F}', '\u{16A5F}'),
+        ('\u{16A6A}', '\u{16A6D}'),
+        ('\u{16ABF}', '\u{16ABF}'),
+        ('\u{16ACA}', '\u{16ACF}'),
+        ('\u{16AEE}', '\u{16AEF}'),
+        ('\u{16AF6}', '\u{16AFF}'),
+        ('\u{16B46}', '\u{16B4F}'),
+        ('\u{16B5A}', '\u{16B5A}'),
+        ('\u{16B62}', '\u{16B62}'),
+        ('\u{16B78}', '\u{16B7C}'),
+        ('\u{16B90}', '\u{16D3F}'),
+        ('\u{16D7A}', '\u{16E3F}'),
+        ('\u{16E9B}', '\u{16EFF}'),
+        ('\u{16F4B}', '\u{16F4E}'),
+        ('\u{16F88}', '\u{16F8E}'),
+        ('\u{16FA0}', '\u{16FDF}'),
+        ('\u{16FE5}', '\u{16FEF}'),
+        ('\u{16FF2}', '\u{16FFF}'),
+        ('\u{187F8}', '\u{187FF}'),
+        ('\u{18CD6}', '\u{18CFE}'),
+        ('\u{18D09}', '\u{1AFEF}'),
+        ('\u{1AFF4}', '\u{1AFF4}'),
+        ('\u{1AFFC}', '\u{1AFFC}'),
+        ('\u{1AFFF}', '\u{1AFFF}'),
+        ('\u{1B123}', '\u{1B131}'),
+        ('\u{1B133}', '\u{1B14F}'),
+        ('\u{1B153}', '\u{1B154}'),
+        ('\u{1B156}', '\u{1B163}'),
+        ('\u{1B168}', '\u{1B16F}'),
+        ('\u{1B2FC}', '\u{1BBFF}'),
+        ('\u{1BC6B}', '\u{1BC6F}'),
+        ('\u{1BC7D}', '\u{1BC7F}'),
+        ('\u{1BC89}', '\u{1BC8F}'),
+        ('\u{1BC9A}', '\u{1BC9B}'),
+        ('\u{1BCA4}', '\u{1CBFF}'),
+        ('\u{1CCFA}', '\u{1CCFF}'),
+        ('\u{1CEB4}', '\u{1CEFF}'),
+        ('\u{1CF2E}', '\u{1CF2F}'),
+        ('\u{1CF47}', '\u{1CF4F}'),
+        ('\u{1CFC4}', '\u{1CFFF}'),
+        ('\u{1D0F6}', '\u{1D0FF}'),
+        ('\u{1D127}', '\u{1D128}'),
+        ('\u{1D1EB}', '\u{1D1FF}'),
+        ('\u{1D246}', '\u{1D2BF}'),
+        ('\u{1D2D4}', '\u{1D2DF}'),
+        ('\u{1D2F4}', '\u{1D2FF}'),
+        ('\u{1D357}', '\u{1D35F}'),
+        ('\u{1D379}', '\u{1D3FF}'),
+        ('\u{1D455}', '\u{1D455}'),
+        ('\u{1D49D}', '\u{1D49D}'),
+        ('\u{1D4A0}', '\u{1D4A1}'),
+        ('\u{1D4A3}', '\u{1D4A4}'),
+        ('\u{1D4A7}', '\u{1D4A8}'),
+        ('\u{1D4AD}', '\u{1D4AD}'),
+        ('\u{1D4BA}', '\u{1D4BA}'),
+        ('\u{1D4BC}', '\u{1D4BC}'),
+        ('\u{1D4C4}', '\u{1D4C4}'),
+        ('\u{1D506}', '\u{1D506}'),
+        ('\u{1D50B}', '\u{1D50C}'),
+        ('\u{1D515}', '\u{1D515}'),
+        ('\u{1D51D}', '\u{1D51D}'),
+        ('\u{1D53A}', '\u{1D53A}'),
+        ('\u{1D53F}', '\u{1D53F}'),
+        ('\u{1D545}', '\u{1D545}'),
+        ('\u{1D547}', '\u{1D549}'),
+        ('\u{1D551}', '\u{1D551}'),
+        ('\u{1D6A6}', '\u{1D6A7}'),
+        ('\u{1D7CC}', '\u{1D7CD}'),
+        ('\u{1DA8C}', '\u{1DA9A}'),
+        ('\u{1DAA0}', '\u{1DAA0}'),
+        ('\u{1DAB0}', '\u{1DEFF}'),
+        ('\u{1DF1F}', '\u{1DF24}'),
+        ('\u{1DF2B}', '\u{1DFFF}'),
+        ('\u{1E007}', '\u{1E007}'),
+        ('\u{1E019}', '\u{1E01A}'),
+        ('\u{1E022}', '\u{1E022}'),
+        ('\u{1E025}', '\u{1E025}'),
+        ('\u{1E02B}', '\u{1E02F}'),
+        ('\u{1E06E}', '\u{1E08E}'),
+        ('\u{1E090}', '\u{1E0FF}'),
+        ('\u{1E12D}', '\u{1E12F}'),
+        ('\u{1E13E}', '\u{1E13F}'),
+        ('\u{1E14A}', '\u{1E14D}'),
+        ('\u{1E150}', '\u{1E28F}'),
+        ('\u{1E2AF}', '\u{1E2BF}'),
+        ('\u{1E2FA}', '\u{1E2FE}'),
+        ('\u{1E300}', '\u{1E4CF}'),
+        ('\u{1E4FA}', '\u{1E5CF}'),
+        ('\u{1E5FB}', '\u{1E5FE}'),
+        ('\u{1E600}', '\u{1E7DF}'),
+        ('\u{1E7E7}', '\u{1E7E7}'),
+        ('\u{1E7EC}', '\u{1E7EC}'),
+        ('\u{1E7EF}', '\u{1E7EF}'),
+        ('\u{1E7FF}', '\u{1E7FF}'),
+        ('\u{1E8C5}', '\u{1E8C6}'),
+        ('\u{1E8D7}', '\u{1E8FF}'),
+        ('\u{1E94C}', '\u{1E94F}'),
+        ('\u{1E95A}', '\u{1E95D}'),
+        ('\u{1E960}', '\u{1EC70}'),
+        ('\u{1ECB5}', '\u{1ED00}'),
+        ('\u{1ED3E}', '\u{1EDFF}'),
+        ('\u{1EE04}', '\u{1EE04}'),
+        ('\u{1EE20}', '\u{1EE20}'),
+        ('\u{1EE23}', '\u{1EE23}'),
+        ('\u{1EE25}', '\u{1EE26}'),
+        ('\u{1EE28}', '\u{1EE28}'),
+        ('\u{1EE33}', '\u{1EE33}'),
+        ('\u{1EE38}', '\u{1EE38}'),
+        ('\u{1EE3A}', '\u{1EE3A}'),
+        ('\u{1EE3C}', '\u{1EE41}'),
+        ('\u{1EE43}', '\u{1EE46}'),
+        ('\u{1EE48}', '\u{1EE48}'),
+        ('\u{1EE4A}', '\u{1EE4A}'),
+        ('\u{1EE4C}', '\u{1EE4C}'),
+        ('\u{1EE50}', '\u{1EE50}'),
+        ('\u{1EE53}', '\u{1EE53}'),
+        ('\u{1EE55}', '\u{1EE56}'),
+        ('\u{1EE58}', '\u{1EE58}'),
+        ('\u{1EE5A}', '\u{1EE5A}'),
+        ('\u{1EE5C}', '\u{1EE5C}'),
+        ('\u{1EE5E}', '\u{1EE5E}'),
+        ('\u{1EE60}', '\u{1EE60}'),
+        ('\u{1EE63}', '\u{1EE63}'),
+        ('\u{1EE65}', '\u{1EE66}'),
+        ('\u{1EE6B}', '\u{1EE6B}'),
+        ('\u{1EE73}', '\u{1EE73}'),
+        ('\u{1EE78}', '\u{1EE78}'),
+        ('\u{1EE7D}', '\u{1EE7D}'),
+        ('\u{1EE7F}', '\u{1EE7F}'),
+        ('\u{1EE8A}', '\u{1EE8A}'),
+        ('\u{1EE9C}', '\u{1EEA0}'),
+        ('\u{1EEA4}', '\u{1EEA4}'),
+        ('\u{1EEAA}', '\u{1EEAA}'),
+        ('\u{1EEBC}', '\u{1EEEF}'),
+        ('\u{1EEF2}', '\u{1EFFF}'),
+        ('\u{1F02C}', '\u{1F02F}'),
+        ('\u{1F094}', '\u{1F09F}'),
+        ('\u{1F0AF}', '\u{1F0B0}'),
+        ('\u{1F0C0}', '\u{1F0C0}'),
+        ('\u{1F0D0}', '\u{1F0D0}'),
+        ('\u{1F0F6}', '\u{1F0FF}'),
+        ('\u{1F1AE}', '\u{1F1E5}'),
+        ('\u{1F203}', '\u{1F20F}'),
+        ('\u{1F23C}', '\u{1F23F}'),
+        ('\u{1F249}', '\u{1F24F}'),
+        ('\u{1F252}', '\u{1F25F}'),
+        ('\u{1F266}', '\u{1F2FF}'),
+        ('\u{1F6D8}', '\u{1F6DB}'),
+        ('\u{1F6ED}', '\u{1F6EF}'),
+        ('\u{1F6FD}', '\u{1F6FF}'),
+        ('\u{1F777}', '\u{1F77A}'),
+        ('\u{1F7DA}', '\u{1F7DF}'),
+        ('\u{1F7EC}', '\u{1F7EF}'),
+        ('\u{1F7F1}', '\u{1F7FF}'),
+        ('\u{1F80C}', '\u{1F80F}'),
+        ('\u{1F848}', '\u{1F84F}'),
+        ('\u{1F85A}', '\u{1F85F}'),
+        ('\u{1F888}', '\u{1F88F}'),
+        ('\u{1F8AE}', '\u{1F8AF}'),
+        ('\u{1F8BC}', '\u{1F8BF}'),
+        ('\u{1F8C2}', '\u{1F8FF}'),
+        ('\u{1FA54}', '\u{1FA5F}'),
+        ('\u{1FA6E}', '\u{1FA6F}'),
+        ('\u{1FA7D}', '\u{1FA7F}'),
+        ('\u{1FA8A}', '\u{1FA8E}'),
+        ('\u{1FAC7}', '\u{1FACD}'),
+        ('\u{1FADD}', '\u{1FADE}'),
+        ('\u{1FAEA}', '\u{1FAEF}'),
+        ('\u{1FAF9}', '\u{1FAFF}'),
+        ('\u{1FB93}', '\u{1FB93}'),
+        ('\u{1FBFA}', '\u{1FFFF}'),
+        ('\u{2A6E0}', '\u{2A6FF}'),
+        ('\u{2B73A}', '\u{2B73F}'),
+        ('\u{2B81E}', '\u{2B81F}'),
+        ('\u{2CEA2}', '\u{2CEAF}'),
+        ('\u{2EBE1}', '\u{2EBEF}'),
+        ('\u{2EE5E}', '\u{2F7FF}'),
+        ('\u{2FA1E}', '\u{2FFFF}'),
+        ('\u{3134B}', '\u{3134F}'),
+        ('\u{323B0}', '\u{E0000}'),
+        ('\u{E0002}', '\u{E001F}'),
+        ('\u{E0080}', '\u{E00FF}'),
+        ('\u{E01F0}', '\u{EFFFF}'),
+        ('\u{FFFFE}', '\u{FFFFF}'),
+        ('\u{10FFFE}', '\u{10FFFF}'),
+    ];
+
+    pub(crate) const WHITE_SPACE: &[(char, char)] = &[
+        ('\u{9}', '\u{D}'),
+        ('\u{20}', '\u{20}'),
+        ('\u{85}', '\u{85}'),
+        ('\u{A0}', '\u{A0}'),
+        ('\u{1680}', '\u{1680}'),
+        ('\u{2000}', '\u{200A}'),
+        ('\u{2028}', '\u{2029}'),
+        ('\u{202F}', '\u{202F}'),
+        ('\u{205F}', '\u{205F}'),
+        ('\u{3000}', '\u{3000}'),
+    ];
+}
+
+pub(crate) use self::data::*;
+
+/// Check whether a character is in one of the tables.
+pub(crate) fn contains(table: &[(char, char)], ch: char) -> bool {
+    table
+        .binary_search_by(|&(first, last)| {
+            if ch < first {
+                core::cmp::Ordering::Greater
+            } else if ch > last {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
@@ -0,0 +1,17 @@
+//! Helpers for testing code that prints or quotes untrusted text.
+//!
+//! `golden_cases` has the expected output for a set of tricky inputs, so
+//! downstream tests notice when a new version quotes something differently.
+//! See the `testkit` module for checking output against real shells.
+//!
+//! # Optional
+//! `strategies` requires the `proptest` feature, which needs a newer
+//! compiler. `golden_cases` only requires the `testkit` feature.
+
+#[cfg(feature = "testkit")]
+mod golden;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+
+#[cfg(feature = "testkit")]
+pub use self::golden::{golden_cases, GoldenCase};
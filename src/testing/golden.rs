@@ -0,0 +1,934 @@
+//! A table of inputs with their expected output in each style, for
+//! [`testing::golden_cases`](crate::testing::golden_cases).
+//!
+//! Each entry is the input, the output with quotes forced and the output
+//! without. If a change to the crate changes any of them it shows up here.
+
+use std::vec::Vec;
+
+use crate::Style;
+
+/// An input with its expected output in one style, from
+/// [`golden_cases`](crate::testing::golden_cases).
+#[derive(Debug, Copy, Clone)]
+pub struct GoldenCase {
+    style: Style,
+    input: &'static str,
+    quoted: &'static str,
+    maybe_quoted: &'static str,
+}
+
+impl GoldenCase {
+    /// The style the input is quoted in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// The text to quote.
+    pub fn input(&self) -> &'static str {
+        self.input
+    }
+
+    /// The expected output of `Quoted::new(style, input)`.
+    pub fn quoted(&self) -> &'static str {
+        self.quoted
+    }
+
+    /// The expected output with [`Quoted::force(false)`](crate::Quoted::force).
+    pub fn maybe_quoted(&self) -> &'static str {
+        self.maybe_quoted
+    }
+}
+
+/// Every case for every style that's enabled.
+///
+/// ```
+/// use os_display::{testing, Quoted};
+///
+/// for case in testing::golden_cases() {
+///     let quoted = Quoted::new(case.style(), case.input());
+///     assert_eq!(quoted.to_string(), case.quoted());
+///     assert_eq!(quoted.force(false).to_string(), case.maybe_quoted());
+/// }
+/// ```
+///
+/// The inputs only use characters that every supported version of Unicode
+/// agrees on, so the outputs don't depend on the `unicodeNN` features or on
+/// `unicode-width`. Options like [`Quoted::quoting`](crate::Quoted::quoting)
+/// aren't covered.
+pub fn golden_cases() -> Vec<GoldenCase> {
+    let mut cases = Vec::new();
+    #[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+    add(&mut cases, Style::Unix, UNIX);
+    #[cfg(any(feature = "windows", all(feature = "native", windows)))]
+    add(&mut cases, Style::Powershell, POWERSHELL);
+    #[cfg(feature = "windows")]
+    add(&mut cases, Style::WindowsArgv, WINDOWS_ARGV);
+    #[cfg(feature = "sql")]
+    add(&mut cases, Style::Sql, SQL);
+    #[cfg(feature = "html")]
+    add(&mut cases, Style::Html, HTML);
+    #[cfg(feature = "systemd")]
+    add(&mut cases, Style::SystemdUnit, SYSTEMD_UNIT);
+    #[cfg(feature = "dotenv")]
+    add(&mut cases, Style::Dotenv, DOTENV);
+    #[cfg(feature = "dockerfile")]
+    add(&mut cases, Style::Dockerfile, DOCKERFILE);
+    #[cfg(feature = "cmake")]
+    add(&mut cases, Style::Cmake, CMAKE);
+    #[cfg(feature = "reg")]
+    add(&mut cases, Style::Reg, REG);
+    #[cfg(feature = "sudoers")]
+    add(&mut cases, Style::Sudoers, SUDOERS);
+    #[cfg(feature = "git")]
+    add(&mut cases, Style::GitIgnore, GIT_IGNORE);
+    #[cfg(feature = "git")]
+    add(&mut cases, Style::GitAttributes, GIT_ATTRIBUTES);
+    #[cfg(feature = "git")]
+    add(&mut cases, Style::GitConfig, GIT_CONFIG);
+    #[cfg(feature = "sanitize")]
+    add(&mut cases, Style::Sanitize, SANITIZE);
+    cases
+}
+
+fn add(
+    cases: &mut Vec<GoldenCase>,
+    style: Style,
+    table: &[(&'static str, &'static str, &'static str)],
+) {
+    for &(input, quoted, maybe_quoted) in table {
+        cases.push(GoldenCase {
+            style,
+            input,
+            quoted,
+            maybe_quoted,
+        });
+    }
+}
+
+#[cfg(any(feature = "unix", all(feature = "native", not(windows))))]
+#[rustfmt::skip]
+const UNIX: &[(&str, &str, &str)] = &[
+    ("", "''", "''"),
+    ("foo", "'foo'", "foo"),
+    ("foo bar", "'foo bar'", "'foo bar'"),
+    ("foo/bar.baz", "'foo/bar.baz'", "foo/bar.baz"),
+    ("-x", "'-x'", "-x"),
+    ("--%", "'--%'", "--%"),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "'\"quoted\"'", "'\"quoted\"'"),
+    ("a\"b'c", "'a\"b'\\''c'", "'a\"b'\\''c'"),
+    ("$HOME", "'$HOME'", "'$HOME'"),
+    ("`cmd`", "'`cmd`'", "'`cmd`'"),
+    ("a\\b", "'a\\b'", "'a\\b'"),
+    ("C:\\Program Files", "'C:\\Program Files'", "'C:\\Program Files'"),
+    ("100%", "'100%'", "100%"),
+    ("a=b", "'a=b'", "'a=b'"),
+    ("~user", "'~user'", "'~user'"),
+    ("#tag", "'#tag'", "'#tag'"),
+    ("a#b", "'a#b'", "a#b"),
+    ("!", "'!'", "'!'"),
+    ("*.txt", "'*.txt'", "'*.txt'"),
+    ("[a]", "'[a]'", "'[a]'"),
+    ("{a,b}", "'{a,b}'", "'{a,b}'"),
+    ("a;b", "'a;b'", "'a;b'"),
+    ("a|b", "'a|b'", "'a|b'"),
+    ("a&b", "'a&b'", "'a&b'"),
+    ("(x)", "'(x)'", "'(x)'"),
+    ("<in>", "'<in>'", "'<in>'"),
+    ("semi;colon", "'semi;colon'", "'semi;colon'"),
+    ("tab\there", "$'tab\\there'", "$'tab\\there'"),
+    ("line\nbreak", "$'line\\nbreak'", "$'line\\nbreak'"),
+    ("cr\rlf", "$'cr\\rlf'", "$'cr\\rlf'"),
+    ("nul\0byte", "$'nul\\x00'$'byte'", "$'nul\\x00'$'byte'"),
+    ("\u{1B}[31m", "$'\\x1B[31m'", "$'\\x1B[31m'"),
+    ("\u{7F}", "$'\\x7F'", "$'\\x7F'"),
+    ("caf\u{E9}", "'caf\u{E9}'", "caf\u{E9}"),
+    ("a\u{200B}b", "'a\u{200B}b'", "a\u{200B}b"),
+    ("\u{2000}", "'\u{2000}'", "'\u{2000}'"),
+    ("a\u{85}b", "$'a\\xC2\\x85'$'b'", "$'a\\xC2\\x85'$'b'"),
+    ("it\u{2019}s", "'it\u{2019}s'", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "'x\u{201C}y\u{201D}'", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "$'user\\xE2\\x80\\xAEtxt.exe'", "$'user\\xE2\\x80\\xAEtxt.exe'"),
+    ("a\u{2066}b\u{2069}c", "'a\u{2066}b\u{2069}c'", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "'smile\u{1F600}'", "smile\u{1F600}"),
+    ("a\u{301}", "'a\u{301}'", "a\u{301}"),
+    ("a/b/c/", "'a/b/c/'", "a/b/c/"),
+    ("/mnt/my-disk", "'/mnt/my-disk'", "/mnt/my-disk"),
+    ("<script>", "'<script>'", "'<script>'"),
+    ("&amp;", "'&amp;'", "'&amp;'"),
+    ("${x}", "'${x}'", "'${x}'"),
+    ("a\\\"b", "'a\\\"b'", "'a\\\"b'"),
+    ("trailing\\", "'trailing\\'", "'trailing\\'"),
+];
+#[cfg(any(feature = "windows", all(feature = "native", windows)))]
+#[rustfmt::skip]
+const POWERSHELL: &[(&str, &str, &str)] = &[
+    ("", "''", "''"),
+    ("foo", "'foo'", "foo"),
+    ("foo bar", "'foo bar'", "'foo bar'"),
+    ("foo/bar.baz", "'foo/bar.baz'", "foo/bar.baz"),
+    ("-x", "'-x'", "'-x'"),
+    ("--%", "'--%'", "'--%'"),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "'\"quoted\"'", "'\"quoted\"'"),
+    ("a\"b'c", "'a\"b''c'", "'a\"b''c'"),
+    ("$HOME", "'$HOME'", "'$HOME'"),
+    ("`cmd`", "'`cmd`'", "'`cmd`'"),
+    ("a\\b", "'a\\b'", "a\\b"),
+    ("C:\\Program Files", "'C:\\Program Files'", "'C:\\Program Files'"),
+    ("100%", "'100%'", "'100%'"),
+    ("a=b", "'a=b'", "'a=b'"),
+    ("~user", "'~user'", "'~user'"),
+    ("#tag", "'#tag'", "'#tag'"),
+    ("a#b", "'a#b'", "a#b"),
+    ("!", "'!'", "'!'"),
+    ("*.txt", "'*.txt'", "'*.txt'"),
+    ("[a]", "'[a]'", "'[a]'"),
+    ("{a,b}", "'{a,b}'", "'{a,b}'"),
+    ("a;b", "'a;b'", "'a;b'"),
+    ("a|b", "'a|b'", "'a|b'"),
+    ("a&b", "'a&b'", "'a&b'"),
+    ("(x)", "'(x)'", "'(x)'"),
+    ("<in>", "'<in>'", "'<in>'"),
+    ("semi;colon", "'semi;colon'", "'semi;colon'"),
+    ("tab\there", "\"tab`there\"", "\"tab`there\""),
+    ("line\nbreak", "\"line`nbreak\"", "\"line`nbreak\""),
+    ("cr\rlf", "\"cr`rlf\"", "\"cr`rlf\""),
+    ("nul\0byte", "\"nul`0byte\"", "\"nul`0byte\""),
+    ("\u{1B}[31m", "\"`u{1B}[31m\"", "\"`u{1B}[31m\""),
+    ("\u{7F}", "\"`u{7F}\"", "\"`u{7F}\""),
+    ("caf\u{E9}", "'caf\u{E9}'", "caf\u{E9}"),
+    ("a\u{200B}b", "'a\u{200B}b'", "a\u{200B}b"),
+    ("\u{2000}", "'\u{2000}'", "'\u{2000}'"),
+    ("a\u{85}b", "\"a`u{85}b\"", "\"a`u{85}b\""),
+    ("it\u{2019}s", "\"it\u{2019}s\"", "\"it\u{2019}s\""),
+    ("x\u{201C}y\u{201D}", "'x\u{201C}y\u{201D}'", "'x\u{201C}y\u{201D}'"),
+    ("user\u{202E}txt.exe", "\"user`u{202E}txt.exe\"", "\"user`u{202E}txt.exe\""),
+    ("a\u{2066}b\u{2069}c", "'a\u{2066}b\u{2069}c'", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "'smile\u{1F600}'", "smile\u{1F600}"),
+    ("a\u{301}", "'a\u{301}'", "a\u{301}"),
+    ("a/b/c/", "'a/b/c/'", "a/b/c/"),
+    ("/mnt/my-disk", "'/mnt/my-disk'", "/mnt/my-disk"),
+    ("<script>", "'<script>'", "'<script>'"),
+    ("&amp;", "'&amp;'", "'&amp;'"),
+    ("${x}", "'${x}'", "'${x}'"),
+    ("a\\\"b", "'a\\\"b'", "'a\\\"b'"),
+    ("trailing\\", "'trailing\\'", "trailing\\"),
+];
+#[cfg(feature = "windows")]
+#[rustfmt::skip]
+const WINDOWS_ARGV: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "\"foo\"", "foo"),
+    ("foo bar", "\"foo bar\"", "\"foo bar\""),
+    ("foo/bar.baz", "\"foo/bar.baz\"", "foo/bar.baz"),
+    ("-x", "\"-x\"", "-x"),
+    ("--%", "\"--%\"", "--%"),
+    ("can't", "\"can't\"", "can't"),
+    ("\"quoted\"", "\"\\\"quoted\\\"\"", "\"\\\"quoted\\\"\""),
+    ("a\"b'c", "\"a\\\"b'c\"", "\"a\\\"b'c\""),
+    ("$HOME", "\"$HOME\"", "$HOME"),
+    ("`cmd`", "\"`cmd`\"", "`cmd`"),
+    ("a\\b", "\"a\\b\"", "a\\b"),
+    ("C:\\Program Files", "\"C:\\Program Files\"", "\"C:\\Program Files\""),
+    ("100%", "\"100%\"", "100%"),
+    ("a=b", "\"a=b\"", "a=b"),
+    ("~user", "\"~user\"", "~user"),
+    ("#tag", "\"#tag\"", "#tag"),
+    ("a#b", "\"a#b\"", "a#b"),
+    ("!", "\"!\"", "!"),
+    ("*.txt", "\"*.txt\"", "*.txt"),
+    ("[a]", "\"[a]\"", "[a]"),
+    ("{a,b}", "\"{a,b}\"", "{a,b}"),
+    ("a;b", "\"a;b\"", "a;b"),
+    ("a|b", "\"a|b\"", "a|b"),
+    ("a&b", "\"a&b\"", "a&b"),
+    ("(x)", "\"(x)\"", "(x)"),
+    ("<in>", "\"<in>\"", "<in>"),
+    ("semi;colon", "\"semi;colon\"", "semi;colon"),
+    ("tab\there", "\"tab\there\"", "\"tab\there\""),
+    ("line\nbreak", "\"line\nbreak\"", "\"line\nbreak\""),
+    ("cr\rlf", "\"cr\rlf\"", "cr\rlf"),
+    ("nul\0byte", "\"nul\0byte\"", "nul\0byte"),
+    ("\u{1B}[31m", "\"\u{1B}[31m\"", "\u{1B}[31m"),
+    ("\u{7F}", "\"\u{7F}\"", "\u{7F}"),
+    ("caf\u{E9}", "\"caf\u{E9}\"", "caf\u{E9}"),
+    ("a\u{200B}b", "\"a\u{200B}b\"", "a\u{200B}b"),
+    ("\u{2000}", "\"\u{2000}\"", "\u{2000}"),
+    ("a\u{85}b", "\"a\u{85}b\"", "a\u{85}b"),
+    ("it\u{2019}s", "\"it\u{2019}s\"", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "\"x\u{201C}y\u{201D}\"", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "\"user\u{202E}txt.exe\"", "user\u{202E}txt.exe"),
+    ("a\u{2066}b\u{2069}c", "\"a\u{2066}b\u{2069}c\"", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "\"smile\u{1F600}\"", "smile\u{1F600}"),
+    ("a\u{301}", "\"a\u{301}\"", "a\u{301}"),
+    ("a/b/c/", "\"a/b/c/\"", "a/b/c/"),
+    ("/mnt/my-disk", "\"/mnt/my-disk\"", "/mnt/my-disk"),
+    ("<script>", "\"<script>\"", "<script>"),
+    ("&amp;", "\"&amp;\"", "&amp;"),
+    ("${x}", "\"${x}\"", "${x}"),
+    ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+    ("trailing\\", "\"trailing\\\\\"", "trailing\\"),
+];
+#[cfg(feature = "sql")]
+#[rustfmt::skip]
+const SQL: &[(&str, &str, &str)] = &[
+    ("", "''", "''"),
+    ("foo", "'foo'", "'foo'"),
+    ("foo bar", "'foo bar'", "'foo bar'"),
+    ("foo/bar.baz", "'foo/bar.baz'", "'foo/bar.baz'"),
+    ("-x", "'-x'", "'-x'"),
+    ("--%", "'--%'", "'--%'"),
+    ("can't", "'can''t'", "'can''t'"),
+    ("\"quoted\"", "'\"quoted\"'", "'\"quoted\"'"),
+    ("a\"b'c", "'a\"b''c'", "'a\"b''c'"),
+    ("$HOME", "'$HOME'", "'$HOME'"),
+    ("`cmd`", "'`cmd`'", "'`cmd`'"),
+    ("a\\b", "'a\\b'", "'a\\b'"),
+    ("C:\\Program Files", "'C:\\Program Files'", "'C:\\Program Files'"),
+    ("100%", "'100%'", "'100%'"),
+    ("a=b", "'a=b'", "'a=b'"),
+    ("~user", "'~user'", "'~user'"),
+    ("#tag", "'#tag'", "'#tag'"),
+    ("a#b", "'a#b'", "'a#b'"),
+    ("!", "'!'", "'!'"),
+    ("*.txt", "'*.txt'", "'*.txt'"),
+    ("[a]", "'[a]'", "'[a]'"),
+    ("{a,b}", "'{a,b}'", "'{a,b}'"),
+    ("a;b", "'a;b'", "'a;b'"),
+    ("a|b", "'a|b'", "'a|b'"),
+    ("a&b", "'a&b'", "'a&b'"),
+    ("(x)", "'(x)'", "'(x)'"),
+    ("<in>", "'<in>'", "'<in>'"),
+    ("semi;colon", "'semi;colon'", "'semi;colon'"),
+    ("tab\there", "'tab' || x'09' || 'here'", "'tab' || x'09' || 'here'"),
+    ("line\nbreak", "'line' || x'0A' || 'break'", "'line' || x'0A' || 'break'"),
+    ("cr\rlf", "'cr' || x'0D' || 'lf'", "'cr' || x'0D' || 'lf'"),
+    ("nul\0byte", "'nul' || x'00' || 'byte'", "'nul' || x'00' || 'byte'"),
+    ("\u{1B}[31m", "x'1B' || '[31m'", "x'1B' || '[31m'"),
+    ("\u{7F}", "x'7F'", "x'7F'"),
+    ("caf\u{E9}", "'caf\u{E9}'", "'caf\u{E9}'"),
+    ("a\u{200B}b", "'a\u{200B}b'", "'a\u{200B}b'"),
+    ("\u{2000}", "'\u{2000}'", "'\u{2000}'"),
+    ("a\u{85}b", "'a' || x'C285' || 'b'", "'a' || x'C285' || 'b'"),
+    ("it\u{2019}s", "'it\u{2019}s'", "'it\u{2019}s'"),
+    ("x\u{201C}y\u{201D}", "'x\u{201C}y\u{201D}'", "'x\u{201C}y\u{201D}'"),
+    ("user\u{202E}txt.exe", "'user' || x'E280AE' || 'txt.exe'", "'user' || x'E280AE' || 'txt.exe'"),
+    ("a\u{2066}b\u{2069}c", "'a' || x'E281A6' || 'b' || x'E281A9' || 'c'", "'a' || x'E281A6' || 'b' || x'E281A9' || 'c'"),
+    ("smile\u{1F600}", "'smile\u{1F600}'", "'smile\u{1F600}'"),
+    ("a\u{301}", "'a\u{301}'", "'a\u{301}'"),
+    ("a/b/c/", "'a/b/c/'", "'a/b/c/'"),
+    ("/mnt/my-disk", "'/mnt/my-disk'", "'/mnt/my-disk'"),
+    ("<script>", "'<script>'", "'<script>'"),
+    ("&amp;", "'&amp;'", "'&amp;'"),
+    ("${x}", "'${x}'", "'${x}'"),
+    ("a\\\"b", "'a\\\"b'", "'a\\\"b'"),
+    ("trailing\\", "'trailing\\'", "'trailing\\'"),
+];
+#[cfg(feature = "html")]
+#[rustfmt::skip]
+const HTML: &[(&str, &str, &str)] = &[
+    ("", "", ""),
+    ("foo", "foo", "foo"),
+    ("foo bar", "foo bar", "foo bar"),
+    ("foo/bar.baz", "foo/bar.baz", "foo/bar.baz"),
+    ("-x", "-x", "-x"),
+    ("--%", "--%", "--%"),
+    ("can't", "can&#39;t", "can&#39;t"),
+    ("\"quoted\"", "&quot;quoted&quot;", "&quot;quoted&quot;"),
+    ("a\"b'c", "a&quot;b&#39;c", "a&quot;b&#39;c"),
+    ("$HOME", "$HOME", "$HOME"),
+    ("`cmd`", "`cmd`", "`cmd`"),
+    ("a\\b", "a\\b", "a\\b"),
+    ("C:\\Program Files", "C:\\Program Files", "C:\\Program Files"),
+    ("100%", "100%", "100%"),
+    ("a=b", "a=b", "a=b"),
+    ("~user", "~user", "~user"),
+    ("#tag", "#tag", "#tag"),
+    ("a#b", "a#b", "a#b"),
+    ("!", "!", "!"),
+    ("*.txt", "*.txt", "*.txt"),
+    ("[a]", "[a]", "[a]"),
+    ("{a,b}", "{a,b}", "{a,b}"),
+    ("a;b", "a;b", "a;b"),
+    ("a|b", "a|b", "a|b"),
+    ("a&b", "a&amp;b", "a&amp;b"),
+    ("(x)", "(x)", "(x)"),
+    ("<in>", "&lt;in&gt;", "&lt;in&gt;"),
+    ("semi;colon", "semi;colon", "semi;colon"),
+    ("tab\there", "tab&#x9;here", "tab&#x9;here"),
+    ("line\nbreak", "line&#xA;break", "line&#xA;break"),
+    ("cr\rlf", "cr&#xD;lf", "cr&#xD;lf"),
+    ("nul\0byte", "nul&#x0;byte", "nul&#x0;byte"),
+    ("\u{1B}[31m", "&#x1B;[31m", "&#x1B;[31m"),
+    ("\u{7F}", "&#x7F;", "&#x7F;"),
+    ("caf\u{E9}", "caf\u{E9}", "caf\u{E9}"),
+    ("a\u{200B}b", "a\u{200B}b", "a\u{200B}b"),
+    ("\u{2000}", "\u{2000}", "\u{2000}"),
+    ("a\u{85}b", "a&#x85;b", "a&#x85;b"),
+    ("it\u{2019}s", "it\u{2019}s", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "user&#x202E;txt.exe", "user&#x202E;txt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "smile\u{1F600}", "smile\u{1F600}"),
+    ("a\u{301}", "a\u{301}", "a\u{301}"),
+    ("a/b/c/", "a/b/c/", "a/b/c/"),
+    ("/mnt/my-disk", "/mnt/my-disk", "/mnt/my-disk"),
+    ("<script>", "&lt;script&gt;", "&lt;script&gt;"),
+    ("&amp;", "&amp;amp;", "&amp;amp;"),
+    ("${x}", "${x}", "${x}"),
+    ("a\\\"b", "a\\&quot;b", "a\\&quot;b"),
+    ("trailing\\", "trailing\\", "trailing\\"),
+];
+#[cfg(feature = "systemd")]
+#[rustfmt::skip]
+const SYSTEMD_UNIT: &[(&str, &str, &str)] = &[
+    ("", "-", "-"),
+    ("foo", "foo", "foo"),
+    ("foo bar", "foo\\x20bar", "foo\\x20bar"),
+    ("foo/bar.baz", "foo-bar.baz", "foo-bar.baz"),
+    ("-x", "\\x2dx", "\\x2dx"),
+    ("--%", "\\x2d\\x2d\\x25", "\\x2d\\x2d\\x25"),
+    ("can't", "can\\x27t", "can\\x27t"),
+    ("\"quoted\"", "\\x22quoted\\x22", "\\x22quoted\\x22"),
+    ("a\"b'c", "a\\x22b\\x27c", "a\\x22b\\x27c"),
+    ("$HOME", "\\x24HOME", "\\x24HOME"),
+    ("`cmd`", "\\x60cmd\\x60", "\\x60cmd\\x60"),
+    ("a\\b", "a\\x5cb", "a\\x5cb"),
+    ("C:\\Program Files", "C:\\x5cProgram\\x20Files", "C:\\x5cProgram\\x20Files"),
+    ("100%", "100\\x25", "100\\x25"),
+    ("a=b", "a\\x3db", "a\\x3db"),
+    ("~user", "\\x7euser", "\\x7euser"),
+    ("#tag", "\\x23tag", "\\x23tag"),
+    ("a#b", "a\\x23b", "a\\x23b"),
+    ("!", "\\x21", "\\x21"),
+    ("*.txt", "\\x2a.txt", "\\x2a.txt"),
+    ("[a]", "\\x5ba\\x5d", "\\x5ba\\x5d"),
+    ("{a,b}", "\\x7ba\\x2cb\\x7d", "\\x7ba\\x2cb\\x7d"),
+    ("a;b", "a\\x3bb", "a\\x3bb"),
+    ("a|b", "a\\x7cb", "a\\x7cb"),
+    ("a&b", "a\\x26b", "a\\x26b"),
+    ("(x)", "\\x28x\\x29", "\\x28x\\x29"),
+    ("<in>", "\\x3cin\\x3e", "\\x3cin\\x3e"),
+    ("semi;colon", "semi\\x3bcolon", "semi\\x3bcolon"),
+    ("tab\there", "tab\\x09here", "tab\\x09here"),
+    ("line\nbreak", "line\\x0abreak", "line\\x0abreak"),
+    ("cr\rlf", "cr\\x0dlf", "cr\\x0dlf"),
+    ("nul\0byte", "nul\\x00byte", "nul\\x00byte"),
+    ("\u{1B}[31m", "\\x1b\\x5b31m", "\\x1b\\x5b31m"),
+    ("\u{7F}", "\\x7f", "\\x7f"),
+    ("caf\u{E9}", "caf\\xc3\\xa9", "caf\\xc3\\xa9"),
+    ("a\u{200B}b", "a\\xe2\\x80\\x8bb", "a\\xe2\\x80\\x8bb"),
+    ("\u{2000}", "\\xe2\\x80\\x80", "\\xe2\\x80\\x80"),
+    ("a\u{85}b", "a\\xc2\\x85b", "a\\xc2\\x85b"),
+    ("it\u{2019}s", "it\\xe2\\x80\\x99s", "it\\xe2\\x80\\x99s"),
+    ("x\u{201C}y\u{201D}", "x\\xe2\\x80\\x9cy\\xe2\\x80\\x9d", "x\\xe2\\x80\\x9cy\\xe2\\x80\\x9d"),
+    ("user\u{202E}txt.exe", "user\\xe2\\x80\\xaetxt.exe", "user\\xe2\\x80\\xaetxt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\\xe2\\x81\\xa6b\\xe2\\x81\\xa9c", "a\\xe2\\x81\\xa6b\\xe2\\x81\\xa9c"),
+    ("smile\u{1F600}", "smile\\xf0\\x9f\\x98\\x80", "smile\\xf0\\x9f\\x98\\x80"),
+    ("a\u{301}", "a\\xcc\\x81", "a\\xcc\\x81"),
+    ("a/b/c/", "a-b-c", "a-b-c"),
+    ("/mnt/my-disk", "mnt-my\\x2ddisk", "mnt-my\\x2ddisk"),
+    ("<script>", "\\x3cscript\\x3e", "\\x3cscript\\x3e"),
+    ("&amp;", "\\x26amp\\x3b", "\\x26amp\\x3b"),
+    ("${x}", "\\x24\\x7bx\\x7d", "\\x24\\x7bx\\x7d"),
+    ("a\\\"b", "a\\x5c\\x22b", "a\\x5c\\x22b"),
+    ("trailing\\", "trailing\\x5c", "trailing\\x5c"),
+];
+#[cfg(feature = "dotenv")]
+#[rustfmt::skip]
+const DOTENV: &[(&str, &str, &str)] = &[
+    ("", "''", "''"),
+    ("foo", "'foo'", "foo"),
+    ("foo bar", "'foo bar'", "'foo bar'"),
+    ("foo/bar.baz", "'foo/bar.baz'", "foo/bar.baz"),
+    ("-x", "'-x'", "-x"),
+    ("--%", "'--%'", "--%"),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "'\"quoted\"'", "'\"quoted\"'"),
+    ("a\"b'c", "\"a\\\"b'c\"", "\"a\\\"b'c\""),
+    ("$HOME", "'$HOME'", "'$HOME'"),
+    ("`cmd`", "'`cmd`'", "'`cmd`'"),
+    ("a\\b", "\"a\\\\b\"", "\"a\\\\b\""),
+    ("C:\\Program Files", "\"C:\\\\Program Files\"", "\"C:\\\\Program Files\""),
+    ("100%", "'100%'", "100%"),
+    ("a=b", "'a=b'", "'a=b'"),
+    ("~user", "'~user'", "'~user'"),
+    ("#tag", "'#tag'", "'#tag'"),
+    ("a#b", "'a#b'", "'a#b'"),
+    ("!", "'!'", "'!'"),
+    ("*.txt", "'*.txt'", "'*.txt'"),
+    ("[a]", "'[a]'", "'[a]'"),
+    ("{a,b}", "'{a,b}'", "'{a,b}'"),
+    ("a;b", "'a;b'", "'a;b'"),
+    ("a|b", "'a|b'", "'a|b'"),
+    ("a&b", "'a&b'", "'a&b'"),
+    ("(x)", "'(x)'", "'(x)'"),
+    ("<in>", "'<in>'", "'<in>'"),
+    ("semi;colon", "'semi;colon'", "'semi;colon'"),
+    ("tab\there", "\"tab\\there\"", "\"tab\\there\""),
+    ("line\nbreak", "\"line\\nbreak\"", "\"line\\nbreak\""),
+    ("cr\rlf", "\"cr\\rlf\"", "\"cr\\rlf\""),
+    ("nul\0byte", "\"nul\0byte\"", "\"nul\0byte\""),
+    ("\u{1B}[31m", "\"\u{1B}[31m\"", "\"\u{1B}[31m\""),
+    ("\u{7F}", "\"\u{7F}\"", "\"\u{7F}\""),
+    ("caf\u{E9}", "'caf\u{E9}'", "'caf\u{E9}'"),
+    ("a\u{200B}b", "'a\u{200B}b'", "'a\u{200B}b'"),
+    ("\u{2000}", "'\u{2000}'", "'\u{2000}'"),
+    ("a\u{85}b", "'a\u{85}b'", "'a\u{85}b'"),
+    ("it\u{2019}s", "'it\u{2019}s'", "'it\u{2019}s'"),
+    ("x\u{201C}y\u{201D}", "'x\u{201C}y\u{201D}'", "'x\u{201C}y\u{201D}'"),
+    ("user\u{202E}txt.exe", "'user\u{202E}txt.exe'", "'user\u{202E}txt.exe'"),
+    ("a\u{2066}b\u{2069}c", "'a\u{2066}b\u{2069}c'", "'a\u{2066}b\u{2069}c'"),
+    ("smile\u{1F600}", "'smile\u{1F600}'", "'smile\u{1F600}'"),
+    ("a\u{301}", "'a\u{301}'", "'a\u{301}'"),
+    ("a/b/c/", "'a/b/c/'", "a/b/c/"),
+    ("/mnt/my-disk", "'/mnt/my-disk'", "/mnt/my-disk"),
+    ("<script>", "'<script>'", "'<script>'"),
+    ("&amp;", "'&amp;'", "'&amp;'"),
+    ("${x}", "'${x}'", "'${x}'"),
+    ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+    ("trailing\\", "\"trailing\\\\\"", "\"trailing\\\\\""),
+];
+#[cfg(feature = "dockerfile")]
+#[rustfmt::skip]
+const DOCKERFILE: &[(&str, &str, &str)] = &[
+    ("", "''", "''"),
+    ("foo", "'foo'", "foo"),
+    ("foo bar", "'foo bar'", "'foo bar'"),
+    ("foo/bar.baz", "'foo/bar.baz'", "foo/bar.baz"),
+    ("-x", "'-x'", "-x"),
+    ("--%", "'--%'", "--%"),
+    ("can't", "'can'\\''t'", "'can'\\''t'"),
+    ("\"quoted\"", "'\"quoted\"'", "'\"quoted\"'"),
+    ("a\"b'c", "'a\"b'\\''c'", "'a\"b'\\''c'"),
+    ("$HOME", "'$HOME'", "'$HOME'"),
+    ("`cmd`", "'`cmd`'", "'`cmd`'"),
+    ("a\\b", "'a\\b'", "'a\\b'"),
+    ("C:\\Program Files", "'C:\\Program Files'", "'C:\\Program Files'"),
+    ("100%", "'100%'", "100%"),
+    ("a=b", "'a=b'", "a=b"),
+    ("~user", "'~user'", "'~user'"),
+    ("#tag", "'#tag'", "'#tag'"),
+    ("a#b", "'a#b'", "'a#b'"),
+    ("!", "'!'", "'!'"),
+    ("*.txt", "'*.txt'", "'*.txt'"),
+    ("[a]", "'[a]'", "'[a]'"),
+    ("{a,b}", "'{a,b}'", "'{a,b}'"),
+    ("a;b", "'a;b'", "'a;b'"),
+    ("a|b", "'a|b'", "'a|b'"),
+    ("a&b", "'a&b'", "'a&b'"),
+    ("(x)", "'(x)'", "'(x)'"),
+    ("<in>", "'<in>'", "'<in>'"),
+    ("semi;colon", "'semi;colon'", "'semi;colon'"),
+    ("tab\there", "\"$(printf 'tab\\011here')\"", "\"$(printf 'tab\\011here')\""),
+    ("line\nbreak", "\"$(printf 'line\\012break')\"", "\"$(printf 'line\\012break')\""),
+    ("cr\rlf", "\"$(printf 'cr\\015lf')\"", "\"$(printf 'cr\\015lf')\""),
+    ("nul\0byte", "\"$(printf 'nul\\000byte')\"", "\"$(printf 'nul\\000byte')\""),
+    ("\u{1B}[31m", "\"$(printf '\\033[31m')\"", "\"$(printf '\\033[31m')\""),
+    ("\u{7F}", "\"$(printf '\\177')\"", "\"$(printf '\\177')\""),
+    ("caf\u{E9}", "'caf\u{E9}'", "'caf\u{E9}'"),
+    ("a\u{200B}b", "'a\u{200B}b'", "'a\u{200B}b'"),
+    ("\u{2000}", "'\u{2000}'", "'\u{2000}'"),
+    ("a\u{85}b", "\"$(printf 'a\\302\\205b')\"", "\"$(printf 'a\\302\\205b')\""),
+    ("it\u{2019}s", "'it\u{2019}s'", "'it\u{2019}s'"),
+    ("x\u{201C}y\u{201D}", "'x\u{201C}y\u{201D}'", "'x\u{201C}y\u{201D}'"),
+    ("user\u{202E}txt.exe", "\"$(printf 'user\\342\\200\\256txt.exe')\"", "\"$(printf 'user\\342\\200\\256txt.exe')\""),
+    ("a\u{2066}b\u{2069}c", "\"$(printf 'a\\342\\201\\246b\\342\\201\\251c')\"", "\"$(printf 'a\\342\\201\\246b\\342\\201\\251c')\""),
+    ("smile\u{1F600}", "'smile\u{1F600}'", "'smile\u{1F600}'"),
+    ("a\u{301}", "'a\u{301}'", "'a\u{301}'"),
+    ("a/b/c/", "'a/b/c/'", "a/b/c/"),
+    ("/mnt/my-disk", "'/mnt/my-disk'", "/mnt/my-disk"),
+    ("<script>", "'<script>'", "'<script>'"),
+    ("&amp;", "'&amp;'", "'&amp;'"),
+    ("${x}", "'${x}'", "'${x}'"),
+    ("a\\\"b", "'a\\\"b'", "'a\\\"b'"),
+    ("trailing\\", "'trailing\\'", "'trailing\\'"),
+];
+#[cfg(feature = "cmake")]
+#[rustfmt::skip]
+const CMAKE: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "\"foo\"", "foo"),
+    ("foo bar", "\"foo bar\"", "\"foo bar\""),
+    ("foo/bar.baz", "\"foo/bar.baz\"", "foo/bar.baz"),
+    ("-x", "\"-x\"", "-x"),
+    ("--%", "\"--%\"", "--%"),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "\"\\\"quoted\\\"\"", "\"\\\"quoted\\\"\""),
+    ("a\"b'c", "\"a\\\"b'c\"", "\"a\\\"b'c\""),
+    ("$HOME", "\"\\$HOME\"", "\"\\$HOME\""),
+    ("`cmd`", "\"`cmd`\"", "\"`cmd`\""),
+    ("a\\b", "\"a\\\\b\"", "\"a\\\\b\""),
+    ("C:\\Program Files", "\"C:\\\\Program Files\"", "\"C:\\\\Program Files\""),
+    ("100%", "\"100%\"", "100%"),
+    ("a=b", "\"a=b\"", "a=b"),
+    ("~user", "\"~user\"", "\"~user\""),
+    ("#tag", "\"#tag\"", "\"#tag\""),
+    ("a#b", "\"a#b\"", "\"a#b\""),
+    ("!", "\"!\"", "\"!\""),
+    ("*.txt", "\"*.txt\"", "\"*.txt\""),
+    ("[a]", "\"[a]\"", "\"[a]\""),
+    ("{a,b}", "\"{a,b}\"", "\"{a,b}\""),
+    ("a;b", "\"a;b\"", "\"a;b\""),
+    ("a|b", "\"a|b\"", "\"a|b\""),
+    ("a&b", "\"a&b\"", "\"a&b\""),
+    ("(x)", "\"(x)\"", "\"(x)\""),
+    ("<in>", "\"<in>\"", "\"<in>\""),
+    ("semi;colon", "\"semi;colon\"", "\"semi;colon\""),
+    ("tab\there", "\"tab\\there\"", "\"tab\\there\""),
+    ("line\nbreak", "\"line\\nbreak\"", "\"line\\nbreak\""),
+    ("cr\rlf", "\"cr\\rlf\"", "\"cr\\rlf\""),
+    ("nul\0byte", "\"nul\0byte\"", "\"nul\0byte\""),
+    ("\u{1B}[31m", "\"\u{1B}[31m\"", "\"\u{1B}[31m\""),
+    ("\u{7F}", "\"\u{7F}\"", "\"\u{7F}\""),
+    ("caf\u{E9}", "\"caf\u{E9}\"", "\"caf\u{E9}\""),
+    ("a\u{200B}b", "\"a\u{200B}b\"", "\"a\u{200B}b\""),
+    ("\u{2000}", "\"\u{2000}\"", "\"\u{2000}\""),
+    ("a\u{85}b", "\"a\u{85}b\"", "\"a\u{85}b\""),
+    ("it\u{2019}s", "\"it\u{2019}s\"", "\"it\u{2019}s\""),
+    ("x\u{201C}y\u{201D}", "\"x\u{201C}y\u{201D}\"", "\"x\u{201C}y\u{201D}\""),
+    ("user\u{202E}txt.exe", "\"user\u{202E}txt.exe\"", "\"user\u{202E}txt.exe\""),
+    ("a\u{2066}b\u{2069}c", "\"a\u{2066}b\u{2069}c\"", "\"a\u{2066}b\u{2069}c\""),
+    ("smile\u{1F600}", "\"smile\u{1F600}\"", "\"smile\u{1F600}\""),
+    ("a\u{301}", "\"a\u{301}\"", "\"a\u{301}\""),
+    ("a/b/c/", "\"a/b/c/\"", "a/b/c/"),
+    ("/mnt/my-disk", "\"/mnt/my-disk\"", "/mnt/my-disk"),
+    ("<script>", "\"<script>\"", "\"<script>\""),
+    ("&amp;", "\"&amp;\"", "\"&amp;\""),
+    ("${x}", "\"\\${x}\"", "\"\\${x}\""),
+    ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+    ("trailing\\", "\"trailing\\\\\"", "\"trailing\\\\\""),
+];
+#[cfg(feature = "reg")]
+#[rustfmt::skip]
+const REG: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "\"foo\"", "\"foo\""),
+    ("foo bar", "\"foo bar\"", "\"foo bar\""),
+    ("foo/bar.baz", "\"foo/bar.baz\"", "\"foo/bar.baz\""),
+    ("-x", "\"-x\"", "\"-x\""),
+    ("--%", "\"--%\"", "\"--%\""),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "\"\\\"quoted\\\"\"", "\"\\\"quoted\\\"\""),
+    ("a\"b'c", "\"a\\\"b'c\"", "\"a\\\"b'c\""),
+    ("$HOME", "\"$HOME\"", "\"$HOME\""),
+    ("`cmd`", "\"`cmd`\"", "\"`cmd`\""),
+    ("a\\b", "\"a\\\\b\"", "\"a\\\\b\""),
+    ("C:\\Program Files", "\"C:\\\\Program Files\"", "\"C:\\\\Program Files\""),
+    ("100%", "\"100%\"", "\"100%\""),
+    ("a=b", "\"a=b\"", "\"a=b\""),
+    ("~user", "\"~user\"", "\"~user\""),
+    ("#tag", "\"#tag\"", "\"#tag\""),
+    ("a#b", "\"a#b\"", "\"a#b\""),
+    ("!", "\"!\"", "\"!\""),
+    ("*.txt", "\"*.txt\"", "\"*.txt\""),
+    ("[a]", "\"[a]\"", "\"[a]\""),
+    ("{a,b}", "\"{a,b}\"", "\"{a,b}\""),
+    ("a;b", "\"a;b\"", "\"a;b\""),
+    ("a|b", "\"a|b\"", "\"a|b\""),
+    ("a&b", "\"a&b\"", "\"a&b\""),
+    ("(x)", "\"(x)\"", "\"(x)\""),
+    ("<in>", "\"<in>\"", "\"<in>\""),
+    ("semi;colon", "\"semi;colon\"", "\"semi;colon\""),
+    ("tab\there", "hex(1):74,00,61,00,62,00,09,00,68,00,65,00,72,00,65,00,00,00", "hex(1):74,00,61,00,62,00,09,00,68,00,65,00,72,00,65,00,00,00"),
+    ("line\nbreak", "hex(1):6c,00,69,00,6e,00,65,00,0a,00,62,00,72,00,65,00,61,00,6b,00,00,00", "hex(1):6c,00,69,00,6e,00,65,00,0a,00,62,00,72,00,65,00,61,00,6b,00,00,00"),
+    ("cr\rlf", "hex(1):63,00,72,00,0d,00,6c,00,66,00,00,00", "hex(1):63,00,72,00,0d,00,6c,00,66,00,00,00"),
+    ("nul\0byte", "hex(1):6e,00,75,00,6c,00,00,00,62,00,79,00,74,00,65,00,00,00", "hex(1):6e,00,75,00,6c,00,00,00,62,00,79,00,74,00,65,00,00,00"),
+    ("\u{1B}[31m", "hex(1):1b,00,5b,00,33,00,31,00,6d,00,00,00", "hex(1):1b,00,5b,00,33,00,31,00,6d,00,00,00"),
+    ("\u{7F}", "hex(1):7f,00,00,00", "hex(1):7f,00,00,00"),
+    ("caf\u{E9}", "\"caf\u{E9}\"", "\"caf\u{E9}\""),
+    ("a\u{200B}b", "\"a\u{200B}b\"", "\"a\u{200B}b\""),
+    ("\u{2000}", "\"\u{2000}\"", "\"\u{2000}\""),
+    ("a\u{85}b", "hex(1):61,00,85,00,62,00,00,00", "hex(1):61,00,85,00,62,00,00,00"),
+    ("it\u{2019}s", "\"it\u{2019}s\"", "\"it\u{2019}s\""),
+    ("x\u{201C}y\u{201D}", "\"x\u{201C}y\u{201D}\"", "\"x\u{201C}y\u{201D}\""),
+    ("user\u{202E}txt.exe", "hex(1):75,00,73,00,65,00,72,00,2e,20,74,00,78,00,74,00,2e,00,65,00,78,00,65,00,00,00", "hex(1):75,00,73,00,65,00,72,00,2e,20,74,00,78,00,74,00,2e,00,65,00,78,00,65,00,00,00"),
+    ("a\u{2066}b\u{2069}c", "hex(1):61,00,66,20,62,00,69,20,63,00,00,00", "hex(1):61,00,66,20,62,00,69,20,63,00,00,00"),
+    ("smile\u{1F600}", "\"smile\u{1F600}\"", "\"smile\u{1F600}\""),
+    ("a\u{301}", "\"a\u{301}\"", "\"a\u{301}\""),
+    ("a/b/c/", "\"a/b/c/\"", "\"a/b/c/\""),
+    ("/mnt/my-disk", "\"/mnt/my-disk\"", "\"/mnt/my-disk\""),
+    ("<script>", "\"<script>\"", "\"<script>\""),
+    ("&amp;", "\"&amp;\"", "\"&amp;\""),
+    ("${x}", "\"${x}\"", "\"${x}\""),
+    ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+    ("trailing\\", "\"trailing\\\\\"", "\"trailing\\\\\""),
+];
+#[cfg(feature = "sudoers")]
+#[rustfmt::skip]
+const SUDOERS: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "foo", "foo"),
+    ("foo bar", "foo\\ bar", "foo\\ bar"),
+    ("foo/bar.baz", "foo/bar.baz", "foo/bar.baz"),
+    ("-x", "-x", "-x"),
+    ("--%", "--%", "--%"),
+    ("can't", "can't", "can't"),
+    ("\"quoted\"", "\"quoted\"", "\"quoted\""),
+    ("a\"b'c", "a\"b'c", "a\"b'c"),
+    ("$HOME", "$HOME", "$HOME"),
+    ("`cmd`", "`cmd`", "`cmd`"),
+    ("a\\b", "a\\\\b", "a\\\\b"),
+    ("C:\\Program Files", "C\\:\\\\Program\\ Files", "C\\:\\\\Program\\ Files"),
+    ("100%", "100%", "100%"),
+    ("a=b", "a\\=b", "a\\=b"),
+    ("~user", "~user", "~user"),
+    ("#tag", "\\#tag", "\\#tag"),
+    ("a#b", "a\\#b", "a\\#b"),
+    ("!", "!", "!"),
+    ("*.txt", "\\*.txt", "\\*.txt"),
+    ("[a]", "\\[a\\]", "\\[a\\]"),
+    ("{a,b}", "{a\\,b}", "{a\\,b}"),
+    ("a;b", "a;b", "a;b"),
+    ("a|b", "a|b", "a|b"),
+    ("a&b", "a&b", "a&b"),
+    ("(x)", "(x)", "(x)"),
+    ("<in>", "<in>", "<in>"),
+    ("semi;colon", "semi;colon", "semi;colon"),
+    ("tab\there", "tab\\\there", "tab\\\there"),
+    ("line\nbreak", "line\u{FFFD}break", "line\u{FFFD}break"),
+    ("cr\rlf", "cr\u{FFFD}lf", "cr\u{FFFD}lf"),
+    ("nul\0byte", "nul\u{FFFD}byte", "nul\u{FFFD}byte"),
+    ("\u{1B}[31m", "\u{FFFD}\\[31m", "\u{FFFD}\\[31m"),
+    ("\u{7F}", "\u{FFFD}", "\u{FFFD}"),
+    ("caf\u{E9}", "caf\u{E9}", "caf\u{E9}"),
+    ("a\u{200B}b", "a\u{200B}b", "a\u{200B}b"),
+    ("\u{2000}", "\u{2000}", "\u{2000}"),
+    ("a\u{85}b", "a\u{FFFD}b", "a\u{FFFD}b"),
+    ("it\u{2019}s", "it\u{2019}s", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "user\u{202E}txt.exe", "user\u{202E}txt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "smile\u{1F600}", "smile\u{1F600}"),
+    ("a\u{301}", "a\u{301}", "a\u{301}"),
+    ("a/b/c/", "a/b/c/", "a/b/c/"),
+    ("/mnt/my-disk", "/mnt/my-disk", "/mnt/my-disk"),
+    ("<script>", "<script>", "<script>"),
+    ("&amp;", "&amp;", "&amp;"),
+    ("${x}", "${x}", "${x}"),
+    ("a\\\"b", "a\\\\\"b", "a\\\\\"b"),
+    ("trailing\\", "trailing\\\\", "trailing\\\\"),
+];
+#[cfg(feature = "git")]
+#[rustfmt::skip]
+const GIT_IGNORE: &[(&str, &str, &str)] = &[
+    ("", "", ""),
+    ("foo", "foo", "foo"),
+    ("foo bar", "foo\\ bar", "foo\\ bar"),
+    ("foo/bar.baz", "foo/bar.baz", "foo/bar.baz"),
+    ("-x", "-x", "-x"),
+    ("--%", "--%", "--%"),
+    ("can't", "can't", "can't"),
+    ("\"quoted\"", "\"quoted\"", "\"quoted\""),
+    ("a\"b'c", "a\"b'c", "a\"b'c"),
+    ("$HOME", "$HOME", "$HOME"),
+    ("`cmd`", "`cmd`", "`cmd`"),
+    ("a\\b", "a\\\\b", "a\\\\b"),
+    ("C:\\Program Files", "C:\\\\Program\\ Files", "C:\\\\Program\\ Files"),
+    ("100%", "100%", "100%"),
+    ("a=b", "a=b", "a=b"),
+    ("~user", "~user", "~user"),
+    ("#tag", "\\#tag", "\\#tag"),
+    ("a#b", "a#b", "a#b"),
+    ("!", "\\!", "\\!"),
+    ("*.txt", "\\*.txt", "\\*.txt"),
+    ("[a]", "\\[a]", "\\[a]"),
+    ("{a,b}", "{a,b}", "{a,b}"),
+    ("a;b", "a;b", "a;b"),
+    ("a|b", "a|b", "a|b"),
+    ("a&b", "a&b", "a&b"),
+    ("(x)", "(x)", "(x)"),
+    ("<in>", "<in>", "<in>"),
+    ("semi;colon", "semi;colon", "semi;colon"),
+    ("tab\there", "tab\\\there", "tab\\\there"),
+    ("line\nbreak", "line\u{FFFD}break", "line\u{FFFD}break"),
+    ("cr\rlf", "cr\u{FFFD}lf", "cr\u{FFFD}lf"),
+    ("nul\0byte", "nul\u{FFFD}byte", "nul\u{FFFD}byte"),
+    ("\u{1B}[31m", "\u{FFFD}\\[31m", "\u{FFFD}\\[31m"),
+    ("\u{7F}", "\u{FFFD}", "\u{FFFD}"),
+    ("caf\u{E9}", "caf\u{E9}", "caf\u{E9}"),
+    ("a\u{200B}b", "a\u{200B}b", "a\u{200B}b"),
+    ("\u{2000}", "\u{2000}", "\u{2000}"),
+    ("a\u{85}b", "a\u{FFFD}b", "a\u{FFFD}b"),
+    ("it\u{2019}s", "it\u{2019}s", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "user\u{202E}txt.exe", "user\u{202E}txt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "smile\u{1F600}", "smile\u{1F600}"),
+    ("a\u{301}", "a\u{301}", "a\u{301}"),
+    ("a/b/c/", "a/b/c/", "a/b/c/"),
+    ("/mnt/my-disk", "/mnt/my-disk", "/mnt/my-disk"),
+    ("<script>", "<script>", "<script>"),
+    ("&amp;", "&amp;", "&amp;"),
+    ("${x}", "${x}", "${x}"),
+    ("a\\\"b", "a\\\\\"b", "a\\\\\"b"),
+    ("trailing\\", "trailing\\\\", "trailing\\\\"),
+];
+#[cfg(feature = "git")]
+#[rustfmt::skip]
+const GIT_ATTRIBUTES: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "foo", "foo"),
+    ("foo bar", "\"foo bar\"", "\"foo bar\""),
+    ("foo/bar.baz", "foo/bar.baz", "foo/bar.baz"),
+    ("-x", "-x", "-x"),
+    ("--%", "--%", "--%"),
+    ("can't", "can't", "can't"),
+    ("\"quoted\"", "\"\\\"quoted\\\"\"", "\"\\\"quoted\\\"\""),
+    ("a\"b'c", "a\"b'c", "a\"b'c"),
+    ("$HOME", "$HOME", "$HOME"),
+    ("`cmd`", "`cmd`", "`cmd`"),
+    ("a\\b", "a\\\\b", "a\\\\b"),
+    ("C:\\Program Files", "\"C:\\\\\\\\Program Files\"", "\"C:\\\\\\\\Program Files\""),
+    ("100%", "100%", "100%"),
+    ("a=b", "a=b", "a=b"),
+    ("~user", "~user", "~user"),
+    ("#tag", "\\#tag", "\\#tag"),
+    ("a#b", "a#b", "a#b"),
+    ("!", "\\!", "\\!"),
+    ("*.txt", "\\*.txt", "\\*.txt"),
+    ("[a]", "\\[a]", "\\[a]"),
+    ("{a,b}", "{a,b}", "{a,b}"),
+    ("a;b", "a;b", "a;b"),
+    ("a|b", "a|b", "a|b"),
+    ("a&b", "a&b", "a&b"),
+    ("(x)", "(x)", "(x)"),
+    ("<in>", "<in>", "<in>"),
+    ("semi;colon", "semi;colon", "semi;colon"),
+    ("tab\there", "\"tab\\there\"", "\"tab\\there\""),
+    ("line\nbreak", "\"line\\nbreak\"", "\"line\\nbreak\""),
+    ("cr\rlf", "\"cr\\rlf\"", "\"cr\\rlf\""),
+    ("nul\0byte", "\"nul\u{FFFD}byte\"", "\"nul\u{FFFD}byte\""),
+    ("\u{1B}[31m", "\"\\033\\\\[31m\"", "\"\\033\\\\[31m\""),
+    ("\u{7F}", "\"\\177\"", "\"\\177\""),
+    ("caf\u{E9}", "caf\u{E9}", "caf\u{E9}"),
+    ("a\u{200B}b", "a\u{200B}b", "a\u{200B}b"),
+    ("\u{2000}", "\u{2000}", "\u{2000}"),
+    ("a\u{85}b", "\"a\\302\\205b\"", "\"a\\302\\205b\""),
+    ("it\u{2019}s", "it\u{2019}s", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "user\u{202E}txt.exe", "user\u{202E}txt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "smile\u{1F600}", "smile\u{1F600}"),
+    ("a\u{301}", "a\u{301}", "a\u{301}"),
+    ("a/b/c/", "a/b/c/", "a/b/c/"),
+    ("/mnt/my-disk", "/mnt/my-disk", "/mnt/my-disk"),
+    ("<script>", "<script>", "<script>"),
+    ("&amp;", "&amp;", "&amp;"),
+    ("${x}", "${x}", "${x}"),
+    ("a\\\"b", "a\\\\\"b", "a\\\\\"b"),
+    ("trailing\\", "trailing\\\\", "trailing\\\\"),
+];
+#[cfg(feature = "git")]
+#[rustfmt::skip]
+const GIT_CONFIG: &[(&str, &str, &str)] = &[
+    ("", "\"\"", "\"\""),
+    ("foo", "\"foo\"", "foo"),
+    ("foo bar", "\"foo bar\"", "\"foo bar\""),
+    ("foo/bar.baz", "\"foo/bar.baz\"", "foo/bar.baz"),
+    ("-x", "\"-x\"", "-x"),
+    ("--%", "\"--%\"", "--%"),
+    ("can't", "\"can't\"", "\"can't\""),
+    ("\"quoted\"", "\"\\\"quoted\\\"\"", "\"\\\"quoted\\\"\""),
+    ("a\"b'c", "\"a\\\"b'c\"", "\"a\\\"b'c\""),
+    ("$HOME", "\"$HOME\"", "\"$HOME\""),
+    ("`cmd`", "\"`cmd`\"", "\"`cmd`\""),
+    ("a\\b", "\"a\\\\b\"", "\"a\\\\b\""),
+    ("C:\\Program Files", "\"C:\\\\Program Files\"", "\"C:\\\\Program Files\""),
+    ("100%", "\"100%\"", "100%"),
+    ("a=b", "\"a=b\"", "a=b"),
+    ("~user", "\"~user\"", "~user"),
+    ("#tag", "\"#tag\"", "\"#tag\""),
+    ("a#b", "\"a#b\"", "\"a#b\""),
+    ("!", "\"!\"", "\"!\""),
+    ("*.txt", "\"*.txt\"", "\"*.txt\""),
+    ("[a]", "\"[a]\"", "\"[a]\""),
+    ("{a,b}", "\"{a,b}\"", "\"{a,b}\""),
+    ("a;b", "\"a;b\"", "\"a;b\""),
+    ("a|b", "\"a|b\"", "\"a|b\""),
+    ("a&b", "\"a&b\"", "\"a&b\""),
+    ("(x)", "\"(x)\"", "\"(x)\""),
+    ("<in>", "\"<in>\"", "\"<in>\""),
+    ("semi;colon", "\"semi;colon\"", "\"semi;colon\""),
+    ("tab\there", "\"tab\\there\"", "\"tab\\there\""),
+    ("line\nbreak", "\"line\\nbreak\"", "\"line\\nbreak\""),
+    ("cr\rlf", "\"cr\u{FFFD}lf\"", "\"cr\u{FFFD}lf\""),
+    ("nul\0byte", "\"nul\u{FFFD}byte\"", "\"nul\u{FFFD}byte\""),
+    ("\u{1B}[31m", "\"\u{FFFD}[31m\"", "\"\u{FFFD}[31m\""),
+    ("\u{7F}", "\"\u{FFFD}\"", "\"\u{FFFD}\""),
+    ("caf\u{E9}", "\"caf\u{E9}\"", "\"caf\u{E9}\""),
+    ("a\u{200B}b", "\"a\u{200B}b\"", "\"a\u{200B}b\""),
+    ("\u{2000}", "\"\u{2000}\"", "\"\u{2000}\""),
+    ("a\u{85}b", "\"a\u{FFFD}b\"", "\"a\u{FFFD}b\""),
+    ("it\u{2019}s", "\"it\u{2019}s\"", "\"it\u{2019}s\""),
+    ("x\u{201C}y\u{201D}", "\"x\u{201C}y\u{201D}\"", "\"x\u{201C}y\u{201D}\""),
+    ("user\u{202E}txt.exe", "\"user\u{202E}txt.exe\"", "\"user\u{202E}txt.exe\""),
+    ("a\u{2066}b\u{2069}c", "\"a\u{2066}b\u{2069}c\"", "\"a\u{2066}b\u{2069}c\""),
+    ("smile\u{1F600}", "\"smile\u{1F600}\"", "\"smile\u{1F600}\""),
+    ("a\u{301}", "\"a\u{301}\"", "\"a\u{301}\""),
+    ("a/b/c/", "\"a/b/c/\"", "a/b/c/"),
+    ("/mnt/my-disk", "\"/mnt/my-disk\"", "/mnt/my-disk"),
+    ("<script>", "\"<script>\"", "\"<script>\""),
+    ("&amp;", "\"&amp;\"", "\"&amp;\""),
+    ("${x}", "\"${x}\"", "\"${x}\""),
+    ("a\\\"b", "\"a\\\\\\\"b\"", "\"a\\\\\\\"b\""),
+    ("trailing\\", "\"trailing\\\\\"", "\"trailing\\\\\""),
+];
+
+#[cfg(feature = "sanitize")]
+#[rustfmt::skip]
+const SANITIZE: &[(&str, &str, &str)] = &[
+    ("", "", ""),
+    ("foo", "foo", "foo"),
+    ("foo bar", "foo bar", "foo bar"),
+    ("foo/bar.baz", "foo/bar.baz", "foo/bar.baz"),
+    ("-x", "-x", "-x"),
+    ("--%", "--%", "--%"),
+    ("can't", "can't", "can't"),
+    ("\"quoted\"", "\"quoted\"", "\"quoted\""),
+    ("a\"b'c", "a\"b'c", "a\"b'c"),
+    ("$HOME", "$HOME", "$HOME"),
+    ("`cmd`", "`cmd`", "`cmd`"),
+    ("a\\b", "a\\b", "a\\b"),
+    ("C:\\Program Files", "C:\\Program Files", "C:\\Program Files"),
+    ("100%", "100%", "100%"),
+    ("a=b", "a=b", "a=b"),
+    ("~user", "~user", "~user"),
+    ("#tag", "#tag", "#tag"),
+    ("a#b", "a#b", "a#b"),
+    ("!", "!", "!"),
+    ("*.txt", "*.txt", "*.txt"),
+    ("[a]", "[a]", "[a]"),
+    ("{a,b}", "{a,b}", "{a,b}"),
+    ("a;b", "a;b", "a;b"),
+    ("a|b", "a|b", "a|b"),
+    ("a&b", "a&b", "a&b"),
+    ("(x)", "(x)", "(x)"),
+    ("<in>", "<in>", "<in>"),
+    ("semi;colon", "semi;colon", "semi;colon"),
+    ("tab\there", "tab\\there", "tab\\there"),
+    ("line\nbreak", "line\\nbreak", "line\\nbreak"),
+    ("cr\rlf", "cr\\rlf", "cr\\rlf"),
+    ("nul\0byte", "nul\\x00byte", "nul\\x00byte"),
+    ("\u{1B}[31m", "\\x1B[31m", "\\x1B[31m"),
+    ("\u{7F}", "\\x7F", "\\x7F"),
+    ("caf\u{E9}", "caf\u{E9}", "caf\u{E9}"),
+    ("a\u{200B}b", "a\u{200B}b", "a\u{200B}b"),
+    ("\u{2000}", "\u{2000}", "\u{2000}"),
+    ("a\u{85}b", "a\\u{85}b", "a\\u{85}b"),
+    ("it\u{2019}s", "it\u{2019}s", "it\u{2019}s"),
+    ("x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}", "x\u{201C}y\u{201D}"),
+    ("user\u{202E}txt.exe", "user\\u{202E}txt.exe", "user\\u{202E}txt.exe"),
+    ("a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c", "a\u{2066}b\u{2069}c"),
+    ("smile\u{1F600}", "smile\u{1F600}", "smile\u{1F600}"),
+    ("a\u{301}", "a\u{301}", "a\u{301}"),
+    ("a/b/c/", "a/b/c/", "a/b/c/"),
+    ("/mnt/my-disk", "/mnt/my-disk", "/mnt/my-disk"),
+    ("<script>", "<script>", "<script>"),
+    ("&amp;", "&amp;", "&amp;"),
+    ("${x}", "${x}", "${x}"),
+    ("a\\\"b", "a\\\"b", "a\\\"b"),
+    ("trailing\\", "trailing\\", "trailing\\"),
+];
@@ -0,0 +1,213 @@
+//! [proptest](https://docs.rs/proptest) strategies for hostile file names.
+//!
+//! These build text out of ordinary pieces and the characters that have
+//! caused trouble for quoting over the years: bidi controls, unpaired
+//! surrogates, unusual whitespace, zero-width characters, terminal escape
+//! sequences, shell syntax and leading dashes.
+//!
+//! ```
+//! use os_display::testing::strategies;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // This would usually be a #[test].
+//!     fn no_control_characters(name in strategies::hostile_str()) {
+//!         # #[cfg(feature = "unix")] {
+//!         let quoted = os_display::Quoted::unix(&name).to_string();
+//!         prop_assert!(!quoted.chars().any(char::is_control));
+//!         # }
+//!     }
+//! }
+//!
+//! no_control_characters();
+//! ```
+//!
+//! # Optional
+//! This requires the `proptest` feature.
+
+use std::ffi::OsString;
+use std::string::String;
+use std::vec::Vec;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+/// Pieces of ordinary file names.
+const ORDINARY: &[&str] = &[
+    "a", "foo", "bar.txt", "x", "0", "_", ".", "..", "/", "my", "file", "é", "日本",
+];
+
+/// Bidi control characters, and right-to-left letters for them to act on.
+const BIDI: &[&str] = &[
+    "\u{202A}", "\u{202B}", "\u{202C}", "\u{202D}", "\u{202E}", "\u{2066}", "\u{2067}", "\u{2068}",
+    "\u{2069}", "\u{061C}", "\u{200E}", "\u{200F}", "א", "ب",
+];
+
+/// Whitespace other than a plain space, including the kinds that don't
+/// look like whitespace at all.
+const WHITESPACE: &[&str] = &[
+    " ", "\t", "\n", "\r", "\x0B", "\x0C", "\u{85}", "\u{A0}", "\u{1680}", "\u{2000}", "\u{2007}",
+    "\u{200A}", "\u{2028}", "\u{2029}", "\u{202F}", "\u{205F}", "\u{3000}", "\u{2800}",
+];
+
+/// Characters that might not show up at all.
+const INVISIBLE: &[&str] = &[
+    "\u{200B}",
+    "\u{200C}",
+    "\u{200D}",
+    "\u{2060}",
+    "\u{FEFF}",
+    "\u{AD}",
+    "\u{180E}",
+    "\u{E0041}",
+    "\u{FE0F}",
+    "\u{301}",
+];
+
+/// Control characters and terminal escape sequences.
+const CONTROL: &[&str] = &[
+    "\0",
+    "\x07",
+    "\x08",
+    "\x1B",
+    "\x7F",
+    "\u{80}",
+    "\u{9B}",
+    "\x1B[31m",
+    "\x1B[2J",
+    "\x1B]0;title\x07",
+    "\x1B]8;;https://example.com\x1B\\",
+    "\x1BP+q\x1B\\",
+    "\u{9D}0;x\u{9C}",
+];
+
+/// Shell syntax for bash, PowerShell and `cmd.exe`, and characters that
+/// look like it.
+const SYNTAX: &[&str] = &[
+    "'", "\"", "`", "\\", "$", "$(", "${", "*", "?", "[", "]", "{", "}", "~", "#", "!", "%", "^",
+    "&", "|", ";", "<", ">", "(", ")", "=", ",", "@(", "--%", "$?", "\u{2018}", "\u{2019}",
+    "\u{201C}", "\u{201D}", "\u{2013}", "\u{2014}", "\u{FF04}", "\u{FF07}",
+];
+
+/// Lone continuation bytes, truncated sequences, overlong encodings and
+/// encoded surrogates.
+const INVALID_UTF8: &[&[u8]] = &[
+    b"\x80",
+    b"\xBF",
+    b"\xC3",
+    b"\xE2\x80",
+    b"\xF0\x9F\x98",
+    b"\xC0\xAF",
+    b"\xED\xA0\x80",
+    b"\xF8\x88\x80\x80\x80",
+    b"\xFF",
+    b"\xFE",
+];
+
+fn pieces(table: &'static [&'static str]) -> impl Strategy<Value = String> {
+    select(table).prop_map(String::from)
+}
+
+/// Join ordinary pieces and hostile pieces, with a leading dash now and then.
+fn mixed(hostile: impl Strategy<Value = String>) -> impl Strategy<Value = String> {
+    (
+        any::<bool>(),
+        vec(prop_oneof![pieces(ORDINARY), hostile], 0..8),
+    )
+        .prop_map(|(dash, pieces)| {
+            let mut text = if dash {
+                String::from("-")
+            } else {
+                String::new()
+            };
+            text.extend(pieces);
+            text
+        })
+}
+
+/// Any of the hostile pieces mixed with ordinary text.
+pub fn hostile_str() -> impl Strategy<Value = String> {
+    mixed(prop_oneof![
+        pieces(BIDI),
+        pieces(WHITESPACE),
+        pieces(INVISIBLE),
+        pieces(CONTROL),
+        pieces(SYNTAX),
+    ])
+}
+
+/// Text full of bidi controls, nested, unbalanced or both, with
+/// right-to-left letters.
+pub fn bidi_bomb() -> impl Strategy<Value = String> {
+    mixed(pieces(BIDI))
+}
+
+/// Text with unusual whitespace, like non-breaking spaces and line
+/// separators.
+pub fn whitespace() -> impl Strategy<Value = String> {
+    mixed(pieces(WHITESPACE))
+}
+
+/// Text with zero-width and other invisible characters.
+pub fn invisible() -> impl Strategy<Value = String> {
+    mixed(pieces(INVISIBLE))
+}
+
+/// Text with control characters and terminal escape sequences.
+pub fn control() -> impl Strategy<Value = String> {
+    mixed(pieces(CONTROL))
+}
+
+/// Text that starts with one or two dashes, so that it looks like an option.
+pub fn leading_dash() -> impl Strategy<Value = String> {
+    (select(&["-", "--", "-\u{200B}-"][..]), hostile_str())
+        .prop_map(|(dash, rest)| String::from(dash) + &rest)
+}
+
+/// Bytes that are mostly but not always valid UTF-8, like a Unix file name.
+pub fn invalid_utf8() -> impl Strategy<Value = Vec<u8>> {
+    let invalid = select(INVALID_UTF8).prop_map(<[u8]>::to_vec);
+    vec(
+        prop_oneof![
+            2 => hostile_str().prop_map(String::into_bytes),
+            1 => invalid,
+        ],
+        0..4,
+    )
+    .prop_map(|chunks| chunks.concat())
+}
+
+/// UTF-16 that's mostly but not always valid, like a Windows file name.
+pub fn unpaired_surrogates() -> impl Strategy<Value = Vec<u16>> {
+    vec(
+        prop_oneof![
+            2 => hostile_str().prop_map(|text| text.encode_utf16().collect::<Vec<u16>>()),
+            1 => (0xD800_u16..=0xDFFF).prop_map(|unit| std::vec![unit]),
+        ],
+        0..4,
+    )
+    .prop_map(|chunks| chunks.concat())
+}
+
+/// A hostile `OsString` for the current platform, from [`invalid_utf8`] on
+/// Unix and from [`unpaired_surrogates`] on Windows. Elsewhere it's always
+/// valid unicode.
+pub fn hostile_os_string() -> impl Strategy<Value = OsString> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        invalid_utf8().prop_map(OsString::from_vec)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        unpaired_surrogates().prop_map(|units| OsString::from_wide(&units))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        hostile_str().prop_map(OsString::from)
+    }
+}
@@ -0,0 +1,190 @@
+//! Checking output against real shells, for integration tests.
+//!
+//! This is the harness that the crate's own fuzz targets use. It keeps a
+//! shell running in the background, feeds it one line at a time and reads
+//! back the argument that it ends up with.
+//!
+//! [`testing::golden_cases`](crate::testing::golden_cases) has the expected
+//! output for a set of tricky inputs, to compare against without running
+//! anything.
+//!
+//! ```no_run
+//! # #[cfg(feature = "unix")] {
+//! use os_display::{testkit::Shell, Quoted};
+//!
+//! let bash = Shell::bash();
+//! let name = b"my \xFF file\n";
+//! assert_eq!(bash.roundtrip(Quoted::unix_raw(name)), name);
+//! # }
+//! ```
+//!
+//! # Optional
+//! This requires the `testkit` feature.
+
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+/// Shells that don't support the syntax are best used for text that doesn't
+/// need it: dash, posh and yash don't have `$'...'`, and fish and csh treat
+/// backslashes inside single quotes differently.
+///
+/// A `Shell` can be shared between threads (for example in a static), but it
+/// only handles one line at a time.
+#[derive(Debug)]
+pub struct Shell {
+    child: Mutex<Child>,
+    raw: bool,
+}
+
+// Set-Variable is used because `$s = ...` sees a bare string as a command.
+const PWSH_SCRIPT: &str = r#"
+foreach($line in [System.IO.File]::ReadLines("/dev/stdin")) {
+    Invoke-Expression ("Set-Variable s {0}" -f $line)
+    "{0}`0" -f $s
+}
+"#;
+
+impl Shell {
+    /// Start a shell that reads commands from stdin and has a `printf`
+    /// builtin or command. It's sent `printf '%s\0\n' <argument>`.
+    ///
+    /// # Errors
+    /// If the shell can't be started.
+    pub fn new(cmd: &mut Command) -> io::Result<Self> {
+        let child = cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+        Ok(Shell {
+            child: Mutex::new(child),
+            raw: false,
+        })
+    }
+
+    /// Start a program that's sent each argument as it is, on its own line,
+    /// and that writes out the result followed by `"\0\n"`. This is for
+    /// shells that need a script to get there, like PowerShell.
+    ///
+    /// # Errors
+    /// If the program can't be started.
+    pub fn raw(cmd: &mut Command) -> io::Result<Self> {
+        let mut shell = Self::new(cmd)?;
+        shell.raw = true;
+        Ok(shell)
+    }
+
+    /// Start `bash`.
+    ///
+    /// # Panics
+    /// If it can't be started.
+    pub fn bash() -> Self {
+        Self::start("bash")
+    }
+
+    /// Start `zsh`.
+    ///
+    /// # Panics
+    /// If it can't be started.
+    pub fn zsh() -> Self {
+        Self::start("zsh")
+    }
+
+    /// Start `ksh`.
+    ///
+    /// # Panics
+    /// If it can't be started.
+    pub fn ksh() -> Self {
+        Self::start("ksh")
+    }
+
+    /// Start `dash`, which doesn't support `$'...'`.
+    ///
+    /// # Panics
+    /// If it can't be started.
+    pub fn dash() -> Self {
+        Self::start("dash")
+    }
+
+    /// Start PowerShell Core (`pwsh`), which passes each line to
+    /// `Invoke-Expression`.
+    ///
+    /// This reads from `/dev/stdin`, so it only works on Unix.
+    ///
+    /// # Panics
+    /// If it can't be started.
+    pub fn powershell() -> Self {
+        Self::raw(Command::new("pwsh").arg("-c").arg(PWSH_SCRIPT))
+            .unwrap_or_else(|err| panic!("couldn't start pwsh: {}", err))
+    }
+
+    fn start(name: &str) -> Self {
+        Self::new(&mut Command::new(name))
+            .unwrap_or_else(|err| panic!("couldn't start {}: {}", name, err))
+    }
+
+    /// Pass the output to the shell and return the bytes of the argument
+    /// that it stands for.
+    ///
+    /// The output can't contain a newline, since the shell is fed one line
+    /// at a time. Use [`Quoted::unix`](crate::Quoted::unix)'s default
+    /// `$'...'` escapes, which don't.
+    ///
+    /// # Panics
+    /// If the shell exits or talking to it fails, for example because the
+    /// output was a syntax error.
+    pub fn roundtrip(&self, output: impl Display) -> Vec<u8> {
+        let mut child = self.child.lock().unwrap_or_else(|err| err.into_inner());
+        let stdin = child.stdin.as_mut().unwrap();
+        let written = if self.raw {
+            writeln!(stdin, "{}", output)
+        } else {
+            // \0 as an unambiguous separator, \n in case there's line buffering
+            writeln!(stdin, "printf '%s\\0\\n' {}", output)
+        };
+        written.unwrap_or_else(|err| panic!("couldn't write to the shell: {}", err));
+
+        let mut result = Vec::new();
+        let mut pos = 0;
+        loop {
+            result.resize(pos + 8192, 0);
+            pos += read_uninterrupted(&mut child, &mut result[pos..]);
+            result.truncate(pos);
+            if result.contains(&0) {
+                if result.last() == Some(&0) {
+                    result.push(0);
+                    assert_eq!(read_uninterrupted(&mut child, &mut result[pos..]), 1);
+                }
+                assert!(
+                    result.ends_with(b"\0\n"),
+                    "unexpected output from the shell"
+                );
+                result.truncate(result.len() - 2);
+                return result;
+            }
+        }
+    }
+}
+
+fn read_uninterrupted(child: &mut Child, buf: &mut [u8]) -> usize {
+    let stdout = child.stdout.as_mut().unwrap();
+    loop {
+        match stdout.read(buf) {
+            Ok(0) => panic!("the shell exited"),
+            Ok(n) => return n,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => panic!("couldn't read from the shell: {}", err),
+        }
+    }
+}
+
+impl Drop for Shell {
+    /// Closing stdin tells the shell to exit.
+    fn drop(&mut self) {
+        let child = match self.child.get_mut() {
+            Ok(child) => child,
+            Err(err) => err.into_inner(),
+        };
+        child.stdin = None;
+        let _ = child.wait();
+    }
+}
@@ -1,33 +1,154 @@
-use core::fmt::{self, Formatter, Write};
-use core::str::from_utf8;
+use core::fmt::{self, Write};
 
-use unicode_width::UnicodeWidthChar;
+use crate::scan::{self, class};
+use crate::security::{self, Bidi};
+use crate::shell_chars::unix::{DOUBLE_UNSAFE, SPECIAL_SHELL_CHARS_START};
+use crate::sink::{Buf, Sink};
+use crate::tables;
+use crate::util::utf8_chunks;
+use crate::width;
+use crate::{
+    Fallback, Multiline, Options, Original, QuoteChar, QuoteContext, Quoting, TextEscapes,
+};
 
-/// Characters with special meaning outside quotes.
-/// https://pubs.opengroup.org/onlinepubs/9699919799/utilities/V3_chap02.html#tag_18_02
-/// I don't know why % is in there. GNU doesn't quote it either.
-/// zsh and fish have trouble with standalone {}.
-/// ^ was used for piping in old shells and GNU quotes it.
-const SPECIAL_SHELL_CHARS: &[u8] = b"|&;<>()$`\\\"'*?[]=^{} ";
+/// Whether a character from SPECIAL_SHELL_CHARS can go without quotes for
+/// Quoting::Minimal.
+/// = only matters in assignments and at the start in zsh, but bash also
+/// expands a ~ after it in arguments that look like assignments.
+/// A lone ] doesn't form a glob.
+/// Brace expansion needs both braces.
+fn is_minimal_plain(byte: u8, text: &str) -> bool {
+    match byte {
+        b'=' => !text.starts_with('=') && !text.contains('~'),
+        b'^' | b']' => true,
+        b'{' | b'}' => !(text.contains('{') && text.contains('}')),
+        _ => false,
+    }
+}
+
+/// Whether a character outside quotes means the text has to be quoted.
+fn is_special(byte: u8, text: &str, opts: &Options) -> bool {
+    if CLASS[byte as usize] & class::SPECIAL == 0 || opts.extra_safe.contains(byte) {
+        return false;
+    }
+    opts.quoting != Quoting::Minimal || !is_minimal_plain(byte, text)
+}
+
+/// The classes of each byte for the scan in write(), see [`scan::class`].
+/// A byte that's [`SPECIAL`](class::SPECIAL) but nothing else only matters
+/// until we know that we need quotes. Bytes without flags don't matter,
+/// except at the start of the string.
+#[rustfmt::skip]
+static CLASS: [u8; 256] = {
+    const __: u8 = 0;
+    const SP: u8 = class::SPECIAL;
+    const DU: u8 = class::SPECIAL | class::DOUBLE_UNSAFE;
+    const SQ: u8 = class::SPECIAL | class::SINGLE_QUOTE;
+    const DQ: u8 = class::SPECIAL | class::DOUBLE_UNSAFE | class::DOUBLE_QUOTE;
+    const CT: u8 = class::CONTROL;
+    const NA: u8 = class::NON_ASCII;
+    [
+        CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 00..0F
+        CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 10..1F
+        SP, __, DQ, __, DU, __, SP, SQ, SP, SP, SP, __, __, __, __, __, // 20..2F
+        __, __, __, __, __, __, __, __, __, __, __, SP, SP, SP, SP, SP, // 30..3F
+        __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 40..4F
+        __, __, __, __, __, __, __, __, __, __, __, SP, DU, SP, SP, __, // 50..5F
+        DU, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 60..6F
+        __, __, __, __, __, __, __, __, __, __, __, SP, SP, SP, __, CT, // 70..7F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 80..8F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 90..9F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // A0..AF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // B0..BF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // C0..CF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // D0..DF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // E0..EF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // F0..FF
+    ]
+};
+
+fn is_plain(byte: u8, requires_quote: bool) -> bool {
+    CLASS[byte as usize]
+        < if requires_quote {
+            class::ALWAYS
+        } else {
+            class::SPECIAL
+        }
+}
+
+/// Bytes that are written as-is by write_inside_double().
+fn is_double_plain(byte: u8) -> bool {
+    match byte {
+        b'"' | b'$' | b'`' | b'\\' => false,
+        b' '..=b'~' => true,
+        _ => false,
+    }
+}
 
-/// Characters with a special meaning at the beginning of a name.
-/// ~ expands a home directory.
-/// # starts a comment.
-/// ! is a common extension for expanding the shell history.
-const SPECIAL_SHELL_CHARS_START: &[char] = &['~', '#', '!'];
+/// Bytes that are written as-is by write_escaped().
+fn is_escaped_plain(byte: u8) -> bool {
+    match byte {
+        b'\\' | b'\'' => false,
+        b' '..=b'~' => true,
+        _ => false,
+    }
+}
 
-/// Characters that are interpreted specially in a double-quoted string.
-const DOUBLE_UNSAFE: &[u8] = &[b'"', b'`', b'$', b'\\'];
+/// Whether a word is a control operator or a redirection, for
+/// Quoted::operator(). Redirections may start with a file descriptor and
+/// end with a target for `<&` and `>&`, like `2>&1` or `<&-`.
+pub(crate) fn is_operator(word: &str) -> bool {
+    match word {
+        "&&" | "||" | "|" | "|&" | ";" | ";;" | "&" | "!" | "(" | ")" | "{" | "}" => return true,
+        "&>" | "&>>" => return true,
+        _ => (),
+    }
+    let rest = word.trim_start_matches(|ch: char| ch.is_ascii_digit());
+    let redirections = ["<<<", "<<-", "<<", "<>", "<&", "<", ">>", ">|", ">&", ">"];
+    let op = match redirections.iter().find(|op| rest.starts_with(**op)) {
+        Some(op) => op,
+        None => return false,
+    };
+    let target = &rest[op.len()..];
+    target.is_empty()
+        || (op.ends_with('&')
+            && (target == "-" || target.bytes().all(|byte| byte.is_ascii_digit())))
+}
 
-pub(crate) fn write(f: &mut Formatter<'_>, text: &str, force_quote: bool) -> fmt::Result {
+pub(crate) fn write(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if opts.context == QuoteContext::InsideDoubleQuotes {
+        return write_inside_double(out, text.as_bytes(), opts);
+    }
+    // cron can't leave a backslash right before a `%`, see Crontab.
+    if opts.crontab && text.contains("\\%") {
+        return write_escaped(out, text.as_bytes(), opts);
+    }
+    if opts.multiline == Multiline::HereDoc && is_here_doc_safe(text, opts) {
+        return write_here_doc(out, text);
+    }
+
+    // The scan decides how to quote the text and remembers where the quotes
+    // and backslashes are, so the writer doesn't have to look at it again:
+    // it's either copied out as a whole or cut up at those positions. If it
+    // turns out to need escapes, the part that was already scanned is written
+    // the same way and the escaped writer takes over from there.
+    // With alloc the positions are all kept. Without it only the first few
+    // are, and the writer searches the rest of the text a second time, see
+    // scan::Marks.
     let mut is_single_safe = true;
     let mut is_double_safe = true;
-    let mut requires_quote = force_quote;
+    let mut requires_quote = opts.force_quote;
     let mut is_bidi = false;
+    let mut combining = scan::Run::default();
+    let mut marks = scan::Marks::new();
+
+    if !requires_quote && opts.quotes_anyway(text) {
+        requires_quote = true;
+    }
 
     if !requires_quote {
         if let Some(first) = text.chars().next() {
-            if SPECIAL_SHELL_CHARS_START.contains(&first) {
+            if SPECIAL_SHELL_CHARS_START.contains(&first) && !opts.extra_safe.contains_char(first) {
                 requires_quote = true;
             }
 
@@ -38,10 +159,11 @@ pub(crate) fn write(f: &mut Formatter<'_>, text: &str, force_quote: bool) -> fmt
             // So if there's a zero-width character at the start we need quotes, but
             // if it's at the end we don't need to bother.
             // (This also ensures non-empty zero-width strings end up quoted.)
-            if !requires_quote && first.width().unwrap_or(0) == 0 {
+            if !requires_quote && width::may_be_zero_width(first, opts.ambiguous_width) {
                 // .width() returns Some(1) for unassigned codepoints.
                 // This means we can't pre-emptively quote unknown codepoints in
-                // case they become zero-width in the future.
+                // case they become zero-width in the future. The
+                // strict-unassigned feature escapes them instead.
                 // (None is only returned for certain ASCII characters.)
                 requires_quote = true;
             }
@@ -51,74 +173,146 @@ pub(crate) fn write(f: &mut Formatter<'_>, text: &str, force_quote: bool) -> fmt
         }
     }
 
-    for ch in text.chars() {
+    // Where the scan found a character that has to be escaped, if it did.
+    let mut escaped_from = None;
+    let mut rest = text;
+    loop {
+        let plain = scan::plain_prefix(rest.as_bytes(), |byte| is_plain(byte, requires_quote));
+        rest = &rest[plain..];
+        let ch = match rest.chars().next() {
+            Some(ch) => ch,
+            None => break,
+        };
+        let index = text.len() - rest.len();
+        rest = &rest[ch.len_utf8()..];
         if ch.is_ascii() {
             let ch = ch as u8;
-            if ch == b'\'' {
+            let class = CLASS[ch as usize];
+            if class & class::SINGLE_QUOTE != 0 {
                 is_single_safe = false;
+                marks.push(index);
             }
-            if is_double_safe && DOUBLE_UNSAFE.contains(&ch) {
+            if class & class::DOUBLE_UNSAFE != 0 {
                 is_double_safe = false;
+                if ch == b'\\' {
+                    marks.push(index);
+                }
             }
-            if !requires_quote && SPECIAL_SHELL_CHARS.contains(&ch) {
+            if !requires_quote && class & class::SPECIAL != 0 && is_special(ch, text, opts) {
                 requires_quote = true;
             }
-            if ch.is_ascii_control() {
-                return write_escaped(f, text.as_bytes());
+            if class & class::CONTROL != 0 {
+                escaped_from = Some(index);
+                break;
             }
         } else {
-            if !requires_quote && (ch.is_whitespace() || ch == '\u{2800}') {
-                // yash splits on unicode whitespace.
-                // fish ignores unicode whitespace at the start of a bare string.
-                // Therefore we quote unicode whitespace.
-                // U+2800 BRAILLE PATTERN BLANK is not technically whitespace but we
-                // quote it too.
-                // This check goes stale when new whitespace codepoints are assigned.
-                requires_quote = true;
-            }
-            if crate::is_bidi(ch) {
-                is_bidi = true;
+            if scan::is_notable(ch) {
+                if security::is_combining(ch) {
+                    // None of the other checks apply to these.
+                    combining.push(index, ch.len_utf8());
+                } else {
+                    if !requires_quote
+                        && (tables::contains(tables::WHITE_SPACE, ch) || ch == '\u{2800}')
+                    {
+                        // yash splits on unicode whitespace.
+                        // fish ignores unicode whitespace at the start of a bare string.
+                        // Therefore we quote unicode whitespace.
+                        // U+2800 BRAILLE PATTERN BLANK is not technically whitespace but we
+                        // quote it too.
+                        // This check goes stale when new whitespace codepoints are assigned.
+                        requires_quote = true;
+                    }
+                    if security::is_bidi(ch) {
+                        is_bidi = true;
+                    }
+                    if crate::requires_escape(ch)
+                        || (opts.escape_hidden_text && security::is_hidden_text(ch))
+                    {
+                        escaped_from = Some(index);
+                        break;
+                    }
+                }
             }
-            if crate::requires_escape(ch) {
-                return write_escaped(f, text.as_bytes());
+            if opts.escape_invisible && security::is_invisible(ch) {
+                escaped_from = Some(index);
+                break;
             }
         }
     }
 
-    if is_bidi && crate::is_suspicious_bidi(text) {
-        return write_escaped(f, text.as_bytes());
+    if let Some(end) = escaped_from {
+        let mixed = is_bidi || combining.longest() > 0;
+        return write_escaped_after(out, text, end, &marks, mixed, opts);
+    }
+
+    if opts.exceeds_combining(combining.longest())
+        || opts.escapes_confusables(text.chars().map(Some))
+    {
+        return write_escaped(out, text.as_bytes(), opts);
+    }
+
+    // There are no newlines at this point, so the text is a single paragraph.
+    // write_escaped() escapes the bidi control characters no matter what.
+    let mut isolate = false;
+    if is_bidi {
+        match security::apply_bidi_policy(text, opts.bidi) {
+            Bidi::Ok => (),
+            Bidi::Unterminated => isolate = true,
+            Bidi::Suspicious => return write_escaped(out, text.as_bytes(), opts),
+        }
+    }
+
+    // Interactive bash and zsh expand history with ! even inside double
+    // quotes, and a backslash in front of it would stay in. It's not in
+    // CLASS because it's harmless everywhere else.
+    if is_double_safe
+        && requires_quote
+        && (opts.prefer == QuoteChar::Double || !is_single_safe)
+        && text.contains('!')
+    {
+        is_double_safe = false;
     }
 
+    if isolate {
+        out.open("\u{2068}")?;
+    }
     if !requires_quote {
-        f.write_str(text)
+        out.literal(text)?;
+    } else if is_double_safe && opts.prefer == QuoteChar::Double {
+        write_simple(out, text, "\"")?;
     } else if is_single_safe {
-        write_simple(f, text, '\'')
+        write_simple(out, text, "'")?;
     } else if is_double_safe {
-        write_simple(f, text, '\"')
+        write_simple(out, text, "\"")?;
     } else {
-        write_single_escaped(f, text)
+        write_single_escaped(out, text, &marks)?;
+    }
+    if isolate {
+        out.close("\u{2069}")?;
     }
+    Ok(())
 }
 
-fn write_simple(f: &mut Formatter<'_>, text: &str, quote: char) -> fmt::Result {
-    f.write_char(quote)?;
-    f.write_str(text)?;
-    f.write_char(quote)?;
+fn write_simple(out: &mut dyn Sink, text: &str, quote: &str) -> fmt::Result {
+    out.open(quote)?;
+    out.literal(text)?;
+    out.close(quote)?;
     Ok(())
 }
 
-fn write_single_escaped(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
-    let mut iter = text.split('\'');
-    if let Some(chunk) = iter.next() {
-        if !chunk.is_empty() {
-            write_simple(f, chunk, '\'')?;
+/// Write the text in single quotes, with `\'` outside them for each single
+/// quote. The scan already found the quotes, see [`scan::Marks`].
+fn write_single_escaped(out: &mut dyn Sink, text: &str, marks: &scan::Marks) -> fmt::Result {
+    let mut pos = 0;
+    for (index, _) in marks.find(text, |ch| ch == '\'') {
+        if index > pos {
+            write_simple(out, &text[pos..index], "'")?;
         }
+        out.escape(Original::Char('\''), "\\'")?;
+        pos = index + 1;
     }
-    for chunk in iter {
-        f.write_str("\\'")?;
-        if !chunk.is_empty() {
-            write_simple(f, chunk, '\'')?;
-        }
+    if pos < text.len() {
+        write_simple(out, &text[pos..], "'")?;
     }
     Ok(())
 }
@@ -140,136 +334,605 @@ fn write_single_escaped(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
 ///
 /// There's a proposal to add it to POSIX:
 /// https://www.austingroupbugs.net/view.php?id=249
-pub(crate) fn write_escaped(f: &mut Formatter<'_>, text: &[u8]) -> fmt::Result {
-    f.write_str("$'")?;
-    // ksh variants accept more than two digits for a \x escape code,
-    // e.g. \xA691. We have to take care to not accidentally output
-    // something like that. If necessary we interrupt the quoting with
-    // `'$'`.
-    let mut in_escape = false;
-    for chunk in from_utf8_iter(text) {
-        match chunk {
-            Ok(chunk) => {
-                for ch in chunk.chars() {
-                    let was_escape = in_escape;
-                    in_escape = false;
-                    match ch {
-                        '\n' => f.write_str("\\n")?,
-                        '\t' => f.write_str("\\t")?,
-                        '\r' => f.write_str("\\r")?,
-                        // We could do \a, \b, \f, \v, but those are
-                        // rare enough to be confusing.
-                        // \0 is actually a case of the octal \nnn syntax,
-                        // and null bytes can't appear in arguments anyway,
-                        // so let's stay clear of that.
-                        // Some but not all shells have \e for \x1B.
-                        ch if crate::requires_escape(ch) || crate::is_bidi(ch) => {
-                            // Most shells support \uXXXX escape codes, but busybox sh
-                            // doesn't, so we always encode the raw UTF-8. Bit unfortunate,
-                            // but GNU does the same.
-                            for &byte in ch.encode_utf8(&mut [0; 4]).as_bytes() {
-                                write!(f, "\\x{:02X}", byte)?;
+pub(crate) fn write_escaped(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    if opts.context == QuoteContext::InsideDoubleQuotes {
+        return write_inside_double(out, text, opts);
+    }
+    if opts.fallback == Fallback::PrintfCommand {
+        return write_printf(out, text, opts);
+    }
+
+    let mut writer = EscapedWriter::new(out, opts, text_escapes(text, opts))?;
+    writer.write(out, text)?;
+    writer.finish(out)
+}
+
+/// Like [`write_escaped`], for when the scan in write() found a character
+/// that has to be escaped at `end`.
+///
+/// Before that point the only characters that need escapes are the quotes
+/// and backslashes in `marks`, so the scanned part is written in pieces
+/// between them. That doesn't hold if it had bidi controls or combining
+/// marks (`mixed`), or if confusable characters might be escaped because of
+/// the rest of the text.
+fn write_escaped_after(
+    out: &mut dyn Sink,
+    text: &str,
+    end: usize,
+    marks: &scan::Marks,
+    mixed: bool,
+    opts: &Options,
+) -> fmt::Result {
+    #[cfg(feature = "confusables")]
+    let mixed = mixed || opts.confusables;
+    if mixed || opts.fallback == Fallback::PrintfCommand {
+        return write_escaped(out, text.as_bytes(), opts);
+    }
+    // Without combining marks in the scanned part, any run of them that's
+    // too long is in the rest.
+    let rest = &text.as_bytes()[end..];
+    let escapes = text_escapes(rest, opts);
+    let mut writer = EscapedWriter::new(out, opts, escapes)?;
+    let scanned = &text[..end];
+    let mut pos = 0;
+    for (index, found) in marks.find(scanned, |ch| ch == '\'' || ch == '\\') {
+        if index > pos {
+            out.literal(&scanned[pos..index])?;
+        }
+        writer.write_char(out, found.chars().next().unwrap())?;
+        pos = index + found.len();
+    }
+    if end > pos {
+        out.literal(&scanned[pos..])?;
+    }
+    writer.write(out, rest)?;
+    writer.finish(out)
+}
+
+/// An ANSI-C quoted string that's written piece by piece, for
+/// [`quote_stream`](crate::quote_stream).
+///
+/// The pieces must not split UTF-8 sequences, or the halves will be escaped
+/// as invalid bytes.
+pub(crate) struct EscapedWriter<'a> {
+    opts: &'a Options,
+    escapes: TextEscapes,
+    /// Whether the last thing we wrote was a `\x` escape.
+    in_escape: bool,
+}
+
+impl<'a> EscapedWriter<'a> {
+    pub(crate) fn new(
+        out: &mut dyn Sink,
+        opts: &'a Options,
+        escapes: TextEscapes,
+    ) -> Result<Self, fmt::Error> {
+        out.open("$'")?;
+        Ok(EscapedWriter {
+            opts,
+            escapes,
+            in_escape: false,
+        })
+    }
+
+    pub(crate) fn write(&mut self, out: &mut dyn Sink, text: &[u8]) -> fmt::Result {
+        // ksh variants accept more than two digits for a \x escape code,
+        // e.g. \xA691. We have to take care to not accidentally output
+        // something like that. If necessary we interrupt the quoting with
+        // `'$'`.
+        for chunk in utf8_chunks(text) {
+            match chunk {
+                Ok(chunk) => {
+                    // Everything that's written as-is goes out in one piece,
+                    // so that Truncate sees whole grapheme clusters.
+                    let mut start = 0;
+                    let mut pos = 0;
+                    loop {
+                        if !self.in_escape {
+                            pos += scan::plain_prefix(&chunk.as_bytes()[pos..], is_escaped_plain);
+                        }
+                        let ch = match chunk[pos..].chars().next() {
+                            Some(ch) => ch,
+                            None => break,
+                        };
+                        if self.is_literal(ch) {
+                            if self.in_escape && ch.is_ascii_hexdigit() {
+                                out.close("'")?;
+                                out.open("$'")?;
+                            }
+                            self.in_escape = false;
+                        } else {
+                            if pos > start {
+                                out.literal(&chunk[start..pos])?;
                             }
-                            in_escape = true;
+                            self.write_char(out, ch)?;
+                            start = pos + ch.len_utf8();
                         }
-                        '\\' | '\'' => {
-                            // '?' and '"' can also be escaped this way
-                            // but AFAICT there's no reason to do so.
-                            f.write_char('\\')?;
-                            f.write_char(ch)?;
+                        pos += ch.len_utf8();
+                    }
+                    if pos > start {
+                        out.literal(&chunk[start..pos])?;
+                    }
+                }
+                Err(unit) => {
+                    out.escape_fmt(Original::Byte(unit), format_args!("\\x{:02X}", unit))?;
+                    self.in_escape = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a character is written as-is, rather than escaped.
+    fn is_literal(&self, ch: char) -> bool {
+        let escape = self.opts.escapes(ch) || self.escapes.contains(ch);
+        escape_sequence(ch, escape).is_none()
+    }
+
+    fn write_char(&mut self, out: &mut dyn Sink, ch: char) -> fmt::Result {
+        let was_escape = self.in_escape;
+        self.in_escape = false;
+        let escape = self.opts.escapes(ch) || self.escapes.contains(ch);
+        match escape_sequence(ch, escape) {
+            Some(Escape::Fixed(rendered)) => out.escape(Original::Char(ch), rendered),
+            Some(Escape::Hex) => {
+                // Most shells support \uXXXX escape codes, but busybox sh
+                // doesn't, so we always encode the raw UTF-8. Bit unfortunate,
+                // but GNU does the same.
+                let mut buf = [0; 4];
+                let bytes = ch.encode_utf8(&mut buf).as_bytes();
+                self.in_escape = true;
+                out.escape_fmt(Original::Char(ch), format_args!("{}", HexEscapes(bytes)))
+            }
+            None if was_escape && ch.is_ascii_hexdigit() => {
+                out.close("'")?;
+                out.open("$'")?;
+                out.literal(ch.encode_utf8(&mut [0; 4]))
+            }
+            None => out.literal(ch.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    pub(crate) fn finish(self, out: &mut dyn Sink) -> fmt::Result {
+        out.close("'")
+    }
+}
+
+/// Write the inside of a double-quoted string, for
+/// [`QuoteContext::InsideDoubleQuotes`].
+///
+/// There are no escape sequences for control characters inside double
+/// quotes, so those are written by closing the string, switching to `$'...'`,
+/// and opening it again. The caller's quotes take care of the rest.
+fn write_inside_double(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    let escapes = text_escapes(text, opts);
+    // Whether we're in the middle of a "$'...'" detour.
+    let mut in_escaped = false;
+    for chunk in utf8_chunks(text) {
+        match chunk {
+            Ok(chunk) => {
+                // Unescaped text is written in runs, like in EscapedWriter.
+                let mut start = 0;
+                let mut pos = 0;
+                loop {
+                    if !in_escaped {
+                        pos += scan::plain_prefix(&chunk.as_bytes()[pos..], is_double_plain);
+                    }
+                    let ch = match chunk[pos..].chars().next() {
+                        Some(ch) => ch,
+                        None => break,
+                    };
+                    let is_escape = opts.escapes(ch) || escapes.contains(ch);
+                    let is_unsafe = ch.is_ascii() && DOUBLE_UNSAFE.contains(&(ch as u8));
+                    if !is_escape && !is_unsafe {
+                        if in_escaped {
+                            out.close("'\"")?;
+                            in_escaped = false;
                         }
-                        ch if was_escape && ch.is_ascii_hexdigit() => {
-                            f.write_str("'$'")?;
-                            f.write_char(ch)?;
+                        pos += ch.len_utf8();
+                        continue;
+                    }
+                    if pos > start {
+                        out.literal(&chunk[start..pos])?;
+                    }
+                    pos += ch.len_utf8();
+                    start = pos;
+                    if is_escape {
+                        if !in_escaped {
+                            out.open("\"$'")?;
+                            in_escaped = true;
                         }
-                        ch => {
-                            f.write_char(ch)?;
+                        match escape_sequence(ch, true) {
+                            Some(Escape::Fixed(rendered)) => {
+                                out.escape(Original::Char(ch), rendered)?
+                            }
+                            _ => {
+                                let mut buf = [0; 4];
+                                let bytes = ch.encode_utf8(&mut buf).as_bytes();
+                                out.escape_fmt(
+                                    Original::Char(ch),
+                                    format_args!("{}", HexEscapes(bytes)),
+                                )?;
+                            }
                         }
+                        continue;
+                    }
+                    if in_escaped {
+                        out.close("'\"")?;
+                        in_escaped = false;
                     }
+                    out.escape_fmt(Original::Char(ch), format_args!("\\{}", ch))?;
+                }
+                if pos > start {
+                    out.literal(&chunk[start..pos])?;
                 }
             }
             Err(unit) => {
-                write!(f, "\\x{:02X}", unit)?;
-                in_escape = true;
+                if !in_escaped {
+                    out.open("\"$'")?;
+                    in_escaped = true;
+                }
+                out.escape_fmt(Original::Byte(unit), format_args!("\\x{:02X}", unit))?;
             }
         }
     }
-    f.write_char('\'')?;
+    if in_escaped {
+        out.close("'\"")?;
+    }
     Ok(())
 }
 
-fn from_utf8_iter(bytes: &[u8]) -> impl Iterator<Item = Result<&str, u8>> {
-    struct Iter<'a> {
-        bytes: &'a [u8],
+/// Write a `printf` command that prints the text, for
+/// [`Fallback::PrintfCommand`].
+///
+/// The format string is single-quoted, so we only have to deal with printf's
+/// own escapes. POSIX only has octal escapes, and those are always written with
+/// three digits so that a digit after them can't be read as part of them.
+fn write_printf(out: &mut dyn Sink, text: &[u8], opts: &Options) -> fmt::Result {
+    let escapes = text_escapes(text, opts);
+    out.open("printf '")?;
+    let mut text = text;
+    if text.first() == Some(&b'-') {
+        // Otherwise it would look like an option.
+        out.escape(Original::Char('-'), "\\055")?;
+        text = &text[1..];
+    }
+    for chunk in utf8_chunks(text) {
+        match chunk {
+            Ok(chunk) => {
+                // Unescaped text is written in runs, like in EscapedWriter.
+                let mut start = 0;
+                let mut pos = 0;
+                loop {
+                    pos += scan::plain_prefix(&chunk.as_bytes()[pos..], is_printf_plain);
+                    let ch = match chunk[pos..].chars().next() {
+                        Some(ch) => ch,
+                        None => break,
+                    };
+                    let escape = opts.escapes(ch) || escapes.contains(ch);
+                    if !escape && !"\n\t\r\\%'".contains(ch) {
+                        pos += ch.len_utf8();
+                        continue;
+                    }
+                    if pos > start {
+                        out.literal(&chunk[start..pos])?;
+                    }
+                    pos += ch.len_utf8();
+                    start = pos;
+                    match ch {
+                        '\n' => out.escape(Original::Char(ch), "\\n")?,
+                        '\t' => out.escape(Original::Char(ch), "\\t")?,
+                        '\r' => out.escape(Original::Char(ch), "\\r")?,
+                        '\\' => out.escape(Original::Char(ch), "\\\\")?,
+                        '%' => out.escape(Original::Char(ch), "%%")?,
+                        '\'' => out.escape(Original::Char(ch), "\\047")?,
+                        _ => {
+                            let mut buf = [0; 4];
+                            let bytes = ch.encode_utf8(&mut buf).as_bytes();
+                            out.escape_fmt(
+                                Original::Char(ch),
+                                format_args!("{}", OctalEscapes(bytes)),
+                            )?;
+                        }
+                    }
+                }
+                if pos > start {
+                    out.literal(&chunk[start..pos])?;
+                }
+            }
+            Err(byte) => out.escape_fmt(Original::Byte(byte), format_args!("\\{:03o}", byte))?,
+        }
+    }
+    out.close("'")
+}
+
+/// Bytes that are written as-is by write_printf().
+fn is_printf_plain(byte: u8) -> bool {
+    match byte {
+        b'\\' | b'\'' | b'%' => false,
+        b' '..=b'~' => true,
+        _ => false,
+    }
+}
+
+/// Formats bytes as a series of octal escapes.
+struct OctalEscapes<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for OctalEscapes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "\\{:03o}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check whether text can be written as a here-document: it has to end with a
+/// newline (which the here-document adds) and contain nothing else that we'd
+/// escape.
+fn is_here_doc_safe(text: &str, opts: &Options) -> bool {
+    text.ends_with('\n')
+        && text
+            .chars()
+            .all(|ch| ch == '\n' || ch == '\t' || !opts.escapes(ch))
+        && !opts.escapes_confusables(text.chars().map(Some))
+        && !opts.escapes_combining(text.chars().map(Some))
+}
+
+/// Write a here-document with a quoted delimiter, so that nothing in it is
+/// expanded.
+///
+/// The newline before the delimiter is part of the closing piece. That way
+/// the output still works if it's truncated in the middle of a line.
+fn write_here_doc(out: &mut dyn Sink, text: &str) -> fmt::Result {
+    // Find a delimiter that doesn't appear as a line of its own.
+    let mut number = 0;
+    while text.lines().any(|line| is_delimiter(line, number)) {
+        number += 1;
+    }
+    let mut open = Buf::new();
+    writeln!(open, "<<'EOF{}'", Suffix(number))?;
+    out.open(open.as_str())?;
+    out.literal(&text[..text.len() - 1])?;
+    let mut close = Buf::new();
+    write!(close, "\nEOF{}", Suffix(number))?;
+    out.close(close.as_str())
+}
+
+/// The number after a here-document delimiter, if any.
+struct Suffix(usize);
+
+impl fmt::Display for Suffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => Ok(()),
+            number => write!(f, "{}", number),
+        }
+    }
+}
+
+/// Check whether a line would end a here-document with the given suffix.
+fn is_delimiter(line: &str, number: usize) -> bool {
+    if !line.starts_with("EOF") {
+        return false;
+    }
+    let rest = &line[3..];
+    match number {
+        0 => rest.is_empty(),
+        // This also catches things like "EOF01", which is harmless.
+        _ => rest.parse() == Ok(number),
+    }
+}
+
+/// Check which characters have to be escaped because of the rest of the
+/// text, in possibly invalid UTF-8.
+fn text_escapes(text: &[u8], opts: &Options) -> TextEscapes {
+    opts.text_escapes(crate::utf8::chars(text))
+}
+
+/// How a character is written inside `$'...'`.
+pub(crate) enum Escape {
+    /// A fixed escape sequence, like `\n`.
+    Fixed(&'static str),
+    /// `\x` escapes for each byte of the UTF-8 encoding.
+    Hex,
+}
+
+/// Decide how to escape a character inside `$'...'`. `escape` says whether
+/// it's one of the characters that always have to be escaped.
+///
+/// `None` means that the character can be written as-is, unless it's a hex
+/// digit that follows a `\x` escape.
+pub(crate) fn escape_sequence(ch: char, escape: bool) -> Option<Escape> {
+    match ch {
+        '\n' => Some(Escape::Fixed("\\n")),
+        '\t' => Some(Escape::Fixed("\\t")),
+        '\r' => Some(Escape::Fixed("\\r")),
+        // We could do \a, \b, \f, \v, but those are
+        // rare enough to be confusing.
+        // \0 is actually a case of the octal \nnn syntax,
+        // and null bytes can't appear in arguments anyway,
+        // so let's stay clear of that.
+        // Some but not all shells have \e for \x1B.
+        _ if escape => Some(Escape::Hex),
+        // '?' and '"' can also be escaped this way
+        // but AFAICT there's no reason to do so.
+        '\\' => Some(Escape::Fixed("\\\\")),
+        '\'' => Some(Escape::Fixed("\\'")),
+        _ => None,
     }
+}
+
+/// Formats bytes as a series of `\xXX` escapes.
+pub(crate) struct HexEscapes<'a>(pub(crate) &'a [u8]);
+
+impl<'a> fmt::Display for HexEscapes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            write!(f, "\\x{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
 
-    impl<'a> Iterator for Iter<'a> {
-        type Item = Result<&'a str, u8>;
+/// Puts a backslash before every `%` in the output, for
+/// [`Quoted::crontab`](crate::Quoted::crontab).
+///
+/// cron removes the backslash from `\%` wherever it is, but it also skips
+/// over whatever follows any other backslash. So `\\%` doesn't work, and
+/// write() falls back to `$'...'` (where backslashes come in pairs) for text
+/// with a backslash right before a `%`.
+pub(crate) struct Crontab<'a>(pub(crate) &'a mut dyn Sink);
 
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.bytes.is_empty() {
-                return None;
+impl<'a> Crontab<'a> {
+    /// Escape a piece of syntax and pass it on with `pass`.
+    fn syntax(
+        &mut self,
+        text: &str,
+        pass: impl FnOnce(&mut dyn Sink, &str) -> fmt::Result,
+    ) -> fmt::Result {
+        if !text.contains('%') {
+            return pass(self.0, text);
+        }
+        let mut buf = Buf::new();
+        for ch in text.chars() {
+            if ch == '%' {
+                buf.write_char('\\')?;
             }
-            match from_utf8(self.bytes) {
-                Ok(text) => {
-                    self.bytes = &[];
-                    Some(Ok(text))
-                }
-                Err(err) if err.valid_up_to() == 0 => {
-                    let res = self.bytes[0];
-                    self.bytes = &self.bytes[1..];
-                    Some(Err(res))
-                }
-                Err(err) => {
-                    let (valid, rest) = self.bytes.split_at(err.valid_up_to());
-                    self.bytes = rest;
-                    Some(Ok(from_utf8(valid).unwrap()))
-                }
+            buf.write_char(ch)?;
+        }
+        pass(self.0, buf.as_str())
+    }
+}
+
+impl<'a> Sink for Crontab<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.open(text))
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.close(text))
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        let mut pieces = text.split('%');
+        if let Some(piece) = pieces.next() {
+            if !piece.is_empty() {
+                self.0.literal(piece)?;
+            }
+        }
+        for piece in pieces {
+            self.0.escape(Original::Char('%'), "\\%")?;
+            if !piece.is_empty() {
+                self.0.literal(piece)?;
             }
         }
+        Ok(())
     }
 
-    Iter { bytes }
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        self.syntax(rendered, |out, rendered| out.escape(original, rendered))
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shell_chars::unix::SPECIAL_SHELL_CHARS;
 
-    use std::vec::Vec;
+    #[test]
+    fn test_class_table() {
+        for byte in 0..=255 {
+            let mut expected = 0;
+            if SPECIAL_SHELL_CHARS.contains(&byte) {
+                expected |= class::SPECIAL;
+            }
+            if DOUBLE_UNSAFE.contains(&byte) {
+                expected |= class::DOUBLE_UNSAFE;
+            }
+            if byte == b'\'' {
+                expected |= class::SINGLE_QUOTE;
+            }
+            if byte == b'"' {
+                expected |= class::DOUBLE_QUOTE;
+            }
+            if byte.is_ascii_control() {
+                expected |= class::CONTROL;
+            }
+            if !byte.is_ascii() {
+                expected |= class::NON_ASCII;
+            }
+            assert_eq!(CLASS[byte as usize], expected, "{:?}", byte as char);
+        }
+    }
 
+    #[cfg(feature = "unix")]
     #[test]
-    fn test_utf8_iter() {
-        type ByteStr = &'static [u8];
-        type Chunk = Result<&'static str, u8>;
-        const CASES: &[(ByteStr, &[Chunk])] = &[
-            (b"", &[]),
-            (b"hello", &[Ok("hello")]),
-            // Immediately invalid
-            (b"\xFF", &[Err(b'\xFF')]),
-            // Incomplete UTF-8
-            (b"\xC2", &[Err(b'\xC2')]),
-            (b"\xF4\x8F", &[Err(b'\xF4'), Err(b'\x8F')]),
-            (b"\xFF\xFF", &[Err(b'\xFF'), Err(b'\xFF')]),
-            (b"hello\xC2", &[Ok("hello"), Err(b'\xC2')]),
-            (b"\xFFhello", &[Err(b'\xFF'), Ok("hello")]),
-            (b"\xFF\xC2hello", &[Err(b'\xFF'), Err(b'\xC2'), Ok("hello")]),
-            (b"foo\xFFbar", &[Ok("foo"), Err(b'\xFF'), Ok("bar")]),
-            (
-                b"foo\xF4\x8Fbar",
-                &[Ok("foo"), Err(b'\xF4'), Err(b'\x8F'), Ok("bar")],
-            ),
-            (
-                b"foo\xFF\xC2bar",
-                &[Ok("foo"), Err(b'\xFF'), Err(b'\xC2'), Ok("bar")],
-            ),
+    fn test_escaped_after() {
+        // Whatever part of the text write() scanned before it gives up on
+        // quotes, the output has to be the same as starting over.
+        let pieces = [
+            "a",
+            "'",
+            "\\",
+            "\n",
+            "\x01",
+            "é",
+            "0",
+            " ",
+            "$",
+            "\u{202E}",
+            "\u{202C}",
+            "\u{300}",
+            "\u{200B}",
+            "\u{E0041}",
+            "\u{85}",
         ];
-        for &(case, expected) in CASES {
-            assert_eq!(
-                from_utf8_iter(case).collect::<Vec<_>>().as_slice(),
-                expected
-            );
+        let mut state = 1u32;
+        for round in 0..5000 {
+            let mut text = std::string::String::new();
+            for _ in 0..round % 20 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                text.push_str(pieces[(state >> 16) as usize % pieces.len()]);
+            }
+            let quoted = crate::Quoted::unix(&text);
+            let variants = [
+                quoted,
+                quoted.max_combining(Some(0)),
+                quoted.escape_invisible(true),
+                quoted.escape_hidden_text(false),
+            ];
+            for quoted in &variants {
+                let mut written = std::string::String::new();
+                write(&mut crate::sink::Plain(&mut written), &text, &quoted.opts).unwrap();
+                if !written.starts_with("$'") {
+                    continue;
+                }
+                let mut expected = std::string::String::new();
+                write_escaped(
+                    &mut crate::sink::Plain(&mut expected),
+                    text.as_bytes(),
+                    &quoted.opts,
+                )
+                .unwrap();
+                assert_eq!(written, expected, "{:?}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_notable() {
+        for ch in (0x80..=0x10FFFF).filter_map(core::char::from_u32) {
+            if tables::contains(tables::WHITE_SPACE, ch)
+                || ch == '\u{2800}'
+                || security::is_bidi(ch)
+                || crate::requires_escape(ch)
+                || security::is_combining(ch)
+                || security::is_hidden_text(ch)
+            {
+                assert!(scan::is_notable(ch), "{:?}", ch);
+            }
         }
     }
 }
@@ -0,0 +1,122 @@
+//! Helpers for text that may not be valid UTF-8.
+
+/// The characters in possibly invalid UTF-8, with `None` for invalid bytes.
+#[cfg(any(
+    feature = "unix",
+    feature = "sql",
+    feature = "html",
+    feature = "sanitize",
+    all(feature = "native", not(windows))
+))]
+pub(crate) fn chars(bytes: &[u8]) -> impl Iterator<Item = Option<char>> + Clone + '_ {
+    crate::util::utf8_chunks(bytes).flat_map(|chunk| {
+        let (chunk, invalid) = match chunk {
+            Ok(chunk) => (chunk, None),
+            Err(_) => ("", Some(None)),
+        };
+        chunk.chars().map(Some).chain(invalid)
+    })
+}
+
+/// Whether the bytes are the start of a UTF-8 sequence that might still be
+/// completed.
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+pub(crate) fn is_incomplete(bytes: &[u8]) -> bool {
+    match core::str::from_utf8(bytes) {
+        Ok(_) => false,
+        Err(err) => err.error_len().is_none(),
+    }
+}
+
+/// The length of an incomplete UTF-8 sequence at the end of the bytes.
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+pub(crate) fn incomplete_tail(bytes: &[u8]) -> usize {
+    let start = bytes.len().saturating_sub(3);
+    for index in (start..bytes.len()).rev() {
+        // Look for the last byte that isn't a continuation byte.
+        if bytes[index] & 0xC0 != 0x80 {
+            if is_incomplete(&bytes[index..]) {
+                return bytes.len() - index;
+            }
+            return 0;
+        }
+    }
+    0
+}
+
+/// The start of a UTF-8 sequence that was cut off at the end of a buffer,
+/// held back until the rest comes in.
+///
+/// Only continuation bytes can complete it. If anything else comes first
+/// it's passed on as it is, as invalid bytes.
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Pending {
+    bytes: [u8; 4],
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+#[cfg(any(
+    feature = "unix",
+    feature = "windows",
+    feature = "native",
+    feature = "sanitize"
+))]
+impl Pending {
+    /// Pass the bytes on in chunks that don't end in the middle of a UTF-8
+    /// sequence, after whatever was held back. An incomplete sequence at the
+    /// end is held back in turn.
+    pub(crate) fn push<E>(
+        &mut self,
+        mut bytes: &[u8],
+        mut f: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        while self.len > 0 && is_incomplete(&self.bytes[..self.len]) {
+            match bytes.first() {
+                Some(&byte) if byte & 0xC0 == 0x80 => {
+                    self.bytes[self.len] = byte;
+                    self.len += 1;
+                    bytes = &bytes[1..];
+                }
+                Some(_) => break,
+                None => return Ok(()),
+            }
+        }
+        // Either complete or invalid, which f takes care of.
+        self.finish(&mut f)?;
+        let end = bytes.len() - incomplete_tail(bytes);
+        f(&bytes[..end])?;
+        self.len = bytes.len() - end;
+        self.bytes[..self.len].copy_from_slice(&bytes[end..]);
+        Ok(())
+    }
+
+    /// Pass on whatever is held back, if anything.
+    pub(crate) fn finish<E>(&mut self, mut f: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        let len = self.len;
+        self.len = 0;
+        if len > 0 {
+            f(&self.bytes[..len])?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+//! Helpers for working with text that aren't about quoting as such.
+//!
+//! These are used internally, and exposed because they come in handy in
+//! programs that deal with the same kind of text.
+
+use core::iter::FusedIterator;
+use core::str::from_utf8;
+
+/// Split bytes into runs of valid UTF-8 and single invalid bytes.
+///
+/// This is how invalid UTF-8 is escaped throughout this crate: every byte
+/// that isn't part of a valid character stands on its own, even if it's the
+/// start of a sequence that's cut short.
+///
+/// ```
+/// use os_display::util::utf8_chunks;
+///
+/// let chunks: Vec<_> = utf8_chunks(b"foo\xF4\x8Fbar").collect();
+/// assert_eq!(chunks, [Ok("foo"), Err(0xF4), Err(0x8F), Ok("bar")]);
+///
+/// let lossy: String = utf8_chunks(b"caf\xC3\xA9 \xFF")
+///     .map(|chunk| chunk.unwrap_or("\u{FFFD}"))
+///     .collect();
+/// assert_eq!(lossy, "caf\u{E9} \u{FFFD}");
+/// ```
+///
+/// The valid chunks are never empty, and they're as long as possible.
+pub fn utf8_chunks(bytes: &[u8]) -> Utf8Chunks<'_> {
+    Utf8Chunks { bytes }
+}
+
+/// An iterator over the valid and invalid parts of some bytes, made by
+/// [`utf8_chunks`].
+#[derive(Debug, Clone)]
+pub struct Utf8Chunks<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Utf8Chunks<'a> {
+    /// The bytes that haven't been split up yet.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for Utf8Chunks<'a> {
+    type Item = Result<&'a str, u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        match from_utf8(self.bytes) {
+            Ok(text) => {
+                self.bytes = &[];
+                Some(Ok(text))
+            }
+            Err(err) if err.valid_up_to() == 0 => {
+                let res = self.bytes[0];
+                self.bytes = &self.bytes[1..];
+                Some(Err(res))
+            }
+            Err(err) => {
+                let (valid, rest) = self.bytes.split_at(err.valid_up_to());
+                self.bytes = rest;
+                Some(Ok(from_utf8(valid).unwrap()))
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for Utf8Chunks<'a> {}
+
+/// Decode UTF-16, with `Err` for unpaired surrogates.
+///
+/// This is the counterpart of [`utf8_chunks`] for Windows file names and
+/// other text that may not be valid UTF-16. Unlike UTF-8 a run of UTF-16
+/// can't be borrowed as a `str`, so this yields characters one at a time.
+///
+/// ```
+/// use os_display::util::utf16_chunks;
+///
+/// let units = [0x61, 0xD800, 0xD83D, 0xDE00];
+/// let chunks: Vec<_> = utf16_chunks(&units).collect();
+/// assert_eq!(chunks, [Ok('a'), Err(0xD800), Ok('\u{1F600}')]);
+/// ```
+pub fn utf16_chunks(units: &[u16]) -> Utf16Chunks<'_> {
+    Utf16Chunks { units }
+}
+
+/// An iterator over the characters and unpaired surrogates in UTF-16, made
+/// by [`utf16_chunks`].
+#[derive(Debug, Clone)]
+pub struct Utf16Chunks<'a> {
+    units: &'a [u16],
+}
+
+impl<'a> Utf16Chunks<'a> {
+    /// The code units that haven't been decoded yet.
+    pub fn as_units(&self) -> &'a [u16] {
+        self.units
+    }
+}
+
+fn is_high_surrogate(unit: u16) -> bool {
+    unit >= 0xD800 && unit <= 0xDBFF
+}
+
+fn is_low_surrogate(unit: u16) -> bool {
+    unit >= 0xDC00 && unit <= 0xDFFF
+}
+
+impl<'a> Iterator for Utf16Chunks<'a> {
+    type Item = Result<char, u16>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.units.split_first()?;
+        if is_high_surrogate(first) {
+            if let Some(&second) = rest.first() {
+                if is_low_surrogate(second) {
+                    self.units = &rest[1..];
+                    let code = 0x10000
+                        + ((u32::from(first) - 0xD800) << 10)
+                        + (u32::from(second) - 0xDC00);
+                    // Every surrogate pair is a valid character.
+                    return Some(Ok(core::char::from_u32(code).unwrap()));
+                }
+            }
+        }
+        self.units = rest;
+        match core::char::from_u32(u32::from(first)) {
+            Some(ch) => Some(Ok(ch)),
+            None => Some(Err(first)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.units.len();
+        ((len + 1) / 2, Some(len))
+    }
+}
+
+impl<'a> FusedIterator for Utf16Chunks<'a> {}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::vec::Vec;
+
+    #[test]
+    fn test_utf8_chunks() {
+        type ByteStr = &'static [u8];
+        type Chunk = Result<&'static str, u8>;
+        const CASES: &[(ByteStr, &[Chunk])] = &[
+            (b"", &[]),
+            (b"hello", &[Ok("hello")]),
+            // Immediately invalid
+            (b"\xFF", &[Err(b'\xFF')]),
+            // Incomplete UTF-8
+            (b"\xC2", &[Err(b'\xC2')]),
+            (b"\xF4\x8F", &[Err(b'\xF4'), Err(b'\x8F')]),
+            (b"\xFF\xFF", &[Err(b'\xFF'), Err(b'\xFF')]),
+            (b"hello\xC2", &[Ok("hello"), Err(b'\xC2')]),
+            (b"\xFFhello", &[Err(b'\xFF'), Ok("hello")]),
+            (b"\xFF\xC2hello", &[Err(b'\xFF'), Err(b'\xC2'), Ok("hello")]),
+            (b"foo\xFFbar", &[Ok("foo"), Err(b'\xFF'), Ok("bar")]),
+            (
+                b"foo\xF4\x8Fbar",
+                &[Ok("foo"), Err(b'\xF4'), Err(b'\x8F'), Ok("bar")],
+            ),
+            (
+                b"foo\xFF\xC2bar",
+                &[Ok("foo"), Err(b'\xFF'), Err(b'\xC2'), Ok("bar")],
+            ),
+            // Surrogates and overlong encodings
+            (b"\xED\xA0\x80", &[Err(b'\xED'), Err(b'\xA0'), Err(b'\x80')]),
+            (b"\xC0\x80a", &[Err(b'\xC0'), Err(b'\x80'), Ok("a")]),
+        ];
+        for &(case, expected) in CASES {
+            assert_eq!(utf8_chunks(case).collect::<Vec<_>>().as_slice(), expected);
+        }
+    }
+
+    #[test]
+    fn test_utf16_chunks() {
+        type Chunk = Result<char, u16>;
+        const CASES: &[(&[u16], &[Chunk])] = &[
+            (&[], &[]),
+            (&[0x61, 0x62], &[Ok('a'), Ok('b')]),
+            (&[0xD800], &[Err(0xD800)]),
+            (&[0xDC00], &[Err(0xDC00)]),
+            (&[0xDC00, 0xD800], &[Err(0xDC00), Err(0xD800)]),
+            (&[0xD800, 0xD800, 0xDC00], &[Err(0xD800), Ok('\u{10000}')]),
+            (&[0xD800, 0x61], &[Err(0xD800), Ok('a')]),
+            (&[0xDBFF, 0xDFFF], &[Ok('\u{10FFFF}')]),
+            (&[0xD83D, 0xDE00, 0xDE00], &[Ok('\u{1F600}'), Err(0xDE00)]),
+            (&[0xFFFF], &[Ok('\u{FFFF}')]),
+        ];
+        for &(case, expected) in CASES {
+            let chunks: Vec<_> = utf16_chunks(case).collect();
+            assert_eq!(chunks.as_slice(), expected);
+            let decoded: Vec<_> = core::char::decode_utf16(case.iter().cloned())
+                .map(|res| res.map_err(|err| err.unpaired_surrogate()))
+                .collect();
+            assert_eq!(chunks, decoded);
+        }
+
+        let mut chunks = utf16_chunks(&[0xD83D, 0xDE00, 0x61]);
+        chunks.next();
+        assert_eq!(chunks.as_units(), &[0x61]);
+    }
+
+    #[test]
+    fn as_bytes() {
+        let mut chunks = utf8_chunks(b"foo\xFFbar");
+        assert_eq!(chunks.as_bytes(), b"foo\xFFbar");
+        chunks.next();
+        assert_eq!(chunks.as_bytes(), b"\xFFbar");
+        chunks.next();
+        assert_eq!(chunks.as_bytes(), b"bar");
+        chunks.next();
+        assert_eq!(chunks.as_bytes(), b"");
+        assert_eq!(chunks.next(), None);
+    }
+}
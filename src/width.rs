@@ -0,0 +1,52 @@
+//! Terminal column widths, from `unicode-width` if it's enabled.
+//!
+//! Without it we have to guess. For quoting decisions we assume the worst,
+//! and for measuring output every character takes up a single column.
+
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "unicode-width")]
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::Width;
+
+/// Whether a character might not show up at all.
+#[cfg(feature = "unicode-width")]
+pub(crate) fn may_be_zero_width(ch: char, ambiguous: Width) -> bool {
+    let width = match ambiguous {
+        Width::Narrow => ch.width(),
+        Width::Wide => ch.width_cjk(),
+    };
+    width.unwrap_or(0) == 0
+}
+
+#[cfg(not(feature = "unicode-width"))]
+pub(crate) fn may_be_zero_width(ch: char, _ambiguous: Width) -> bool {
+    !ch.is_ascii() || ch.is_ascii_control()
+}
+
+#[cfg(feature = "unicode-width")]
+pub(crate) fn str_width(text: &str, ambiguous: Width) -> usize {
+    match ambiguous {
+        Width::Narrow => text.width(),
+        Width::Wide => text.width_cjk(),
+    }
+}
+
+#[cfg(not(feature = "unicode-width"))]
+pub(crate) fn str_width(text: &str, _ambiguous: Width) -> usize {
+    text.chars().count()
+}
+
+/// Split text into the pieces that truncation mustn't cut through: grapheme
+/// clusters if `unicode-segmentation` is enabled, otherwise characters.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn clusters<'a>(text: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    text.graphemes(true)
+}
+
+#[cfg(not(feature = "unicode-segmentation"))]
+pub(crate) fn clusters<'a>(text: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    text.char_indices()
+        .map(move |(index, ch)| &text[index..index + ch.len_utf8()])
+}
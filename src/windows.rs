@@ -1,61 +1,129 @@
-use core::fmt::{self, Formatter, Write};
+use core::fmt;
 
-use unicode_width::UnicodeWidthChar;
+use crate::scan::{self, class};
+use crate::security::{self, Bidi};
+use crate::shell_chars::windows::SPECIAL_SHELL_CHARS_START;
+use crate::sink::{Buf, Literals, Sink};
+use crate::width;
+#[cfg(feature = "windows")]
+use crate::CmdContext;
+use crate::{MagicTokens, Options, Original, QuoteChar, QuoteContext, TextEscapes};
 
 // Much of this code is similar to the Unix version.
 // Not all comments are repeated, so read that first.
 
-/// I'm not too familiar with PowerShell, much of this is based on
-/// experimentation rather than documentation or deep understanding.
-/// I have noticed that ~?*[] only get expanded in some contexts, so watch
-/// out for that if doing your own tests.
-/// Get-ChildItem seems unwilling to quote anything so it doesn't help.
-/// The omission of \ is important because it's used in file paths.
-const SPECIAL_SHELL_CHARS: &[u8] = b"|&;<>()$`\"'*?[]=,{} ";
+/// The classes of each byte for the scan in write(), see [`scan::class`].
+/// A byte that's [`SPECIAL`](class::SPECIAL) but nothing else only matters
+/// until we know that we need quotes. Bytes without flags don't matter,
+/// except at the start of the string.
+#[rustfmt::skip]
+static CLASS: [u8; 256] = {
+    const __: u8 = 0;
+    const SP: u8 = class::SPECIAL;
+    const DU: u8 = class::SPECIAL | class::DOUBLE_UNSAFE;
+    const SQ: u8 = class::SPECIAL | class::SINGLE_QUOTE;
+    const DQ: u8 = class::SPECIAL | class::DOUBLE_UNSAFE | class::DOUBLE_QUOTE;
+    const CT: u8 = class::CONTROL;
+    const NA: u8 = class::NON_ASCII;
+    [
+        CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 00..0F
+        CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, CT, // 10..1F
+        SP, __, DQ, __, DU, __, SP, SQ, SP, SP, SP, __, SP, __, __, __, // 20..2F
+        __, __, __, __, __, __, __, __, __, __, __, SP, SP, SP, SP, SP, // 30..3F
+        __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 40..4F
+        __, __, __, __, __, __, __, __, __, __, __, SP, __, SP, __, __, // 50..5F
+        DU, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 60..6F
+        __, __, __, __, __, __, __, __, __, __, __, SP, SP, SP, __, CT, // 70..7F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 80..8F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // 90..9F
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // A0..AF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // B0..BF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // C0..CF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // D0..DF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // E0..EF
+        NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, NA, // F0..FF
+    ]
+};
 
-/// A single stand-alone exclamation mark seems to have some special meaning.
-/// Tildes are unclear: In Powershell on Linux, quoting a tilde keeps it from
-/// expanding if passed to an external program, but not if passed to Get-ChildItem.
-const SPECIAL_SHELL_CHARS_START: &[char] = &['~', '#', '@', '!'];
+fn is_plain(byte: u8, requires_quote: bool) -> bool {
+    CLASS[byte as usize]
+        < if requires_quote {
+            class::ALWAYS
+        } else {
+            class::SPECIAL
+        }
+}
 
-const DOUBLE_UNSAFE: &[u8] = &[b'"', b'`', b'$'];
+/// Whether a word is an operator or a redirection, for Quoted::operator().
+/// Redirections may start with a stream number or `*`, and only the success
+/// stream can be merged into, like `2>&1`.
+pub(crate) fn is_operator(word: &str) -> bool {
+    match word {
+        "&&" | "||" | "|" | ";" | "&" | "(" | ")" | "{" | "}" | ">" | ">>" => true,
+        _ => {
+            let stream = match word.chars().next() {
+                Some('*') | Some('1'..='6') => &word[1..],
+                _ => return false,
+            };
+            stream == ">" || stream == ">>" || stream == ">&1"
+        }
+    }
+}
 
-pub(crate) fn write(
-    f: &mut Formatter<'_>,
-    text: &str,
-    force_quote: bool,
-    external: bool,
-) -> fmt::Result {
+pub(crate) fn write(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    if opts.context == QuoteContext::InsideDoubleQuotes {
+        return write_escaped(out, text.chars().map(Ok), opts);
+    }
+
+    let external = opts.external;
     match text {
         "" if external => {
             // If we write '' PowerShell will swallow the argument.
-            return f.write_str(r#"'""'"#);
+            out.open(r#"'""#)?;
+            return out.close(r#""'"#);
         }
         "--%" if external => {
             // PowerShell doesn't want to pass this to external commands at all.
             // This does work:
-            return f.write_str(r#"'"--%"'"#);
+            out.open(r#"'""#)?;
+            out.literal("--%")?;
+            return out.close(r#""'"#);
             // It does not work if PSNativeCommandArgumentPassing is enabled.
         }
         "--%" => {
             // This one's still special if used internally.
-            return f.write_str("'--%'");
+            return write_simple(out, text, "'");
         }
         _ => (),
     }
 
+    if opts.magic_tokens == MagicTokens::Strict && (text.starts_with('$') || text.contains("@(")) {
+        // Single quotes would do, but only as long as they're there.
+        // Escaping with backticks inside double quotes is more robust.
+        return write_escaped(out, text.chars().map(Ok), opts);
+    }
+
+    // Like on Unix, the scan remembers where the characters are that the
+    // writers have to do something about: the quotes, and the characters
+    // that have to be escaped between double quotes.
     let mut is_single_safe = true;
     let mut is_double_safe = true;
     let mut has_ascii_double = false;
-    let mut requires_quote = force_quote;
+    let mut requires_quote = opts.force_quote;
     let mut is_bidi = false;
+    let mut combining = scan::Run::default();
+    let mut marks = scan::Marks::new();
+
+    if !requires_quote && opts.quotes_anyway(text) {
+        requires_quote = true;
+    }
 
     if !requires_quote {
         let mut chars = text.chars();
         if let Some(first) = chars.next() {
             let second = chars.next();
 
-            if SPECIAL_SHELL_CHARS_START.contains(&first) {
+            if SPECIAL_SHELL_CHARS_START.contains(&first) && !opts.extra_safe.contains_char(first) {
                 requires_quote = true;
             }
 
@@ -105,7 +173,7 @@ pub(crate) fn write(
                 }
             }
 
-            if !requires_quote && first.width().unwrap_or(0) == 0 {
+            if !requires_quote && width::may_be_zero_width(first, opts.ambiguous_width) {
                 requires_quote = true;
             }
         } else {
@@ -114,70 +182,126 @@ pub(crate) fn write(
         }
     }
 
-    for ch in text.chars() {
+    let mut escaped_from = None;
+    let mut rest = text;
+    loop {
+        let plain = scan::plain_prefix(rest.as_bytes(), |byte| is_plain(byte, requires_quote));
+        rest = &rest[plain..];
+        let ch = match rest.chars().next() {
+            Some(ch) => ch,
+            None => break,
+        };
+        let index = text.len() - rest.len();
+        rest = &rest[ch.len_utf8()..];
         if ch.is_ascii() {
             let ch = ch as u8;
-            if ch == b'\'' {
+            let class = CLASS[ch as usize];
+            if class & class::SINGLE_QUOTE != 0 {
                 is_single_safe = false;
             }
-            if ch == b'"' {
+            if class & class::DOUBLE_QUOTE != 0 {
                 has_ascii_double = true;
             }
-            if is_double_safe && DOUBLE_UNSAFE.contains(&ch) {
+            if class & class::DOUBLE_UNSAFE != 0 {
                 is_double_safe = false;
             }
-            if !requires_quote && SPECIAL_SHELL_CHARS.contains(&ch) {
-                requires_quote = true;
-            }
-            if ch.is_ascii_control() {
-                return write_escaped(f, text.chars().map(Ok), external);
+            if class & (class::SINGLE_QUOTE | class::DOUBLE_UNSAFE) != 0 {
+                marks.push(index);
             }
-        } else {
-            if !requires_quote && unicode::is_whitespace(ch) {
+            if !requires_quote && class & class::SPECIAL != 0 && !opts.extra_safe.contains(ch) {
                 requires_quote = true;
             }
-            if (!requires_quote || is_double_safe) && unicode::is_double_quote(ch) {
-                is_double_safe = false;
-                requires_quote = true;
+            if class & class::CONTROL != 0 {
+                escaped_from = Some(index);
+                break;
             }
-            if (!requires_quote || is_single_safe) && unicode::is_single_quote(ch) {
-                is_single_safe = false;
-                requires_quote = true;
-            }
-            if crate::is_bidi(ch) {
-                is_bidi = true;
+        } else {
+            if scan::is_notable(ch) {
+                if security::is_combining(ch) {
+                    // None of the other checks apply to these.
+                    combining.push(index, ch.len_utf8());
+                } else {
+                    if !requires_quote && unicode::is_whitespace(ch) {
+                        requires_quote = true;
+                    }
+                    if unicode::is_double_quote(ch) {
+                        is_double_safe = false;
+                        requires_quote = true;
+                        marks.push(index);
+                    }
+                    if unicode::is_single_quote(ch) {
+                        is_single_safe = false;
+                        requires_quote = true;
+                        marks.push(index);
+                    }
+                    if security::is_bidi(ch) {
+                        is_bidi = true;
+                    }
+                    if crate::requires_escape(ch)
+                        || (opts.escape_hidden_text && security::is_hidden_text(ch))
+                    {
+                        escaped_from = Some(index);
+                        break;
+                    }
+                }
             }
-            if crate::requires_escape(ch) {
-                return write_escaped(f, text.chars().map(Ok), external);
+            if opts.escape_invisible && security::is_invisible(ch) {
+                escaped_from = Some(index);
+                break;
             }
         }
     }
 
-    if is_bidi && crate::is_suspicious_bidi(text) {
-        return write_escaped(f, text.chars().map(Ok), external);
+    if let Some(end) = escaped_from {
+        let mixed = is_bidi || combining.longest() > 0;
+        return write_escaped_after(out, text, end, &marks, mixed, opts);
     }
 
+    if opts.exceeds_combining(combining.longest())
+        || opts.escapes_confusables(text.chars().map(Some))
+    {
+        return write_escaped(out, text.chars().map(Ok), opts);
+    }
+
+    let mut isolate = false;
+    if is_bidi {
+        match security::apply_bidi_policy(text, opts.bidi) {
+            Bidi::Ok => (),
+            Bidi::Unterminated => isolate = true,
+            Bidi::Suspicious => return write_escaped(out, text.chars().map(Ok), opts),
+        }
+    }
+
+    if isolate {
+        out.open("\u{2068}")?;
+    }
     if !requires_quote {
-        f.write_str(text)
+        out.literal(text)?;
     } else if external && has_ascii_double {
-        write_external_escaped(f, text)
+        write_external_escaped(out, text, &marks)?;
+    } else if is_double_safe && opts.prefer == QuoteChar::Double {
+        write_simple(out, text, "\"")?;
     } else if is_single_safe {
-        write_simple(f, text, '\'')
+        write_simple(out, text, "'")?;
     } else if is_double_safe {
-        write_simple(f, text, '\"')
+        write_simple(out, text, "\"")?;
     } else {
-        write_single_escaped(f, text)
+        write_single_escaped(out, text, &marks)?;
     }
+    if isolate {
+        out.close("\u{2069}")?;
+    }
+    Ok(())
 }
 
-fn write_simple(f: &mut Formatter<'_>, text: &str, quote: char) -> fmt::Result {
-    f.write_char(quote)?;
-    f.write_str(text)?;
-    f.write_char(quote)?;
+fn write_simple(out: &mut dyn Sink, text: &str, quote: &str) -> fmt::Result {
+    out.open(quote)?;
+    out.literal(text)?;
+    out.close(quote)?;
     Ok(())
 }
 
-fn write_single_escaped(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
+fn write_single_escaped(out: &mut dyn Sink, text: &str, quotes: &scan::Marks) -> fmt::Result {
     // Quotes in PowerShell are escaped by doubling them.
     // The second quote is used, so '‘ becomes ‘.
     // Therefore we insert a ' before every quote we find.
@@ -188,118 +312,624 @@ fn write_single_escaped(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
     // double quotes or in a bare string. We can't unquote, use a bare string,
     // then requote, as we would in Unix: PowerShell sees that as multiple
     // arguments.
-    f.write_char('\'')?;
+    out.open("'")?;
     let mut pos = 0;
-    for (index, _) in text.match_indices(unicode::is_single_quote) {
-        f.write_str(&text[pos..index])?;
-        f.write_char('\'')?;
-        pos = index;
+    for (index, quote) in quotes.find(text, unicode::is_single_quote) {
+        out.literal(&text[pos..index])?;
+        write_doubled_quote(out, quote)?;
+        pos = index + quote.len();
     }
-    f.write_str(&text[pos..])?;
-    f.write_char('\'')?;
+    out.literal(&text[pos..])?;
+    out.close("'")?;
     Ok(())
 }
 
-fn write_external_escaped(f: &mut Formatter<'_>, text: &str) -> fmt::Result {
-    f.write_char('\'')?;
+fn write_doubled_quote(out: &mut dyn Sink, quote: &str) -> fmt::Result {
+    let ch = quote.chars().next().unwrap();
+    out.escape_fmt(Original::Char(ch), format_args!("'{}", quote))
+}
+
+fn write_external_escaped(out: &mut dyn Sink, text: &str, quotes: &scan::Marks) -> fmt::Result {
+    out.open("'")?;
     let mut pos = 0;
-    for (index, quote) in text.match_indices(|ch: char| ch == '"' || unicode::is_single_quote(ch)) {
-        f.write_str(&text[pos..index])?;
+    for (index, quote) in quotes.find(text, |ch| ch == '"' || unicode::is_single_quote(ch)) {
         if quote == "\"" {
             // Let n be the number of backslashes before the quote.
-            // We need to turn that into 2n + 1 backslahes.
-            // Therefore we need to write n + 1 more.
+            // We need to turn that into 2n + 1 backslahes: each of them is
+            // doubled and the quote gets one of its own.
             // The logic behind this is that double quotes must be escaped with
             // backslashes, and backslashes must be escaped only if they precede
             // a double quote.
             let backslashes = text[..index]
-                .chars()
+                .bytes()
                 .rev()
-                .take_while(|&ch| ch == '\\')
-                .count()
-                + 1;
+                .take_while(|&byte| byte == b'\\')
+                .count();
+            out.literal(&text[pos..index - backslashes])?;
             for _ in 0..backslashes {
-                f.write_char('\\')?;
+                out.escape(Original::Char('\\'), "\\\\")?;
             }
+            out.escape(Original::Char('"'), "\\\"")?;
         } else {
-            f.write_char('\'')?;
+            out.literal(&text[pos..index])?;
+            write_doubled_quote(out, quote)?;
         }
-        pos = index;
+        pos = index + quote.len();
+    }
+    out.literal(&text[pos..])?;
+    out.close("'")?;
+    Ok(())
+}
+
+/// Write a single argument the way `CommandLineToArgvW` and the Microsoft C
+/// runtime parse it:
+/// https://learn.microsoft.com/en-us/cpp/c-language/parsing-c-command-line-arguments
+///
+/// This has nothing to do with PowerShell. There are no escape sequences
+/// other than for double quotes and the backslashes before them, so
+/// everything else is written as-is.
+#[cfg(feature = "windows")]
+pub(crate) fn write_argv(out: &mut dyn Sink, text: &str, opts: &Options) -> fmt::Result {
+    // For QuoteContext::InsideDoubleQuotes the caller writes the quotes.
+    let quoted = opts.context == QuoteContext::Standalone;
+    if quoted {
+        let requires_quote = opts.force_quote
+            || text.is_empty()
+            || text
+                .contains(|ch| ch == ' ' || ch == '\t' || ch == '\n' || ch == '\x0b' || ch == '"');
+        if !requires_quote {
+            return out.literal(text);
+        }
+        out.open("\"")?;
+    }
+    let mut pos = 0;
+    for (index, _) in text.match_indices('"') {
+        // Same as in write_external_escaped(): 2n + 1 backslashes.
+        let backslashes = text[..index]
+            .bytes()
+            .rev()
+            .take_while(|&byte| byte == b'\\')
+            .count();
+        out.literal(&text[pos..index - backslashes])?;
+        for _ in 0..backslashes {
+            out.escape(Original::Char('\\'), "\\\\")?;
+        }
+        out.escape(Original::Char('"'), "\\\"")?;
+        pos = index + 1;
+    }
+    // Backslashes at the end come before the closing quote, so they're
+    // doubled too.
+    let backslashes = text[pos..]
+        .bytes()
+        .rev()
+        .take_while(|&byte| byte == b'\\')
+        .count();
+    out.literal(&text[pos..text.len() - backslashes])?;
+    for _ in 0..backslashes {
+        out.escape(Original::Char('\\'), "\\\\")?;
+    }
+    if quoted {
+        out.close("\"")?;
     }
-    f.write_str(&text[pos..])?;
-    f.write_char('\'')?;
     Ok(())
 }
 
+/// Escapes a command line again for `cmd.exe`, for
+/// [`Quoted::cmd_context`](crate::Quoted::cmd_context).
+///
+/// Every character that `cmd.exe` treats specially gets a `^`, double quotes
+/// included. That way it never thinks it's inside quotes and always removes
+/// the carets, so there's no need to keep track of its state.
+///
+/// See <https://stackoverflow.com/a/4095133> for the phases that a line
+/// goes through.
+#[cfg(feature = "windows")]
+pub(crate) struct CmdEscaped<'a> {
+    out: &'a mut dyn Sink,
+    context: CmdContext,
+    /// With delayed expansion, an argument with a `!` in it has its carets
+    /// removed a second time.
+    twice: bool,
+}
+
+#[cfg(feature = "windows")]
+impl<'a> CmdEscaped<'a> {
+    pub(crate) fn new(out: &'a mut dyn Sink, context: CmdContext, text: &str) -> Self {
+        CmdEscaped {
+            out,
+            context,
+            twice: context == CmdContext::DelayedExpansion && text.contains('!'),
+        }
+    }
+
+    /// What to write for a character, if it's not written as it is.
+    fn rendered(&self, ch: char) -> Option<&'static str> {
+        Some(match ch {
+            '%' if self.context == CmdContext::Interactive => "^%",
+            '%' => "%%",
+            '!' if self.twice => "^^!",
+            '^' if self.twice => "^^^^",
+            '^' => "^^",
+            '&' => "^&",
+            '|' => "^|",
+            '<' => "^<",
+            '>' => "^>",
+            '(' => "^(",
+            ')' => "^)",
+            '"' => "^\"",
+            // A line break would end the command.
+            '\n' | '\r' => "\u{FFFD}",
+            _ => return None,
+        })
+    }
+
+    /// Escape a piece of syntax and pass it on with `pass`.
+    fn syntax(
+        &mut self,
+        text: &str,
+        pass: impl FnOnce(&mut dyn Sink, &str) -> fmt::Result,
+    ) -> fmt::Result {
+        if !text.chars().any(|ch| self.rendered(ch).is_some()) {
+            return pass(self.out, text);
+        }
+        let mut buf = Buf::new();
+        for ch in text.chars() {
+            match self.rendered(ch) {
+                Some(rendered) => fmt::Write::write_str(&mut buf, rendered)?,
+                None => fmt::Write::write_char(&mut buf, ch)?,
+            }
+        }
+        pass(self.out, buf.as_str())
+    }
+}
+
+#[cfg(feature = "windows")]
+impl<'a> Sink for CmdEscaped<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.open(text))
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.close(text))
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        let mut pos = 0;
+        for (index, ch) in text.char_indices() {
+            if let Some(rendered) = self.rendered(ch) {
+                if index > pos {
+                    self.out.literal(&text[pos..index])?;
+                }
+                self.out.escape(Original::Char(ch), rendered)?;
+                pos = index + ch.len_utf8();
+            }
+        }
+        if pos < text.len() {
+            self.out.literal(&text[pos..])?;
+        }
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        self.syntax(rendered, |out, rendered| out.escape(original, rendered))
+    }
+}
+
 pub(crate) fn write_escaped(
-    f: &mut Formatter<'_>,
-    text: impl Iterator<Item = Result<char, u16>>,
-    external: bool,
+    out: &mut dyn Sink,
+    text: impl Iterator<Item = Result<char, u16>> + Clone,
+    opts: &Options,
 ) -> fmt::Result {
-    // ` takes the role of \ since \ is already used as the path separator.
-    // Things are UTF-16-oriented, so we escape bad code units as "`u{1234}".
+    let escapes = opts.text_escapes(text.clone().map(Result::ok));
+    let mut writer = EscapedWriter::new(out, opts, escapes)?;
+    writer.write(out, text)?;
+    writer.finish(out)
+}
 
-    f.write_char('"')?;
-    let mut backslashes: u32 = 0;
-    for ch in text {
-        match ch {
-            Ok(ch) => {
-                match ch {
-                    '\0' => f.write_str("`0")?,
-                    '\r' => f.write_str("`r")?,
-                    '\n' => f.write_str("`n")?,
-                    '\t' => f.write_str("`t")?,
-                    // Code unit escapes are only supported in PowerShell Core,
-                    // so we're more willing to use weird escapes here than on Unix.
-                    // There's also `e, for \x1B, but that one's Core-exclusive.
-                    '\x07' => f.write_str("`a")?,
-                    '\x08' => f.write_str("`b")?,
-                    '\x0b' => f.write_str("`v")?,
-                    '\x0c' => f.write_str("`f")?,
-                    ch if crate::requires_escape(ch) || crate::is_bidi(ch) => {
-                        write!(f, "`u{{{:02X}}}", ch as u32)?
-                    }
-                    '`' => f.write_str("``")?,
-                    '$' => f.write_str("`$")?,
-                    '"' if external => {
-                        // First we need to escape all the backslashes that came before.
-                        for _ in 0..backslashes {
-                            f.write_char('\\')?;
-                        }
-                        // Then we need to escape this double quote for CommandLineToArgv.
-                        f.write_char('\\')?;
-                        // Then we need to escape it for the PowerShell string.
-                        f.write_char('`')?;
-                        // And then we can finally write the quote itself.
-                        f.write_char('"')?;
-                    }
-                    ch if unicode::is_double_quote(ch) => {
-                        // We can quote this with either ` or ".
-                        // But if we use " and the PowerShell version doesn't actually
-                        // see this as a double quote then we're in trouble.
-                        // ` is safer.
-                        f.write_char('`')?;
-                        f.write_char(ch)?;
-                    }
-                    ch => f.write_char(ch)?,
+/// Like [`write_escaped`], for when the scan in write() found a character
+/// that has to be escaped at `end`. See the Unix version.
+///
+/// Before that point only the characters in `marks` that are special inside
+/// double quotes need escapes. Backslashes are handed to the writer along
+/// with what comes after them, in case that's a double quote.
+fn write_escaped_after(
+    out: &mut dyn Sink,
+    text: &str,
+    end: usize,
+    marks: &scan::Marks,
+    mixed: bool,
+    opts: &Options,
+) -> fmt::Result {
+    #[cfg(feature = "confusables")]
+    let mixed = mixed || opts.confusables;
+    if mixed {
+        return write_escaped(out, text.chars().map(Ok), opts);
+    }
+    let rest = &text[end..];
+    let escapes = opts.text_escapes(rest.chars().map(Some));
+    let mut writer = EscapedWriter::new(out, opts, escapes)?;
+    let scanned = &text[..end];
+    let mut pos = 0;
+    for (index, found) in marks.find(scanned, is_expandable) {
+        let start = index - trailing_backslashes(&scanned[pos..index]);
+        if start > pos {
+            out.literal(&scanned[pos..start])?;
+        }
+        writer.write(out, scanned[start..index + found.len()].chars().map(Ok))?;
+        pos = index + found.len();
+    }
+    let start = end - trailing_backslashes(&scanned[pos..]);
+    if start > pos {
+        out.literal(&scanned[pos..start])?;
+    }
+    writer.write(out, text[start..].chars().map(Ok))?;
+    writer.finish(out)
+}
+
+fn trailing_backslashes(text: &str) -> usize {
+    text.bytes().rev().take_while(|&byte| byte == b'\\').count()
+}
+
+/// A double-quoted string that's written piece by piece, for
+/// [`quote_stream`](crate::quote_stream).
+pub(crate) struct EscapedWriter<'a> {
+    opts: &'a Options,
+    escapes: TextEscapes,
+    /// For QuoteContext::InsideDoubleQuotes the caller writes the quotes.
+    quoted: bool,
+    /// With Quoted::expression() we use [char] casts instead of `u{} escapes,
+    /// which need PowerShell 6. Outside a string that's a concatenation like
+    /// ("a" + [char]0xD800), inside it's a subexpression like "a$([char]0xD800)".
+    concatenate: bool,
+    in_string: bool,
+    /// Backslashes only need to be escaped if they precede a double quote, so
+    /// we hold on to them until we know what comes next.
+    backslashes: usize,
+}
+
+impl<'a> EscapedWriter<'a> {
+    pub(crate) fn new(
+        out: &mut dyn Sink,
+        opts: &'a Options,
+        escapes: TextEscapes,
+    ) -> Result<Self, fmt::Error> {
+        let quoted = opts.context == QuoteContext::Standalone;
+        let concatenate = opts.expression && quoted;
+        if quoted {
+            if concatenate {
+                out.open("(")?;
+            }
+            out.open("\"")?;
+        }
+        Ok(EscapedWriter {
+            opts,
+            escapes,
+            quoted,
+            concatenate,
+            in_string: true,
+            backslashes: 0,
+        })
+    }
+
+    pub(crate) fn write(
+        &mut self,
+        out: &mut dyn Sink,
+        text: impl Iterator<Item = Result<char, u16>>,
+    ) -> fmt::Result {
+        let opts = self.opts;
+        let external = opts.external;
+        let concatenate = self.concatenate;
+        // ` takes the role of \ since \ is already used as the path separator.
+        // Things are UTF-16-oriented, so we escape bad code units as "`u{1234}".
+        let mut literals = Literals::new();
+        for ch in text {
+            if ch == Ok('\\') {
+                self.backslashes += 1;
+                continue;
+            }
+            let escape = match ch {
+                Ok(ch) => {
+                    let escape = opts.escapes(ch) || self.escapes.contains(ch);
+                    escape_sequence(ch, escape, external)
                 }
-                if ch == '\\' {
-                    backslashes += 1;
+                Err(_) => Some(Escape::Unicode),
+            };
+            let is_cast = match escape {
+                Some(Escape::Unicode) => concatenate,
+                _ => false,
+            };
+            if !self.in_string && (self.backslashes > 0 || !is_cast) {
+                literals.flush(out)?;
+                out.open(" + \"")?;
+                self.in_string = true;
+            }
+            let escape_backslashes = external && ch == Ok('"');
+            for _ in 0..self.backslashes {
+                if escape_backslashes {
+                    literals.flush(out)?;
+                    out.escape(Original::Char('\\'), "\\\\")?;
                 } else {
-                    backslashes = 0;
+                    literals.push(out, '\\')?;
+                }
+            }
+            self.backslashes = 0;
+            if let (Ok(ch), None) = (ch, &escape) {
+                literals.push(out, ch)?;
+                continue;
+            }
+            literals.flush(out)?;
+            if is_cast && self.in_string {
+                out.close("\"")?;
+                self.in_string = false;
+            }
+            let mut buf = [0; 2];
+            match (ch, escape) {
+                (Ok(ch), Some(Escape::Fixed(rendered))) => {
+                    out.escape(Original::Char(ch), rendered)?
+                }
+                (Ok(ch), Some(Escape::Backtick)) => {
+                    out.escape_fmt(Original::Char(ch), format_args!("`{}", ch))?
+                }
+                (Ok(_), None) => unreachable!(),
+                (Ok(ch), Some(Escape::Unicode)) if opts.expression => out.escape_fmt(
+                    Original::Char(ch),
+                    format_args!("{}", CharCasts(ch.encode_utf16(&mut buf), concatenate)),
+                )?,
+                (Ok(ch), Some(Escape::Unicode)) => {
+                    out.escape_fmt(Original::Char(ch), format_args!("`u{{{:02X}}}", ch as u32))?
+                }
+                (Err(unit), _) if opts.expression => out.escape_fmt(
+                    Original::Unit(unit),
+                    format_args!("{}", CharCasts(&[unit], concatenate)),
+                )?,
+                (Err(unit), _) => {
+                    out.escape_fmt(Original::Unit(unit), format_args!("`u{{{:04X}}}", unit))?
                 }
             }
-            Err(unit) => write!(f, "`u{{{:04X}}}", unit)?,
         }
+        literals.flush(out)
+    }
+
+    pub(crate) fn finish(mut self, out: &mut dyn Sink) -> fmt::Result {
+        if self.backslashes > 0 && !self.in_string {
+            out.open(" + \"")?;
+            self.in_string = true;
+        }
+        for _ in 0..self.backslashes {
+            out.literal("\\")?;
+        }
+        if self.quoted {
+            if self.in_string {
+                out.close("\"")?;
+            }
+            if self.concatenate {
+                out.close(")")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decode WTF-8, for [`Quoted::windows_wtf8`](crate::Quoted::windows_wtf8).
+///
+/// Unpaired surrogates are encoded like any other code point, as three bytes
+/// starting with 0xED, and come out as `Err`. Bytes that aren't valid WTF-8
+/// can't happen in an `OsStr`, so we don't try hard and replace them.
+#[cfg(feature = "windows")]
+#[derive(Clone)]
+pub(crate) struct Wtf8Chars<'a> {
+    chars: core::str::Chars<'a>,
+    rest: &'a [u8],
+}
+
+#[cfg(feature = "windows")]
+impl<'a> Wtf8Chars<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Wtf8Chars {
+            chars: "".chars(),
+            rest: bytes,
+        }
+    }
+}
+
+#[cfg(feature = "windows")]
+impl<'a> Iterator for Wtf8Chars<'a> {
+    type Item = Result<char, u16>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ch) = self.chars.next() {
+            return Some(Ok(ch));
+        }
+        let chunk = crate::util::utf8_chunks(self.rest).next()?;
+        match chunk {
+            Ok(valid) => {
+                self.rest = &self.rest[valid.len()..];
+                self.chars = valid.chars();
+                self.next()
+            }
+            Err(_) => {
+                if let Some(unit) = surrogate(self.rest) {
+                    self.rest = &self.rest[3..];
+                    return Some(Err(unit));
+                }
+                self.rest = &self.rest[1..];
+                Some(Ok(core::char::REPLACEMENT_CHARACTER))
+            }
+        }
+    }
+}
+
+/// Whether the bytes are valid WTF-8, so that [`Wtf8Chars`] won't have to
+/// replace anything.
+#[cfg(feature = "windows")]
+pub(crate) fn is_wtf8(mut bytes: &[u8]) -> bool {
+    loop {
+        match core::str::from_utf8(bytes) {
+            Ok(_) => return true,
+            Err(err) => {
+                let rest = &bytes[err.valid_up_to()..];
+                if surrogate(rest).is_none() {
+                    return false;
+                }
+                bytes = &rest[3..];
+            }
+        }
+    }
+}
+
+/// The surrogate that the bytes start with, if any. They're 0xD800 to 0xDFFF,
+/// so the first byte is always 0xED.
+#[cfg(feature = "windows")]
+fn surrogate(bytes: &[u8]) -> Option<u16> {
+    if bytes.len() < 3 || bytes[0] != 0xED {
+        return None;
+    }
+    let is_continuation = |byte: u8| byte & 0xC0 == 0x80;
+    if bytes[1] < 0xA0 || !is_continuation(bytes[1]) || !is_continuation(bytes[2]) {
+        return None;
+    }
+    Some(0xD000 | u16::from(bytes[1] & 0x3F) << 6 | u16::from(bytes[2] & 0x3F))
+}
+
+/// Formats UTF-16 code units as `[char]` casts, for [`Quoted::expression`](crate::Quoted::expression).
+/// The flag says whether they're concatenated or written as subexpressions.
+struct CharCasts<'a>(&'a [u16], bool);
+
+impl<'a> fmt::Display for CharCasts<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &unit in self.0 {
+            if self.1 {
+                write!(f, " + [char]0x{:04X}", unit)?;
+            } else {
+                write!(f, "$([char]0x{:04X})", unit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a character is written inside `"..."`.
+pub(crate) enum Escape {
+    /// A fixed escape sequence, like `` `n ``.
+    Fixed(&'static str),
+    /// A `` `u{XXXX} `` escape with the codepoint.
+    Unicode,
+    /// The character itself, after a backtick.
+    Backtick,
+}
+
+/// Decide how to escape a character inside `"..."`. `escape` says whether
+/// it's one of the characters that always have to be escaped.
+///
+/// Backslashes are not handled here, they depend on what follows them.
+pub(crate) fn escape_sequence(ch: char, escape: bool, external: bool) -> Option<Escape> {
+    match ch {
+        '\0' => Some(Escape::Fixed("`0")),
+        '\r' => Some(Escape::Fixed("`r")),
+        '\n' => Some(Escape::Fixed("`n")),
+        '\t' => Some(Escape::Fixed("`t")),
+        // Code unit escapes are only supported in PowerShell Core,
+        // so we're more willing to use weird escapes here than on Unix.
+        // There's also `e, for \x1B, but that one's Core-exclusive.
+        '\x07' => Some(Escape::Fixed("`a")),
+        '\x08' => Some(Escape::Fixed("`b")),
+        '\x0b' => Some(Escape::Fixed("`v")),
+        '\x0c' => Some(Escape::Fixed("`f")),
+        _ if escape => Some(Escape::Unicode),
+        '`' => Some(Escape::Fixed("``")),
+        '$' => Some(Escape::Fixed("`$")),
+        // We need to escape this double quote for CommandLineToArgv,
+        // and then for the PowerShell string.
+        '"' if external => Some(Escape::Fixed("\\`\"")),
+        // We can quote this with either ` or ".
+        // But if we use " and the PowerShell version doesn't actually
+        // see this as a double quote then we're in trouble.
+        // ` is safer.
+        ch if unicode::is_double_quote(ch) => Some(Escape::Backtick),
+        _ => None,
+    }
+}
+
+/// Escapes the output again so it can go inside a double-quoted string that's
+/// passed to `Invoke-Expression`, for [`Quoted::iex_safe`](crate::Quoted::iex_safe).
+///
+/// Every piece stays in one piece, so this works with truncation too.
+pub(crate) struct IexSafe<'a>(pub(crate) &'a mut dyn Sink);
+
+/// Characters that are special inside a double-quoted string.
+fn is_expandable(ch: char) -> bool {
+    ch == '`' || ch == '$' || unicode::is_double_quote(ch)
+}
+
+/// Put a backtick before the special characters of an expandable string.
+struct Backticked<'a>(&'a str);
+
+impl<'a> fmt::Display for Backticked<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+
+        for ch in self.0.chars() {
+            if is_expandable(ch) {
+                f.write_char('`')?;
+            }
+            f.write_char(ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IexSafe<'a> {
+    /// Escape a piece of syntax and pass it on with `pass`.
+    fn syntax(
+        &mut self,
+        text: &str,
+        pass: impl FnOnce(&mut dyn Sink, &str) -> fmt::Result,
+    ) -> fmt::Result {
+        if !text.contains(is_expandable) {
+            return pass(self.0, text);
+        }
+        let mut buf = Buf::new();
+        fmt::Write::write_fmt(&mut buf, format_args!("{}", Backticked(text)))?;
+        pass(self.0, buf.as_str())
+    }
+}
+
+impl<'a> Sink for IexSafe<'a> {
+    fn open(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.open(text))
+    }
+
+    fn close(&mut self, text: &str) -> fmt::Result {
+        self.syntax(text, |out, text| out.close(text))
+    }
+
+    fn literal(&mut self, text: &str) -> fmt::Result {
+        let mut pos = 0;
+        for (index, ch) in text.char_indices().filter(|&(_, ch)| is_expandable(ch)) {
+            if index > pos {
+                self.0.literal(&text[pos..index])?;
+            }
+            self.0
+                .escape_fmt(Original::Char(ch), format_args!("`{}", ch))?;
+            pos = index + ch.len_utf8();
+        }
+        if pos < text.len() {
+            self.0.literal(&text[pos..])?;
+        }
+        Ok(())
+    }
+
+    fn escape(&mut self, original: Original, rendered: &str) -> fmt::Result {
+        self.syntax(rendered, |out, rendered| out.escape(original, rendered))
     }
-    f.write_char('"')?;
-    Ok(())
 }
 
 /// PowerShell makes liberal use of Unicode:
 /// <https://github.com/PowerShell/PowerShell/blob/master/src/System.Management.Automation/engine/parser/CharTraits.cs>
 /// This may have to be updated in the future.
 mod unicode {
+    use crate::tables;
+
     /// PowerShell considers these to be whitespace:
     /// 1. ASCII: Space, Horizontal tab, Form feed, Carriage return
     /// 2. Unicode: No-break space, Next line
@@ -322,22 +952,11 @@ mod unicode {
         }
     }
 
-    /// I don't want to add a dependency just for this, and
-    /// as of writing, the unicode_categories crate is out of
-    /// date anyway. So hardcode the category check.
-    ///
-    /// curl -s https://www.unicode.org/Public/UCD/latest/ucd/UnicodeData.txt \
-    ///     | grep -e Zl -e Zp -e Zs | cut -d ';' -f 1
-    ///
-    /// Unicode 15.0 will release on September 11, 2022.
+    /// The categories {space, line, paragraph} separator, from the tables
+    /// generated by scripts/gen_tables.py.
     fn is_separator(ch: char) -> bool {
-        match ch {
-            '\u{0020}' | '\u{00A0}' | '\u{1680}' | '\u{2000}' | '\u{2001}' | '\u{2002}'
-            | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}' | '\u{2007}' | '\u{2008}'
-            | '\u{2009}' | '\u{200A}' | '\u{2028}' | '\u{2029}' | '\u{202F}' | '\u{205F}'
-            | '\u{3000}' => true,
-            _ => false,
-        }
+        tables::contains(tables::SPACE_SEPARATOR, ch)
+            || tables::contains(tables::LINE_PARAGRAPH_SEPARATOR, ch)
     }
 
     /// These can be used to start options.
@@ -364,3 +983,99 @@ mod unicode {
         }
     }
 }
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell_chars::windows::{DOUBLE_UNSAFE, SPECIAL_SHELL_CHARS};
+
+    #[test]
+    fn test_class_table() {
+        for byte in 0..=255 {
+            let mut expected = 0;
+            if SPECIAL_SHELL_CHARS.contains(&byte) {
+                expected |= class::SPECIAL;
+            }
+            if DOUBLE_UNSAFE.contains(&byte) {
+                expected |= class::DOUBLE_UNSAFE;
+            }
+            if byte == b'\'' {
+                expected |= class::SINGLE_QUOTE;
+            }
+            if byte == b'"' {
+                expected |= class::DOUBLE_QUOTE;
+            }
+            if byte.is_ascii_control() {
+                expected |= class::CONTROL;
+            }
+            if !byte.is_ascii() {
+                expected |= class::NON_ASCII;
+            }
+            assert_eq!(CLASS[byte as usize], expected, "{:?}", byte as char);
+        }
+    }
+
+    #[cfg(feature = "windows")]
+    #[test]
+    fn test_escaped_after() {
+        // Same as on Unix: the output has to be the same as starting over.
+        let pieces = [
+            "a", "'", "\"", "\u{201C}", "\u{2019}", "\\", "`", "$", "\n", "\x01", "é", " ",
+            "\u{202E}", "\u{300}", "\u{200B}", "\u{85}",
+        ];
+        let mut state = 1u32;
+        for round in 0..5000 {
+            let mut text = std::string::String::new();
+            for _ in 0..round % 20 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                text.push_str(pieces[(state >> 16) as usize % pieces.len()]);
+            }
+            let quoted = crate::Quoted::windows(&text);
+            let variants = [
+                quoted,
+                quoted.external(true),
+                quoted.expression(true),
+                quoted.max_combining(Some(0)),
+                quoted.escape_invisible(true),
+            ];
+            for quoted in &variants {
+                let opts = &quoted.opts;
+                let escaped = text.chars().any(|ch| {
+                    crate::requires_escape(ch)
+                        || (opts.escape_hidden_text && security::is_hidden_text(ch))
+                        || (opts.escape_invisible && security::is_invisible(ch))
+                });
+                if !escaped {
+                    continue;
+                }
+                let mut written = std::string::String::new();
+                write(&mut crate::sink::Plain(&mut written), &text, opts).unwrap();
+                let mut expected = std::string::String::new();
+                write_escaped(
+                    &mut crate::sink::Plain(&mut expected),
+                    text.chars().map(Ok),
+                    opts,
+                )
+                .unwrap();
+                assert_eq!(written, expected, "{:?}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_notable() {
+        for ch in (0x80..=0x10FFFF).filter_map(core::char::from_u32) {
+            if unicode::is_whitespace(ch)
+                || unicode::is_single_quote(ch)
+                || unicode::is_double_quote(ch)
+                || security::is_bidi(ch)
+                || crate::requires_escape(ch)
+                || security::is_combining(ch)
+                || security::is_hidden_text(ch)
+            {
+                assert!(scan::is_notable(ch), "{:?}", ch);
+            }
+        }
+    }
+}